@@ -0,0 +1,678 @@
+//! Typed representation of AQL expressions.
+//!
+//! Expressions are used in `FILTER` conditions, `LET` assignments, `RETURN`
+//! projections and as documents or keys in data modification operations.
+//! Values of Rust types are never written into the query string. Instead
+//! they are passed as bind parameters when the query is built.
+
+#[cfg(test)]
+mod tests;
+
+use rincon_core::api::query::Query;
+use rincon_core::api::types::Value;
+
+use query::QueryBuilder;
+
+/// The prefix used for the names of generated bind parameters for values.
+pub const VALUE_PARAM_PREFIX: &str = "value";
+
+/// The prefix used for the names of generated bind parameters for
+/// collections.
+pub const COLLECTION_PARAM_PREFIX: &str = "@collection";
+
+/// The prefix used for the names of generated variables.
+///
+/// Names starting with this prefix are reserved and must not be used for
+/// variables of a query.
+pub const GENERATED_VARIABLE_PREFIX: &str = "_rincon_var";
+
+// The list of keywords is shared with the `aql!` macro.
+include!("../../../rincon_aql_macros/src/keywords.rs");
+
+/// An expression in an AQL query.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expression {
+    /// A variable, e.g. the loop variable of a `FOR` operation
+    Variable(String),
+    /// The attribute of the document an expression evaluates to
+    Attribute(Box<Expression>, String),
+    /// The element at an index of the array an expression evaluates to
+    Index(Box<Expression>, Box<Expression>),
+    /// A value that is passed to the query as bind parameter
+    Value(Value),
+    /// A collection whose name is passed to the query as bind parameter
+    Collection(String),
+    /// The `null` literal
+    Null,
+    /// An array of expressions
+    Array(Vec<Expression>),
+    /// A document with attribute names and expressions as values
+    Object(Vec<(String, Expression)>),
+    /// A range of integers from the first to the second expression
+    Range(Box<Expression>, Box<Expression>),
+    /// A binary operation
+    Binary(Box<Expression>, Operator, Box<Expression>),
+    /// The negation of an expression
+    Not(Box<Expression>),
+    /// A call of an AQL function with the given arguments
+    Function(FunctionName, Vec<Expression>),
+    /// A subquery
+    Subquery(Box<QueryBuilder>),
+}
+
+/// The name of an AQL function.
+///
+/// A `FunctionName` can only be created by the `function()` constructor,
+/// which checks that the name is valid. This guarantees that the name can be
+/// rendered into the query string as is.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FunctionName(String);
+
+impl FunctionName {
+    /// Returns the name as string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Binary operators of AQL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operator {
+    /// `==`
+    Equal,
+    /// `!=`
+    NotEqual,
+    /// `<`
+    Less,
+    /// `<=`
+    LessOrEqual,
+    /// `>`
+    Greater,
+    /// `>=`
+    GreaterOrEqual,
+    /// `IN`
+    In,
+    /// `NOT IN`
+    NotIn,
+    /// `LIKE`
+    Like,
+    /// `=~`
+    RegexMatch,
+    /// `AND`
+    And,
+    /// `OR`
+    Or,
+    /// `+`
+    Add,
+    /// `-`
+    Subtract,
+    /// `*`
+    Multiply,
+    /// `/`
+    Divide,
+    /// `%`
+    Modulo,
+}
+
+impl Operator {
+    /// Returns the AQL representation of this operator.
+    pub fn as_str(&self) -> &'static str {
+        use self::Operator::*;
+        match *self {
+            Equal => "==",
+            NotEqual => "!=",
+            Less => "<",
+            LessOrEqual => "<=",
+            Greater => ">",
+            GreaterOrEqual => ">=",
+            In => "IN",
+            NotIn => "NOT IN",
+            Like => "LIKE",
+            RegexMatch => "=~",
+            And => "AND",
+            Or => "OR",
+            Add => "+",
+            Subtract => "-",
+            Multiply => "*",
+            Divide => "/",
+            Modulo => "%",
+        }
+    }
+}
+
+/// Creates a variable expression with the given name.
+pub fn var<N>(name: N) -> Expression
+where
+    N: Into<String>,
+{
+    Expression::Variable(name.into())
+}
+
+/// Creates an expression for a collection with the given name.
+///
+/// The name of the collection is passed to the query as bind parameter.
+pub fn collection<N>(name: N) -> Expression
+where
+    N: Into<String>,
+{
+    Expression::Collection(name.into())
+}
+
+/// Creates an expression for a value that is passed to the query as bind
+/// parameter.
+pub fn value<V>(value: V) -> Expression
+where
+    V: Into<Value>,
+{
+    Expression::Value(value.into())
+}
+
+/// Creates the `null` literal.
+pub fn null() -> Expression {
+    Expression::Null
+}
+
+/// Creates an array from the given expressions.
+pub fn array<I, E>(elements: I) -> Expression
+where
+    I: IntoIterator<Item = E>,
+    E: Into<Expression>,
+{
+    Expression::Array(elements.into_iter().map(Into::into).collect())
+}
+
+/// Creates a document from the given pairs of attribute name and expression.
+pub fn object<I, N, E>(attributes: I) -> Expression
+where
+    I: IntoIterator<Item = (N, E)>,
+    N: Into<String>,
+    E: Into<Expression>,
+{
+    Expression::Object(
+        attributes
+            .into_iter()
+            .map(|(name, value)| (name.into(), value.into()))
+            .collect(),
+    )
+}
+
+/// Creates a range of integers from `from` to `to` (both inclusive).
+pub fn range<F, T>(from: F, to: T) -> Expression
+where
+    F: Into<Expression>,
+    T: Into<Expression>,
+{
+    Expression::Range(Box::new(from.into()), Box::new(to.into()))
+}
+
+/// Creates a call of the AQL function with the given name and arguments.
+///
+/// The name of a user defined function is qualified by its namespace, e.g.
+/// `MYFUNCTIONS::TEMPERATURE::CELSIUS_TO_FAHRENHEIT`.
+///
+/// # Panics
+///
+/// Panics if the name is not a valid function name. A valid name consists of
+/// one or more identifiers separated by `::`, where an identifier starts
+/// with an ASCII letter or underscore followed by ASCII letters, digits or
+/// underscores.
+pub fn function<N, I, E>(name: N, arguments: I) -> Expression
+where
+    N: Into<String>,
+    I: IntoIterator<Item = E>,
+    E: Into<Expression>,
+{
+    let name = name.into();
+    assert!(
+        is_function_name(&name),
+        "invalid AQL function name: {:?}",
+        name
+    );
+    Expression::Function(
+        FunctionName(name),
+        arguments.into_iter().map(Into::into).collect(),
+    )
+}
+
+/// Creates the negation of the given expression.
+pub fn not<E>(expression: E) -> Expression
+where
+    E: Into<Expression>,
+{
+    Expression::Not(Box::new(expression.into()))
+}
+
+/// Creates a subquery from the given query.
+///
+/// The bind parameters of the subquery become bind parameters of the
+/// enclosing query.
+pub fn subquery(query: QueryBuilder) -> Expression {
+    Expression::Subquery(Box::new(query))
+}
+
+impl Expression {
+    /// Returns an expression for the attribute with the given name of the
+    /// document this expression evaluates to.
+    pub fn field<N>(self, name: N) -> Expression
+    where
+        N: Into<String>,
+    {
+        Expression::Attribute(Box::new(self), name.into())
+    }
+
+    /// Returns an expression for the element at the given index of the array
+    /// this expression evaluates to.
+    pub fn at<I>(self, index: I) -> Expression
+    where
+        I: Into<Expression>,
+    {
+        Expression::Index(Box::new(self), Box::new(index.into()))
+    }
+
+    /// Combines this expression with the given one using the given operator.
+    pub fn binary<E>(self, operator: Operator, other: E) -> Expression
+    where
+        E: Into<Expression>,
+    {
+        Expression::Binary(Box::new(self), operator, Box::new(other.into()))
+    }
+
+    /// `self == other`
+    pub fn eq<E>(self, other: E) -> Expression
+    where
+        E: Into<Expression>,
+    {
+        self.binary(Operator::Equal, other)
+    }
+
+    /// `self != other`
+    pub fn ne<E>(self, other: E) -> Expression
+    where
+        E: Into<Expression>,
+    {
+        self.binary(Operator::NotEqual, other)
+    }
+
+    /// `self < other`
+    pub fn lt<E>(self, other: E) -> Expression
+    where
+        E: Into<Expression>,
+    {
+        self.binary(Operator::Less, other)
+    }
+
+    /// `self <= other`
+    pub fn le<E>(self, other: E) -> Expression
+    where
+        E: Into<Expression>,
+    {
+        self.binary(Operator::LessOrEqual, other)
+    }
+
+    /// `self > other`
+    pub fn gt<E>(self, other: E) -> Expression
+    where
+        E: Into<Expression>,
+    {
+        self.binary(Operator::Greater, other)
+    }
+
+    /// `self >= other`
+    pub fn ge<E>(self, other: E) -> Expression
+    where
+        E: Into<Expression>,
+    {
+        self.binary(Operator::GreaterOrEqual, other)
+    }
+
+    /// `self IN other`
+    pub fn is_in<E>(self, other: E) -> Expression
+    where
+        E: Into<Expression>,
+    {
+        self.binary(Operator::In, other)
+    }
+
+    /// `self NOT IN other`
+    pub fn not_in<E>(self, other: E) -> Expression
+    where
+        E: Into<Expression>,
+    {
+        self.binary(Operator::NotIn, other)
+    }
+
+    /// `self LIKE pattern`
+    pub fn like<E>(self, pattern: E) -> Expression
+    where
+        E: Into<Expression>,
+    {
+        self.binary(Operator::Like, pattern)
+    }
+
+    /// `self =~ pattern`
+    pub fn matches<E>(self, pattern: E) -> Expression
+    where
+        E: Into<Expression>,
+    {
+        self.binary(Operator::RegexMatch, pattern)
+    }
+
+    /// `self AND other`
+    pub fn and<E>(self, other: E) -> Expression
+    where
+        E: Into<Expression>,
+    {
+        self.binary(Operator::And, other)
+    }
+
+    /// `self OR other`
+    pub fn or<E>(self, other: E) -> Expression
+    where
+        E: Into<Expression>,
+    {
+        self.binary(Operator::Or, other)
+    }
+
+    /// `self + other`
+    pub fn plus<E>(self, other: E) -> Expression
+    where
+        E: Into<Expression>,
+    {
+        self.binary(Operator::Add, other)
+    }
+
+    /// `self - other`
+    pub fn minus<E>(self, other: E) -> Expression
+    where
+        E: Into<Expression>,
+    {
+        self.binary(Operator::Subtract, other)
+    }
+
+    /// `self * other`
+    pub fn times<E>(self, other: E) -> Expression
+    where
+        E: Into<Expression>,
+    {
+        self.binary(Operator::Multiply, other)
+    }
+
+    /// `self / other`
+    pub fn divided_by<E>(self, other: E) -> Expression
+    where
+        E: Into<Expression>,
+    {
+        self.binary(Operator::Divide, other)
+    }
+
+    /// `self % other`
+    pub fn modulo<E>(self, other: E) -> Expression
+    where
+        E: Into<Expression>,
+    {
+        self.binary(Operator::Modulo, other)
+    }
+}
+
+macro_rules! impl_from_value_for_expression {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Expression {
+                fn from(value: $ty) -> Self {
+                    Expression::Value(value.into())
+                }
+            }
+        )*
+    }
+}
+
+impl_from_value_for_expression!(
+    String,
+    bool,
+    f64,
+    f32,
+    isize,
+    i64,
+    i32,
+    i16,
+    i8,
+    usize,
+    u64,
+    u32,
+    u16,
+    u8,
+    Vec<String>,
+    Vec<bool>,
+    Vec<f64>,
+    Vec<f32>,
+    Vec<isize>,
+    Vec<i64>,
+    Vec<i32>,
+    Vec<i16>,
+    Vec<i8>,
+    Vec<usize>,
+    Vec<u64>,
+    Vec<u32>,
+    Vec<u16>,
+    Vec<u8>
+);
+
+impl<'a> From<&'a str> for Expression {
+    fn from(value: &str) -> Self {
+        Expression::Value(value.into())
+    }
+}
+
+impl<'a> From<Vec<&'a str>> for Expression {
+    fn from(value: Vec<&str>) -> Self {
+        Expression::Value(value.into())
+    }
+}
+
+impl From<Value> for Expression {
+    fn from(value: Value) -> Self {
+        Expression::Value(value)
+    }
+}
+
+/// Collects the query string and the bind parameters while a query is
+/// rendered.
+#[derive(Debug)]
+pub(crate) struct Bindings {
+    string: String,
+    params: Vec<(String, Value)>,
+    value_count: usize,
+    collection_count: usize,
+    variable_count: usize,
+}
+
+impl Bindings {
+    pub(crate) fn new() -> Self {
+        Bindings {
+            string: String::new(),
+            params: Vec::new(),
+            value_count: 0,
+            collection_count: 0,
+            variable_count: 0,
+        }
+    }
+
+    /// Adds the given value as bind parameter and returns its name.
+    pub(crate) fn bind_value(&mut self, value: Value) -> String {
+        let name = format!("{}{}", VALUE_PARAM_PREFIX, self.value_count);
+        self.value_count += 1;
+        self.params.push((name.clone(), value));
+        name
+    }
+
+    /// Adds the given collection name as bind parameter and returns the name
+    /// of the parameter.
+    pub(crate) fn bind_collection(&mut self, collection: &str) -> String {
+        let name = format!("{}{}", COLLECTION_PARAM_PREFIX, self.collection_count);
+        self.collection_count += 1;
+        self.params.push((name.clone(), collection.into()));
+        name
+    }
+
+    /// Returns the name of a new variable that is not used anywhere else in
+    /// the query.
+    pub(crate) fn generate_variable(&mut self) -> String {
+        let name = format!("{}{}", GENERATED_VARIABLE_PREFIX, self.variable_count);
+        self.variable_count += 1;
+        name
+    }
+
+    /// Returns the query string rendered so far.
+    pub(crate) fn string(&mut self) -> &mut String {
+        &mut self.string
+    }
+
+    pub(crate) fn into_query(self) -> Query {
+        let mut query = Query::new(self.string);
+        for (name, value) in self.params {
+            query.set_parameter(name, value);
+        }
+        query
+    }
+}
+
+/// Renders an expression into the query string of the given bindings.
+pub(crate) fn render(expression: &Expression, bindings: &mut Bindings) {
+    use self::Expression::*;
+    match *expression {
+        Variable(ref name) => write_name(name, bindings.string()),
+        Attribute(ref expression, ref name) => {
+            render_operand(expression, bindings);
+            bindings.string().push('.');
+            write_name(name, bindings.string());
+        },
+        Index(ref expression, ref index) => {
+            render_operand(expression, bindings);
+            bindings.string().push('[');
+            render(index, bindings);
+            bindings.string().push(']');
+        },
+        Expression::Value(ref value) => {
+            let name = bindings.bind_value(value.clone());
+            bindings.string().push('@');
+            bindings.string().push_str(&name);
+        },
+        Collection(ref collection) => {
+            let name = bindings.bind_collection(collection);
+            bindings.string().push('@');
+            bindings.string().push_str(&name);
+        },
+        Null => bindings.string().push_str("null"),
+        Array(ref elements) => {
+            bindings.string().push('[');
+            render_list(elements, bindings);
+            bindings.string().push(']');
+        },
+        Object(ref attributes) => {
+            bindings.string().push('{');
+            let mut first = true;
+            for &(ref name, ref value) in attributes {
+                if first {
+                    first = false;
+                } else {
+                    bindings.string().push_str(", ");
+                }
+                write_name(name, bindings.string());
+                bindings.string().push_str(": ");
+                render(value, bindings);
+            }
+            bindings.string().push('}');
+        },
+        Range(ref from, ref to) => {
+            render_operand(from, bindings);
+            bindings.string().push_str("..");
+            render_operand(to, bindings);
+        },
+        Binary(ref left, ref operator, ref right) => {
+            render_operand(left, bindings);
+            bindings.string().push(' ');
+            bindings.string().push_str(operator.as_str());
+            bindings.string().push(' ');
+            render_operand(right, bindings);
+        },
+        Not(ref expression) => {
+            bindings.string().push_str("NOT ");
+            render_operand(expression, bindings);
+        },
+        Function(ref name, ref arguments) => {
+            bindings.string().push_str(name.as_str());
+            bindings.string().push('(');
+            render_list(arguments, bindings);
+            bindings.string().push(')');
+        },
+        Subquery(ref query) => {
+            bindings.string().push('(');
+            query.render(bindings);
+            bindings.string().push(')');
+        },
+    }
+}
+
+/// Renders an expression that is an operand of an operator and therefore
+/// is put in parenthesis if it is composite.
+fn render_operand(expression: &Expression, bindings: &mut Bindings) {
+    match *expression {
+        Expression::Binary(..) | Expression::Not(..) | Expression::Range(..) => {
+            bindings.string().push('(');
+            render(expression, bindings);
+            bindings.string().push(')');
+        },
+        _ => render(expression, bindings),
+    }
+}
+
+fn render_list(expressions: &[Expression], bindings: &mut Bindings) {
+    let mut first = true;
+    for expression in expressions {
+        if first {
+            first = false;
+        } else {
+            bindings.string().push_str(", ");
+        }
+        render(expression, bindings);
+    }
+}
+
+/// Writes the name of a variable or attribute and quotes it with backticks
+/// if it is not a valid identifier or a keyword.
+pub(crate) fn write_name(name: &str, string: &mut String) {
+    if is_identifier(name) {
+        string.push_str(name);
+    } else {
+        string.push('`');
+        for c in name.chars() {
+            if c == '`' || c == '\\' {
+                string.push('\\');
+            }
+            string.push(c);
+        }
+        string.push('`');
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    is_name_part(name)
+        && !KEYWORDS
+            .iter()
+            .any(|keyword| keyword.eq_ignore_ascii_case(name))
+}
+
+/// Returns whether the given name is a valid name of a built-in function or
+/// a user defined function qualified by its namespace.
+fn is_function_name(name: &str) -> bool {
+    name.split("::").all(is_name_part)
+}
+
+fn is_name_part(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {},
+        _ => return false,
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
@@ -0,0 +1,128 @@
+use super::*;
+
+fn render_to_query(expression: &Expression) -> Query {
+    let mut bindings = Bindings::new();
+    render(expression, &mut bindings);
+    bindings.into_query()
+}
+
+#[test]
+fn render_attribute_of_variable() {
+    let query = render_to_query(&var("u").field("address").field("city"));
+
+    assert_eq!("u.address.city", query.str());
+}
+
+#[test]
+fn render_attribute_names_that_need_quoting() {
+    let query = render_to_query(&var("u").field("first name").field("filter"));
+
+    assert_eq!("u.`first name`.`filter`", query.str());
+}
+
+#[test]
+fn render_values_as_bind_parameters() {
+    let query = render_to_query(
+        &var("u")
+            .field("name")
+            .eq("O'Neil")
+            .and(var("u").field("age").ge(21u8)),
+    );
+
+    assert_eq!("(u.name == @value0) AND (u.age >= @value1)", query.str());
+    assert_eq!(Some(&"O'Neil".to_owned()), query.parameter("value0"));
+    assert_eq!(Some(&21u8), query.parameter("value1"));
+}
+
+#[test]
+fn render_collection_as_bind_parameter() {
+    let query = render_to_query(&collection("customers"));
+
+    assert_eq!("@@collection0", query.str());
+    assert_eq!(
+        Some(&"customers".to_owned()),
+        query.parameter("@collection0")
+    );
+}
+
+#[test]
+fn render_object_array_and_index() {
+    let query = render_to_query(&object(vec![
+        ("name", var("u").field("name")),
+        ("tags", array(vec![var("u").field("tags").at(0), null()])),
+    ]));
+
+    assert_eq!("{name: u.name, tags: [u.tags[@value0], null]}", query.str());
+    assert_eq!(Some(&0i32), query.parameter("value0"));
+}
+
+#[test]
+fn render_function_call_with_negation() {
+    let query = render_to_query(&not(function(
+        "HAS",
+        vec![var("u"), Expression::from("email")],
+    )));
+
+    assert_eq!("NOT HAS(u, @value0)", query.str());
+}
+
+#[test]
+fn render_call_of_user_defined_function() {
+    let query = render_to_query(&function(
+        "MYFUNCTIONS::TEMPERATURE::CELSIUS_TO_FAHRENHEIT",
+        vec![var("t")],
+    ));
+
+    assert_eq!(
+        "MYFUNCTIONS::TEMPERATURE::CELSIUS_TO_FAHRENHEIT(t)",
+        query.str()
+    );
+}
+
+#[test]
+#[should_panic(expected = "invalid AQL function name")]
+fn function_with_name_containing_query_text_panics() {
+    function(
+        "LENGTH(u) FOR x IN users REMOVE x IN users RETURN LENGTH",
+        vec![var("u")],
+    );
+}
+
+#[test]
+#[should_panic(expected = "invalid AQL function name")]
+fn function_with_empty_namespace_panics() {
+    function("MYFUNCTIONS::", Vec::<Expression>::new());
+}
+
+#[test]
+fn is_function_name_for_valid_and_invalid_names() {
+    assert!(is_function_name("LENGTH"));
+    assert!(is_function_name("_private"));
+    assert!(is_function_name("my_functions::v2::score"));
+    assert!(!is_function_name(""));
+    assert!(!is_function_name("2LENGTH"));
+    assert!(!is_function_name("LEN GTH"));
+    assert!(!is_function_name("a:b"));
+    assert!(!is_function_name("::a"));
+}
+
+#[test]
+fn render_keywords_prune_and_search_as_quoted_names() {
+    let query = render_to_query(&var("u").field("prune").field("search"));
+
+    assert_eq!("u.`prune`.`search`", query.str());
+}
+
+#[test]
+fn render_range_as_operand() {
+    let query = render_to_query(&var("i").is_in(range(1, var("n"))));
+
+    assert_eq!("i IN (@value0..n)", query.str());
+}
+
+#[test]
+fn render_backtick_in_name_is_escaped() {
+    let query = render_to_query(&var("u").field("a`b"));
+
+    assert_eq!("u.`a\\`b`", query.str());
+}
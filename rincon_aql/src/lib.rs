@@ -1,3 +1,62 @@
+//! A typesafe builder for [AQL] queries.
+//!
+//! The `QueryBuilder` composes AQL queries from high-level operations like
+//! `FOR`, `FILTER`, `SORT`, `LIMIT`, `COLLECT`, `LET` and `RETURN`, data
+//! modification operations like `INSERT`, `UPDATE`, `REPLACE`, `REMOVE` and
+//! `UPSERT` as well as graph traversals. Conditions and projections are
+//! composed of typed `Expression`s.
+//!
+//! Values of Rust types and names of collections are never written into the
+//! query string. Instead bind parameters are generated automatically and set
+//! on the resulting `Query`. This way values do not need to be quoted or
+//! escaped.
+//!
+//! # Example
+//!
+//! ```
+//! # extern crate rincon_aql;
+//! # fn main() {
+//! use rincon_aql::{collection, object, var, QueryBuilder};
+//!
+//! let query = QueryBuilder::new()
+//!     .for_in("u", collection("users"))
+//!     .filter(var("u").field("name").eq("O'Neil"))
+//!     .returns(object(vec![("name", var("u").field("name"))]))
+//!     .build();
+//!
+//! assert_eq!(
+//!     "FOR u IN @@collection0 FILTER u.name == @value0 RETURN {name: u.name}",
+//!     query.str()
+//! );
+//! assert_eq!(Some(&"O'Neil".to_owned()), query.parameter("value0"));
+//! # }
+//! ```
+//!
+//! # Checked query strings
+//!
+//! Queries written as plain strings can be checked at compile time with the
+//! `aql!` macro. It rejects obvious syntax errors like unbalanced brackets
+//! or a missing `RETURN` and sets the bind parameters of the query from
+//! variables in scope or from explicitly given values.
+//!
+//! ```
+//! # extern crate rincon_aql;
+//! # fn main() {
+//! use rincon_aql::aql;
+//!
+//! let min_age = 18;
+//! let query = aql!(
+//!     "FOR u IN @@users FILTER u.age >= @min_age RETURN u",
+//!     users = "customers",
+//! );
+//!
+//! assert_eq!(Some(&18), query.parameter("min_age"));
+//! assert_eq!(Some(&"customers".to_owned()), query.parameter("@users"));
+//! # }
+//! ```
+//!
+//! [AQL]: https://docs.arangodb.com/3.2/AQL/index.html
+
 #![doc(html_root_url = "https://docs.rs/rincon_aql/0.1.0")]
 #![warn(
     missing_copy_implementations,
@@ -11,4 +70,17 @@
     unused_qualifications,
 )]
 
+extern crate rincon_aql_macros;
 extern crate rincon_core;
+
+pub mod expression;
+pub mod query;
+
+pub use expression::{
+    array, collection, function, not, null, object, range, subquery, value, var, Expression,
+    FunctionName, Operator,
+};
+pub use query::{Collect, Direction, Graph, Operation, QueryBuilder, SortDirection, Traversal};
+
+pub use rincon_aql_macros::aql;
+pub use rincon_core::api::query::Query;
@@ -0,0 +1,724 @@
+//! Building of AQL queries.
+//!
+//! A `QueryBuilder` holds a sequence of high-level operations like `FOR`,
+//! `FILTER` and `RETURN`. When the query is built all values are passed as
+//! bind parameters to the resulting `Query`.
+
+#[cfg(test)]
+mod tests;
+
+use rincon_core::api::query::Query;
+
+use expression::{collection, render, write_name, Bindings, Expression};
+
+/// The direction used for sorting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SortDirection {
+    /// Ascending order
+    Asc,
+    /// Descending order
+    Desc,
+}
+
+impl SortDirection {
+    fn as_str(&self) -> &'static str {
+        match *self {
+            SortDirection::Asc => "ASC",
+            SortDirection::Desc => "DESC",
+        }
+    }
+}
+
+/// The direction of edges followed in a graph traversal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Follow outgoing edges
+    Outbound,
+    /// Follow incoming edges
+    Inbound,
+    /// Follow edges in both directions
+    Any,
+}
+
+impl Direction {
+    fn as_str(&self) -> &'static str {
+        match *self {
+            Direction::Outbound => "OUTBOUND",
+            Direction::Inbound => "INBOUND",
+            Direction::Any => "ANY",
+        }
+    }
+}
+
+/// The graph a traversal is performed on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Graph {
+    /// A named graph
+    Named(String),
+    /// An anonymous graph given by a list of edge collections
+    EdgeCollections(Vec<String>),
+}
+
+/// A graph traversal used in a `FOR` operation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Traversal {
+    vertex: String,
+    edge: Option<String>,
+    path: Option<String>,
+    min_depth: u32,
+    max_depth: u32,
+    direction: Direction,
+    start_vertex: Expression,
+    graph: Graph,
+}
+
+impl Traversal {
+    /// Creates a new traversal that starts at the given vertex and follows
+    /// edges in the given direction within the given graph.
+    ///
+    /// The traversal has a depth of one by default.
+    pub fn new<V, S>(vertex: V, direction: Direction, start_vertex: S, graph: Graph) -> Self
+    where
+        V: Into<String>,
+        S: Into<Expression>,
+    {
+        Traversal {
+            vertex: vertex.into(),
+            edge: None,
+            path: None,
+            min_depth: 1,
+            max_depth: 1,
+            direction,
+            start_vertex: start_vertex.into(),
+            graph,
+        }
+    }
+
+    /// Creates a new traversal that follows outgoing edges in the named
+    /// graph.
+    pub fn outbound<V, S, G>(vertex: V, start_vertex: S, graph_name: G) -> Self
+    where
+        V: Into<String>,
+        S: Into<Expression>,
+        G: Into<String>,
+    {
+        Traversal::new(
+            vertex,
+            Direction::Outbound,
+            start_vertex,
+            Graph::Named(graph_name.into()),
+        )
+    }
+
+    /// Creates a new traversal that follows incoming edges in the named
+    /// graph.
+    pub fn inbound<V, S, G>(vertex: V, start_vertex: S, graph_name: G) -> Self
+    where
+        V: Into<String>,
+        S: Into<Expression>,
+        G: Into<String>,
+    {
+        Traversal::new(
+            vertex,
+            Direction::Inbound,
+            start_vertex,
+            Graph::Named(graph_name.into()),
+        )
+    }
+
+    /// Creates a new traversal that follows edges in both directions in the
+    /// named graph.
+    pub fn any<V, S, G>(vertex: V, start_vertex: S, graph_name: G) -> Self
+    where
+        V: Into<String>,
+        S: Into<Expression>,
+        G: Into<String>,
+    {
+        Traversal::new(
+            vertex,
+            Direction::Any,
+            start_vertex,
+            Graph::Named(graph_name.into()),
+        )
+    }
+
+    /// Sets the name of the variable for the edges.
+    pub fn with_edge<E>(mut self, edge: E) -> Self
+    where
+        E: Into<String>,
+    {
+        self.edge = Some(edge.into());
+        self
+    }
+
+    /// Sets the name of the variable for the paths.
+    ///
+    /// If no edge variable is set, a variable with a generated name is used
+    /// for the edges.
+    pub fn with_path<P>(mut self, path: P) -> Self
+    where
+        P: Into<String>,
+    {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Sets the minimal and maximal depth of the traversal.
+    pub fn with_depth(mut self, min_depth: u32, max_depth: u32) -> Self {
+        self.min_depth = min_depth;
+        self.max_depth = max_depth;
+        self
+    }
+
+    fn render(&self, bindings: &mut Bindings) {
+        write_name(&self.vertex, bindings.string());
+        match (&self.edge, &self.path) {
+            (&Some(ref edge), &Some(ref path)) => {
+                bindings.string().push_str(", ");
+                write_name(edge, bindings.string());
+                bindings.string().push_str(", ");
+                write_name(path, bindings.string());
+            },
+            (&Some(ref edge), &None) => {
+                bindings.string().push_str(", ");
+                write_name(edge, bindings.string());
+            },
+            (&None, &Some(ref path)) => {
+                let edge = bindings.generate_variable();
+                bindings.string().push_str(", ");
+                bindings.string().push_str(&edge);
+                bindings.string().push_str(", ");
+                write_name(path, bindings.string());
+            },
+            (&None, &None) => {},
+        }
+        let depth = format!(" IN {}..{} ", self.min_depth, self.max_depth);
+        bindings.string().push_str(&depth);
+        bindings.string().push_str(self.direction.as_str());
+        bindings.string().push(' ');
+        render(&self.start_vertex, bindings);
+        match self.graph {
+            Graph::Named(ref name) => {
+                let name = bindings.bind_value(name.as_str().into());
+                bindings.string().push_str(" GRAPH @");
+                bindings.string().push_str(&name);
+            },
+            Graph::EdgeCollections(ref edge_collections) => {
+                let mut first = true;
+                for edge_collection in edge_collections {
+                    if first {
+                        first = false;
+                        bindings.string().push(' ');
+                    } else {
+                        bindings.string().push_str(", ");
+                    }
+                    render(&collection(edge_collection.as_str()), bindings);
+                }
+            },
+        }
+    }
+}
+
+/// A `COLLECT` operation used to group or aggregate documents.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Collect {
+    groups: Vec<(String, Expression)>,
+    aggregates: Vec<(String, Expression)>,
+    into: Option<String>,
+    count_into: Option<String>,
+}
+
+impl Collect {
+    /// Creates a new `COLLECT` operation without any groups.
+    pub fn new() -> Self {
+        Collect::default()
+    }
+
+    /// Adds a group with the given variable name and expression.
+    pub fn group<N, E>(mut self, name: N, expression: E) -> Self
+    where
+        N: Into<String>,
+        E: Into<Expression>,
+    {
+        self.groups.push((name.into(), expression.into()));
+        self
+    }
+
+    /// Adds an aggregate with the given variable name and expression.
+    pub fn aggregate<N, E>(mut self, name: N, expression: E) -> Self
+    where
+        N: Into<String>,
+        E: Into<Expression>,
+    {
+        self.aggregates.push((name.into(), expression.into()));
+        self
+    }
+
+    /// Sets the name of the variable the grouped documents are stored into.
+    pub fn into_variable<N>(mut self, name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        self.into = Some(name.into());
+        self.count_into = None;
+        self
+    }
+
+    /// Sets the name of the variable the number of grouped documents is
+    /// stored into.
+    pub fn with_count_into<N>(mut self, name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        self.count_into = Some(name.into());
+        self.into = None;
+        self
+    }
+
+    fn render(&self, bindings: &mut Bindings) {
+        bindings.string().push_str("COLLECT");
+        render_assignments(&self.groups, bindings);
+        if !self.aggregates.is_empty() {
+            bindings.string().push_str(" AGGREGATE");
+            render_assignments(&self.aggregates, bindings);
+        }
+        if let Some(ref into) = self.into {
+            bindings.string().push_str(" INTO ");
+            write_name(into, bindings.string());
+        }
+        if let Some(ref count_into) = self.count_into {
+            bindings.string().push_str(" WITH COUNT INTO ");
+            write_name(count_into, bindings.string());
+        }
+    }
+}
+
+fn render_assignments(assignments: &[(String, Expression)], bindings: &mut Bindings) {
+    let mut first = true;
+    for &(ref name, ref expression) in assignments {
+        if first {
+            first = false;
+            bindings.string().push(' ');
+        } else {
+            bindings.string().push_str(", ");
+        }
+        write_name(name, bindings.string());
+        bindings.string().push_str(" = ");
+        render(expression, bindings);
+    }
+}
+
+/// A high-level operation of an AQL query.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operation {
+    /// `FOR variable IN expression`
+    For(String, Expression),
+    /// `FOR vertex, edge, path IN min..max direction start GRAPH graph`
+    Traverse(Traversal),
+    /// `FILTER condition`
+    Filter(Expression),
+    /// `SORT expression direction, ...`
+    Sort(Vec<(Expression, SortDirection)>),
+    /// `LIMIT offset, count`
+    Limit(Option<u64>, u64),
+    /// `LET variable = expression`
+    Let(String, Expression),
+    /// `COLLECT ...`
+    Collect(Collect),
+    /// `RETURN expression` or `RETURN DISTINCT expression`
+    Return(bool, Expression),
+    /// `INSERT document INTO collection`
+    Insert(Expression, String),
+    /// `UPDATE key WITH document IN collection`
+    Update(Expression, Option<Expression>, String),
+    /// `REPLACE key WITH document IN collection`
+    Replace(Expression, Option<Expression>, String),
+    /// `REMOVE key IN collection`
+    Remove(Expression, String),
+    /// `UPSERT search INSERT insert UPDATE update IN collection` or
+    /// `UPSERT search INSERT insert REPLACE update IN collection` if the flag
+    /// is set
+    Upsert(Expression, Expression, Expression, bool, String),
+}
+
+impl Operation {
+    fn render(&self, bindings: &mut Bindings) {
+        use self::Operation::*;
+        match *self {
+            For(ref variable, ref expression) => {
+                bindings.string().push_str("FOR ");
+                write_name(variable, bindings.string());
+                bindings.string().push_str(" IN ");
+                render(expression, bindings);
+            },
+            Traverse(ref traversal) => {
+                bindings.string().push_str("FOR ");
+                traversal.render(bindings);
+            },
+            Filter(ref condition) => {
+                bindings.string().push_str("FILTER ");
+                render(condition, bindings);
+            },
+            Sort(ref criteria) => {
+                bindings.string().push_str("SORT ");
+                let mut first = true;
+                for &(ref expression, ref direction) in criteria {
+                    if first {
+                        first = false;
+                    } else {
+                        bindings.string().push_str(", ");
+                    }
+                    render(expression, bindings);
+                    bindings.string().push(' ');
+                    bindings.string().push_str(direction.as_str());
+                }
+            },
+            Limit(offset, count) => {
+                let limit = match offset {
+                    Some(offset) => format!("LIMIT {}, {}", offset, count),
+                    None => format!("LIMIT {}", count),
+                };
+                bindings.string().push_str(&limit);
+            },
+            Let(ref variable, ref expression) => {
+                bindings.string().push_str("LET ");
+                write_name(variable, bindings.string());
+                bindings.string().push_str(" = ");
+                render(expression, bindings);
+            },
+            Operation::Collect(ref collect) => collect.render(bindings),
+            Return(distinct, ref expression) => {
+                if distinct {
+                    bindings.string().push_str("RETURN DISTINCT ");
+                } else {
+                    bindings.string().push_str("RETURN ");
+                }
+                render(expression, bindings);
+            },
+            Insert(ref document, ref collection_name) => {
+                bindings.string().push_str("INSERT ");
+                render(document, bindings);
+                render_in_collection(" INTO ", collection_name, bindings);
+            },
+            Update(ref key, ref document, ref collection_name) => {
+                bindings.string().push_str("UPDATE ");
+                render_modification(key, document, collection_name, bindings);
+            },
+            Replace(ref key, ref document, ref collection_name) => {
+                bindings.string().push_str("REPLACE ");
+                render_modification(key, document, collection_name, bindings);
+            },
+            Remove(ref key, ref collection_name) => {
+                bindings.string().push_str("REMOVE ");
+                render(key, bindings);
+                render_in_collection(" IN ", collection_name, bindings);
+            },
+            Upsert(ref search, ref insert, ref update, replace, ref collection_name) => {
+                bindings.string().push_str("UPSERT ");
+                render(search, bindings);
+                bindings.string().push_str(" INSERT ");
+                render(insert, bindings);
+                if replace {
+                    bindings.string().push_str(" REPLACE ");
+                } else {
+                    bindings.string().push_str(" UPDATE ");
+                }
+                render(update, bindings);
+                render_in_collection(" IN ", collection_name, bindings);
+            },
+        }
+    }
+}
+
+fn render_modification(
+    key: &Expression,
+    document: &Option<Expression>,
+    collection_name: &str,
+    bindings: &mut Bindings,
+) {
+    render(key, bindings);
+    if let Some(ref document) = *document {
+        bindings.string().push_str(" WITH ");
+        render(document, bindings);
+    }
+    render_in_collection(" IN ", collection_name, bindings);
+}
+
+fn render_in_collection(keyword: &str, collection_name: &str, bindings: &mut Bindings) {
+    bindings.string().push_str(keyword);
+    render(&collection(collection_name), bindings);
+}
+
+/// A builder for AQL queries.
+///
+/// The operations of the query are added in the order they appear in the
+/// query by calling the according methods. The `build()` method renders the
+/// query string and generates bind parameters for all values and collection
+/// names.
+///
+/// # Example
+///
+/// ```
+/// # extern crate rincon_aql;
+/// # fn main() {
+/// use rincon_aql::{collection, var, QueryBuilder, SortDirection};
+///
+/// let query = QueryBuilder::new()
+///     .for_in("c", collection("customers"))
+///     .filter(var("c").field("age").ge(18))
+///     .sort(var("c").field("name"), SortDirection::Asc)
+///     .limit(10)
+///     .returns(var("c").field("name"))
+///     .build();
+///
+/// assert_eq!(
+///     "FOR c IN @@collection0 FILTER c.age >= @value0 SORT c.name ASC \
+///      LIMIT 10 RETURN c.name",
+///     query.str()
+/// );
+/// assert_eq!(Some(&"customers".to_owned()), query.parameter("@collection0"));
+/// assert_eq!(Some(&18), query.parameter::<i32>("value0"));
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct QueryBuilder {
+    operations: Vec<Operation>,
+}
+
+impl QueryBuilder {
+    /// Creates a new empty `QueryBuilder`.
+    pub fn new() -> Self {
+        QueryBuilder::default()
+    }
+
+    /// Returns the operations added to this builder so far.
+    pub fn operations(&self) -> &[Operation] {
+        &self.operations
+    }
+
+    /// Adds the given operation.
+    pub fn operation(mut self, operation: Operation) -> Self {
+        self.operations.push(operation);
+        self
+    }
+
+    /// Adds a `FOR variable IN expression` operation.
+    pub fn for_in<V, E>(self, variable: V, expression: E) -> Self
+    where
+        V: Into<String>,
+        E: Into<Expression>,
+    {
+        self.operation(Operation::For(variable.into(), expression.into()))
+    }
+
+    /// Adds a `FOR` operation that performs the given graph traversal.
+    pub fn traverse(self, traversal: Traversal) -> Self {
+        self.operation(Operation::Traverse(traversal))
+    }
+
+    /// Adds a `FILTER condition` operation.
+    pub fn filter<E>(self, condition: E) -> Self
+    where
+        E: Into<Expression>,
+    {
+        self.operation(Operation::Filter(condition.into()))
+    }
+
+    /// Adds a sort criterion.
+    ///
+    /// Consecutive calls of this method are combined into one `SORT`
+    /// operation.
+    pub fn sort<E>(mut self, expression: E, direction: SortDirection) -> Self
+    where
+        E: Into<Expression>,
+    {
+        if let Some(&mut Operation::Sort(ref mut criteria)) = self.operations.last_mut() {
+            criteria.push((expression.into(), direction));
+            return self;
+        }
+        self.operation(Operation::Sort(vec![(expression.into(), direction)]))
+    }
+
+    /// Adds a `LIMIT count` operation.
+    pub fn limit(self, count: u64) -> Self {
+        self.operation(Operation::Limit(None, count))
+    }
+
+    /// Adds a `LIMIT offset, count` operation.
+    pub fn limit_with_offset(self, offset: u64, count: u64) -> Self {
+        self.operation(Operation::Limit(Some(offset), count))
+    }
+
+    /// Adds a `LET variable = expression` operation.
+    pub fn let_var<V, E>(self, variable: V, expression: E) -> Self
+    where
+        V: Into<String>,
+        E: Into<Expression>,
+    {
+        self.operation(Operation::Let(variable.into(), expression.into()))
+    }
+
+    /// Adds the given `COLLECT` operation.
+    pub fn collect(self, collect: Collect) -> Self {
+        self.operation(Operation::Collect(collect))
+    }
+
+    /// Adds a `RETURN expression` operation.
+    pub fn returns<E>(self, expression: E) -> Self
+    where
+        E: Into<Expression>,
+    {
+        self.operation(Operation::Return(false, expression.into()))
+    }
+
+    /// Adds a `RETURN DISTINCT expression` operation.
+    pub fn returns_distinct<E>(self, expression: E) -> Self
+    where
+        E: Into<Expression>,
+    {
+        self.operation(Operation::Return(true, expression.into()))
+    }
+
+    /// Adds an `INSERT document INTO collection` operation.
+    pub fn insert<E, C>(self, document: E, collection_name: C) -> Self
+    where
+        E: Into<Expression>,
+        C: Into<String>,
+    {
+        self.operation(Operation::Insert(document.into(), collection_name.into()))
+    }
+
+    /// Adds an `UPDATE document IN collection` operation.
+    ///
+    /// The given document must contain the `_key` attribute.
+    pub fn update<E, C>(self, document: E, collection_name: C) -> Self
+    where
+        E: Into<Expression>,
+        C: Into<String>,
+    {
+        self.operation(Operation::Update(
+            document.into(),
+            None,
+            collection_name.into(),
+        ))
+    }
+
+    /// Adds an `UPDATE key WITH document IN collection` operation.
+    pub fn update_with<K, E, C>(self, key: K, document: E, collection_name: C) -> Self
+    where
+        K: Into<Expression>,
+        E: Into<Expression>,
+        C: Into<String>,
+    {
+        self.operation(Operation::Update(
+            key.into(),
+            Some(document.into()),
+            collection_name.into(),
+        ))
+    }
+
+    /// Adds a `REPLACE document IN collection` operation.
+    ///
+    /// The given document must contain the `_key` attribute.
+    pub fn replace<E, C>(self, document: E, collection_name: C) -> Self
+    where
+        E: Into<Expression>,
+        C: Into<String>,
+    {
+        self.operation(Operation::Replace(
+            document.into(),
+            None,
+            collection_name.into(),
+        ))
+    }
+
+    /// Adds a `REPLACE key WITH document IN collection` operation.
+    pub fn replace_with<K, E, C>(self, key: K, document: E, collection_name: C) -> Self
+    where
+        K: Into<Expression>,
+        E: Into<Expression>,
+        C: Into<String>,
+    {
+        self.operation(Operation::Replace(
+            key.into(),
+            Some(document.into()),
+            collection_name.into(),
+        ))
+    }
+
+    /// Adds a `REMOVE key IN collection` operation.
+    pub fn remove<K, C>(self, key: K, collection_name: C) -> Self
+    where
+        K: Into<Expression>,
+        C: Into<String>,
+    {
+        self.operation(Operation::Remove(key.into(), collection_name.into()))
+    }
+
+    /// Adds an `UPSERT search INSERT insert UPDATE update IN collection`
+    /// operation.
+    pub fn upsert<S, I, U, C>(self, search: S, insert: I, update: U, collection_name: C) -> Self
+    where
+        S: Into<Expression>,
+        I: Into<Expression>,
+        U: Into<Expression>,
+        C: Into<String>,
+    {
+        self.operation(Operation::Upsert(
+            search.into(),
+            insert.into(),
+            update.into(),
+            false,
+            collection_name.into(),
+        ))
+    }
+
+    /// Adds an `UPSERT search INSERT insert REPLACE replace IN collection`
+    /// operation.
+    pub fn upsert_replace<S, I, R, C>(
+        self,
+        search: S,
+        insert: I,
+        replace: R,
+        collection_name: C,
+    ) -> Self
+    where
+        S: Into<Expression>,
+        I: Into<Expression>,
+        R: Into<Expression>,
+        C: Into<String>,
+    {
+        self.operation(Operation::Upsert(
+            search.into(),
+            insert.into(),
+            replace.into(),
+            true,
+            collection_name.into(),
+        ))
+    }
+
+    /// Builds the `Query` with the query string and the bind parameters.
+    pub fn build(&self) -> Query {
+        let mut bindings = Bindings::new();
+        self.render(&mut bindings);
+        bindings.into_query()
+    }
+
+    pub(crate) fn render(&self, bindings: &mut Bindings) {
+        let mut first = true;
+        for operation in &self.operations {
+            if first {
+                first = false;
+            } else {
+                bindings.string().push(' ');
+            }
+            operation.render(bindings);
+        }
+    }
+}
+
+impl From<QueryBuilder> for Query {
+    fn from(builder: QueryBuilder) -> Self {
+        builder.build()
+    }
+}
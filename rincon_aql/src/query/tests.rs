@@ -0,0 +1,243 @@
+use super::*;
+use expression::{function, object, subquery, var};
+
+#[test]
+fn build_for_filter_sort_limit_return() {
+    let query = QueryBuilder::new()
+        .for_in("u", collection("users"))
+        .filter(var("u").field("active").eq(true))
+        .sort(var("u").field("age"), SortDirection::Desc)
+        .sort(var("u").field("name"), SortDirection::Asc)
+        .limit_with_offset(20, 10)
+        .returns(var("u"))
+        .build();
+
+    assert_eq!(
+        "FOR u IN @@collection0 FILTER u.active == @value0 \
+         SORT u.age DESC, u.name ASC LIMIT 20, 10 RETURN u",
+        query.str()
+    );
+    assert_eq!(Some(&"users".to_owned()), query.parameter("@collection0"));
+    assert_eq!(Some(&true), query.parameter("value0"));
+}
+
+#[test]
+fn build_let_and_return_distinct() {
+    let query = QueryBuilder::new()
+        .for_in("u", collection("users"))
+        .let_var("city", var("u").field("address").field("city"))
+        .returns_distinct(var("city"))
+        .build();
+
+    assert_eq!(
+        "FOR u IN @@collection0 LET city = u.address.city RETURN DISTINCT city",
+        query.str()
+    );
+}
+
+#[test]
+fn build_collect_with_aggregate_into() {
+    let query = QueryBuilder::new()
+        .for_in("o", collection("orders"))
+        .collect(
+            Collect::new()
+                .group("customer", var("o").field("customer"))
+                .aggregate("total", function("SUM", vec![var("o").field("amount")]))
+                .into_variable("orders"),
+        )
+        .returns(object(vec![
+            ("customer", var("customer")),
+            ("total", var("total")),
+        ]))
+        .build();
+
+    assert_eq!(
+        "FOR o IN @@collection0 COLLECT customer = o.customer \
+         AGGREGATE total = SUM(o.amount) INTO orders \
+         RETURN {customer: customer, total: total}",
+        query.str()
+    );
+}
+
+#[test]
+fn build_collect_with_count_into() {
+    let query = QueryBuilder::new()
+        .for_in("u", collection("users"))
+        .collect(
+            Collect::new()
+                .group("age", var("u").field("age"))
+                .with_count_into("count"),
+        )
+        .returns(var("count"))
+        .build();
+
+    assert_eq!(
+        "FOR u IN @@collection0 COLLECT age = u.age WITH COUNT INTO count RETURN count",
+        query.str()
+    );
+}
+
+#[test]
+fn build_graph_traversal_in_named_graph() {
+    let query = QueryBuilder::new()
+        .traverse(
+            Traversal::outbound("v", "persons/alice", "social")
+                .with_edge("e")
+                .with_path("p")
+                .with_depth(1, 3),
+        )
+        .returns(var("p"))
+        .build();
+
+    assert_eq!(
+        "FOR v, e, p IN 1..3 OUTBOUND @value0 GRAPH @value1 RETURN p",
+        query.str()
+    );
+    assert_eq!(Some(&"persons/alice".to_owned()), query.parameter("value0"));
+    assert_eq!(Some(&"social".to_owned()), query.parameter("value1"));
+}
+
+#[test]
+fn build_graph_traversals_with_path_but_without_edge_variable() {
+    let query = QueryBuilder::new()
+        .let_var("e", 42)
+        .traverse(Traversal::outbound("v", "persons/alice", "social").with_path("p"))
+        .traverse(Traversal::outbound("w", var("v"), "social").with_path("q"))
+        .returns(var("e"))
+        .build();
+
+    assert_eq!(
+        "LET e = @value0 \
+         FOR v, _rincon_var0, p IN 1..1 OUTBOUND @value1 GRAPH @value2 \
+         FOR w, _rincon_var1, q IN 1..1 OUTBOUND v GRAPH @value3 RETURN e",
+        query.str()
+    );
+}
+
+#[test]
+fn build_graph_traversal_over_edge_collections() {
+    let query = QueryBuilder::new()
+        .traverse(Traversal::new(
+            "v",
+            Direction::Any,
+            "persons/alice",
+            Graph::EdgeCollections(vec!["knows".to_owned(), "likes".to_owned()]),
+        ))
+        .returns(var("v"))
+        .build();
+
+    assert_eq!(
+        "FOR v IN 1..1 ANY @value0 @@collection0, @@collection1 RETURN v",
+        query.str()
+    );
+    assert_eq!(Some(&"knows".to_owned()), query.parameter("@collection0"));
+    assert_eq!(Some(&"likes".to_owned()), query.parameter("@collection1"));
+}
+
+#[test]
+fn build_insert_returning_new() {
+    let query = QueryBuilder::new()
+        .insert(
+            object(vec![
+                ("name", Expression::from("Jane Doe")),
+                ("age", Expression::from(42)),
+            ]),
+            "users",
+        )
+        .returns(var("NEW"))
+        .build();
+
+    assert_eq!(
+        "INSERT {name: @value0, age: @value1} INTO @@collection0 RETURN NEW",
+        query.str()
+    );
+    assert_eq!(Some(&"Jane Doe".to_owned()), query.parameter("value0"));
+    assert_eq!(Some(&42), query.parameter::<i32>("value1"));
+    assert_eq!(Some(&"users".to_owned()), query.parameter("@collection0"));
+}
+
+#[test]
+fn build_update_with_and_replace() {
+    let query = QueryBuilder::new()
+        .for_in("u", collection("users"))
+        .update_with(var("u"), object(vec![("active", false)]), "users")
+        .build();
+
+    assert_eq!(
+        "FOR u IN @@collection0 UPDATE u WITH {active: @value0} IN @@collection1",
+        query.str()
+    );
+
+    let query = QueryBuilder::new()
+        .for_in("u", collection("users"))
+        .replace(var("u"), "archive")
+        .build();
+
+    assert_eq!(
+        "FOR u IN @@collection0 REPLACE u IN @@collection1",
+        query.str()
+    );
+}
+
+#[test]
+fn build_remove() {
+    let query = QueryBuilder::new()
+        .for_in("u", collection("users"))
+        .filter(var("u").field("active").eq(false))
+        .remove(var("u").field("_key"), "users")
+        .build();
+
+    assert_eq!(
+        "FOR u IN @@collection0 FILTER u.active == @value0 REMOVE u._key IN @@collection1",
+        query.str()
+    );
+}
+
+#[test]
+fn build_upsert() {
+    let query = QueryBuilder::new()
+        .upsert(
+            object(vec![("name", "superuser")]),
+            object(vec![
+                ("name", Expression::from("superuser")),
+                ("logins", Expression::from(1)),
+            ]),
+            object(vec![("logins", var("OLD").field("logins").plus(1))]),
+            "users",
+        )
+        .build();
+
+    assert_eq!(
+        "UPSERT {name: @value0} INSERT {name: @value1, logins: @value2} \
+         UPDATE {logins: OLD.logins + @value3} IN @@collection0",
+        query.str()
+    );
+}
+
+#[test]
+fn build_query_with_subquery() {
+    let query = QueryBuilder::new()
+        .for_in("u", collection("users"))
+        .let_var(
+            "orders",
+            subquery(
+                QueryBuilder::new()
+                    .for_in("o", collection("orders"))
+                    .filter(var("o").field("user").eq(var("u").field("_key")))
+                    .limit(3)
+                    .returns(var("o")),
+            ),
+        )
+        .filter(var("u").field("age").gt(30))
+        .returns(object(vec![("user", var("u")), ("orders", var("orders"))]))
+        .build();
+
+    assert_eq!(
+        "FOR u IN @@collection0 LET orders = (FOR o IN @@collection1 \
+         FILTER o.user == u._key LIMIT 3 RETURN o) FILTER u.age > @value0 \
+         RETURN {user: u, orders: orders}",
+        query.str()
+    );
+    assert_eq!(Some(&"orders".to_owned()), query.parameter("@collection1"));
+    assert_eq!(Some(&30), query.parameter::<i32>("value0"));
+}
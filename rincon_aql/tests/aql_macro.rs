@@ -0,0 +1,39 @@
+extern crate rincon_aql;
+
+use rincon_aql::aql;
+
+#[test]
+fn aql_takes_bind_parameters_from_variables_in_scope() {
+    let min_age = 18;
+    let users = "customers";
+
+    let query = aql!("FOR u IN @@users FILTER u.age >= @min_age RETURN u");
+
+    assert_eq!(
+        "FOR u IN @@users FILTER u.age >= @min_age RETURN u",
+        query.str()
+    );
+    assert_eq!(Some(&18), query.parameter("min_age"));
+    assert_eq!(Some(&"customers".to_owned()), query.parameter("@users"));
+}
+
+#[test]
+fn aql_does_not_shadow_variable_named_query() {
+    let query = "Jane";
+
+    let aql_query = aql!("FOR u IN users FILTER u.name == @query RETURN u");
+
+    assert_eq!(Some(&"Jane".to_owned()), aql_query.parameter("query"));
+}
+
+#[test]
+fn aql_does_not_shadow_variable_named_query_in_explicit_value() {
+    let query = "Jane";
+
+    let aql_query = aql!(
+        "FOR u IN users FILTER u.name == @name RETURN u",
+        name = query,
+    );
+
+    assert_eq!(Some(&"Jane".to_owned()), aql_query.parameter("name"));
+}
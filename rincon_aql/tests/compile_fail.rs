@@ -0,0 +1,7 @@
+extern crate trybuild;
+
+#[test]
+fn aql_reports_errors_at_compile_time() {
+    let tests = trybuild::TestCases::new();
+    tests.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,7 @@
+extern crate rincon_aql;
+
+use rincon_aql::aql;
+
+fn main() {
+    let _query = aql!("FOR u IN users FILTER u.age >= @min_age RETURN u");
+}
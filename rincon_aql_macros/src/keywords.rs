@@ -0,0 +1,45 @@
+// The reserved keywords of AQL.
+//
+// This file is included by the `syntax` module of this crate and by the
+// `expression` module of the `rincon_aql` crate, so that the `aql!` macro and
+// the query builder agree on which names must be quoted.
+
+/// Reserved keywords of AQL that must be quoted when used as names.
+const KEYWORDS: &[&str] = &[
+    "AGGREGATE",
+    "ALL",
+    "AND",
+    "ANY",
+    "ASC",
+    "COLLECT",
+    "DESC",
+    "DISTINCT",
+    "FALSE",
+    "FILTER",
+    "FOR",
+    "GRAPH",
+    "IN",
+    "INBOUND",
+    "INSERT",
+    "INTO",
+    "K_SHORTEST_PATHS",
+    "LET",
+    "LIKE",
+    "LIMIT",
+    "NONE",
+    "NOT",
+    "NULL",
+    "OR",
+    "OUTBOUND",
+    "PRUNE",
+    "REMOVE",
+    "REPLACE",
+    "RETURN",
+    "SEARCH",
+    "SHORTEST_PATH",
+    "SORT",
+    "TRUE",
+    "UPDATE",
+    "UPSERT",
+    "WITH",
+];
@@ -0,0 +1,164 @@
+//! Procedural macros for the `rincon_aql` crate.
+//!
+//! This crate provides the `aql!` macro which checks an AQL query string at
+//! compile time and creates a `Query` with its bind parameters set. It is
+//! re-exported by the `rincon_aql` crate and should be used from there.
+
+#![doc(html_root_url = "https://docs.rs/rincon_aql_macros/0.1.0")]
+#![warn(
+    missing_copy_implementations,
+    missing_debug_implementations,
+//    missing_docs,
+    trivial_casts,
+    trivial_numeric_casts,
+    unsafe_code,
+    unstable_features,
+    unused_import_braces,
+    unused_qualifications,
+)]
+
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+mod syntax;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Expr, Ident, LitStr, Token};
+
+/// Creates a `Query` from an AQL query string that is checked at compile
+/// time.
+///
+/// The query string must be a string literal. The macro reports an error at
+/// compile time if the query contains obvious syntax errors like unbalanced
+/// brackets, unterminated strings, a missing `RETURN` or operations
+/// following a `RETURN`. The checks do not cover the complete AQL grammar,
+/// so the server may still reject a query that passes them.
+///
+/// Values for the bind parameters of the query can be given explicitly as
+/// `name = expression` pairs after the query string. Bind parameters without
+/// an explicit value are taken from the variable of the same name that is in
+/// scope where the macro is invoked. For collection parameters like
+/// `@@users` the variable named `users` is used. It is an error if an
+/// explicitly given parameter is not used in the query.
+///
+/// The values must implement `Into<rincon_core::api::types::Value>`.
+/// Variables are cloned, so they remain usable after the macro invocation.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use rincon_aql::aql;
+///
+/// let min_age = 18;
+/// let query = aql!(
+///     "FOR u IN @@users FILTER u.age >= @min_age RETURN u",
+///     users = "customers",
+/// );
+/// ```
+#[proc_macro]
+pub fn aql(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as AqlInput);
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// The arguments of an `aql!` invocation.
+struct AqlInput {
+    query: LitStr,
+    arguments: Vec<Argument>,
+}
+
+/// An explicitly given value of a bind parameter.
+struct Argument {
+    name: Ident,
+    value: Expr,
+}
+
+impl Parse for AqlInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let query = input.parse()?;
+        let arguments = if input.is_empty() {
+            Vec::new()
+        } else {
+            input.parse::<Token![,]>()?;
+            Punctuated::<Argument, Token![,]>::parse_terminated(input)?
+                .into_iter()
+                .collect()
+        };
+        Ok(AqlInput { query, arguments })
+    }
+}
+
+impl Parse for Argument {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.call(Ident::parse_any)?;
+        input.parse::<Token![=]>()?;
+        let value = input.parse()?;
+        Ok(Argument { name, value })
+    }
+}
+
+fn expand(input: &AqlInput) -> syn::Result<TokenStream2> {
+    let query = &input.query;
+    let parameters = syntax::check(&query.value())
+        .map_err(|error| syn::Error::new(query.span(), error.to_string()))?;
+
+    for argument in &input.arguments {
+        let name = argument.name.to_string();
+        let name = name.trim_start_matches("r#");
+        if !parameters.iter().any(|parameter| parameter.name() == name) {
+            return Err(syn::Error::new(
+                argument.name.span(),
+                format!("bind parameter '{}' is not used in the query", name),
+            ));
+        }
+    }
+
+    let mut set_parameters = Vec::with_capacity(parameters.len());
+    for parameter in &parameters {
+        let key = parameter.key();
+        let argument = input.arguments.iter().find(|argument| {
+            argument.name.to_string().trim_start_matches("r#") == parameter.name()
+        });
+        let value = match argument {
+            Some(argument) => {
+                let value = &argument.value;
+                quote!(#value)
+            },
+            None => {
+                if !syntax::is_rust_identifier(parameter.name()) {
+                    return Err(syn::Error::new(
+                        query.span(),
+                        format!(
+                            "bind parameter '{}' can not be taken from a variable, pass \
+                             its value explicitly",
+                            key
+                        ),
+                    ));
+                }
+                let variable = Ident::new(parameter.name(), query.span());
+                quote!(::std::clone::Clone::clone(&#variable))
+            },
+        };
+        set_parameters.push(quote! {
+            __rincon_aql_query.set_parameter(#key, #value);
+        });
+    }
+
+    Ok(quote! {
+        {
+            let mut __rincon_aql_query = ::rincon_aql::Query::new(#query);
+            #(#set_parameters)*
+            __rincon_aql_query
+        }
+    })
+}
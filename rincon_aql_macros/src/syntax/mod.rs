@@ -0,0 +1,554 @@
+//! Tokenizing and checking of AQL query strings.
+//!
+//! The checks performed here do not cover the complete AQL grammar. They
+//! detect the errors most commonly made when writing queries by hand:
+//!
+//! * invalid characters, unterminated strings, names and comments
+//! * unbalanced parenthesis, brackets and braces
+//! * queries or subqueries that do not start with an operation or do not
+//!   end with a `RETURN` or a data modification operation
+//! * operations following a `RETURN` operation
+//! * malformed `FOR`, `LET` and `UPSERT` operations
+//! * invalid bind parameter names
+
+#[cfg(test)]
+mod tests;
+
+use std::fmt;
+
+/// Keywords that start a high-level operation.
+const OPERATIONS: &[&str] = &[
+    "COLLECT", "FILTER", "FOR", "INSERT", "LET", "LIMIT", "REMOVE", "REPLACE", "RETURN", "SORT",
+    "UPDATE", "UPSERT", "WITH",
+];
+
+/// Keywords that start a data modification operation.
+const MODIFICATIONS: &[&str] = &["INSERT", "REMOVE", "REPLACE", "UPDATE", "UPSERT"];
+
+// The list of keywords is shared with the query builder of `rincon_aql`.
+include!("../keywords.rs");
+
+/// Operators and separators ordered so that longer ones are matched first.
+const PUNCTUATION: &[&str] = &[
+    "==", "!=", "<=", ">=", "=~", "!~", "&&", "||", "..", "<", ">", "=", "!", "+", "-", "*", "/",
+    "%", ".", ",", ":", "?", "(", ")", "[", "]", "{", "}",
+];
+
+/// An error found in an AQL query string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxError {
+    message: String,
+    position: usize,
+}
+
+impl SyntaxError {
+    fn new<M>(message: M, position: usize) -> Self
+    where
+        M: Into<String>,
+    {
+        SyntaxError {
+            message: message.into(),
+            position,
+        }
+    }
+
+    /// Returns the message of this error.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the byte position in the query string where the error has
+    /// been found.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "AQL syntax error at position {}: {}",
+            self.position, self.message
+        )
+    }
+}
+
+/// A token of an AQL query string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token {
+    /// A keyword or an unquoted name
+    Word(String),
+    /// A name quoted with backticks or forward ticks
+    QuotedName(String),
+    /// A string literal
+    Str,
+    /// A number literal
+    Number,
+    /// A bind parameter for a value, e.g. `@name`
+    Parameter(String),
+    /// A bind parameter for a collection, e.g. `@@name`
+    CollectionParameter(String),
+    /// An operator or separator
+    Punct(&'static str),
+}
+
+impl Token {
+    fn is_keyword(&self, keyword: &str) -> bool {
+        match *self {
+            Token::Word(ref word) => word.eq_ignore_ascii_case(keyword),
+            _ => false,
+        }
+    }
+
+    fn is_any_keyword_of(&self, keywords: &[&str]) -> bool {
+        keywords.iter().any(|keyword| self.is_keyword(keyword))
+    }
+
+    fn is_name(&self) -> bool {
+        match *self {
+            Token::Word(_) => !self.is_any_keyword_of(KEYWORDS),
+            Token::QuotedName(_) => true,
+            _ => false,
+        }
+    }
+
+    fn is_punct(&self, punct: &str) -> bool {
+        match *self {
+            Token::Punct(value) => value == punct,
+            _ => false,
+        }
+    }
+}
+
+/// A bind parameter used in a query.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Parameter {
+    name: String,
+    collection: bool,
+}
+
+impl Parameter {
+    /// Returns the name of the parameter without the leading `@` or `@@`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns whether this is a bind parameter for a collection.
+    pub fn is_collection(&self) -> bool {
+        self.collection
+    }
+
+    /// Returns the key under which the value of this parameter is passed to
+    /// the server.
+    pub fn key(&self) -> String {
+        if self.collection {
+            format!("@{}", self.name)
+        } else {
+            self.name.clone()
+        }
+    }
+}
+
+/// Checks the given query string and returns the bind parameters used in
+/// the query in the order of their first occurrence.
+pub fn check(aql: &str) -> Result<Vec<Parameter>, SyntaxError> {
+    let tokens = tokenize(aql)?;
+    check_brackets(&tokens)?;
+    check_operations(&tokens, aql.len())?;
+    let mut parameters: Vec<Parameter> = Vec::new();
+    for &(_, ref token) in &tokens {
+        let parameter = match *token {
+            Token::Parameter(ref name) => Parameter {
+                name: name.clone(),
+                collection: false,
+            },
+            Token::CollectionParameter(ref name) => Parameter {
+                name: name.clone(),
+                collection: true,
+            },
+            _ => continue,
+        };
+        if !parameters.contains(&parameter) {
+            parameters.push(parameter);
+        }
+    }
+    Ok(parameters)
+}
+
+/// Splits the given query string into tokens.
+///
+/// Returns the tokens together with their byte position in the query
+/// string. Whitespace and comments are skipped.
+pub fn tokenize(aql: &str) -> Result<Vec<(usize, Token)>, SyntaxError> {
+    let bytes = aql.as_bytes();
+    let mut tokens = Vec::new();
+    let mut position = 0;
+    while position < bytes.len() {
+        let rest = &aql[position..];
+        let c = rest.chars().next().unwrap_or_default();
+        if c.is_whitespace() {
+            position += c.len_utf8();
+        } else if rest.starts_with("//") {
+            position += rest.find('\n').unwrap_or_else(|| rest.len());
+        } else if rest.starts_with("/*") {
+            match rest[2..].find("*/") {
+                Some(end) => position += end + 4,
+                None => return Err(SyntaxError::new("unterminated comment", position)),
+            }
+        } else if c == '\'' || c == '"' {
+            let end = find_closing_quote(rest, c)
+                .ok_or_else(|| SyntaxError::new("unterminated string", position))?;
+            tokens.push((position, Token::Str));
+            position += end;
+        } else if c == '`' || c == '´' {
+            let end = find_closing_quote(rest, c)
+                .ok_or_else(|| SyntaxError::new("unterminated quoted name", position))?;
+            let name = rest[c.len_utf8()..end - c.len_utf8()].to_owned();
+            tokens.push((position, Token::QuotedName(name)));
+            position += end;
+        } else if c == '@' {
+            let (token, length) = if rest.starts_with("@@") {
+                let name = take_while(&rest[2..], is_name_char);
+                (Token::CollectionParameter(name.to_owned()), 2 + name.len())
+            } else {
+                let name = take_while(&rest[1..], is_name_char);
+                (Token::Parameter(name.to_owned()), 1 + name.len())
+            };
+            match token {
+                Token::Parameter(ref name) | Token::CollectionParameter(ref name)
+                    if name.is_empty() =>
+                {
+                    return Err(SyntaxError::new("missing name of bind parameter", position));
+                },
+                _ => {},
+            }
+            tokens.push((position, token));
+            position += length;
+        } else if c.is_ascii_digit() {
+            tokens.push((position, Token::Number));
+            position += number_length(rest);
+        } else if c.is_ascii_alphabetic() || c == '_' || c == '$' {
+            let word = take_while(rest, |c| is_name_char(c) || c == '$');
+            tokens.push((position, Token::Word(word.to_owned())));
+            position += word.len();
+        } else if let Some(&punct) = PUNCTUATION.iter().find(|&&punct| rest.starts_with(punct)) {
+            tokens.push((position, Token::Punct(punct)));
+            position += punct.len();
+        } else {
+            return Err(SyntaxError::new(
+                format!("unexpected character '{}'", c),
+                position,
+            ));
+        }
+    }
+    Ok(tokens)
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+fn take_while<P>(string: &str, predicate: P) -> &str
+where
+    P: Fn(char) -> bool,
+{
+    let end = string
+        .char_indices()
+        .find(|&(_, c)| !predicate(c))
+        .map_or_else(|| string.len(), |(index, _)| index);
+    &string[..end]
+}
+
+/// Returns the position after the closing quote of a string that starts
+/// with the given quote character.
+fn find_closing_quote(string: &str, quote: char) -> Option<usize> {
+    let mut escaped = false;
+    for (index, c) in string.char_indices().skip(1) {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == quote {
+            return Some(index + c.len_utf8());
+        }
+    }
+    None
+}
+
+/// Returns the length of the number literal at the start of the given
+/// string.
+///
+/// A dot is only part of the number if it is followed by a digit, so that
+/// ranges like `1..3` are tokenized correctly.
+fn number_length(string: &str) -> usize {
+    let bytes = string.as_bytes();
+    let mut length = take_while(string, |c| c.is_ascii_alphanumeric()).len();
+    if length < bytes.len()
+        && bytes[length] == b'.'
+        && bytes.get(length + 1).map_or(false, u8::is_ascii_digit)
+    {
+        length += 1 + take_while(&string[length + 1..], |c| c.is_ascii_alphanumeric()).len();
+    }
+    if length < bytes.len()
+        && (bytes[length - 1] == b'e' || bytes[length - 1] == b'E')
+        && (bytes[length] == b'+' || bytes[length] == b'-')
+    {
+        length += 1 + take_while(&string[length + 1..], |c| c.is_ascii_digit()).len();
+    }
+    length
+}
+
+/// Checks that all parenthesis, brackets and braces are balanced.
+fn check_brackets(tokens: &[(usize, Token)]) -> Result<(), SyntaxError> {
+    let mut open: Vec<(usize, &str)> = Vec::new();
+    for &(position, ref token) in tokens {
+        match *token {
+            Token::Punct(punct @ "(") | Token::Punct(punct @ "[") | Token::Punct(punct @ "{") => {
+                open.push((position, punct))
+            },
+            Token::Punct(closing @ ")")
+            | Token::Punct(closing @ "]")
+            | Token::Punct(closing @ "}") => {
+                let expected = match open.pop() {
+                    Some((_, "(")) => ")",
+                    Some((_, "[")) => "]",
+                    Some(_) => "}",
+                    None => {
+                        return Err(SyntaxError::new(
+                            format!("unexpected '{}'", closing),
+                            position,
+                        ))
+                    },
+                };
+                if closing != expected {
+                    return Err(SyntaxError::new(
+                        format!("expected '{}' but found '{}'", expected, closing),
+                        position,
+                    ));
+                }
+            },
+            _ => {},
+        }
+    }
+    match open.pop() {
+        Some((position, punct)) => Err(SyntaxError::new(format!("unclosed '{}'", punct), position)),
+        None => Ok(()),
+    }
+}
+
+/// The state of a query or subquery while its operations are checked.
+#[derive(Debug)]
+struct Scope {
+    depth: usize,
+    position: usize,
+    last_operation: Option<String>,
+    returned: bool,
+    modified: bool,
+}
+
+impl Scope {
+    fn new(depth: usize, position: usize) -> Self {
+        Scope {
+            depth,
+            position,
+            last_operation: None,
+            returned: false,
+            modified: false,
+        }
+    }
+
+    fn finish(&self) -> Result<(), SyntaxError> {
+        if self.returned || self.modified {
+            Ok(())
+        } else {
+            Err(SyntaxError::new(
+                "query must end with a RETURN or a data modification operation",
+                self.position,
+            ))
+        }
+    }
+}
+
+/// Checks the sequence of high-level operations of the query and all of its
+/// subqueries.
+fn check_operations(tokens: &[(usize, Token)], end: usize) -> Result<(), SyntaxError> {
+    match tokens.first() {
+        Some(&(_, ref token)) if token.is_any_keyword_of(OPERATIONS) => {},
+        Some(&(position, _)) => {
+            return Err(SyntaxError::new(
+                "query must start with an operation like FOR, LET or RETURN",
+                position,
+            ))
+        },
+        None => return Err(SyntaxError::new("query is empty", 0)),
+    }
+    let mut scopes = vec![Scope::new(0, 0)];
+    let mut depth = 0;
+    let mut upsert_clauses: Vec<&str> = Vec::new();
+    for (index, &(position, ref token)) in tokens.iter().enumerate() {
+        let previous = if index > 0 {
+            Some(&tokens[index - 1].1)
+        } else {
+            None
+        };
+        let next = tokens.get(index + 1).map(|&(_, ref token)| token);
+        if token.is_punct("(") || token.is_punct("[") || token.is_punct("{") {
+            depth += 1;
+            if token.is_punct("(") && next.map_or(false, |next| next.is_any_keyword_of(OPERATIONS))
+            {
+                scopes.push(Scope::new(depth, position));
+            }
+            continue;
+        }
+        if token.is_punct(")") || token.is_punct("]") || token.is_punct("}") {
+            if scopes.len() > 1 && scopes[scopes.len() - 1].depth == depth {
+                if let Some(scope) = scopes.pop() {
+                    scope.finish()?;
+                }
+            }
+            depth -= 1;
+            continue;
+        }
+        let scope = scopes.last_mut().expect("there is always a scope");
+        if depth != scope.depth
+            || !token.is_any_keyword_of(OPERATIONS)
+            || previous.map_or(false, |previous| previous.is_punct("."))
+        {
+            continue;
+        }
+        let keyword = match *token {
+            Token::Word(ref word) => word.to_ascii_uppercase(),
+            _ => continue,
+        };
+        // keywords that are part of another operation
+        if let Some(&expected) = upsert_clauses.last() {
+            if keyword == expected || (expected == "UPDATE" && keyword == "REPLACE") {
+                upsert_clauses.pop();
+                continue;
+            }
+        }
+        match keyword.as_str() {
+            "WITH" => match scope.last_operation.as_ref().map(String::as_str) {
+                // `WITH collection, ...` at the start of a query
+                None => {},
+                // `UPDATE/REPLACE key WITH document` or `WITH COUNT INTO`
+                Some("UPDATE") | Some("REPLACE") | Some("COLLECT") => continue,
+                Some(_) => {
+                    return Err(SyntaxError::new(
+                        "WITH is only allowed at the start of a query",
+                        position,
+                    ))
+                },
+            },
+            _ => {},
+        }
+        if scope.returned {
+            return Err(SyntaxError::new(
+                format!("{} is not allowed after RETURN", keyword),
+                position,
+            ));
+        }
+        if scope.modified && keyword != "LET" && keyword != "RETURN" {
+            return Err(SyntaxError::new(
+                format!(
+                    "{} is not allowed after a data modification operation",
+                    keyword
+                ),
+                position,
+            ));
+        }
+        match next {
+            None => {
+                return Err(SyntaxError::new(
+                    format!("{} must be followed by an expression", keyword),
+                    end,
+                ))
+            },
+            Some(next) if next.is_punct(")") => {
+                return Err(SyntaxError::new(
+                    format!("{} must be followed by an expression", keyword),
+                    position,
+                ))
+            },
+            _ => {},
+        }
+        match keyword.as_str() {
+            "FOR" => check_for(&tokens[index + 1..], position)?,
+            "LET" => check_let(&tokens[index + 1..], position)?,
+            "RETURN" => scope.returned = true,
+            "UPSERT" => {
+                upsert_clauses.push("UPDATE");
+                upsert_clauses.push("INSERT");
+                scope.modified = true;
+            },
+            _ if MODIFICATIONS.contains(&keyword.as_str()) => scope.modified = true,
+            _ => {},
+        }
+        scope.last_operation = Some(keyword);
+    }
+    if let Some(&expected) = upsert_clauses.last() {
+        return Err(SyntaxError::new(
+            format!("UPSERT is missing the {} clause", expected),
+            end,
+        ));
+    }
+    for scope in scopes.iter().rev() {
+        scope.finish()?;
+    }
+    Ok(())
+}
+
+/// Checks `FOR variable[, variable[, variable]] IN`.
+fn check_for(tokens: &[(usize, Token)], position: usize) -> Result<(), SyntaxError> {
+    let mut expect_name = true;
+    for &(_, ref token) in tokens.iter().take(6) {
+        if expect_name {
+            if !token.is_name() {
+                break;
+            }
+        } else if token.is_keyword("IN") {
+            return Ok(());
+        } else if !token.is_punct(",") {
+            break;
+        }
+        expect_name = !expect_name;
+    }
+    Err(SyntaxError::new(
+        "FOR must be followed by one to three variable names and IN",
+        position,
+    ))
+}
+
+/// Checks `LET variable =`.
+fn check_let(tokens: &[(usize, Token)], position: usize) -> Result<(), SyntaxError> {
+    match (tokens.get(0), tokens.get(1)) {
+        (Some(&(_, ref name)), Some(&(_, ref assign)))
+            if name.is_name() && assign.is_punct("=") =>
+        {
+            Ok(())
+        },
+        _ => Err(SyntaxError::new(
+            "LET must be followed by a variable name and '='",
+            position,
+        )),
+    }
+}
+
+/// Returns whether the given name of a bind parameter can be used as the
+/// name of a Rust variable.
+pub fn is_rust_identifier(name: &str) -> bool {
+    const RUST_KEYWORDS: &[&str] = &[
+        "as", "break", "const", "continue", "crate", "else", "enum", "extern", "false", "fn",
+        "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+        "return", "self", "Self", "static", "struct", "super", "trait", "true", "type", "unsafe",
+        "use", "where", "while", "abstract", "alignof", "become", "box", "do", "final", "macro",
+        "offsetof", "override", "priv", "proc", "pure", "sizeof", "typeof", "unsized", "virtual",
+        "yield", "async", "await", "dyn", "try", "_",
+    ];
+    match name.chars().next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {},
+        _ => return false,
+    }
+    name.chars().all(is_name_char) && !RUST_KEYWORDS.contains(&name)
+}
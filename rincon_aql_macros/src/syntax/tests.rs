@@ -0,0 +1,205 @@
+use super::*;
+
+fn error_message(aql: &str) -> String {
+    check(aql).unwrap_err().message().to_owned()
+}
+
+#[test]
+fn tokenize_simple_query() {
+    let tokens = tokenize("FOR u IN @@users FILTER u.age >= @min_age RETURN u").unwrap();
+
+    assert_eq!(
+        vec![
+            (0, Token::Word("FOR".to_owned())),
+            (4, Token::Word("u".to_owned())),
+            (6, Token::Word("IN".to_owned())),
+            (9, Token::CollectionParameter("users".to_owned())),
+            (17, Token::Word("FILTER".to_owned())),
+            (24, Token::Word("u".to_owned())),
+            (25, Token::Punct(".")),
+            (26, Token::Word("age".to_owned())),
+            (30, Token::Punct(">=")),
+            (33, Token::Parameter("min_age".to_owned())),
+            (42, Token::Word("RETURN".to_owned())),
+            (49, Token::Word("u".to_owned())),
+        ],
+        tokens
+    );
+}
+
+#[test]
+fn tokenize_strings_names_numbers_and_comments() {
+    let tokens =
+        tokenize("RETURN /* comment */ ['it\\'s', \"x\", `first name`, 1..3, 1.5e-3] // end")
+            .unwrap();
+
+    let tokens: Vec<Token> = tokens.into_iter().map(|(_, token)| token).collect();
+    assert_eq!(
+        vec![
+            Token::Word("RETURN".to_owned()),
+            Token::Punct("["),
+            Token::Str,
+            Token::Punct(","),
+            Token::Str,
+            Token::Punct(","),
+            Token::QuotedName("first name".to_owned()),
+            Token::Punct(","),
+            Token::Number,
+            Token::Punct(".."),
+            Token::Number,
+            Token::Punct(","),
+            Token::Number,
+            Token::Punct("]"),
+        ],
+        tokens
+    );
+}
+
+#[test]
+fn check_returns_parameters_in_order_of_first_occurrence() {
+    let parameters =
+        check("FOR u IN @@users FILTER u.age >= @min AND u.age <= @max OR u.age == @min RETURN u")
+            .unwrap();
+
+    let names: Vec<String> = parameters.iter().map(Parameter::key).collect();
+    assert_eq!(vec!["@users", "min", "max"], names);
+    assert!(parameters[0].is_collection());
+}
+
+#[test]
+fn check_accepts_data_modification_queries() {
+    assert!(check("INSERT { name: @name } INTO @@users RETURN NEW").is_ok());
+    assert!(check("FOR u IN users UPDATE u WITH { active: false } IN users").is_ok());
+    assert!(check("FOR u IN users REPLACE u WITH { name: 'x' } IN users").is_ok());
+    assert!(check("FOR u IN users REMOVE u IN users LET removed = OLD RETURN removed").is_ok());
+    assert!(check(
+        "UPSERT { name: 'root' } INSERT { name: 'root', logins: 1 } \
+             UPDATE { logins: OLD.logins + 1 } IN users RETURN NEW"
+    )
+    .is_ok());
+}
+
+#[test]
+fn check_accepts_subqueries_and_collect() {
+    assert!(check(
+        "WITH users FOR u IN users LET orders = (FOR o IN orders FILTER o.user == u._key \
+             RETURN o) COLLECT city = u.city WITH COUNT INTO count \
+             SORT count DESC LIMIT 0, 10 RETURN { city, count, orders: LENGTH(orders) }"
+    )
+    .is_ok());
+}
+
+#[test]
+fn check_accepts_attribute_names_that_are_keywords() {
+    assert!(check("FOR d IN docs FILTER d.filter == 1 RETURN { return: d.return }").is_ok());
+}
+
+#[test]
+fn check_rejects_empty_query() {
+    assert_eq!("query is empty", error_message("  // nothing "));
+}
+
+#[test]
+fn check_rejects_query_not_starting_with_operation() {
+    assert_eq!(
+        "query must start with an operation like FOR, LET or RETURN",
+        error_message("u IN users RETURN u")
+    );
+}
+
+#[test]
+fn check_rejects_query_without_return() {
+    assert_eq!(
+        "query must end with a RETURN or a data modification operation",
+        error_message("FOR u IN users FILTER u.active == true")
+    );
+}
+
+#[test]
+fn check_rejects_subquery_without_return() {
+    assert_eq!(
+        "query must end with a RETURN or a data modification operation",
+        error_message("LET x = (FOR u IN users FILTER u.active) RETURN x")
+    );
+}
+
+#[test]
+fn check_rejects_operation_after_return() {
+    assert_eq!(
+        "FILTER is not allowed after RETURN",
+        error_message("FOR u IN users RETURN u FILTER u.active")
+    );
+}
+
+#[test]
+fn check_rejects_malformed_for() {
+    assert_eq!(
+        "FOR must be followed by one to three variable names and IN",
+        error_message("FOR IN users RETURN 1")
+    );
+}
+
+#[test]
+fn check_rejects_malformed_let() {
+    assert_eq!(
+        "LET must be followed by a variable name and '='",
+        error_message("LET x == 1 RETURN x")
+    );
+}
+
+#[test]
+fn check_rejects_keyword_as_variable_name() {
+    assert_eq!(
+        "LET must be followed by a variable name and '='",
+        error_message("LET prune = 1 RETURN prune")
+    );
+}
+
+#[test]
+fn check_rejects_incomplete_upsert() {
+    assert_eq!(
+        "UPSERT is missing the UPDATE clause",
+        error_message("UPSERT { a: 1 } INSERT { a: 1 } IN things")
+    );
+}
+
+#[test]
+fn check_rejects_unbalanced_brackets() {
+    let error = check("FOR u IN users FILTER (u.a == 1 RETURN u").unwrap_err();
+
+    assert_eq!("unclosed '('", error.message());
+    assert_eq!(22, error.position());
+    assert_eq!("expected ']' but found ')'", error_message("RETURN [1, 2)"));
+}
+
+#[test]
+fn check_rejects_unterminated_string() {
+    assert_eq!(
+        "unterminated string",
+        error_message("FOR u IN users FILTER u.name == 'x RETURN u")
+    );
+}
+
+#[test]
+fn check_rejects_bind_parameter_without_name() {
+    assert_eq!(
+        "missing name of bind parameter",
+        error_message("FOR u IN users FILTER u.name == @ RETURN u")
+    );
+}
+
+#[test]
+fn check_rejects_unexpected_character() {
+    assert_eq!(
+        "unexpected character ';'",
+        error_message("FOR u IN users RETURN u;")
+    );
+}
+
+#[test]
+fn rust_identifiers() {
+    assert!(is_rust_identifier("min_age"));
+    assert!(is_rust_identifier("_x1"));
+    assert!(!is_rust_identifier("1st"));
+    assert!(!is_rust_identifier("type"));
+}
@@ -0,0 +1,534 @@
+//! A connector based on `std::future` that uses HTTP/HTTPS as transport
+//! protocol.
+//!
+//! The `AsyncJsonHttpConnector` implements the `AsyncConnector` trait of the
+//! [`rincon_core`] API. It uses current versions of `hyper` and `rustls` and
+//! must be used within a tokio 1.x runtime.
+//!
+//! [`rincon_core`]: https://docs.rs/rincon_core
+
+#[cfg(test)]
+mod tests;
+
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full};
+use hyper::header::{
+    HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE, USER_AGENT,
+};
+use hyper::{Request, StatusCode, Uri};
+use hyper_rustls::{FixedServerNameResolver, HttpsConnector, HttpsConnectorBuilder};
+use hyper_util::client::legacy::connect::HttpConnector;
+use hyper_util::client::legacy::Client;
+use hyper_util::rt::TokioExecutor;
+use log::{debug, trace};
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+use serde_json::Value;
+
+use rincon_core::api::auth::{Authentication, Credentials, Jwt};
+use rincon_core::api::connector::{AsyncConnector, AsyncExecute, AsyncFutureResult, Error};
+use rincon_core::api::datasource::{ContentFormat, DataSource};
+use rincon_core::api::method::{Method, Operation, Parameters, Prepare, RpcReturnType};
+use rincon_core::api::payload::{parse_return_type, serialize_payload};
+use rincon_core::api::user_agent::{RinconUserAgent, UserAgent};
+use rincon_core::arango::protocol::{
+    HEADER_QUEUE_TIME_SECONDS, PATH_DB, PATH_OPEN_AUTH, SYSTEM_DATABASE,
+};
+use rincon_core::jwt::{is_token_expired, superuser_token};
+use rincon_core::velocypack::CONTENT_TYPE_VELOCYPACK;
+
+use crate::tls;
+
+type HttpClient = Client<HttpsConnector<HttpConnector>, Full<Bytes>>;
+
+/// The characters that are percent encoded in paths and query parameters.
+const ENCODE_SET: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'<')
+    .add(b'>')
+    .add(b'`')
+    .add(b'?')
+    .add(b'{')
+    .add(b'}');
+
+/// A connector that uses JSON or VelocyPack over HTTP/HTTPS and executes
+/// method calls as `std::future::Future`s.
+///
+/// The payload is serialized as JSON unless the `DataSource` specifies
+/// `ContentFormat::VelocyPack` as content format.
+///
+/// All authentication methods of the `DataSource` are supported. With
+/// `Authentication::Jwt` the connector logs in before the first method call
+/// and logs in again when the server rejects the token. With
+/// `Authentication::JwtSecret` superuser tokens are signed locally.
+///
+/// Each method call must be completed within the timeout of the `DataSource`
+/// or the timeout returned by `Prepare::timeout()` of the method. Otherwise
+/// it fails with an `Error::Timeout`.
+///
+/// Unlike the `JsonHttpConnector` of the `rincon_connector` crate this
+/// connector sends all requests to the host of the `DataSource`. Failover
+/// endpoints are ignored and failed method calls are not retried. There is no
+/// support for interceptors and metrics sinks yet.
+///
+/// Connectors and their connections are cheap to clone and can be shared
+/// between threads.
+#[derive(Debug, Clone)]
+pub struct AsyncJsonHttpConnector {
+    user_agent: &'static (dyn UserAgent + Sync),
+    datasource: Arc<DataSource>,
+    token: Arc<RwLock<Option<Jwt>>>,
+    client: HttpClient,
+}
+
+impl AsyncJsonHttpConnector {
+    /// Creates a new instance of the `AsyncJsonHttpConnector`.
+    ///
+    /// HTTPS connections are configured by the `TlsOptions` of the
+    /// `DataSource`. Server certificates are verified against the native root
+    /// certificates of the platform and the root certificates specified in
+    /// the `TlsOptions`. Creating the connector fails if the certificates or
+    /// keys specified in the `TlsOptions` can not be read.
+    pub fn new(datasource: DataSource) -> Result<Self, Error> {
+        AsyncJsonHttpConnector::with_user_agent(&RinconUserAgent, datasource)
+    }
+
+    /// Creates a new instance of the `AsyncJsonHttpConnector` that uses the
+    /// given user agent.
+    ///
+    /// The `user_agent` is used to set the User-Agent header field of the HTTP
+    /// protocol on each request sent by this connector.
+    pub fn with_user_agent(
+        user_agent: &'static (dyn UserAgent + Sync),
+        datasource: DataSource,
+    ) -> Result<Self, Error> {
+        let mut http_connector = HttpConnector::new();
+        http_connector.enforce_http(false);
+        http_connector.set_connect_timeout(Some(*datasource.timeout()));
+        let tls_options = datasource.tls_options();
+        let https_connector = HttpsConnectorBuilder::new()
+            .with_tls_config(tls::client_config(tls_options)?)
+            .https_or_http();
+        let https_connector = match tls::server_name(tls_options)? {
+            Some(server_name) => {
+                https_connector.with_server_name_resolver(FixedServerNameResolver::new(server_name))
+            },
+            None => https_connector,
+        };
+        let https_connector = https_connector
+            .enable_http1()
+            .wrap_connector(http_connector);
+        let client = Client::builder(TokioExecutor::new()).build(https_connector);
+        debug!(
+            "Creating new async JSON/HTTP connector for {:?}",
+            &datasource
+        );
+        Ok(AsyncJsonHttpConnector {
+            user_agent,
+            datasource: Arc::new(datasource),
+            token: Arc::new(RwLock::new(None)),
+            client,
+        })
+    }
+}
+
+impl AsyncConnector for AsyncJsonHttpConnector {
+    type Connection = AsyncJsonHttpConnection;
+
+    fn connection(&self, database_name: &str) -> AsyncJsonHttpConnection {
+        AsyncJsonHttpConnection {
+            user_agent: self.user_agent,
+            datasource: self.datasource.clone(),
+            database: Some(database_name.to_owned()),
+            token: self.token.clone(),
+            client: self.client.clone(),
+        }
+    }
+
+    fn system_connection(&self) -> AsyncJsonHttpConnection {
+        self.connection(SYSTEM_DATABASE)
+    }
+
+    fn use_auth_token(&mut self, token: Jwt) {
+        store_token(&self.token, Some(token));
+    }
+
+    fn invalidate_auth_token(&mut self) {
+        store_token(&self.token, None);
+    }
+}
+
+/// A connection that executes method calls using JSON or VelocyPack over
+/// HTTP/HTTPS.
+#[derive(Debug, Clone)]
+pub struct AsyncJsonHttpConnection {
+    user_agent: &'static (dyn UserAgent + Sync),
+    datasource: Arc<DataSource>,
+    database: Option<String>,
+    token: Arc<RwLock<Option<Jwt>>>,
+    client: HttpClient,
+}
+
+impl AsyncJsonHttpConnection {
+    /// Returns the `DataSource` used by the connection.
+    pub fn datasource(&self) -> &DataSource {
+        &self.datasource
+    }
+
+    /// Returns the name of the default database addressed by method calls
+    /// if none is specified by the actual method.
+    pub fn database(&self) -> Option<&String> {
+        self.database
+            .as_ref()
+            .or_else(|| self.datasource.database_name())
+    }
+
+    /// Returns the authentication token used by this connection.
+    pub fn token(&self) -> Option<Jwt> {
+        match self.token.read() {
+            Ok(token) => token.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// Returns the timeout for executing the given method.
+    ///
+    /// This is the timeout specified by the method itself or the timeout of
+    /// the `DataSource` if the method does not specify one.
+    pub fn timeout_for<P>(&self, method: &P) -> Duration
+    where
+        P: Prepare,
+    {
+        method
+            .timeout()
+            .unwrap_or_else(|| *self.datasource.timeout())
+    }
+
+    /// Builds a HTTP-request for a concrete method call and returns it.
+    pub fn prepare_request<P>(&self, method: &P) -> Result<Request<Full<Bytes>>, Error>
+    where
+        P: Prepare,
+    {
+        let content_format = self.datasource.content_format();
+        let uri = build_request_uri(&self.datasource, self.database(), method)?;
+        let mut builder = Request::builder()
+            .method(http_method_for_operation(method.operation()))
+            .uri(uri)
+            .header(USER_AGENT, user_agent_string(self.user_agent))
+            .header(
+                HEADER_QUEUE_TIME_SECONDS,
+                format_seconds(&self.timeout_for(method)),
+            );
+        match *self.datasource.authentication() {
+            Authentication::Basic(ref credentials) => {
+                builder = builder.header(AUTHORIZATION, basic_authorization(credentials));
+            },
+            Authentication::Jwt(_) | Authentication::JwtSecret(_) => match self.token() {
+                Some(token) => {
+                    builder = builder.header(AUTHORIZATION, format!("Bearer {}", token));
+                },
+                None if method.path() == PATH_OPEN_AUTH => {},
+                None => {
+                    return Err(Error::NotAuthenticated(
+                        "the client must be authenticated first, \
+                         when using JWT authentication"
+                            .into(),
+                    ));
+                },
+            },
+            Authentication::None => {},
+        }
+        if content_format == ContentFormat::VelocyPack {
+            builder = builder.header(ACCEPT, CONTENT_TYPE_VELOCYPACK);
+        }
+        for (name, value) in method.header().iter() {
+            builder = builder.header(name.as_str(), value.to_string());
+        }
+        let payload = match method.content() {
+            Some(content) => {
+                let payload =
+                    serialize_payload(content_format, method.is_line_delimited(), content)?;
+                trace!("| request body: {}", String::from_utf8_lossy(&payload));
+                builder = builder
+                    .header(CONTENT_TYPE, content_type_of(content_format))
+                    .header(CONTENT_LENGTH, payload.len());
+                Bytes::from(payload)
+            },
+            None => Bytes::new(),
+        };
+        builder
+            .body(Full::new(payload))
+            .map_err(|cause| Error::Communication(cause.to_string()))
+    }
+
+    /// Prepares the request for the given method and returns a future that
+    /// sends the request and resolves to the result.
+    ///
+    /// The request is prepared before the returned future is polled. Thus the
+    /// returned future does not borrow the method.
+    fn send<M>(&self, method: &M) -> impl Future<Output = Result<<M as Method>::Result, Error>>
+    where
+        M: Method + Prepare,
+    {
+        let prepared = self.prepare_request(method);
+        let timeout = self.timeout_for(method);
+        let return_type = method.return_type();
+        let client = self.client.clone();
+        async move {
+            let request = prepared?;
+            debug!("Sending {:?}", &request);
+            let exchange = async {
+                let response = client
+                    .request(request)
+                    .await
+                    .map_err(|cause| Error::Communication(cause.to_string()))?;
+                let status_code = response.status();
+                let content_format = content_format_of(response.headers());
+                let body = response
+                    .into_body()
+                    .collect()
+                    .await
+                    .map_err(|cause| Error::Communication(cause.to_string()))?
+                    .to_bytes();
+                Ok::<_, Error>((status_code, content_format, body))
+            };
+            let (status_code, content_format, body) = tokio::time::timeout(timeout, exchange)
+                .await
+                .map_err(|_| {
+                    Error::Timeout(format!("no response received within {:?}", timeout))
+                })??;
+            debug!("Received response with code {:?}", status_code);
+            parse_return_type::<M>(&return_type, status_code.as_u16(), content_format, &body)
+        }
+    }
+
+    /// Logs in with the given credentials and stores the received token.
+    async fn login(&self, credentials: &Credentials) -> Result<(), Error> {
+        debug!("Logging in as user {:?}", credentials.username());
+        let login = Login {
+            credentials: serde_json::json!({
+                "username": credentials.username(),
+                "password": credentials.password(),
+            }),
+        };
+        let sending = self.send(&login);
+        let response = sending.await?;
+        store_token(&self.token, Some(response.jwt));
+        Ok(())
+    }
+
+    /// Returns whether this connection has a token that is not about to
+    /// expire.
+    fn has_valid_token(&self) -> bool {
+        self.token()
+            .map_or(false, |token| !is_token_expired(&token, SystemTime::now()))
+    }
+
+    /// Executes the given method using the authentication method of the
+    /// `DataSource`.
+    async fn execute_authenticated<M>(self, method: M) -> Result<<M as Method>::Result, Error>
+    where
+        M: Method + Prepare,
+    {
+        match *self.datasource.authentication() {
+            Authentication::Jwt(ref credentials) => {
+                if !self.has_valid_token() {
+                    self.login(credentials).await?;
+                }
+                let sending = self.send(&method);
+                match sending.await {
+                    Err(ref error) if is_unauthorized(error) => {
+                        debug!("Token has been rejected by the server, logging in again");
+                        store_token(&self.token, None);
+                        self.login(credentials).await?;
+                        let sending = self.send(&method);
+                        sending.await
+                    },
+                    result => result,
+                }
+            },
+            Authentication::JwtSecret(ref secret) => {
+                if !self.has_valid_token() {
+                    debug!("Generating new superuser token");
+                    let token = superuser_token(secret, SystemTime::now());
+                    store_token(&self.token, Some(token));
+                }
+                let sending = self.send(&method);
+                sending.await
+            },
+            Authentication::Basic(_) | Authentication::None => {
+                let sending = self.send(&method);
+                sending.await
+            },
+        }
+    }
+}
+
+impl AsyncExecute for AsyncJsonHttpConnection {
+    fn execute<M>(&self, method: M) -> AsyncFutureResult<M>
+    where
+        M: 'static + Method + Prepare + Send,
+        <M as Method>::Result: Send,
+    {
+        let connection = self.clone();
+        Box::pin(async move { connection.execute_authenticated(method).await })
+    }
+}
+
+/// Stores the given token, replacing any token stored before.
+fn store_token(token: &RwLock<Option<Jwt>>, new_token: Option<Jwt>) {
+    match token.write() {
+        Ok(mut token) => *token = new_token,
+        Err(poisoned) => *poisoned.into_inner() = new_token,
+    }
+}
+
+/// Returns whether the given error signals that the request has been
+/// rejected because of missing or invalid authentication.
+fn is_unauthorized(error: &Error) -> bool {
+    match *error {
+        Error::Method(ref error) => error.status_code() == StatusCode::UNAUTHORIZED.as_u16(),
+        _ => false,
+    }
+}
+
+/// Method to obtain a token for JWT authentication.
+#[derive(Debug)]
+struct Login {
+    credentials: Value,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct LoginResponse {
+    jwt: Jwt,
+}
+
+impl Method for Login {
+    type Result = LoginResponse;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: None,
+    };
+}
+
+impl Prepare for Login {
+    type Content = Value;
+
+    fn operation(&self) -> Operation {
+        Operation::Create
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_OPEN_AUTH)
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Value> {
+        Some(&self.credentials)
+    }
+}
+
+fn build_request_uri<P>(
+    datasource: &DataSource,
+    database_name: Option<&String>,
+    prepare: &P,
+) -> Result<Uri, Error>
+where
+    P: Prepare,
+{
+    let mut request_uri = String::new();
+    request_uri.push_str(datasource.protocol());
+    request_uri.push_str("://");
+    request_uri.push_str(datasource.host());
+    request_uri.push(':');
+    request_uri.push_str(&datasource.port().to_string());
+    if let Some(database_name) = database_name {
+        request_uri.push_str(PATH_DB);
+        request_uri.push_str(&percent_encode(database_name));
+    }
+    request_uri.push_str(&percent_encode(&prepare.path()));
+    let parameters = prepare.parameters();
+    if !parameters.is_empty() {
+        request_uri.push('?');
+        for (key, value) in parameters.iter() {
+            request_uri.push_str(&percent_encode(key));
+            request_uri.push('=');
+            request_uri.push_str(&percent_encode(&value.to_string()));
+            request_uri.push('&');
+        }
+        request_uri.pop();
+    }
+    Uri::from_str(&request_uri).map_err(|cause| Error::Communication(cause.to_string()))
+}
+
+fn percent_encode(value: &str) -> String {
+    utf8_percent_encode(value, ENCODE_SET).to_string()
+}
+
+fn http_method_for_operation(operation: Operation) -> hyper::Method {
+    match operation {
+        Operation::Create => hyper::Method::POST,
+        Operation::Read => hyper::Method::GET,
+        Operation::Modify => hyper::Method::PATCH,
+        Operation::Replace => hyper::Method::PUT,
+        Operation::Delete => hyper::Method::DELETE,
+        Operation::ReadHeader => hyper::Method::HEAD,
+    }
+}
+
+fn user_agent_string(agent: &dyn UserAgent) -> String {
+    format!(
+        "Mozilla/5.0 (compatible; {}/{}.{}; +{})",
+        agent.name(),
+        agent.version().major(),
+        agent.version().minor(),
+        agent.homepage()
+    )
+}
+
+fn basic_authorization(credentials: &Credentials) -> String {
+    let user_pass = format!("{}:{}", credentials.username(), credentials.password());
+    format!("Basic {}", STANDARD.encode(user_pass))
+}
+
+/// Formats the given duration as number of seconds with fractional part.
+fn format_seconds(duration: &Duration) -> String {
+    format!("{}.{:03}", duration.as_secs(), duration.subsec_millis())
+}
+
+fn content_type_of(content_format: ContentFormat) -> HeaderValue {
+    match content_format {
+        ContentFormat::Json => HeaderValue::from_static("application/json"),
+        ContentFormat::VelocyPack => HeaderValue::from_static(CONTENT_TYPE_VELOCYPACK),
+    }
+}
+
+/// Determines the format of the content of a response by its content type
+/// header.
+fn content_format_of(headers: &HeaderMap) -> ContentFormat {
+    let is_velocypack = headers.get(CONTENT_TYPE).map_or(false, |value| {
+        value
+            .as_bytes()
+            .starts_with(CONTENT_TYPE_VELOCYPACK.as_bytes())
+    });
+    if is_velocypack {
+        ContentFormat::VelocyPack
+    } else {
+        ContentFormat::Json
+    }
+}
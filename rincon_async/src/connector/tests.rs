@@ -0,0 +1,99 @@
+use std::iter::FromIterator;
+
+use super::*;
+
+struct Prepared<'a> {
+    operation: Operation,
+    path: &'a str,
+    params: Vec<(&'a str, &'a str)>,
+    content: Option<Value>,
+}
+
+impl<'a> Prepare for Prepared<'a> {
+    type Content = Value;
+
+    fn operation(&self) -> Operation {
+        self.operation
+    }
+
+    fn path(&self) -> String {
+        String::from(self.path)
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::from_iter(self.params.iter())
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        self.content.as_ref()
+    }
+}
+
+#[test]
+fn build_request_uri_for_http() {
+    let datasource = DataSource::from_str("http://localhost:8529").unwrap();
+    let prepared = Prepared {
+        operation: Operation::Read,
+        path: "/_api/user",
+        params: vec![],
+        content: None,
+    };
+
+    let uri = build_request_uri(&datasource, None, &prepared).unwrap();
+
+    assert_eq!("http://localhost:8529/_api/user", uri.to_string());
+}
+
+#[test]
+fn build_request_uri_for_specific_database_with_query_parameters() {
+    let datasource = DataSource::from_str("https://localhost:8529").unwrap();
+    let database_name = "the big one".to_owned();
+    let prepared = Prepared {
+        operation: Operation::Read,
+        path: "/_api/document/people/12345",
+        params: vec![("waitForSync", "true"), ("rev", "{abc}")],
+        content: None,
+    };
+
+    let uri = build_request_uri(&datasource, Some(&database_name), &prepared).unwrap();
+
+    assert_eq!(
+        "https://localhost:8529/_db/the%20big%20one/_api/document/people/12345\
+         ?waitForSync=true&rev=%7Babc%7D",
+        uri.to_string()
+    );
+}
+
+#[test]
+fn format_seconds_with_milliseconds() {
+    assert_eq!("2.500", format_seconds(&Duration::from_millis(2500)));
+    assert_eq!("0.007", format_seconds(&Duration::from_millis(7)));
+    assert_eq!("30.000", format_seconds(&Duration::from_secs(30)));
+}
+
+#[test]
+fn basic_authorization_of_credentials() {
+    let credentials = Credentials::new("micky", "pass");
+
+    assert_eq!("Basic bWlja3k6cGFzcw==", basic_authorization(&credentials));
+}
+
+#[test]
+fn content_format_of_velocypack_response() {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        CONTENT_TYPE,
+        HeaderValue::from_static(CONTENT_TYPE_VELOCYPACK),
+    );
+
+    assert_eq!(ContentFormat::VelocyPack, content_format_of(&headers));
+}
+
+#[test]
+fn content_format_of_response_without_content_type_is_json() {
+    assert_eq!(ContentFormat::Json, content_format_of(&HeaderMap::new()));
+}
@@ -0,0 +1,65 @@
+//! Connector and session API based on `std::future`
+//!
+//! The other crates of the rincon driver are based on version 0.1 of the
+//! `futures` crate, `tokio-core` and `hyper` 0.11. This crate provides a
+//! connector and a session API that are based on `std::future::Future` and
+//! can be used with `async`/`await` on the tokio 1.x runtime.
+//!
+//! The data model of method calls is the same as for the other crates. All
+//! methods of the [`rincon_client`] crate can be executed by the connector
+//! of this crate.
+//!
+//! * `AsyncJsonHttpConnector` : uses [JSON] or [VelocyPack] over HTTP/HTTPS
+//!   with current `hyper` and `rustls`
+//! * `ArangoSession` : the entry point to the session API, equivalent to the
+//!   session API provided by the `rincon_session_async` crate
+//!
+//! The `AsyncJsonHttpConnector` does not support failover, retries,
+//! interceptors and metrics sinks yet. All requests are sent to the host of
+//! the `DataSource`.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use rincon_async::connector::AsyncJsonHttpConnector;
+//! use rincon_async::session::{ArangoSession, Error, Query};
+//! use rincon_core::api::datasource::DataSource;
+//! use std::str::FromStr;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Error> {
+//!     let datasource = DataSource::from_str("http://localhost:8529")
+//!         .expect("invalid URL for datasource")
+//!         .with_basic_authentication("root", "s3cur3");
+//!     let connector = AsyncJsonHttpConnector::new(datasource)?;
+//!     let session = ArangoSession::new(connector);
+//!
+//!     let database = session.use_database("friendsbook");
+//!     let cursor = database
+//!         .query::<String>(Query::new("FOR p IN people RETURN p.name"))
+//!         .await?;
+//!     println!("{:?}", cursor.result());
+//!     Ok(())
+//! }
+//! ```
+//!
+//! [`rincon_client`]: https://docs.rs/rincon_client
+//! [JSON]: https://json.org
+//! [VelocyPack]: https://github.com/arangodb/velocypack
+
+#![doc(html_root_url = "https://docs.rs/rincon_async/0.1.0")]
+#![warn(
+    missing_copy_implementations,
+    missing_debug_implementations,
+    missing_docs,
+    trivial_casts,
+    trivial_numeric_casts,
+    unsafe_code,
+    unstable_features,
+    unused_import_braces,
+    unused_qualifications
+)]
+
+pub mod connector;
+pub mod session;
+mod tls;
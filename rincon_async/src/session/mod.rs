@@ -0,0 +1,379 @@
+//! Session API based on `std::future`.
+//!
+//! The session API of this module is equivalent to the session API provided
+//! by the `rincon_session_async` crate. All functions that communicate with
+//! the server are `async` functions.
+//!
+//! Sessions hold their connector in an `Arc`. Thus sessions can be sent to
+//! other threads and the futures returned by their functions can be spawned
+//! on a multi-threaded tokio runtime.
+
+use std::fmt::{self, Debug};
+use std::future::Future;
+use std::mem;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::vec::IntoIter;
+
+use futures::Stream;
+use serde::de::DeserializeOwned;
+use serde::ser::Serialize;
+
+pub use rincon_client::cursor::types::{Cursor, NewCursor};
+pub use rincon_client::database::types::{Database, NewDatabase};
+pub use rincon_client::graph::types::{EdgeDefinition, Graph, NewGraph};
+pub use rincon_client::user::types::{NewUser, UserExtra};
+pub use rincon_core::api::connector::Error;
+pub use rincon_core::api::query::Query;
+pub use rincon_core::api::types::Empty;
+
+use rincon_client::cursor::methods::{CreateCursor, DeleteCursor, ReadNextBatchFromCursor};
+use rincon_client::database::methods::{CreateDatabase, DropDatabase};
+use rincon_client::graph::methods::{CreateGraph, DropGraph};
+use rincon_core::api::connector::{AsyncConnector, AsyncExecute};
+use rincon_core::arango::protocol::SYSTEM_DATABASE;
+
+/// The future of a batch of query results.
+type CursorFuture<T> = Pin<Box<dyn Future<Output = Result<Cursor<T>, Error>> + Send>>;
+
+/// The entry point to the session API.
+///
+/// An `ArangoSession` provides sessions for the databases on the server.
+#[derive(Debug)]
+pub struct ArangoSession<C> {
+    connector: Arc<C>,
+}
+
+impl<C> ArangoSession<C>
+where
+    C: 'static + AsyncConnector,
+{
+    /// Instantiates a new `ArangoSession` that uses the given connector.
+    pub fn new(connector: C) -> Self {
+        ArangoSession {
+            connector: Arc::new(connector),
+        }
+    }
+
+    /// Returns a session for the system database.
+    pub fn use_system_database(&self) -> DatabaseSession<C> {
+        DatabaseSession::new(SYSTEM_DATABASE.to_owned(), self.connector.clone())
+    }
+
+    /// Returns a session for the database with the given name.
+    pub fn use_database<DbName>(&self, database_name: DbName) -> DatabaseSession<C>
+    where
+        DbName: Into<String>,
+    {
+        DatabaseSession::new(database_name.into(), self.connector.clone())
+    }
+
+    /// Creates a new database and returns a session for the new database.
+    pub async fn create_database<UserInfo>(
+        &self,
+        new_database: NewDatabase<UserInfo>,
+    ) -> Result<DatabaseSession<C>, Error>
+    where
+        UserInfo: UserExtra + Serialize + Send + 'static,
+    {
+        let database_name = new_database.name().to_owned();
+        self.connector
+            .system_connection()
+            .execute(CreateDatabase::new(new_database))
+            .await?;
+        Ok(DatabaseSession::new(database_name, self.connector.clone()))
+    }
+}
+
+/// A session for one database.
+#[derive(Debug)]
+pub struct DatabaseSession<C> {
+    database_name: String,
+    connector: Arc<C>,
+}
+
+impl<C> DatabaseSession<C>
+where
+    C: 'static + AsyncConnector,
+{
+    fn new(database_name: String, connector: Arc<C>) -> Self {
+        DatabaseSession {
+            database_name,
+            connector,
+        }
+    }
+
+    /// Returns the name of the database used in this session.
+    pub fn name(&self) -> &str {
+        &self.database_name
+    }
+
+    /// Drops the database that is used in this session.
+    ///
+    /// After calling this function the associated `DatabaseSession` is no
+    /// longer valid.
+    pub async fn drop(self) -> Result<bool, Error> {
+        self.connector
+            .system_connection()
+            .execute(DropDatabase::new(self.database_name))
+            .await
+    }
+
+    /// Executes a query and returns a cursor with the first result set.
+    ///
+    /// All cursor options and query execution options are left to their default
+    /// settings.
+    ///
+    /// To specify cursor options and/or query execution options use the
+    /// `query_opt(&self, NewCursor)` function.
+    pub async fn query<T>(&self, query: Query) -> Result<Cursor<T>, Error>
+    where
+        T: 'static + DeserializeOwned + Send,
+    {
+        self.connector
+            .connection(&self.database_name)
+            .execute(CreateCursor::from_query(query))
+            .await
+    }
+
+    /// Executes a query and returns a cursor with the first result set.
+    ///
+    /// It requires a `NewCursor` struct as a parameter which allows full
+    /// control over all supported cursor options and query execution options.
+    ///
+    /// To execute a query with all options left at their defaults the
+    /// `query(&self, Query)` function might be more suitable.
+    pub async fn query_opt<T>(&self, new_cursor: NewCursor) -> Result<Cursor<T>, Error>
+    where
+        T: 'static + DeserializeOwned + Send,
+    {
+        self.connector
+            .connection(&self.database_name)
+            .execute(CreateCursor::new(new_cursor))
+            .await
+    }
+
+    /// Executes a query and returns a `Stream` over all results of the query.
+    ///
+    /// The stream fetches the next batch of results from the server when all
+    /// results of the current batch have been consumed. Thus only one batch
+    /// of results is held in memory at a time.
+    ///
+    /// All cursor options and query execution options are left to their default
+    /// settings.
+    ///
+    /// To specify cursor options and/or query execution options use the
+    /// `query_stream_opt(&self, NewCursor)` function.
+    pub fn query_stream<T>(&self, query: Query) -> CursorStream<T, C>
+    where
+        T: 'static + DeserializeOwned + Send,
+    {
+        self.query_stream_opt(query.into())
+    }
+
+    /// Executes a query and returns a `Stream` over all results of the query.
+    ///
+    /// It requires a `NewCursor` struct as a parameter which allows full
+    /// control over all supported cursor options and query execution options.
+    /// The batch size of the cursor determines how many results are fetched
+    /// from the server at once.
+    pub fn query_stream_opt<T>(&self, new_cursor: NewCursor) -> CursorStream<T, C>
+    where
+        T: 'static + DeserializeOwned + Send,
+    {
+        CursorStream::new(
+            self.connector
+                .connection(&self.database_name)
+                .execute(CreateCursor::new(new_cursor)),
+            self.database_name.clone(),
+            self.connector.clone(),
+        )
+    }
+
+    /// Creates a new graph in the database represented by this
+    /// `DatabaseSession`.
+    pub async fn create_graph(&self, new_graph: NewGraph) -> Result<GraphSession<C>, Error> {
+        let graph = self
+            .connector
+            .connection(&self.database_name)
+            .execute(CreateGraph::new(new_graph))
+            .await?;
+        Ok(GraphSession::new(
+            graph,
+            self.database_name.clone(),
+            self.connector.clone(),
+        ))
+    }
+}
+
+/// A `Stream` over all results of a query.
+///
+/// If a `CursorStream` is dropped before all results have been consumed the
+/// cursor is deleted on the server. Deleting the cursor requires that the
+/// stream is dropped within the context of a tokio runtime. Otherwise the
+/// cursor is kept on the server until it times out.
+pub struct CursorStream<T, C>
+where
+    C: 'static + AsyncConnector,
+{
+    state: CursorStreamState<T>,
+    cursor_id: Option<String>,
+    database_name: String,
+    connector: Arc<C>,
+}
+
+enum CursorStreamState<T> {
+    Fetching(CursorFuture<T>),
+    Streaming(IntoIter<T>),
+    Done,
+}
+
+impl<T, C> CursorStream<T, C>
+where
+    T: 'static + DeserializeOwned + Send,
+    C: 'static + AsyncConnector,
+{
+    fn new(first_cursor: CursorFuture<T>, database_name: String, connector: Arc<C>) -> Self {
+        CursorStream {
+            state: CursorStreamState::Fetching(first_cursor),
+            cursor_id: None,
+            database_name,
+            connector,
+        }
+    }
+
+    /// Returns the name of the database the query is executed for.
+    pub fn database_name(&self) -> &str {
+        &self.database_name
+    }
+
+    /// Returns the id of the cursor on the server if there are more results
+    /// to be fetched.
+    pub fn cursor_id(&self) -> Option<&String> {
+        self.cursor_id.as_ref()
+    }
+}
+
+// The state of the stream is never pinned, the future of the next batch is
+// boxed and pinned on its own.
+impl<T, C> Unpin for CursorStream<T, C> where C: 'static + AsyncConnector {}
+
+impl<T, C> Stream for CursorStream<T, C>
+where
+    T: 'static + DeserializeOwned + Send,
+    C: 'static + AsyncConnector,
+{
+    type Item = Result<T, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        use self::CursorStreamState::*;
+        let this = &mut *self;
+        loop {
+            match mem::replace(&mut this.state, Done) {
+                Fetching(mut future) => match future.as_mut().poll(cx) {
+                    Poll::Ready(Ok(cursor)) => {
+                        let has_more = cursor.has_more();
+                        let (cursor_id, _, result) = cursor.unwrap();
+                        this.cursor_id = if has_more { cursor_id } else { None };
+                        this.state = Streaming(result.into_iter());
+                    },
+                    Poll::Ready(Err(error)) => return Poll::Ready(Some(Err(error))),
+                    Poll::Pending => {
+                        this.state = Fetching(future);
+                        return Poll::Pending;
+                    },
+                },
+                Streaming(mut batch) => {
+                    if let Some(next) = batch.next() {
+                        this.state = Streaming(batch);
+                        return Poll::Ready(Some(Ok(next)));
+                    }
+                    if let Some(cursor_id) = this.cursor_id.clone() {
+                        this.state = Fetching(
+                            this.connector
+                                .connection(&this.database_name)
+                                .execute(ReadNextBatchFromCursor::new(cursor_id)),
+                        );
+                    }
+                },
+                Done => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+impl<T, C> Drop for CursorStream<T, C>
+where
+    C: 'static + AsyncConnector,
+{
+    fn drop(&mut self) {
+        if let Some(cursor_id) = self.cursor_id.take() {
+            if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+                let deletion = self
+                    .connector
+                    .connection(&self.database_name)
+                    .execute(DeleteCursor::new(cursor_id));
+                runtime.spawn(async move {
+                    //errors of background tasks can not be reported to anyone
+                    let _ = deletion.await;
+                });
+            }
+        }
+    }
+}
+
+impl<T, C> Debug for CursorStream<T, C>
+where
+    C: 'static + AsyncConnector + Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CursorStream")
+            .field("cursor_id", &self.cursor_id)
+            .field("database_name", &self.database_name)
+            .field("connector", &self.connector)
+            .finish()
+    }
+}
+
+/// A session for one graph.
+#[derive(Debug)]
+pub struct GraphSession<C> {
+    graph: Graph,
+    database_name: String,
+    connector: Arc<C>,
+}
+
+impl<C> GraphSession<C>
+where
+    C: 'static + AsyncConnector,
+{
+    fn new(graph: Graph, database_name: String, connector: Arc<C>) -> Self {
+        GraphSession {
+            graph,
+            database_name,
+            connector,
+        }
+    }
+
+    /// Returns the name of the database the graph belongs to.
+    pub fn database_name(&self) -> &str {
+        &self.database_name
+    }
+
+    /// Returns the graph used in this session.
+    pub fn graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    /// Drops the graph that is used in this session.
+    ///
+    /// After calling this function the associated `GraphSession` is no
+    /// longer valid.
+    pub async fn drop(self) -> Result<bool, Error> {
+        self.connector
+            .connection(&self.database_name)
+            .execute(DropGraph::new(self.graph.name()))
+            .await
+    }
+}
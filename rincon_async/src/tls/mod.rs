@@ -0,0 +1,194 @@
+//! TLS configuration of the `AsyncJsonHttpConnector` based on [rustls].
+//!
+//! The client configuration is built from the `TlsOptions` of a
+//! `DataSource` in the same way as for the `JsonHttpConnector` of the
+//! `rincon_connector` crate.
+//!
+//! [rustls]: https://github.com/rustls/rustls
+
+#[cfg(test)]
+mod tests;
+
+use std::convert::TryFrom;
+use std::path::Path;
+use std::sync::Arc;
+
+use log::{debug, warn};
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::{verify_tls12_signature, verify_tls13_signature, CryptoProvider};
+use rustls::pki_types::pem::{self, PemObject};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+use rustls::{ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme};
+
+use rincon_core::api::connector::Error;
+use rincon_core::api::datasource::TlsOptions;
+
+/// Builds the rustls client configuration for the given `TlsOptions`.
+///
+/// The configuration trusts the root certificates of the native certificate
+/// store of the platform and the root certificates specified in the options.
+pub(crate) fn client_config(tls_options: &TlsOptions) -> Result<ClientConfig, Error> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let root_store = root_store(tls_options)?;
+    let builder = ClientConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .map_err(|cause| invalid_configuration(&cause.to_string()))?;
+    let builder = if tls_options.is_accept_invalid_certificates() {
+        warn!("Verification of server certificates is turned off");
+        builder
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(AcceptAnyCertificate(provider)))
+    } else if root_store.is_empty() {
+        return Err(invalid_configuration(
+            "no root certificates found in the native certificate store",
+        ));
+    } else {
+        builder.with_root_certificates(root_store)
+    };
+    match tls_options.client_certificate() {
+        Some((certificate, private_key)) => {
+            let certificate_chain = read_certificates(certificate)?;
+            let private_key = read_private_key(private_key)?;
+            builder
+                .with_client_auth_cert(certificate_chain, private_key)
+                .map_err(|cause| {
+                    invalid_configuration(&format!("invalid client certificate: {}", cause))
+                })
+        },
+        None => Ok(builder.with_no_client_auth()),
+    }
+}
+
+/// Returns the server name used for SNI and certificate verification if it is
+/// specified in the given `TlsOptions`.
+pub(crate) fn server_name(tls_options: &TlsOptions) -> Result<Option<ServerName<'static>>, Error> {
+    tls_options
+        .server_name()
+        .map(|server_name| {
+            ServerName::try_from(server_name.to_owned()).map_err(|_| {
+                invalid_configuration(&format!("invalid server name {:?}", server_name))
+            })
+        })
+        .transpose()
+}
+
+fn root_store(tls_options: &TlsOptions) -> Result<RootCertStore, Error> {
+    let mut root_store = RootCertStore::empty();
+    let native_certificates = rustls_native_certs::load_native_certs();
+    for error in &native_certificates.errors {
+        warn!("Can not load native root certificates: {}", error);
+    }
+    let (_, skipped) = root_store.add_parsable_certificates(native_certificates.certs);
+    if skipped > 0 {
+        debug!("Skipped {} invalid native root certificates", skipped);
+    }
+    if let Some(path) = tls_options.root_certificates() {
+        let certificates = read_pem_certificates(path)?;
+        match root_store.add_parsable_certificates(certificates) {
+            (valid, _) if valid > 0 => {},
+            _ => {
+                return Err(invalid_configuration(&format!(
+                    "no valid root certificate found in {}",
+                    path.display()
+                )))
+            },
+        }
+    }
+    Ok(root_store)
+}
+
+fn read_pem_certificates(path: &Path) -> Result<Vec<CertificateDer<'static>>, Error> {
+    CertificateDer::pem_file_iter(path)
+        .map_err(|cause| {
+            invalid_configuration(&format!("can not open {}: {}", path.display(), cause))
+        })?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|cause| {
+            invalid_configuration(&format!(
+                "invalid certificate in {}: {}",
+                path.display(),
+                cause
+            ))
+        })
+}
+
+fn read_certificates(path: &Path) -> Result<Vec<CertificateDer<'static>>, Error> {
+    let certificates = read_pem_certificates(path)?;
+    if certificates.is_empty() {
+        Err(invalid_configuration(&format!(
+            "no certificate found in {}",
+            path.display()
+        )))
+    } else {
+        Ok(certificates)
+    }
+}
+
+/// Reads the first private key from the given file.
+///
+/// Keys in PKCS#8, PKCS#1 and SEC1 format are supported.
+fn read_private_key(path: &Path) -> Result<PrivateKeyDer<'static>, Error> {
+    PrivateKeyDer::from_pem_file(path).map_err(|cause| match cause {
+        pem::Error::NoItemsFound => {
+            invalid_configuration(&format!("no private key found in {}", path.display()))
+        },
+        _ => invalid_configuration(&format!("can not read {}: {}", path.display(), cause)),
+    })
+}
+
+fn invalid_configuration(message: &str) -> Error {
+    Error::Communication(format!("Invalid TLS configuration: {}", message))
+}
+
+/// A `ServerCertVerifier` that accepts any certificate presented by a
+/// server.
+///
+/// The signatures of the handshake are still verified with the algorithms of
+/// the given crypto provider.
+#[derive(Debug)]
+struct AcceptAnyCertificate(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for AcceptAnyCertificate {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        certificate: &CertificateDer<'_>,
+        signature: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(
+            message,
+            certificate,
+            signature,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        certificate: &CertificateDer<'_>,
+        signature: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(
+            message,
+            certificate,
+            signature,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
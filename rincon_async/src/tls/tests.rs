@@ -0,0 +1,100 @@
+use std::path::PathBuf;
+
+use super::*;
+
+fn cert_file(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("rincon_connector")
+        .join("tests")
+        .join("certs")
+        .join(name)
+}
+
+fn invalid_configuration_message<T>(result: Result<T, Error>) -> String {
+    match result {
+        Err(Error::Communication(message)) => message,
+        Err(error) => panic!("unexpected error: {:?}", error),
+        Ok(_) => panic!("expected an error, but got a configuration"),
+    }
+}
+
+#[test]
+fn client_config_with_custom_root_certificate() {
+    let tls_options = TlsOptions::new().with_root_certificates(cert_file("ca.pem"));
+
+    assert!(client_config(&tls_options).is_ok());
+}
+
+#[test]
+fn client_config_with_missing_root_certificates_file() {
+    let tls_options = TlsOptions::new().with_root_certificates(cert_file("missing.pem"));
+
+    let message = invalid_configuration_message(client_config(&tls_options));
+
+    assert!(message.starts_with("Invalid TLS configuration: can not open"));
+}
+
+#[test]
+fn client_config_with_root_certificates_file_without_certificates() {
+    let tls_options = TlsOptions::new().with_root_certificates(cert_file("client.key"));
+
+    let message = invalid_configuration_message(client_config(&tls_options));
+
+    assert!(message.starts_with("Invalid TLS configuration: no valid root certificate found"));
+}
+
+#[test]
+fn client_config_with_client_certificate() {
+    let tls_options = TlsOptions::new()
+        .with_root_certificates(cert_file("ca.pem"))
+        .with_client_certificate(cert_file("client.pem"), cert_file("client.key"));
+
+    let config = client_config(&tls_options).unwrap();
+
+    assert!(config.client_auth_cert_resolver.has_certs());
+}
+
+#[test]
+fn client_config_with_client_certificate_but_key_file_without_key() {
+    let tls_options = TlsOptions::new()
+        .with_root_certificates(cert_file("ca.pem"))
+        .with_client_certificate(cert_file("client.pem"), cert_file("client.pem"));
+
+    let message = invalid_configuration_message(client_config(&tls_options));
+
+    assert!(message.starts_with("Invalid TLS configuration: no private key found"));
+}
+
+#[test]
+fn client_config_accepting_invalid_certificates() {
+    let tls_options = TlsOptions::new().with_accept_invalid_certificates(true);
+
+    let config = client_config(&tls_options).unwrap();
+
+    assert!(!config.client_auth_cert_resolver.has_certs());
+}
+
+#[test]
+fn server_name_is_none_by_default() {
+    assert_eq!(None, server_name(&TlsOptions::new()).unwrap());
+}
+
+#[test]
+fn server_name_from_tls_options() {
+    let tls_options = TlsOptions::new().with_server_name("arangodb.example.com");
+
+    assert_eq!(
+        Some(ServerName::try_from("arangodb.example.com").unwrap()),
+        server_name(&tls_options).unwrap()
+    );
+}
+
+#[test]
+fn invalid_server_name_from_tls_options() {
+    let tls_options = TlsOptions::new().with_server_name("not a server name");
+
+    let message = invalid_configuration_message(server_name(&tls_options));
+
+    assert!(message.starts_with("Invalid TLS configuration: invalid server name"));
+}
@@ -3,7 +3,8 @@
 use super::types::*;
 use rincon_core::api::method::{Method, Operation, Parameters, Prepare, RpcReturnType};
 use rincon_core::arango::protocol::{
-    FIELD_CODE, PARAM_DETAILS, PATH_ADMIN, PATH_API_VERSION, PATH_DATABASE, PATH_TARGET_VERSION,
+    FIELD_CODE, FIELD_ROLE, PARAM_DETAILS, PATH_ADMIN, PATH_API_ENGINE, PATH_API_VERSION,
+    PATH_DATABASE, PATH_ROLE, PATH_SERVER, PATH_TARGET_VERSION,
 };
 
 #[allow(missing_copy_implementations)]
@@ -105,3 +106,89 @@ impl Prepare for GetServerVersion {
         None
     }
 }
+
+#[allow(missing_copy_implementations)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetEngine {}
+
+#[cfg_attr(feature = "cargo-clippy", allow(new_without_default_derive))]
+impl GetEngine {
+    pub fn new() -> Self {
+        GetEngine {}
+    }
+}
+
+impl Method for GetEngine {
+    type Result = Engine;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: None,
+    };
+}
+
+impl Prepare for GetEngine {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Read
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_ENGINE)
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+#[allow(missing_copy_implementations)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetServerRole {}
+
+#[cfg_attr(feature = "cargo-clippy", allow(new_without_default_derive))]
+impl GetServerRole {
+    pub fn new() -> Self {
+        GetServerRole {}
+    }
+}
+
+impl Method for GetServerRole {
+    type Result = ServerRole;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: Some(FIELD_ROLE),
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for GetServerRole {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Read
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_ADMIN) + PATH_SERVER + PATH_ROLE
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
@@ -1,7 +1,19 @@
 //! Types used in methods for server administration.
 
+use serde::de::{Deserialize, Deserializer};
+
 use rincon_core::api::types::JsonValue;
 
+const ENGINE_MMFILES: &str = "mmfiles";
+const ENGINE_ROCKSDB: &str = "rocksdb";
+const LICENSE_ENTERPRISE: &str = "enterprise";
+const SERVER_ROLE_SINGLE: &str = "SINGLE";
+const SERVER_ROLE_COORDINATOR: &str = "COORDINATOR";
+const SERVER_ROLE_PRIMARY: &str = "PRIMARY";
+const SERVER_ROLE_SECONDARY: &str = "SECONDARY";
+const SERVER_ROLE_AGENT: &str = "AGENT";
+const SERVER_ROLE_UNDEFINED: &str = "UNDEFINED";
+
 /// Represents the database-version that this server requires.
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -84,3 +96,167 @@ impl ServerVersion {
         self.details.as_ref()
     }
 }
+
+/// Represents the storage engine used by the server.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Engine {
+    /// The name of the storage engine, e.g. 'mmfiles' or 'rocksdb'.
+    name: String,
+    /// The features supported by the storage engine.
+    #[serde(default)]
+    supports: EngineSupports,
+}
+
+impl Engine {
+    /// Returns the name of the storage engine.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the features supported by the storage engine.
+    pub fn supports(&self) -> &EngineSupports {
+        &self.supports
+    }
+
+    /// Returns whether this is the MMFiles storage engine.
+    pub fn is_mmfiles(&self) -> bool {
+        self.name == ENGINE_MMFILES
+    }
+
+    /// Returns whether this is the RocksDB storage engine.
+    pub fn is_rocksdb(&self) -> bool {
+        self.name == ENGINE_ROCKSDB
+    }
+}
+
+/// Represents the features supported by a storage engine.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineSupports {
+    /// Whether the storage engine supports the dfdb tool.
+    #[serde(default)]
+    dfdb: bool,
+    /// The types of indexes supported by the storage engine.
+    #[serde(default)]
+    indexes: Vec<String>,
+}
+
+impl EngineSupports {
+    /// Returns whether the storage engine supports the dfdb tool.
+    pub fn is_dfdb(&self) -> bool {
+        self.dfdb
+    }
+
+    /// Returns the types of indexes supported by the storage engine.
+    pub fn indexes(&self) -> &[String] {
+        &self.indexes
+    }
+}
+
+/// This enum defines the roles a server can have in a deployment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ServerRole {
+    /// The server is a single server instance, not part of a cluster.
+    Single,
+    /// The server is a coordinator in a cluster.
+    Coordinator,
+    /// The server is a DB-server in a cluster.
+    Primary,
+    /// The server is a secondary in an active failover or replication
+    /// setup.
+    Secondary,
+    /// The server is an agent of a cluster.
+    Agent,
+    /// The role of the server is not defined.
+    Undefined,
+}
+
+impl<'de> Deserialize<'de> for ServerRole {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use self::ServerRole::*;
+        use serde::de::Error;
+        let value = String::deserialize(deserializer)?;
+        match &value[..] {
+            SERVER_ROLE_SINGLE => Ok(Single),
+            SERVER_ROLE_COORDINATOR => Ok(Coordinator),
+            SERVER_ROLE_PRIMARY => Ok(Primary),
+            SERVER_ROLE_SECONDARY => Ok(Secondary),
+            SERVER_ROLE_AGENT => Ok(Agent),
+            SERVER_ROLE_UNDEFINED => Ok(Undefined),
+            _ => Err(D::Error::custom(format!(
+                "Unknown server role: {:?}",
+                value
+            ))),
+        }
+    }
+}
+
+/// Describes the capabilities of the server a session is connected to.
+///
+/// Some attributes of methods and their results are only meaningful in a
+/// certain server configuration, e.g. when the server uses a specific storage
+/// engine, is setup as a cluster or is an enterprise edition. The
+/// `ServerCapabilities` are determined at runtime and can be used to check
+/// which of those attributes are supported by the server.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerCapabilities {
+    version: ServerVersion,
+    engine: Engine,
+    role: ServerRole,
+}
+
+impl ServerCapabilities {
+    /// Constructs new `ServerCapabilities` from the version, the storage
+    /// engine and the role of a server.
+    pub fn new(version: ServerVersion, engine: Engine, role: ServerRole) -> Self {
+        ServerCapabilities {
+            version,
+            engine,
+            role,
+        }
+    }
+
+    /// Returns the version of the server.
+    pub fn version(&self) -> &ServerVersion {
+        &self.version
+    }
+
+    /// Returns the storage engine used by the server.
+    pub fn engine(&self) -> &Engine {
+        &self.engine
+    }
+
+    /// Returns the role of the server.
+    pub fn role(&self) -> ServerRole {
+        self.role
+    }
+
+    /// Returns whether the server is an enterprise edition.
+    pub fn is_enterprise(&self) -> bool {
+        self.version.license() == LICENSE_ENTERPRISE
+    }
+
+    /// Returns whether the server is part of a cluster.
+    ///
+    /// This is the case for coordinators, DB-servers and agents.
+    pub fn is_cluster(&self) -> bool {
+        match self.role {
+            ServerRole::Coordinator | ServerRole::Primary | ServerRole::Agent => true,
+            ServerRole::Single | ServerRole::Secondary | ServerRole::Undefined => false,
+        }
+    }
+
+    /// Returns whether the server uses the MMFiles storage engine.
+    pub fn is_mmfiles(&self) -> bool {
+        self.engine.is_mmfiles()
+    }
+
+    /// Returns whether the server uses the RocksDB storage engine.
+    pub fn is_rocksdb(&self) -> bool {
+        self.engine.is_rocksdb()
+    }
+}
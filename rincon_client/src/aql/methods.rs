@@ -3,7 +3,11 @@
 use super::types::*;
 use rincon_core::api::method::{Method, Operation, Parameters, Prepare, RpcReturnType};
 use rincon_core::api::query::Query;
-use rincon_core::arango::protocol::{FIELD_CODE, PATH_API_EXPLAIN, PATH_API_QUERY};
+use rincon_core::api::types::Empty;
+use rincon_core::arango::protocol::{
+    FIELD_CODE, FIELD_RESULT, PARAM_GROUP, PARAM_NAMESPACE, PATH_API_AQLFUNCTION, PATH_API_EXPLAIN,
+    PATH_API_QUERY, PATH_API_QUERY_CACHE, PATH_CURRENT, PATH_ENTRIES, PATH_PROPERTIES, PATH_SLOW,
+};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct ParseQuery {
@@ -110,3 +114,676 @@ impl Prepare for ExplainQuery {
         Some(&self.query_options)
     }
 }
+
+/// Returns the user defined AQL functions registered in the database.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListAqlFunctions {
+    namespace: Option<String>,
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(new_without_default_derive))]
+impl ListAqlFunctions {
+    /// Constructs a new `ListAqlFunctions` method that returns all user
+    /// defined AQL functions.
+    pub fn new() -> Self {
+        ListAqlFunctions { namespace: None }
+    }
+
+    /// Constructs a new `ListAqlFunctions` method that returns only the
+    /// functions in the given namespace.
+    pub fn with_namespace<N>(namespace: N) -> Self
+    where
+        N: Into<String>,
+    {
+        ListAqlFunctions {
+            namespace: Some(namespace.into()),
+        }
+    }
+
+    /// Returns the namespace the functions are filtered by.
+    pub fn namespace(&self) -> Option<&String> {
+        self.namespace.as_ref()
+    }
+}
+
+impl Method for ListAqlFunctions {
+    type Result = Vec<AqlFunction>;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: Some(FIELD_RESULT),
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for ListAqlFunctions {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Read
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_AQLFUNCTION)
+    }
+
+    fn parameters(&self) -> Parameters {
+        let mut params = Parameters::with_capacity(1);
+        if let Some(ref namespace) = self.namespace {
+            params.insert(PARAM_NAMESPACE, namespace.to_owned());
+        }
+        params
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Registers a user defined AQL function.
+///
+/// If a function with the same name is already registered it is replaced by
+/// the new definition.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateAqlFunction {
+    function: NewAqlFunction,
+}
+
+impl CreateAqlFunction {
+    /// Constructs a new `CreateAqlFunction` method for the given function
+    /// definition.
+    pub fn new(function: NewAqlFunction) -> Self {
+        CreateAqlFunction { function }
+    }
+
+    /// Returns the definition of the function to be registered.
+    pub fn function(&self) -> &NewAqlFunction {
+        &self.function
+    }
+}
+
+impl Method for CreateAqlFunction {
+    type Result = AqlFunctionCreated;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for CreateAqlFunction {
+    type Content = NewAqlFunction;
+
+    fn operation(&self) -> Operation {
+        Operation::Create
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_AQLFUNCTION)
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        Some(&self.function)
+    }
+}
+
+/// Removes a user defined AQL function or all functions of a namespace.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeleteAqlFunction {
+    name: String,
+    group: bool,
+}
+
+impl DeleteAqlFunction {
+    /// Constructs a new `DeleteAqlFunction` method that removes the function
+    /// with the given fully qualified name.
+    pub fn with_name<N>(name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        DeleteAqlFunction {
+            name: name.into(),
+            group: false,
+        }
+    }
+
+    /// Constructs a new `DeleteAqlFunction` method that removes all functions
+    /// in the given namespace.
+    pub fn with_namespace<N>(namespace: N) -> Self
+    where
+        N: Into<String>,
+    {
+        DeleteAqlFunction {
+            name: namespace.into(),
+            group: true,
+        }
+    }
+
+    /// Returns the name of the function or the namespace to be removed.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns whether all functions in the namespace given by the name are
+    /// removed.
+    pub fn is_group(&self) -> bool {
+        self.group
+    }
+}
+
+impl Method for DeleteAqlFunction {
+    type Result = AqlFunctionsDeleted;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for DeleteAqlFunction {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Delete
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_AQLFUNCTION) + "/" + &self.name
+    }
+
+    fn parameters(&self) -> Parameters {
+        let mut params = Parameters::with_capacity(1);
+        if self.group {
+            params.insert(PARAM_GROUP, true);
+        }
+        params
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Returns the AQL queries currently running in the database.
+#[allow(missing_copy_implementations)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListCurrentQueries {}
+
+#[cfg_attr(feature = "cargo-clippy", allow(new_without_default_derive))]
+impl ListCurrentQueries {
+    /// Constructs a new instance of the `ListCurrentQueries` method.
+    pub fn new() -> Self {
+        ListCurrentQueries {}
+    }
+}
+
+impl Method for ListCurrentQueries {
+    type Result = Vec<RunningQuery>;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for ListCurrentQueries {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Read
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_QUERY) + PATH_CURRENT
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Returns the list of slow AQL queries tracked for the database.
+#[allow(missing_copy_implementations)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListSlowQueries {}
+
+#[cfg_attr(feature = "cargo-clippy", allow(new_without_default_derive))]
+impl ListSlowQueries {
+    /// Constructs a new instance of the `ListSlowQueries` method.
+    pub fn new() -> Self {
+        ListSlowQueries {}
+    }
+}
+
+impl Method for ListSlowQueries {
+    type Result = Vec<RunningQuery>;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for ListSlowQueries {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Read
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_QUERY) + PATH_SLOW
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Clears the list of slow AQL queries tracked for the database.
+#[allow(missing_copy_implementations)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClearSlowQueries {}
+
+#[cfg_attr(feature = "cargo-clippy", allow(new_without_default_derive))]
+impl ClearSlowQueries {
+    /// Constructs a new instance of the `ClearSlowQueries` method.
+    pub fn new() -> Self {
+        ClearSlowQueries {}
+    }
+}
+
+impl Method for ClearSlowQueries {
+    type Result = Empty;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for ClearSlowQueries {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Delete
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_QUERY) + PATH_SLOW
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Kills a running AQL query.
+///
+/// The query is terminated at the next cancelation point.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KillQuery {
+    id: String,
+}
+
+impl KillQuery {
+    /// Constructs a new `KillQuery` method for the query with the given id.
+    pub fn new<I>(id: I) -> Self
+    where
+        I: Into<String>,
+    {
+        KillQuery { id: id.into() }
+    }
+
+    /// Returns the id of the query to be killed.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl Method for KillQuery {
+    type Result = Empty;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for KillQuery {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Delete
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_QUERY) + "/" + &self.id
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Returns the current configuration of the AQL query tracking.
+#[allow(missing_copy_implementations)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetQueryTrackingProperties {}
+
+#[cfg_attr(feature = "cargo-clippy", allow(new_without_default_derive))]
+impl GetQueryTrackingProperties {
+    /// Constructs a new instance of the `GetQueryTrackingProperties` method.
+    pub fn new() -> Self {
+        GetQueryTrackingProperties {}
+    }
+}
+
+impl Method for GetQueryTrackingProperties {
+    type Result = QueryTrackingProperties;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for GetQueryTrackingProperties {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Read
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_QUERY) + PATH_PROPERTIES
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Changes the configuration of the AQL query tracking.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangeQueryTrackingProperties {
+    updates: QueryTrackingPropertiesUpdate,
+}
+
+impl ChangeQueryTrackingProperties {
+    /// Constructs a new `ChangeQueryTrackingProperties` method with the given
+    /// updates.
+    pub fn new(updates: QueryTrackingPropertiesUpdate) -> Self {
+        ChangeQueryTrackingProperties { updates }
+    }
+
+    /// Returns the updates to be applied to the query tracking properties.
+    pub fn updates(&self) -> &QueryTrackingPropertiesUpdate {
+        &self.updates
+    }
+}
+
+impl Method for ChangeQueryTrackingProperties {
+    type Result = QueryTrackingProperties;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for ChangeQueryTrackingProperties {
+    type Content = QueryTrackingPropertiesUpdate;
+
+    fn operation(&self) -> Operation {
+        Operation::Replace
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_QUERY) + PATH_PROPERTIES
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        Some(&self.updates)
+    }
+}
+
+/// Clears all results in the AQL query result cache of the database.
+#[allow(missing_copy_implementations)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClearQueryCache {}
+
+#[cfg_attr(feature = "cargo-clippy", allow(new_without_default_derive))]
+impl ClearQueryCache {
+    /// Constructs a new instance of the `ClearQueryCache` method.
+    pub fn new() -> Self {
+        ClearQueryCache {}
+    }
+}
+
+impl Method for ClearQueryCache {
+    type Result = Empty;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for ClearQueryCache {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Delete
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_QUERY_CACHE)
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Returns the entries of the AQL query result cache of the database.
+#[allow(missing_copy_implementations)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListQueryCacheEntries {}
+
+#[cfg_attr(feature = "cargo-clippy", allow(new_without_default_derive))]
+impl ListQueryCacheEntries {
+    /// Constructs a new instance of the `ListQueryCacheEntries` method.
+    pub fn new() -> Self {
+        ListQueryCacheEntries {}
+    }
+}
+
+impl Method for ListQueryCacheEntries {
+    type Result = Vec<CachedQuery>;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for ListQueryCacheEntries {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Read
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_QUERY_CACHE) + PATH_ENTRIES
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Returns the global configuration of the AQL query result cache.
+#[allow(missing_copy_implementations)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetQueryCacheProperties {}
+
+#[cfg_attr(feature = "cargo-clippy", allow(new_without_default_derive))]
+impl GetQueryCacheProperties {
+    /// Constructs a new instance of the `GetQueryCacheProperties` method.
+    pub fn new() -> Self {
+        GetQueryCacheProperties {}
+    }
+}
+
+impl Method for GetQueryCacheProperties {
+    type Result = QueryCacheProperties;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for GetQueryCacheProperties {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Read
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_QUERY_CACHE) + PATH_PROPERTIES
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Changes the global configuration of the AQL query result cache.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangeQueryCacheProperties {
+    updates: QueryCachePropertiesUpdate,
+}
+
+impl ChangeQueryCacheProperties {
+    /// Constructs a new `ChangeQueryCacheProperties` method with the given
+    /// updates.
+    pub fn new(updates: QueryCachePropertiesUpdate) -> Self {
+        ChangeQueryCacheProperties { updates }
+    }
+
+    /// Returns the updates to be applied to the query cache properties.
+    pub fn updates(&self) -> &QueryCachePropertiesUpdate {
+        &self.updates
+    }
+}
+
+impl Method for ChangeQueryCacheProperties {
+    type Result = QueryCacheProperties;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for ChangeQueryCacheProperties {
+    type Content = QueryCachePropertiesUpdate;
+
+    fn operation(&self) -> Operation {
+        Operation::Replace
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_QUERY_CACHE) + PATH_PROPERTIES
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        Some(&self.updates)
+    }
+}
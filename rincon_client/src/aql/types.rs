@@ -33,13 +33,9 @@ const EXECUTION_NODE_TYPE_REPLACE_NODE: &str = "ReplaceNode";
 const EXECUTION_NODE_TYPE_UPDATE_NODE: &str = "UpdateNode";
 const EXECUTION_NODE_TYPE_UPSERT_NODE: &str = "UpsertNode";
 const EXECUTION_NODE_TYPE_NO_RESULTS_NODE: &str = "NoResultsNode";
-#[cfg(feature = "cluster")]
 const EXECUTION_NODE_TYPE_SCATTER_NODE: &str = "ScatterNode";
-#[cfg(feature = "cluster")]
 const EXECUTION_NODE_TYPE_GATHER_NODE: &str = "GatherNode";
-#[cfg(feature = "cluster")]
 const EXECUTION_NODE_TYPE_DISTRIBUTE_NODE: &str = "DistributeNode";
-#[cfg(feature = "cluster")]
 const EXECUTION_NODE_TYPE_REMOTE_NODE: &str = "RemoteNode";
 
 const OPTIMIZER_RULE_ALL: &str = "all";
@@ -67,20 +63,30 @@ const OPTIMIZER_RULE_INLINE_SUBQUERIES: &str = "inline-subqueries";
 const OPTIMIZER_RULE_GEO_INDEX_OPTIMIZER: &str = "geo-index-optimizer";
 const OPTIMIZER_RULE_REMOVE_SORT_RAND: &str = "remove-sort-rand";
 const OPTIMIZER_RULE_REDUCE_EXTRACTION_TO_PROJECTION: &str = "reduce-extraction-to-projection";
-#[cfg(feature = "cluster")]
 const OPTIMIZER_RULE_DISTRIBUTE_IN_CLUSTER: &str = "distribute-in-cluster";
-#[cfg(feature = "cluster")]
 const OPTIMIZER_RULE_SCATTER_IN_CLUSTER: &str = "scatter-in-cluster";
-#[cfg(feature = "cluster")]
 const OPTIMIZER_RULE_DISTRIBUTE_FILTERCALC_TO_CLUSTER: &str = "distribute-filtercalc-to-cluster";
-#[cfg(feature = "cluster")]
 const OPTIMIZER_RULE_DISTRIBUTE_SORT_TO_CLUSTER: &str = "distribute-sort-to-cluster";
-#[cfg(feature = "cluster")]
 const OPTIMIZER_RULE_REMOVE_UNNECESSARY_REMOTE_SCATTER: &str = "remove-unnecessary-remote-scatter";
-#[cfg(feature = "cluster")]
 const OPTIMIZER_RULE_UNDISTRIBUTE_REMOVE_AFTER_ENUM_COLL: &str =
     "undistribute-remove-after-enum-coll";
 
+const QUERY_STATE_INITIALIZING: &str = "initializing";
+const QUERY_STATE_PARSING: &str = "parsing";
+const QUERY_STATE_OPTIMIZING_AST: &str = "optimizing ast";
+const QUERY_STATE_LOADING_COLLECTIONS: &str = "loading collections";
+const QUERY_STATE_INSTANTIATING_PLAN: &str = "instantiating plan";
+const QUERY_STATE_OPTIMIZING_PLAN: &str = "optimizing plan";
+const QUERY_STATE_EXECUTING: &str = "executing";
+const QUERY_STATE_FINALIZING: &str = "finalizing";
+const QUERY_STATE_FINISHED: &str = "finished";
+const QUERY_STATE_KILLED: &str = "killed";
+const QUERY_STATE_INVALID: &str = "invalid";
+
+const QUERY_CACHE_MODE_OFF: &str = "off";
+const QUERY_CACHE_MODE_ON: &str = "on";
+const QUERY_CACHE_MODE_DEMAND: &str = "demand";
+
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ParsedQuery {
@@ -369,18 +375,14 @@ pub enum ExecutionNode {
     /// The NoResultsNode will pass an empty result set into the processing
     /// pipeline.
     NoResults(NoResultsNode),
-    #[cfg(feature = "cluster")]
     /// Used on a coordinator to fan-out data to one or multiple shards.
     Scatter(ScatterNode),
-    #[cfg(feature = "cluster")]
     /// Used on a coordinator to aggregate results from one or many shards into
     /// a combined stream of results.
     Gather(GatherNode),
-    #[cfg(feature = "cluster")]
     /// Used on a coordinator to fan-out data to one or multiple shards, taking
     /// into account a collection's shard key.
     Distribute(DistributeNode),
-    #[cfg(feature = "cluster")]
     /// A RemoteNode will perform communication with another ArangoDB instances
     /// in the cluster. For example, the cluster coordinator will need to
     /// communicate with other servers to fetch the actual data from the shards.
@@ -1106,7 +1108,6 @@ impl NoResultsNode {
     }
 }
 
-#[cfg(feature = "cluster")]
 //TODO add node specific fields
 /// Used on a coordinator to fan-out data to one or multiple shards.
 #[derive(Debug, Clone, PartialEq)]
@@ -1117,7 +1118,6 @@ pub struct ScatterNode {
     estimated_nr_items: u64,
 }
 
-#[cfg(feature = "cluster")]
 impl ScatterNode {
     pub fn new<Deps>(
         id: ExecutionNodeId,
@@ -1137,7 +1137,6 @@ impl ScatterNode {
     }
 }
 
-#[cfg(feature = "cluster")]
 //TODO add node specific fields
 /// Used on a coordinator to aggregate results from one or many shards into a
 /// combined stream of results.
@@ -1149,7 +1148,6 @@ pub struct GatherNode {
     estimated_nr_items: u64,
 }
 
-#[cfg(feature = "cluster")]
 impl GatherNode {
     pub fn new<Deps>(
         id: ExecutionNodeId,
@@ -1169,7 +1167,6 @@ impl GatherNode {
     }
 }
 
-#[cfg(feature = "cluster")]
 //TODO add node specific fields
 /// Used on a coordinator to fan-out data to one or multiple shards, taking
 /// into account a collection's shard key.
@@ -1181,7 +1178,6 @@ pub struct DistributeNode {
     estimated_nr_items: u64,
 }
 
-#[cfg(feature = "cluster")]
 impl DistributeNode {
     pub fn new<Deps>(
         id: ExecutionNodeId,
@@ -1201,7 +1197,6 @@ impl DistributeNode {
     }
 }
 
-#[cfg(feature = "cluster")]
 //TODO add node specific fields
 /// A `RemoteNode` will perform communication with another *ArangoDB* instances
 /// in the cluster. For example, the cluster coordinator will need to
@@ -1218,7 +1213,6 @@ pub struct RemoteNode {
     estimated_nr_items: u64,
 }
 
-#[cfg(feature = "cluster")]
 impl RemoteNode {
     pub fn new<Deps>(
         id: ExecutionNodeId,
@@ -1921,18 +1915,14 @@ pub enum ExecutionNodeType {
     /// The NoResultsNode will pass an empty result set into the processing
     /// pipeline.
     NoResultsNode,
-    #[cfg(feature = "cluster")]
     /// used on a coordinator to fan-out data to one or multiple shards.
     ScatterNode,
-    #[cfg(feature = "cluster")]
     /// used on a coordinator to aggregate results from one or many shards into
     /// a combined stream of results.
     GatherNode,
-    #[cfg(feature = "cluster")]
     /// used on a coordinator to fan-out data to one or multiple shards, taking
     /// into account a collection's shard key.
     DistributeNode,
-    #[cfg(feature = "cluster")]
     /// a RemoteNode will perform communication with another ArangoDB instances
     /// in the cluster. For example, the cluster coordinator will need to
     /// communicate with other servers to fetch the actual data from the
@@ -1969,13 +1959,9 @@ impl ExecutionNodeType {
             EXECUTION_NODE_TYPE_UPDATE_NODE => UpdateNode,
             EXECUTION_NODE_TYPE_UPSERT_NODE => UpsertNode,
             EXECUTION_NODE_TYPE_NO_RESULTS_NODE => NoResultsNode,
-            #[cfg(feature = "cluster")]
             EXECUTION_NODE_TYPE_SCATTER_NODE => ScatterNode,
-            #[cfg(feature = "cluster")]
             EXECUTION_NODE_TYPE_GATHER_NODE => GatherNode,
-            #[cfg(feature = "cluster")]
             EXECUTION_NODE_TYPE_DISTRIBUTE_NODE => DistributeNode,
-            #[cfg(feature = "cluster")]
             EXECUTION_NODE_TYPE_REMOTE_NODE => RemoteNode,
             node => Unlisted(node.to_owned()),
         }
@@ -2003,13 +1989,9 @@ impl ExecutionNodeType {
             UpdateNode => EXECUTION_NODE_TYPE_UPDATE_NODE,
             UpsertNode => EXECUTION_NODE_TYPE_UPSERT_NODE,
             NoResultsNode => EXECUTION_NODE_TYPE_NO_RESULTS_NODE,
-            #[cfg(feature = "cluster")]
             ScatterNode => EXECUTION_NODE_TYPE_SCATTER_NODE,
-            #[cfg(feature = "cluster")]
             GatherNode => EXECUTION_NODE_TYPE_GATHER_NODE,
-            #[cfg(feature = "cluster")]
             DistributeNode => EXECUTION_NODE_TYPE_DISTRIBUTE_NODE,
-            #[cfg(feature = "cluster")]
             RemoteNode => EXECUTION_NODE_TYPE_REMOTE_NODE,
             Unlisted(ref node) => node,
         }
@@ -2859,32 +2841,26 @@ pub enum OptimizerRule {
     /// document. This optimizer rule is specific for the RocksDB storage
     /// engine.
     ReduceExtractionToProjection,
-    #[cfg(feature = "cluster")]
     /// will appear when query parts get distributed in a cluster. This is not
     /// an optimization rule, and it cannot be turned off.
     DistributeInCluster,
-    #[cfg(feature = "cluster")]
     /// will appear when scatter, gather, and remote nodes are inserted into a
     /// distributed query. This is not an optimization rule, and it cannot be
     /// turned off.
     ScatterInCluster,
-    #[cfg(feature = "cluster")]
     /// will appear when filters are moved up in a distributed execution plan.
     /// Filters are moved as far up in the plan as possible to make result sets
     /// as small as possible as early as possible.
     DistributeFilterCalcToCluster,
-    #[cfg(feature = "cluster")]
     /// will appear if sorts are moved up in a distributed query. Sorts are
     /// moved as far up in the plan as possible to make result sets as small as
     /// possible as early as possible.
     DistributeSortToCluster,
-    #[cfg(feature = "cluster")]
     /// will appear if a RemoteNode is followed by a ScatterNode, and the
     /// ScatterNode is only followed by calculations or the SingletonNode. In
     /// this case, there is no need to distribute the calculation, and it will
     /// be handled centrally.
     RemoveUnnecessaryRemoteScatter,
-    #[cfg(feature = "cluster")]
     /// will appear if a RemoveNode can be pushed into the same query part that
     /// enumerates over the documents of a collection. This saves inter-cluster
     /// round-trips between the EnumerateCollectionNode and the RemoveNode.
@@ -2923,17 +2899,11 @@ impl OptimizerRule {
             OPTIMIZER_RULE_GEO_INDEX_OPTIMIZER => GeoIndexOptimizer,
             OPTIMIZER_RULE_REMOVE_SORT_RAND => RemoveSortRand,
             OPTIMIZER_RULE_REDUCE_EXTRACTION_TO_PROJECTION => ReduceExtractionToProjection,
-            #[cfg(feature = "cluster")]
             OPTIMIZER_RULE_DISTRIBUTE_IN_CLUSTER => DistributeInCluster,
-            #[cfg(feature = "cluster")]
             OPTIMIZER_RULE_SCATTER_IN_CLUSTER => ScatterInCluster,
-            #[cfg(feature = "cluster")]
             OPTIMIZER_RULE_DISTRIBUTE_FILTERCALC_TO_CLUSTER => DistributeFilterCalcToCluster,
-            #[cfg(feature = "cluster")]
             OPTIMIZER_RULE_DISTRIBUTE_SORT_TO_CLUSTER => DistributeSortToCluster,
-            #[cfg(feature = "cluster")]
             OPTIMIZER_RULE_REMOVE_UNNECESSARY_REMOTE_SCATTER => RemoveUnnecessaryRemoteScatter,
-            #[cfg(feature = "cluster")]
             OPTIMIZER_RULE_UNDISTRIBUTE_REMOVE_AFTER_ENUM_COLL => UnDistributeRemoveAfterEnumColl,
             rule => Custom(rule.to_owned()),
         }
@@ -2968,19 +2938,694 @@ impl OptimizerRule {
             GeoIndexOptimizer => OPTIMIZER_RULE_GEO_INDEX_OPTIMIZER,
             RemoveSortRand => OPTIMIZER_RULE_REMOVE_SORT_RAND,
             ReduceExtractionToProjection => OPTIMIZER_RULE_REDUCE_EXTRACTION_TO_PROJECTION,
-            #[cfg(feature = "cluster")]
             DistributeInCluster => OPTIMIZER_RULE_DISTRIBUTE_IN_CLUSTER,
-            #[cfg(feature = "cluster")]
             ScatterInCluster => OPTIMIZER_RULE_SCATTER_IN_CLUSTER,
-            #[cfg(feature = "cluster")]
             DistributeFilterCalcToCluster => OPTIMIZER_RULE_DISTRIBUTE_FILTERCALC_TO_CLUSTER,
-            #[cfg(feature = "cluster")]
             DistributeSortToCluster => OPTIMIZER_RULE_DISTRIBUTE_SORT_TO_CLUSTER,
-            #[cfg(feature = "cluster")]
             RemoveUnnecessaryRemoteScatter => OPTIMIZER_RULE_REMOVE_UNNECESSARY_REMOTE_SCATTER,
-            #[cfg(feature = "cluster")]
             UnDistributeRemoveAfterEnumColl => OPTIMIZER_RULE_UNDISTRIBUTE_REMOVE_AFTER_ENUM_COLL,
             Custom(ref rule) => rule,
         }
     }
 }
+
+/// A user defined AQL function as registered in a database.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AqlFunction {
+    /// The fully qualified name of the function including its namespace.
+    name: String,
+
+    /// The source code of the function as string.
+    code: String,
+
+    /// Whether the function always produces the same result for the same
+    /// input values.
+    ///
+    /// Only returned by ArangoDB 3.4 and newer servers.
+    is_deterministic: Option<bool>,
+}
+
+impl AqlFunction {
+    /// Returns the fully qualified name of this function.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the namespace of this function.
+    ///
+    /// The namespace is the part of the fully qualified name before the last
+    /// `::` separator.
+    pub fn namespace(&self) -> &str {
+        self.name
+            .rfind("::")
+            .map_or("", |index| &self.name[..index])
+    }
+
+    /// Returns the source code of this function.
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// Returns whether this function is deterministic.
+    ///
+    /// Returns `None` if the server does not report this attribute.
+    pub fn is_deterministic(&self) -> Option<bool> {
+        self.is_deterministic
+    }
+}
+
+/// The definition of a user defined AQL function to be registered.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewAqlFunction {
+    /// The fully qualified name of the function including its namespace,
+    /// e.g. `myfunctions::temperature::celsius_to_fahrenheit`.
+    name: String,
+
+    /// The source code of the function as string.
+    code: String,
+
+    /// Whether the function always produces the same result for the same
+    /// input values.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    is_deterministic: Option<bool>,
+}
+
+impl NewAqlFunction {
+    /// Constructs a new `NewAqlFunction` with the given name and source code.
+    pub fn new<N, C>(name: N, code: C) -> Self
+    where
+        N: Into<String>,
+        C: Into<String>,
+    {
+        NewAqlFunction {
+            name: name.into(),
+            code: code.into(),
+            is_deterministic: None,
+        }
+    }
+
+    /// Returns the fully qualified name of the function.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the namespace of the function.
+    ///
+    /// The namespace is the part of the fully qualified name before the last
+    /// `::` separator.
+    pub fn namespace(&self) -> &str {
+        self.name
+            .rfind("::")
+            .map_or("", |index| &self.name[..index])
+    }
+
+    /// Returns the source code of the function.
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// Sets whether the function is deterministic.
+    pub fn set_deterministic<D>(&mut self, is_deterministic: D)
+    where
+        D: Into<Option<bool>>,
+    {
+        self.is_deterministic = is_deterministic.into();
+    }
+
+    /// Returns whether the function is deterministic.
+    pub fn is_deterministic(&self) -> Option<bool> {
+        self.is_deterministic
+    }
+}
+
+/// The result of registering a user defined AQL function.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AqlFunctionCreated {
+    /// The HTTP status code of the response.
+    code: u16,
+
+    /// Whether the function has been newly created or an existing function
+    /// with the same name has been replaced.
+    ///
+    /// Only returned by ArangoDB 3.4 and newer servers.
+    is_newly_created: Option<bool>,
+}
+
+impl AqlFunctionCreated {
+    /// Returns whether the function has been newly created.
+    ///
+    /// If `false` an existing function with the same name has been replaced.
+    pub fn is_newly_created(&self) -> bool {
+        self.is_newly_created.unwrap_or(self.code == 201)
+    }
+}
+
+/// The result of removing user defined AQL functions.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AqlFunctionsDeleted {
+    /// The number of functions that have been removed.
+    ///
+    /// Only returned by ArangoDB 3.4 and newer servers.
+    deleted_count: Option<u64>,
+}
+
+impl AqlFunctionsDeleted {
+    /// Returns the number of functions that have been removed.
+    ///
+    /// Returns `None` if the server does not report this attribute.
+    pub fn deleted_count(&self) -> Option<u64> {
+        self.deleted_count
+    }
+}
+
+/// A query that is currently running or has been tracked as slow query.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunningQuery {
+    /// The id of the query.
+    id: String,
+
+    /// The query string, possibly truncated to the configured maximum length.
+    query: String,
+
+    /// The bind parameters used by the query.
+    ///
+    /// Only returned if tracking of bind parameters is enabled.
+    #[serde(default)]
+    bind_vars: HashMap<String, JsonValue>,
+
+    /// The date and time when the query was started.
+    started: String,
+
+    /// The runtime of the query in seconds.
+    run_time: f64,
+
+    /// The current execution state of the query.
+    state: QueryState,
+}
+
+impl RunningQuery {
+    /// Returns the id of the query.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the query string.
+    ///
+    /// The query string may be truncated to the maximum length configured in
+    /// the query tracking properties.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Returns the bind parameters used by the query.
+    pub fn bind_vars(&self) -> &HashMap<String, JsonValue> {
+        &self.bind_vars
+    }
+
+    /// Returns the date and time when the query was started.
+    pub fn started(&self) -> &str {
+        &self.started
+    }
+
+    /// Returns the runtime of the query in seconds.
+    pub fn run_time(&self) -> f64 {
+        self.run_time
+    }
+
+    /// Returns the current execution state of the query.
+    pub fn state(&self) -> &QueryState {
+        &self.state
+    }
+}
+
+/// The execution state of a query.
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryState {
+    Initializing,
+    Parsing,
+    OptimizingAst,
+    LoadingCollections,
+    InstantiatingPlan,
+    OptimizingPlan,
+    Executing,
+    Finalizing,
+    Finished,
+    Killed,
+    Invalid,
+    Custom(String),
+}
+
+impl QueryState {
+    pub fn from_api_str(value: &str) -> Self {
+        use self::QueryState::*;
+        match value {
+            QUERY_STATE_INITIALIZING => Initializing,
+            QUERY_STATE_PARSING => Parsing,
+            QUERY_STATE_OPTIMIZING_AST => OptimizingAst,
+            QUERY_STATE_LOADING_COLLECTIONS => LoadingCollections,
+            QUERY_STATE_INSTANTIATING_PLAN => InstantiatingPlan,
+            QUERY_STATE_OPTIMIZING_PLAN => OptimizingPlan,
+            QUERY_STATE_EXECUTING => Executing,
+            QUERY_STATE_FINALIZING => Finalizing,
+            QUERY_STATE_FINISHED => Finished,
+            QUERY_STATE_KILLED => Killed,
+            QUERY_STATE_INVALID => Invalid,
+            _ => Custom(value.to_owned()),
+        }
+    }
+
+    pub fn as_api_str(&self) -> &str {
+        use self::QueryState::*;
+        match *self {
+            Initializing => QUERY_STATE_INITIALIZING,
+            Parsing => QUERY_STATE_PARSING,
+            OptimizingAst => QUERY_STATE_OPTIMIZING_AST,
+            LoadingCollections => QUERY_STATE_LOADING_COLLECTIONS,
+            InstantiatingPlan => QUERY_STATE_INSTANTIATING_PLAN,
+            OptimizingPlan => QUERY_STATE_OPTIMIZING_PLAN,
+            Executing => QUERY_STATE_EXECUTING,
+            Finalizing => QUERY_STATE_FINALIZING,
+            Finished => QUERY_STATE_FINISHED,
+            Killed => QUERY_STATE_KILLED,
+            Invalid => QUERY_STATE_INVALID,
+            Custom(ref state) => state,
+        }
+    }
+}
+
+impl Serialize for QueryState {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.as_api_str().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for QueryState {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        Ok(QueryState::from_api_str(&value))
+    }
+}
+
+/// The configuration of the AQL query tracking.
+#[allow(missing_copy_implementations)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryTrackingProperties {
+    /// Whether query tracking is enabled.
+    enabled: bool,
+
+    /// Whether slow queries are tracked.
+    track_slow_queries: bool,
+
+    /// Whether the bind parameters of queries are tracked.
+    track_bind_vars: bool,
+
+    /// The maximum number of slow queries to keep in the list of slow
+    /// queries.
+    max_slow_queries: u32,
+
+    /// The threshold in seconds for treating a query as slow.
+    slow_query_threshold: f64,
+
+    /// The maximum length of query strings to keep in the lists of queries.
+    max_query_string_length: u32,
+}
+
+impl QueryTrackingProperties {
+    /// Returns whether query tracking is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Returns whether slow queries are tracked.
+    pub fn is_track_slow_queries(&self) -> bool {
+        self.track_slow_queries
+    }
+
+    /// Returns whether the bind parameters of queries are tracked.
+    pub fn is_track_bind_vars(&self) -> bool {
+        self.track_bind_vars
+    }
+
+    /// Returns the maximum number of slow queries to keep in the list of
+    /// slow queries.
+    pub fn max_slow_queries(&self) -> u32 {
+        self.max_slow_queries
+    }
+
+    /// Returns the threshold in seconds for treating a query as slow.
+    pub fn slow_query_threshold(&self) -> f64 {
+        self.slow_query_threshold
+    }
+
+    /// Returns the maximum length of query strings to keep in the lists of
+    /// queries.
+    pub fn max_query_string_length(&self) -> u32 {
+        self.max_query_string_length
+    }
+}
+
+/// Changes to be applied to the configuration of the AQL query tracking.
+///
+/// Only the options that are set are changed.
+#[allow(missing_copy_implementations)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryTrackingPropertiesUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    enabled: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    track_slow_queries: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    track_bind_vars: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_slow_queries: Option<u32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    slow_query_threshold: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_query_string_length: Option<u32>,
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(new_without_default_derive))]
+impl QueryTrackingPropertiesUpdate {
+    /// Constructs a new instance of `QueryTrackingPropertiesUpdate` with no
+    /// options set.
+    pub fn new() -> Self {
+        QueryTrackingPropertiesUpdate {
+            enabled: None,
+            track_slow_queries: None,
+            track_bind_vars: None,
+            max_slow_queries: None,
+            slow_query_threshold: None,
+            max_query_string_length: None,
+        }
+    }
+
+    /// Sets whether query tracking is enabled.
+    pub fn set_enabled<E>(&mut self, enabled: E)
+    where
+        E: Into<Option<bool>>,
+    {
+        self.enabled = enabled.into();
+    }
+
+    /// Returns whether query tracking is enabled.
+    pub fn is_enabled(&self) -> Option<bool> {
+        self.enabled
+    }
+
+    /// Sets whether slow queries are tracked.
+    pub fn set_track_slow_queries<T>(&mut self, track_slow_queries: T)
+    where
+        T: Into<Option<bool>>,
+    {
+        self.track_slow_queries = track_slow_queries.into();
+    }
+
+    /// Returns whether slow queries are tracked.
+    pub fn is_track_slow_queries(&self) -> Option<bool> {
+        self.track_slow_queries
+    }
+
+    /// Sets whether the bind parameters of queries are tracked.
+    pub fn set_track_bind_vars<T>(&mut self, track_bind_vars: T)
+    where
+        T: Into<Option<bool>>,
+    {
+        self.track_bind_vars = track_bind_vars.into();
+    }
+
+    /// Returns whether the bind parameters of queries are tracked.
+    pub fn is_track_bind_vars(&self) -> Option<bool> {
+        self.track_bind_vars
+    }
+
+    /// Sets the maximum number of slow queries to keep in the list of slow
+    /// queries.
+    pub fn set_max_slow_queries<M>(&mut self, max_slow_queries: M)
+    where
+        M: Into<Option<u32>>,
+    {
+        self.max_slow_queries = max_slow_queries.into();
+    }
+
+    /// Returns the maximum number of slow queries to keep in the list of
+    /// slow queries.
+    pub fn max_slow_queries(&self) -> Option<u32> {
+        self.max_slow_queries
+    }
+
+    /// Sets the threshold in seconds for treating a query as slow.
+    pub fn set_slow_query_threshold<T>(&mut self, slow_query_threshold: T)
+    where
+        T: Into<Option<f64>>,
+    {
+        self.slow_query_threshold = slow_query_threshold.into();
+    }
+
+    /// Returns the threshold in seconds for treating a query as slow.
+    pub fn slow_query_threshold(&self) -> Option<f64> {
+        self.slow_query_threshold
+    }
+
+    /// Sets the maximum length of query strings to keep in the lists of
+    /// queries.
+    pub fn set_max_query_string_length<M>(&mut self, max_query_string_length: M)
+    where
+        M: Into<Option<u32>>,
+    {
+        self.max_query_string_length = max_query_string_length.into();
+    }
+
+    /// Returns the maximum length of query strings to keep in the lists of
+    /// queries.
+    pub fn max_query_string_length(&self) -> Option<u32> {
+        self.max_query_string_length
+    }
+}
+
+/// This enum defines the operating modes of the AQL query result cache.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QueryCacheMode {
+    /// The query cache is turned off
+    Off,
+    /// The results of all queries are cached unless the cache is explicitly
+    /// disabled for a query
+    On,
+    /// Only the results of queries that explicitly enable the cache are
+    /// cached
+    Demand,
+}
+
+impl Serialize for QueryCacheMode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use self::QueryCacheMode::*;
+        let mode_str = match *self {
+            Off => QUERY_CACHE_MODE_OFF,
+            On => QUERY_CACHE_MODE_ON,
+            Demand => QUERY_CACHE_MODE_DEMAND,
+        };
+        serializer.serialize_str(mode_str)
+    }
+}
+
+impl<'de> Deserialize<'de> for QueryCacheMode {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use self::QueryCacheMode::*;
+        use serde::de::Error;
+        let value = String::deserialize(deserializer)?;
+        match &value[..] {
+            QUERY_CACHE_MODE_OFF => Ok(Off),
+            QUERY_CACHE_MODE_ON => Ok(On),
+            QUERY_CACHE_MODE_DEMAND => Ok(Demand),
+            _ => Err(D::Error::custom(format!(
+                "Unknown query cache mode: {:?}",
+                value
+            ))),
+        }
+    }
+}
+
+/// The global configuration of the AQL query result cache.
+#[allow(missing_copy_implementations)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryCacheProperties {
+    /// The mode the query cache operates in.
+    mode: QueryCacheMode,
+
+    /// The maximum number of query results stored per database-specific
+    /// cache.
+    max_results: u64,
+}
+
+impl QueryCacheProperties {
+    /// Returns the mode the query cache operates in.
+    pub fn mode(&self) -> QueryCacheMode {
+        self.mode
+    }
+
+    /// Returns the maximum number of query results stored per
+    /// database-specific cache.
+    pub fn max_results(&self) -> u64 {
+        self.max_results
+    }
+}
+
+/// Changes to be applied to the global configuration of the AQL query result
+/// cache.
+///
+/// Only the options that are set are changed.
+#[allow(missing_copy_implementations)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryCachePropertiesUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mode: Option<QueryCacheMode>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_results: Option<u64>,
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(new_without_default_derive))]
+impl QueryCachePropertiesUpdate {
+    /// Constructs a new instance of `QueryCachePropertiesUpdate` with no
+    /// options set.
+    pub fn new() -> Self {
+        QueryCachePropertiesUpdate {
+            mode: None,
+            max_results: None,
+        }
+    }
+
+    /// Sets the mode the query cache shall operate in.
+    pub fn set_mode<M>(&mut self, mode: M)
+    where
+        M: Into<Option<QueryCacheMode>>,
+    {
+        self.mode = mode.into();
+    }
+
+    /// Returns the mode the query cache shall operate in.
+    pub fn mode(&self) -> Option<QueryCacheMode> {
+        self.mode
+    }
+
+    /// Sets the maximum number of query results stored per database-specific
+    /// cache.
+    pub fn set_max_results<M>(&mut self, max_results: M)
+    where
+        M: Into<Option<u64>>,
+    {
+        self.max_results = max_results.into();
+    }
+
+    /// Returns the maximum number of query results stored per
+    /// database-specific cache.
+    pub fn max_results(&self) -> Option<u64> {
+        self.max_results
+    }
+}
+
+/// An entry of the AQL query result cache.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedQuery {
+    /// The hash value calculated from the query string.
+    hash: String,
+
+    /// The query string.
+    query: String,
+
+    /// The bind parameters of the query.
+    #[serde(default)]
+    bind_vars: HashMap<String, JsonValue>,
+
+    /// The size of the cached result in bytes.
+    size: u64,
+
+    /// The number of documents in the cached result.
+    results: u64,
+
+    /// The date and time when the result has been added to the cache.
+    started: String,
+
+    /// The number of times the result has been served from the cache.
+    hits: u64,
+
+    /// The runtime of the query in seconds.
+    run_time: f64,
+
+    /// The names of the collections and views used by the query.
+    data_sources: Vec<String>,
+}
+
+impl CachedQuery {
+    /// Returns the hash value calculated from the query string.
+    pub fn hash(&self) -> &str {
+        &self.hash
+    }
+
+    /// Returns the query string.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Returns the bind parameters of the query.
+    pub fn bind_vars(&self) -> &HashMap<String, JsonValue> {
+        &self.bind_vars
+    }
+
+    /// Returns the size of the cached result in bytes.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns the number of documents in the cached result.
+    pub fn results(&self) -> u64 {
+        self.results
+    }
+
+    /// Returns the date and time when the result has been added to the cache.
+    pub fn started(&self) -> &str {
+        &self.started
+    }
+
+    /// Returns the number of times the result has been served from the
+    /// cache.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Returns the runtime of the query in seconds.
+    pub fn run_time(&self) -> f64 {
+        self.run_time
+    }
+
+    /// Returns the names of the collections and views used by the query.
+    pub fn data_sources(&self) -> &[String] {
+        &self.data_sources
+    }
+}
@@ -2,11 +2,11 @@
 
 use super::types::*;
 use rincon_core::api::method::{Method, Operation, Parameters, Prepare, RpcReturnType};
-#[cfg(feature = "cluster")]
-use rincon_core::arango::protocol::PARAM_WAIT_FOR_SYNC_REPLICATION;
 use rincon_core::arango::protocol::{
-    FIELD_CODE, FIELD_ID, FIELD_RESULT, PARAM_EXCLUDE_SYSTEM, PATH_API_COLLECTION, PATH_PROPERTIES,
-    PATH_RENAME, PATH_REVISION,
+    FIELD_CODE, FIELD_COUNT, FIELD_FIGURES, FIELD_ID, FIELD_RESULT, PARAM_EXCLUDE_SYSTEM,
+    PARAM_WAIT_FOR_SYNC_REPLICATION, PARAM_WITH_DATA, PARAM_WITH_REVISIONS, PATH_API_COLLECTION,
+    PATH_CHECKSUM, PATH_COUNT, PATH_FIGURES, PATH_LOAD, PATH_LOAD_INDEXES_INTO_MEMORY,
+    PATH_PROPERTIES, PATH_RENAME, PATH_REVISION, PATH_ROTATE, PATH_TRUNCATE, PATH_UNLOAD,
 };
 
 /// Retrieves a list of existing collections.
@@ -87,7 +87,6 @@ impl Prepare for ListCollections {
 #[derive(Debug, Clone, PartialEq)]
 pub struct CreateCollection {
     collection: NewCollection,
-    #[cfg(feature = "cluster")]
     wait_for_sync_replication: bool,
 }
 
@@ -97,7 +96,6 @@ impl CreateCollection {
     pub fn new(collection: NewCollection) -> Self {
         CreateCollection {
             collection,
-            #[cfg(feature = "cluster")]
             wait_for_sync_replication: true,
         }
     }
@@ -113,7 +111,6 @@ impl CreateCollection {
     {
         CreateCollection {
             collection: NewCollection::with_name(name),
-            #[cfg(feature = "cluster")]
             wait_for_sync_replication: true,
         }
     }
@@ -128,7 +125,6 @@ impl CreateCollection {
     {
         CreateCollection {
             collection: NewCollection::documents_with_name(name),
-            #[cfg(feature = "cluster")]
             wait_for_sync_replication: true,
         }
     }
@@ -143,12 +139,10 @@ impl CreateCollection {
     {
         CreateCollection {
             collection: NewCollection::edges_with_name(name),
-            #[cfg(feature = "cluster")]
             wait_for_sync_replication: true,
         }
     }
 
-    #[cfg(feature = "cluster")]
     /// Set whether the server shall wait until the new collection has been
     /// created at all replications before it returns the response.
     pub fn set_wait_for_sync_replication(&mut self, wait_for_sync_replication: bool) {
@@ -161,7 +155,6 @@ impl CreateCollection {
         &self.collection
     }
 
-    #[cfg(feature = "cluster")]
     /// Returns whether the request will wait until the new collection has
     /// been created at all replications.
     pub fn is_wait_for_sync_replication(&self) -> bool {
@@ -188,12 +181,6 @@ impl Prepare for CreateCollection {
         String::from(PATH_API_COLLECTION)
     }
 
-    #[cfg(not(feature = "cluster"))]
-    fn parameters(&self) -> Parameters {
-        Parameters::empty()
-    }
-
-    #[cfg(feature = "cluster")]
     fn parameters(&self) -> Parameters {
         let mut params = Parameters::with_capacity(1);
         if !self.wait_for_sync_replication {
@@ -642,3 +629,542 @@ impl RenameCollectionBuilder {
         }
     }
 }
+
+/// Fetch the number of documents in the collection identified by the given
+/// name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetCollectionCount {
+    name: String,
+}
+
+impl GetCollectionCount {
+    /// Constructs a new instance of the `GetCollectionCount` method.
+    pub fn new(name: String) -> Self {
+        GetCollectionCount { name }
+    }
+
+    /// Constructs a new instance of the `GetCollectionCount` method to count
+    /// the documents in the collection with the given name.
+    pub fn with_name<N>(name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        GetCollectionCount { name: name.into() }
+    }
+
+    /// Returns the name of the collection for which the documents shall be
+    /// counted.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Method for GetCollectionCount {
+    type Result = u64;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: Some(FIELD_COUNT),
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for GetCollectionCount {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Read
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_COLLECTION) + "/" + &self.name + PATH_COUNT
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Fetch statistical information about the collection identified by the
+/// given name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetCollectionFigures {
+    name: String,
+}
+
+impl GetCollectionFigures {
+    /// Constructs a new instance of the `GetCollectionFigures` method.
+    pub fn new(name: String) -> Self {
+        GetCollectionFigures { name }
+    }
+
+    /// Constructs a new instance of the `GetCollectionFigures` method to get
+    /// the figures of the collection with the given name.
+    pub fn with_name<N>(name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        GetCollectionFigures { name: name.into() }
+    }
+
+    /// Returns the name of the collection for which the figures shall be
+    /// fetched.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Method for GetCollectionFigures {
+    type Result = CollectionFigures;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: Some(FIELD_FIGURES),
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for GetCollectionFigures {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Read
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_COLLECTION) + "/" + &self.name + PATH_FIGURES
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Calculate a checksum for the collection identified by the given name.
+///
+/// By default the checksum is calculated over the keys of the documents only.
+/// The revision ids and the document data can be included in the calculation
+/// by setting the `with_revisions` and `with_data` options.
+///
+/// **Note:** this method is not available in a cluster.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetCollectionChecksum {
+    name: String,
+    with_revisions: bool,
+    with_data: bool,
+}
+
+impl GetCollectionChecksum {
+    /// Constructs a new instance of the `GetCollectionChecksum` method with all
+    /// parameters specified.
+    pub fn new(name: String, with_revisions: bool, with_data: bool) -> Self {
+        GetCollectionChecksum {
+            name,
+            with_revisions,
+            with_data,
+        }
+    }
+
+    /// Constructs a new instance of the `GetCollectionChecksum` method to
+    /// calculate the checksum over the document keys of the collection with the
+    /// given name.
+    pub fn with_name<N>(name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        GetCollectionChecksum {
+            name: name.into(),
+            with_revisions: false,
+            with_data: false,
+        }
+    }
+
+    /// Sets whether the revision ids of the documents shall be included in
+    /// the calculation of the checksum.
+    pub fn set_with_revisions(&mut self, with_revisions: bool) {
+        self.with_revisions = with_revisions;
+    }
+
+    /// Sets whether the data of the documents shall be included in the
+    /// calculation of the checksum.
+    pub fn set_with_data(&mut self, with_data: bool) {
+        self.with_data = with_data;
+    }
+
+    /// Returns the name of the collection for which the checksum shall be
+    /// calculated.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns whether the revision ids of the documents are included in the
+    /// calculation of the checksum.
+    pub fn is_with_revisions(&self) -> bool {
+        self.with_revisions
+    }
+
+    /// Returns whether the data of the documents is included in the
+    /// calculation of the checksum.
+    pub fn is_with_data(&self) -> bool {
+        self.with_data
+    }
+}
+
+impl Method for GetCollectionChecksum {
+    type Result = CollectionChecksum;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for GetCollectionChecksum {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Read
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_COLLECTION) + "/" + &self.name + PATH_CHECKSUM
+    }
+
+    fn parameters(&self) -> Parameters {
+        let mut params = Parameters::with_capacity(2);
+        if self.with_revisions {
+            params.insert(PARAM_WITH_REVISIONS, true);
+        }
+        if self.with_data {
+            params.insert(PARAM_WITH_DATA, true);
+        }
+        params
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Removes all documents from the collection identified by the given name,
+/// but leaves the indexes intact.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TruncateCollection {
+    name: String,
+}
+
+impl TruncateCollection {
+    /// Constructs a new instance of the `TruncateCollection` method.
+    pub fn new(name: String) -> Self {
+        TruncateCollection { name }
+    }
+
+    /// Constructs a new instance of the `TruncateCollection` method to truncate
+    /// the collection with the given name.
+    pub fn with_name<N>(name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        TruncateCollection { name: name.into() }
+    }
+
+    /// Returns the name of the collection to be truncated.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Method for TruncateCollection {
+    type Result = Collection;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for TruncateCollection {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Replace
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_COLLECTION) + "/" + &self.name + PATH_TRUNCATE
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Loads the collection identified by the given name into memory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadCollection {
+    name: String,
+}
+
+impl LoadCollection {
+    /// Constructs a new instance of the `LoadCollection` method.
+    pub fn new(name: String) -> Self {
+        LoadCollection { name }
+    }
+
+    /// Constructs a new instance of the `LoadCollection` method to load the
+    /// collection with the given name.
+    pub fn with_name<N>(name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        LoadCollection { name: name.into() }
+    }
+
+    /// Returns the name of the collection to be loaded.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Method for LoadCollection {
+    type Result = Collection;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for LoadCollection {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Replace
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_COLLECTION) + "/" + &self.name + PATH_LOAD
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Removes the collection identified by the given name from memory.
+///
+/// The collection is not deleted. It is loaded again on the next access.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnloadCollection {
+    name: String,
+}
+
+impl UnloadCollection {
+    /// Constructs a new instance of the `UnloadCollection` method.
+    pub fn new(name: String) -> Self {
+        UnloadCollection { name }
+    }
+
+    /// Constructs a new instance of the `UnloadCollection` method to unload the
+    /// collection with the given name.
+    pub fn with_name<N>(name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        UnloadCollection { name: name.into() }
+    }
+
+    /// Returns the name of the collection to be unloaded.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Method for UnloadCollection {
+    type Result = Collection;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for UnloadCollection {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Replace
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_COLLECTION) + "/" + &self.name + PATH_UNLOAD
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Loads the indexes of the collection identified by the given name into
+/// memory.
+///
+/// Returns `true` if the indexes have been loaded. Indexes that do not fit
+/// into memory are loaded partially only.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadIndexesIntoMemory {
+    name: String,
+}
+
+impl LoadIndexesIntoMemory {
+    /// Constructs a new instance of the `LoadIndexesIntoMemory` method.
+    pub fn new(name: String) -> Self {
+        LoadIndexesIntoMemory { name }
+    }
+
+    /// Constructs a new instance of the `LoadIndexesIntoMemory` method to load
+    /// the indexes of the collection with the given name.
+    pub fn with_name<N>(name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        LoadIndexesIntoMemory { name: name.into() }
+    }
+
+    /// Returns the name of the collection of which the indexes shall be loaded.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Method for LoadIndexesIntoMemory {
+    type Result = bool;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: Some(FIELD_RESULT),
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for LoadIndexesIntoMemory {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Replace
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_COLLECTION) + "/" + &self.name + PATH_LOAD_INDEXES_INTO_MEMORY
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Rotates the journal of the collection identified by the given name.
+///
+/// The current journal of the collection is closed and made a read-only
+/// datafile. Returns `true` if the journal has been rotated.
+///
+/// **Note:** this method is only supported by the MMFiles storage engine.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RotateCollectionJournal {
+    name: String,
+}
+
+impl RotateCollectionJournal {
+    /// Constructs a new instance of the `RotateCollectionJournal` method.
+    pub fn new(name: String) -> Self {
+        RotateCollectionJournal { name }
+    }
+
+    /// Constructs a new instance of the `RotateCollectionJournal` method to
+    /// rotate the journal of the collection with the given name.
+    pub fn with_name<N>(name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        RotateCollectionJournal { name: name.into() }
+    }
+
+    /// Returns the name of the collection of which the journal shall be
+    /// rotated.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Method for RotateCollectionJournal {
+    type Result = bool;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: Some(FIELD_RESULT),
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for RotateCollectionJournal {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Replace
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_COLLECTION) + "/" + &self.name + PATH_ROTATE
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
@@ -123,6 +123,11 @@ impl From<CollectionProperties> for Collection {
 /// The `name` attribute is mandatory. All other attributes are optional and
 /// if not specified are assigned to their default values as defined by the
 /// ArangoDB server.
+///
+/// Some attributes are only meaningful in a cluster or for a certain storage
+/// engine. Whether they are supported is validated by the server. The
+/// `ServerCapabilities` can be used to find out about the configuration of
+/// the server beforehand.
 #[derive(Debug, Clone, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NewCollection {
@@ -155,7 +160,6 @@ pub struct NewCollection {
     #[serde(skip_serializing_if = "Option::is_none")]
     wait_for_sync: Option<bool>,
 
-    #[cfg(feature = "cluster")]
     /// In a cluster, this value determines the number of shards to create for
     /// the collection.
     /// (The default is 1)
@@ -164,7 +168,6 @@ pub struct NewCollection {
     #[serde(skip_serializing_if = "Option::is_none")]
     number_of_shards: Option<u16>,
 
-    #[cfg(feature = "cluster")]
     /// (The default is [ "_key" ])
     ///
     /// In a cluster, this attribute determines which document attributes are
@@ -177,9 +180,8 @@ pub struct NewCollection {
     ///
     /// In a single server setup, this option is meaningless.
     #[serde(skip_serializing_if = "Option::is_none")]
-    shard_keys: Option<String>,
+    shard_keys: Option<Vec<String>>,
 
-    #[cfg(feature = "cluster")]
     /// The replication factor.
     /// (The default is 1)
     ///
@@ -197,7 +199,6 @@ pub struct NewCollection {
     #[serde(skip_serializing_if = "Option::is_none")]
     replication_factor: Option<u16>,
 
-    #[cfg(feature = "mmfiles")]
     /// If true then the collection data is kept in-memory only and not made
     /// persistent.
     /// (The default is false)
@@ -215,7 +216,6 @@ pub struct NewCollection {
     #[serde(skip_serializing_if = "Option::is_none")]
     is_volatile: Option<bool>,
 
-    #[cfg(feature = "mmfiles")]
     /// Whether or not the collection will be compacted.
     /// (default is true)
     ///
@@ -223,7 +223,6 @@ pub struct NewCollection {
     #[serde(skip_serializing_if = "Option::is_none")]
     do_compact: Option<bool>,
 
-    #[cfg(feature = "mmfiles")]
     /// The number of buckets into which indexes using a hash table are split.
     /// (The default is 16)
     ///
@@ -242,7 +241,6 @@ pub struct NewCollection {
     #[serde(skip_serializing_if = "Option::is_none")]
     index_buckets: Option<u16>,
 
-    #[cfg(feature = "mmfiles")]
     /// The maximal size of a journal or datafile in bytes.
     /// (The default is a configuration parameter)
     ///
@@ -266,19 +264,12 @@ impl NewCollection {
             is_system: is_system.into(),
             key_options: None,
             wait_for_sync: None,
-            #[cfg(feature = "cluster")]
             number_of_shards: None,
-            #[cfg(feature = "cluster")]
             shard_keys: None,
-            #[cfg(feature = "cluster")]
             replication_factor: None,
-            #[cfg(feature = "mmfiles")]
             is_volatile: None,
-            #[cfg(feature = "mmfiles")]
             do_compact: None,
-            #[cfg(feature = "mmfiles")]
             index_buckets: None,
-            #[cfg(feature = "mmfiles")]
             journal_size: None,
         }
     }
@@ -395,7 +386,6 @@ impl NewCollection {
         self.wait_for_sync
     }
 
-    #[cfg(feature = "cluster")]
     /// Sets the number of shards that shall be created for this collection.
     pub fn set_number_of_shards<S>(&mut self, number_of_shards: S)
     where
@@ -404,28 +394,24 @@ impl NewCollection {
         self.number_of_shards = number_of_shards.into();
     }
 
-    #[cfg(feature = "cluster")]
     /// Returns the number of shards that shall be created for this collection.
     pub fn number_of_shards(&self) -> Option<u16> {
         self.number_of_shards
     }
 
-    #[cfg(feature = "cluster")]
     /// Sets the keys to determine the shard for a collection.
     pub fn set_shard_keys<K>(&mut self, shard_keys: K)
     where
-        K: Into<Option<String>>,
+        K: Into<Option<Vec<String>>>,
     {
         self.shard_keys = shard_keys.into();
     }
 
-    #[cfg(feature = "cluster")]
     /// Returns the keys to determine the shard for a collection.
-    pub fn shard_keys(&self) -> Option<&String> {
-        self.shard_keys.as_ref()
+    pub fn shard_keys(&self) -> Option<&[String]> {
+        self.shard_keys.as_ref().map(Vec::as_slice)
     }
 
-    #[cfg(feature = "cluster")]
     /// Sets the number of copies that are kept of each shard.
     pub fn set_replication_factor<R>(&mut self, replication_factor: R)
     where
@@ -434,13 +420,11 @@ impl NewCollection {
         self.replication_factor = replication_factor.into();
     }
 
-    #[cfg(feature = "cluster")]
     /// Returns the number of copies that are kept of each shard.
     pub fn replication_factor(&self) -> Option<u16> {
         self.replication_factor
     }
 
-    #[cfg(feature = "mmfiles")]
     /// Sets whether this collection is going to be a volatile collection.
     pub fn set_volatile<V>(&mut self, volatile: V)
     where
@@ -449,13 +433,11 @@ impl NewCollection {
         self.is_volatile = volatile.into();
     }
 
-    #[cfg(feature = "mmfiles")]
     /// Returns whether this collection is going to be a volatile collection.
     pub fn is_volatile(&self) -> Option<bool> {
         self.is_volatile
     }
 
-    #[cfg(feature = "mmfiles")]
     /// Sets whether this collection is going to be compacted.
     pub fn set_do_compact<C>(&mut self, do_compact: C)
     where
@@ -464,13 +446,11 @@ impl NewCollection {
         self.do_compact = do_compact.into();
     }
 
-    #[cfg(feature = "mmfiles")]
     /// Returns whether this collection is going to be compacted.
     pub fn is_do_compact(&self) -> Option<bool> {
         self.do_compact
     }
 
-    #[cfg(feature = "mmfiles")]
     /// Sets the number of buckets into which indexes using a hash table
     /// are split.
     pub fn set_index_buckets<B>(&mut self, index_buckets: B)
@@ -480,14 +460,12 @@ impl NewCollection {
         self.index_buckets = index_buckets.into();
     }
 
-    #[cfg(feature = "mmfiles")]
     /// Returns the number of buckets into which indexes using a hash table
     /// are split.
     pub fn index_buckets(&self) -> Option<u16> {
         self.index_buckets
     }
 
-    #[cfg(feature = "mmfiles")]
     /// Sets the maximal size of a journal or datafile in bytes.
     pub fn set_journal_size<J>(&mut self, journal_size: J)
     where
@@ -496,7 +474,6 @@ impl NewCollection {
         self.journal_size = journal_size.into();
     }
 
-    #[cfg(feature = "mmfiles")]
     /// Returns the maximal size of a journal or datafile in bytes.
     pub fn journal_size(&self) -> Option<u64> {
         self.journal_size
@@ -699,9 +676,10 @@ pub struct BasicCollectionProperties {
     /// the file system before returning the response.
     wait_for_sync: bool,
 
-    #[cfg(feature = "mmfiles")]
     /// Whether this collection is volatile.
-    is_volatile: bool,
+    ///
+    /// Only returned by servers using the MMFiles storage engine.
+    is_volatile: Option<bool>,
 }
 
 impl BasicCollectionProperties {
@@ -737,9 +715,10 @@ impl BasicCollectionProperties {
         self.wait_for_sync
     }
 
-    #[cfg(feature = "mmfiles")]
     /// Returns whether this collection is a volatile collection.
-    pub fn is_volatile(&self) -> bool {
+    ///
+    /// Returns `None` if the server does not use the MMFiles storage engine.
+    pub fn is_volatile(&self) -> Option<bool> {
         self.is_volatile
     }
 }
@@ -753,7 +732,6 @@ impl From<CollectionProperties> for BasicCollectionProperties {
             status: properties.status,
             is_system: properties.is_system,
             wait_for_sync: properties.wait_for_sync,
-            #[cfg(feature = "mmfiles")]
             is_volatile: properties.is_volatile,
         }
     }
@@ -788,33 +766,40 @@ pub struct CollectionProperties {
     /// the file system before returning the response.
     wait_for_sync: bool,
 
-    #[cfg(feature = "cluster")]
     /// The number of shards of the collection.
-    number_of_shards: u16,
+    ///
+    /// Only returned by a cluster.
+    number_of_shards: Option<u16>,
 
-    #[cfg(feature = "cluster")]
     /// The keys used to identify the shards of a collection.
-    shard_keys: String,
+    ///
+    /// Only returned by a cluster.
+    shard_keys: Option<Vec<String>>,
 
-    #[cfg(feature = "cluster")]
     /// The number of copies that are kept of each shard.
-    replication_factor: u64,
+    ///
+    /// Only returned by a cluster.
+    replication_factor: Option<u64>,
 
-    #[cfg(feature = "mmfiles")]
     /// Whether this collection is volatile.
-    is_volatile: bool,
+    ///
+    /// Only returned by servers using the MMFiles storage engine.
+    is_volatile: Option<bool>,
 
-    #[cfg(feature = "mmfiles")]
     /// Whether this collection is compacted.
-    do_compact: bool,
+    ///
+    /// Only returned by servers using the MMFiles storage engine.
+    do_compact: Option<bool>,
 
-    #[cfg(feature = "mmfiles")]
     /// The number of buckets into which indexes using a hash table are split.
-    index_buckets: u16,
+    ///
+    /// Only returned by servers using the MMFiles storage engine.
+    index_buckets: Option<u16>,
 
-    #[cfg(feature = "mmfiles")]
     /// The maximal size of a journal or datafile in bytes.
-    journal_size: u64,
+    ///
+    /// Only returned by servers using the MMFiles storage engine.
+    journal_size: Option<u64>,
 }
 
 impl CollectionProperties {
@@ -855,46 +840,53 @@ impl CollectionProperties {
         self.wait_for_sync
     }
 
-    #[cfg(feature = "cluster")]
     /// Returns the number of shards for this collection.
-    pub fn number_of_shards(&self) -> u16 {
+    ///
+    /// Returns `None` if the server is not part of a cluster.
+    pub fn number_of_shards(&self) -> Option<u16> {
         self.number_of_shards
     }
 
-    #[cfg(feature = "cluster")]
     /// Returns the keys to determine the shard for a collection.
-    pub fn shard_keys(&self) -> &str {
-        &self.shard_keys
+    ///
+    /// Returns `None` if the server is not part of a cluster.
+    pub fn shard_keys(&self) -> Option<&[String]> {
+        self.shard_keys.as_ref().map(Vec::as_slice)
     }
 
-    #[cfg(feature = "cluster")]
     /// Returns the number of copies that are kept of each shard.
-    pub fn replication_factor(&self) -> u64 {
+    ///
+    /// Returns `None` if the server is not part of a cluster.
+    pub fn replication_factor(&self) -> Option<u64> {
         self.replication_factor
     }
 
-    #[cfg(feature = "mmfiles")]
     /// Returns whether this collection is a volatile collection.
-    pub fn is_volatile(&self) -> bool {
+    ///
+    /// Returns `None` if the server does not use the MMFiles storage engine.
+    pub fn is_volatile(&self) -> Option<bool> {
         self.is_volatile
     }
 
-    #[cfg(feature = "mmfiles")]
     /// Returns whether this collection is compacted.
-    pub fn is_do_compact(&self) -> bool {
+    ///
+    /// Returns `None` if the server does not use the MMFiles storage engine.
+    pub fn is_do_compact(&self) -> Option<bool> {
         self.do_compact
     }
 
-    #[cfg(feature = "mmfiles")]
     /// Returns the number of buckets into which indexes using a hash table
     /// are split.
-    pub fn index_buckets(&self) -> u16 {
+    ///
+    /// Returns `None` if the server does not use the MMFiles storage engine.
+    pub fn index_buckets(&self) -> Option<u16> {
         self.index_buckets
     }
 
-    #[cfg(feature = "mmfiles")]
     /// Returns the maximal size of a journal or datafile in bytes.
-    pub fn journal_size(&self) -> u64 {
+    ///
+    /// Returns `None` if the server does not use the MMFiles storage engine.
+    pub fn journal_size(&self) -> Option<u64> {
         self.journal_size
     }
 }
@@ -909,8 +901,10 @@ pub struct CollectionPropertiesUpdate {
     /// the file system before returning the response.
     wait_for_sync: Option<bool>,
 
-    #[cfg(feature = "mmfiles")]
     /// The maximal size of a journal or datafile in bytes.
+    ///
+    /// This option is meaningful for the MMFiles storage engine only.
+    #[serde(skip_serializing_if = "Option::is_none")]
     journal_size: Option<u64>,
 }
 
@@ -921,7 +915,6 @@ impl CollectionPropertiesUpdate {
     pub fn new() -> Self {
         CollectionPropertiesUpdate {
             wait_for_sync: None,
-            #[cfg(feature = "mmfiles")]
             journal_size: None,
         }
     }
@@ -941,7 +934,6 @@ impl CollectionPropertiesUpdate {
         self.wait_for_sync
     }
 
-    #[cfg(feature = "mmfiles")]
     /// Sets the maximal size of a journal or datafile in bytes.
     pub fn set_journal_size<J>(&mut self, journal_size: J)
     where
@@ -950,7 +942,6 @@ impl CollectionPropertiesUpdate {
         self.journal_size = journal_size.into();
     }
 
-    #[cfg(feature = "mmfiles")]
     /// Returns the maximal size of a journal or datafile in bytes.
     pub fn journal_size(&self) -> Option<u64> {
         self.journal_size
@@ -1021,6 +1012,328 @@ impl KeyOptions {
     }
 }
 
+/// This struct holds the checksum of a collection together with some basic
+/// attributes.
+///
+/// It is returned by the `GetCollectionChecksum` method.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionChecksum {
+    /// The id of the collection.
+    id: String,
+
+    /// The name of the collection.
+    name: String,
+
+    /// The type of the collection.
+    #[serde(rename = "type")]
+    kind: CollectionType,
+
+    /// The status of the collection.
+    status: CollectionStatus,
+
+    /// Whether the collection is system collection or regular collection.
+    is_system: bool,
+
+    /// The calculated checksum of the collection.
+    checksum: String,
+
+    /// The revision of the collection.
+    revision: String,
+}
+
+impl CollectionChecksum {
+    /// Returns the id of the collection.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the name of the collection.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the type of the collection.
+    pub fn kind(&self) -> CollectionType {
+        self.kind
+    }
+
+    /// Returns the status of the collection.
+    pub fn status(&self) -> CollectionStatus {
+        self.status
+    }
+
+    /// Returns whether the collection is a system or regular
+    /// collection.
+    pub fn is_system(&self) -> bool {
+        self.is_system
+    }
+
+    /// Returns the calculated checksum of the collection.
+    pub fn checksum(&self) -> &str {
+        &self.checksum
+    }
+
+    /// Returns the revision of the collection.
+    pub fn revision(&self) -> &str {
+        &self.revision
+    }
+}
+
+/// This struct holds statistical information about a collection.
+///
+/// It is returned by the `GetCollectionFigures` method.
+///
+/// Most of the figures are specific to the storage engine used by the server.
+/// Figures not provided by the storage engine are `None`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionFigures {
+    /// The number and total memory usage of the indexes of the collection.
+    indexes: SizeFigures,
+
+    /// The number and total size of all living documents.
+    ///
+    /// Only returned by servers using the MMFiles storage engine.
+    alive: Option<SizeFigures>,
+
+    /// The number and total size of dead documents and deletion markers.
+    ///
+    /// Only returned by servers using the MMFiles storage engine.
+    dead: Option<DeadFigures>,
+
+    /// The number and total file size of the datafiles.
+    ///
+    /// Only returned by servers using the MMFiles storage engine.
+    datafiles: Option<FileFigures>,
+
+    /// The number and total file size of the journal files.
+    ///
+    /// Only returned by servers using the MMFiles storage engine.
+    journals: Option<FileFigures>,
+
+    /// The number and total file size of the compactor files.
+    ///
+    /// Only returned by servers using the MMFiles storage engine.
+    compactors: Option<FileFigures>,
+
+    /// The number and total size of the document revisions held in memory.
+    ///
+    /// Only returned by servers using the MMFiles storage engine.
+    revisions: Option<SizeFigures>,
+
+    /// The number of markers in the write-ahead log that have not been
+    /// transferred to the datafiles yet.
+    ///
+    /// Only returned by servers using the MMFiles storage engine.
+    uncollected_logfile_entries: Option<u64>,
+
+    /// The number of references to documents in datafiles that are held by
+    /// JavaScript code.
+    ///
+    /// Only returned by servers using the MMFiles storage engine.
+    document_references: Option<u64>,
+
+    /// The reason why the datafiles can not be compacted at the moment.
+    ///
+    /// Only returned by servers using the MMFiles storage engine.
+    waiting_for: Option<String>,
+
+    /// The total size of the documents in bytes.
+    ///
+    /// Only returned by servers using the RocksDB storage engine.
+    documents_size: Option<u64>,
+
+    /// Whether the document cache is in use for the collection.
+    ///
+    /// Only returned by servers using the RocksDB storage engine.
+    cache_in_use: Option<bool>,
+
+    /// The size of the document cache in bytes.
+    ///
+    /// Only returned by servers using the RocksDB storage engine.
+    cache_size: Option<u64>,
+}
+
+impl CollectionFigures {
+    /// Returns the number and total memory usage of the indexes.
+    pub fn indexes(&self) -> &SizeFigures {
+        &self.indexes
+    }
+
+    /// Returns the number and total size of all living documents.
+    ///
+    /// Returns `None` if the server does not use the MMFiles storage engine.
+    pub fn alive(&self) -> Option<&SizeFigures> {
+        self.alive.as_ref()
+    }
+
+    /// Returns the number and total size of dead documents and deletion
+    /// markers.
+    ///
+    /// Returns `None` if the server does not use the MMFiles storage engine.
+    pub fn dead(&self) -> Option<&DeadFigures> {
+        self.dead.as_ref()
+    }
+
+    /// Returns the number and total file size of the datafiles.
+    ///
+    /// Returns `None` if the server does not use the MMFiles storage engine.
+    pub fn datafiles(&self) -> Option<&FileFigures> {
+        self.datafiles.as_ref()
+    }
+
+    /// Returns the number and total file size of the journal files.
+    ///
+    /// Returns `None` if the server does not use the MMFiles storage engine.
+    pub fn journals(&self) -> Option<&FileFigures> {
+        self.journals.as_ref()
+    }
+
+    /// Returns the number and total file size of the compactor files.
+    ///
+    /// Returns `None` if the server does not use the MMFiles storage engine.
+    pub fn compactors(&self) -> Option<&FileFigures> {
+        self.compactors.as_ref()
+    }
+
+    /// Returns the number and total size of the document revisions held in
+    /// memory.
+    ///
+    /// Returns `None` if the server does not use the MMFiles storage engine.
+    pub fn revisions(&self) -> Option<&SizeFigures> {
+        self.revisions.as_ref()
+    }
+
+    /// Returns the number of markers in the write-ahead log that have not
+    /// been transferred to the datafiles yet.
+    ///
+    /// Returns `None` if the server does not use the MMFiles storage engine.
+    pub fn uncollected_logfile_entries(&self) -> Option<u64> {
+        self.uncollected_logfile_entries
+    }
+
+    /// Returns the number of references to documents in datafiles that are
+    /// held by JavaScript code.
+    ///
+    /// Returns `None` if the server does not use the MMFiles storage engine.
+    pub fn document_references(&self) -> Option<u64> {
+        self.document_references
+    }
+
+    /// Returns the reason why the datafiles can not be compacted at the
+    /// moment.
+    ///
+    /// Returns `None` if the server does not use the MMFiles storage engine.
+    pub fn waiting_for(&self) -> Option<&str> {
+        self.waiting_for.as_ref().map(String::as_str)
+    }
+
+    /// Returns the total size of the documents in bytes.
+    ///
+    /// Returns `None` if the server does not use the RocksDB storage engine.
+    pub fn documents_size(&self) -> Option<u64> {
+        self.documents_size
+    }
+
+    /// Returns whether the document cache is in use for the collection.
+    ///
+    /// Returns `None` if the server does not use the RocksDB storage engine.
+    pub fn is_cache_in_use(&self) -> Option<bool> {
+        self.cache_in_use
+    }
+
+    /// Returns the size of the document cache in bytes.
+    ///
+    /// Returns `None` if the server does not use the RocksDB storage engine.
+    pub fn cache_size(&self) -> Option<u64> {
+        self.cache_size
+    }
+}
+
+/// This struct holds the number and total size of some items of a
+/// collection.
+#[allow(missing_copy_implementations)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SizeFigures {
+    /// The number of items.
+    count: u64,
+
+    /// The total size of the items in bytes.
+    size: u64,
+}
+
+impl SizeFigures {
+    /// Returns the number of items.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the total size of the items in bytes.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+/// This struct holds the number and total size of dead documents of a
+/// collection.
+#[allow(missing_copy_implementations)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeadFigures {
+    /// The number of dead documents.
+    count: u64,
+
+    /// The total size of the dead documents in bytes.
+    size: u64,
+
+    /// The total number of deletion markers.
+    deletion: u64,
+}
+
+impl DeadFigures {
+    /// Returns the number of dead documents.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the total size of the dead documents in bytes.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns the total number of deletion markers.
+    pub fn deletion(&self) -> u64 {
+        self.deletion
+    }
+}
+
+/// This struct holds the number and total file size of some kind of files of
+/// a collection.
+#[allow(missing_copy_implementations)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileFigures {
+    /// The number of files.
+    count: u64,
+
+    /// The total size of the files in bytes.
+    file_size: u64,
+}
+
+impl FileFigures {
+    /// Returns the number of files.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the total size of the files in bytes.
+    pub fn file_size(&self) -> u64 {
+        self.file_size
+    }
+}
+
 /// This enum defines the different types of collections.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum CollectionType {
@@ -7,6 +7,7 @@ use std::collections::HashMap;
 use std::mem;
 
 use aql::types::Optimizer;
+use rincon_core::api::metrics::MetricsSink;
 use rincon_core::api::query::Query;
 use rincon_core::api::types::{JsonValue, Value};
 
@@ -111,6 +112,19 @@ impl<T> Cursor<T> {
         }
     }
 
+    /// Records whether the result was served from the query cache and the
+    /// statistics of this cursor to the given metrics sink.
+    ///
+    /// The value `cursor.cached` is recorded as `1` for results served from
+    /// the query cache and as `0` otherwise. The statistics are only recorded
+    /// if they are available, see `CursorStatistics::record_to()`.
+    pub fn record_to(&self, sink: &MetricsSink) {
+        sink.record_value("cursor.cached", if self.cached { 1. } else { 0. });
+        if let Some(stats) = self.stats() {
+            stats.record_to(sink);
+        }
+    }
+
     /// Unwraps this cursor into a tuple of id, count and result.
     pub fn unwrap(self) -> (Option<String>, Option<u64>, Vec<T>) {
         (self.id, self.count, self.result)
@@ -208,6 +222,25 @@ impl CursorStatistics {
     pub fn writes_ignored(&self) -> u64 {
         self.writes_ignored
     }
+
+    /// Records the values of this `CursorStatistics` to the given metrics
+    /// sink.
+    ///
+    /// Each value is recorded with a name prefixed by `cursor.`, e.g.
+    /// `cursor.execution_time`. The full count is only recorded if it is
+    /// available.
+    pub fn record_to(&self, sink: &MetricsSink) {
+        sink.record_value("cursor.execution_time", self.execution_time);
+        sink.record_value("cursor.filtered", self.filtered as f64);
+        if let Some(full_count) = self.full_count {
+            sink.record_value("cursor.full_count", full_count as f64);
+        }
+        sink.record_value("cursor.http_requests", self.http_requests as f64);
+        sink.record_value("cursor.scanned_full", self.scanned_full as f64);
+        sink.record_value("cursor.scanned_index", self.scanned_index as f64);
+        sink.record_value("cursor.writes_executed", self.writes_executed as f64);
+        sink.record_value("cursor.writes_ignored", self.writes_ignored as f64);
+    }
 }
 
 /// This struct defines the parameters of a cursor for an AQL query that is
@@ -453,7 +486,6 @@ pub struct CursorOptions {
     #[serde(skip_serializing_if = "Option::is_none")]
     optimizer: Option<Optimizer>,
 
-    #[cfg(feature = "rocksdb")]
     /// Maximum number of operations after which an intermediate commit is
     /// performed automatically.
     ///
@@ -461,7 +493,6 @@ pub struct CursorOptions {
     #[serde(skip_serializing_if = "Option::is_none")]
     intermediate_commit_count: Option<u32>,
 
-    #[cfg(feature = "rocksdb")]
     /// Maximum total size of operations after which an intermediate commit is
     /// performed automatically.
     ///
@@ -469,14 +500,12 @@ pub struct CursorOptions {
     #[serde(skip_serializing_if = "Option::is_none")]
     intermediate_commit_size: Option<u32>,
 
-    #[cfg(feature = "rocksdb")]
     /// Transaction size limit in bytes.
     ///
     /// Honored by the RocksDB storage engine only.
     #[serde(skip_serializing_if = "Option::is_none")]
     max_transaction_size: Option<u32>,
 
-    #[cfg(feature = "enterprise")]
     /// This enterprise parameter allows to configure how long a DBServer will
     /// have time to bring the satellite collections involved in the query into
     /// sync. The default value is 60.0 (seconds). When the max time has been
@@ -606,7 +635,6 @@ impl CursorOptions {
         self.optimizer.as_ref()
     }
 
-    #[cfg(feature = "rocksdb")]
     /// Sets the maximum number of operations after which an intermediate
     /// commit is performed automatically.
     ///
@@ -618,7 +646,6 @@ impl CursorOptions {
         self.intermediate_commit_count = intermediate_commit_count.into();
     }
 
-    #[cfg(feature = "rocksdb")]
     /// Returns the maximum number of operations after which an intermediate
     /// commit is performed automatically.
     ///
@@ -627,7 +654,6 @@ impl CursorOptions {
         self.intermediate_commit_count
     }
 
-    #[cfg(feature = "rocksdb")]
     /// Sets the maximum total size of operations after which an intermediate
     /// commit is performed automatically.
     ///
@@ -639,7 +665,6 @@ impl CursorOptions {
         self.intermediate_commit_size = intermediate_commit_size.into();
     }
 
-    #[cfg(feature = "rocksdb")]
     /// Returns the maximum total size of operations after which an intermediate
     /// commit is performed automatically.
     ///
@@ -648,7 +673,6 @@ impl CursorOptions {
         self.intermediate_commit_size
     }
 
-    #[cfg(feature = "rocksdb")]
     /// Sets the transaction size limit in bytes.
     ///
     /// Honored by the RocksDB storage engine only.
@@ -659,7 +683,6 @@ impl CursorOptions {
         self.max_transaction_size = max_transaction_size.into();
     }
 
-    #[cfg(feature = "rocksdb")]
     /// Returns the transaction size limit in bytes.
     ///
     /// Honored by the RocksDB storage engine only.
@@ -667,7 +690,6 @@ impl CursorOptions {
         self.max_transaction_size
     }
 
-    #[cfg(feature = "enterprise")]
     /// Sets the enterprise parameter that configures how long a DBServer will
     /// have time to bring the satellite collections involved in the query into
     /// sync.
@@ -681,7 +703,6 @@ impl CursorOptions {
         self.satellite_sync_wait = satellite_sync_wait.into();
     }
 
-    #[cfg(feature = "enterprise")]
     /// Returns the enterprise parameter that configures how long a DBServer
     /// will have time to bring the satellite collections involved in the query
     /// into sync.
@@ -699,13 +720,9 @@ impl Default for CursorOptions {
             full_count: None,
             max_plans: None,
             optimizer: None,
-            #[cfg(feature = "rocksdb")]
             intermediate_commit_count: None,
-            #[cfg(feature = "rocksdb")]
             intermediate_commit_size: None,
-            #[cfg(feature = "rocksdb")]
             max_transaction_size: None,
-            #[cfg(feature = "enterprise")]
             satellite_sync_wait: None,
         }
     }
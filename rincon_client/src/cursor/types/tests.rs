@@ -1,3 +1,5 @@
+use std::cell::RefCell;
+
 use serde_json;
 
 use super::*;
@@ -64,14 +66,8 @@ fn set_cursor_options_on_newly_initialized_new_cursor() {
         cursor_options.set_max_warning_count(None);
         cursor_options.set_max_plans(5);
 
-        #[cfg(feature = "rocksdb")]
-        {
-            cursor_options.set_intermediate_commit_count(1);
-        }
-        #[cfg(feature = "enterprise")]
-        {
-            cursor_options.set_satellite_sync_wait(false);
-        }
+        cursor_options.set_intermediate_commit_count(1);
+        cursor_options.set_satellite_sync_wait(false);
     }
     let new_cursor = new_cursor;
     assert!(new_cursor.options().is_some());
@@ -83,16 +79,10 @@ fn set_cursor_options_on_newly_initialized_new_cursor() {
     assert_eq!(Some(false), cursor_options.is_full_count());
     assert_eq!(Some(5), cursor_options.max_plans());
 
-    #[cfg(feature = "rocksdb")]
-    {
-        assert_eq!(Some(1), cursor_options.intermediate_commit_count());
-        assert_eq!(None, cursor_options.intermediate_commit_size());
-        assert_eq!(None, cursor_options.max_transaction_size());
-    }
-    #[cfg(feature = "enterprise")]
-    {
-        assert_eq!(Some(false), cursor_options.satellite_sync_wait());
-    }
+    assert_eq!(Some(1), cursor_options.intermediate_commit_count());
+    assert_eq!(None, cursor_options.intermediate_commit_size());
+    assert_eq!(None, cursor_options.max_transaction_size());
+    assert_eq!(Some(false), cursor_options.satellite_sync_wait());
 }
 
 #[test]
@@ -132,35 +122,14 @@ fn serialize_new_cursor_with_cursor_options_set() {
         cursor_options.set_max_warning_count(None);
         cursor_options.set_max_plans(5);
 
-        #[cfg(feature = "rocksdb")]
-        {
-            cursor_options.set_intermediate_commit_count(1);
-        }
-        #[cfg(feature = "enterprise")]
-        {
-            cursor_options.set_satellite_sync_wait(false);
-        }
+        cursor_options.set_intermediate_commit_count(1);
+        cursor_options.set_satellite_sync_wait(false);
     }
     let new_cursor = new_cursor;
 
     let json_cursor = serde_json::to_string(&new_cursor).unwrap();
 
-    #[cfg(all(not(feature = "rocksdb"), not(feature = "enterprise")))]
-    {
-        assert_eq!(r#"{"query":"FOR u IN users FILTER u.name = @name RETURN u.name","bindVars":{"name":"simone"},"options":{"failOnWarning":true,"fullCount":false,"maxPlans":5}}"#, &json_cursor);
-    }
-    #[cfg(all(feature = "rocksdb", not(feature = "enterprise")))]
-    {
-        assert_eq!(r#"{"query":"FOR u IN users FILTER u.name = @name RETURN u.name","bindVars":{"name":"simone"},"options":{"failOnWarning":true,"fullCount":false,"maxPlans":5,"intermediateCommitCount":1}}"#, &json_cursor);
-    }
-    #[cfg(all(not(feature = "rocksdb"), feature = "enterprise"))]
-    {
-        assert_eq!(r#"{"query":"FOR u IN users FILTER u.name = @name RETURN u.name","bindVars":{"name":"simone"},"options":{"failOnWarning":true,"fullCount":false,"maxPlans":5,"satelliteSyncWait":false}}"#, &json_cursor);
-    }
-    #[cfg(all(feature = "rocksdb", feature = "enterprise"))]
-    {
-        assert_eq!(r#"{"query":"FOR u IN users FILTER u.name = @name RETURN u.name","bindVars":{"name":"simone"},"options":{"failOnWarning":true,"fullCount":false,"maxPlans":5,"intermediateCommitCount":1,"satelliteSyncWait":false}}"#, &json_cursor);
-    }
+    assert_eq!(r#"{"query":"FOR u IN users FILTER u.name = @name RETURN u.name","bindVars":{"name":"simone"},"options":{"failOnWarning":true,"fullCount":false,"maxPlans":5,"intermediateCommitCount":1,"satelliteSyncWait":false}}"#, &json_cursor);
 }
 
 #[test]
@@ -191,3 +160,96 @@ fn serialize_new_cursor_with_optimizer_rules_set() {
     assert!(json_cursor.contains("+use-index-for-sort"));
     assert!(json_cursor.contains("+inline-subqueries"));
 }
+
+#[derive(Debug, Default)]
+struct RecordingSink {
+    values: RefCell<Vec<(String, f64)>>,
+}
+
+impl MetricsSink for RecordingSink {
+    fn record_value(&self, name: &str, value: f64) {
+        self.values.borrow_mut().push((name.to_owned(), value));
+    }
+}
+
+#[test]
+fn record_cursor_statistics_to_metrics_sink() {
+    let json = r#"{
+        "executionTime": 0.25,
+        "filtered": 3,
+        "httpRequests": 0,
+        "scannedFull": 10,
+        "scannedIndex": 0,
+        "writesExecuted": 0,
+        "writesIgnored": 0
+    }"#;
+    let statistics: CursorStatistics = serde_json::from_str(json).unwrap();
+    let sink = RecordingSink::default();
+
+    statistics.record_to(&sink);
+
+    assert_eq!(
+        vec![
+            ("cursor.execution_time".to_owned(), 0.25),
+            ("cursor.filtered".to_owned(), 3.),
+            ("cursor.http_requests".to_owned(), 0.),
+            ("cursor.scanned_full".to_owned(), 10.),
+            ("cursor.scanned_index".to_owned(), 0.),
+            ("cursor.writes_executed".to_owned(), 0.),
+            ("cursor.writes_ignored".to_owned(), 0.),
+        ],
+        *sink.values.borrow()
+    );
+}
+
+#[test]
+fn record_cursor_served_from_query_cache_to_metrics_sink() {
+    let json = r#"{
+        "result": ["Jane", "John"],
+        "hasMore": false,
+        "cached": true,
+        "error": false,
+        "code": 201
+    }"#;
+    let cursor: Cursor<String> = serde_json::from_str(json).unwrap();
+    let sink = RecordingSink::default();
+
+    cursor.record_to(&sink);
+
+    assert_eq!(
+        vec![("cursor.cached".to_owned(), 1.)],
+        *sink.values.borrow()
+    );
+}
+
+#[test]
+fn record_cursor_not_served_from_query_cache_to_metrics_sink() {
+    let json = r#"{
+        "result": ["Jane", "John"],
+        "hasMore": false,
+        "cached": false,
+        "extra": {
+            "stats": {
+                "executionTime": 0.5,
+                "filtered": 0,
+                "httpRequests": 0,
+                "scannedFull": 2,
+                "scannedIndex": 0,
+                "writesExecuted": 0,
+                "writesIgnored": 0
+            },
+            "warnings": []
+        },
+        "error": false,
+        "code": 201
+    }"#;
+    let cursor: Cursor<String> = serde_json::from_str(json).unwrap();
+    let sink = RecordingSink::default();
+
+    cursor.record_to(&sink);
+
+    let values = sink.values.borrow();
+    assert_eq!(("cursor.cached".to_owned(), 0.), values[0]);
+    assert_eq!(("cursor.execution_time".to_owned(), 0.5), values[1]);
+    assert_eq!(8, values.len());
+}
@@ -10,9 +10,10 @@ use serde::ser::Serialize;
 use super::types::*;
 use rincon_core::api::method::{Method, Operation, Parameters, Prepare, ResultList, RpcReturnType};
 use rincon_core::arango::protocol::{
-    FIELD_CODE, HEADER_IF_MATCH, HEADER_IF_NON_MATCH, PARAM_IGNORE_REVISIONS, PARAM_KEEP_NULL,
-    PARAM_MERGE_OBJECTS, PARAM_ONLY_GET, PARAM_RETURN_NEW, PARAM_RETURN_OLD, PARAM_WAIT_FOR_SYNC,
-    PATH_API_DOCUMENT,
+    FIELD_CODE, HEADER_IF_MATCH, HEADER_IF_NON_MATCH, PARAM_COLLECTION, PARAM_COMPLETE,
+    PARAM_DETAILS, PARAM_FROM_PREFIX, PARAM_IGNORE_REVISIONS, PARAM_KEEP_NULL, PARAM_MERGE_OBJECTS,
+    PARAM_ONLY_GET, PARAM_ON_DUPLICATE, PARAM_OVERWRITE, PARAM_RETURN_NEW, PARAM_RETURN_OLD,
+    PARAM_TO_PREFIX, PARAM_TYPE, PARAM_WAIT_FOR_SYNC, PATH_API_DOCUMENT, PATH_API_IMPORT,
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -1542,3 +1543,159 @@ impl<T> Prepare for DeleteDocumentsReturnOld<T> {
         Some(&self.selectors)
     }
 }
+
+/// Imports documents into a collection.
+///
+/// Importing documents is much faster than inserting them with the
+/// `InsertDocuments` method, especially for large amounts of documents like
+/// when doing an initial load of a collection. In contrast to inserting
+/// documents the import does not return the headers of the created
+/// documents but only the number of imported documents.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportDocuments<T> {
+    collection_name: String,
+    import_type: ImportType,
+    content: ImportContent<T>,
+    options: ImportOptions,
+}
+
+impl<T> ImportDocuments<T> {
+    /// Creates a new `ImportDocuments` method that imports the given
+    /// documents using the `ImportType::Documents` format.
+    pub fn new<N, Docs>(collection_name: N, documents: Docs) -> Self
+    where
+        N: Into<String>,
+        Docs: IntoIterator<Item = T>,
+    {
+        ImportDocuments {
+            collection_name: collection_name.into(),
+            import_type: ImportType::Documents,
+            content: ImportContent::new(None, Vec::from_iter(documents.into_iter())),
+            options: Default::default(),
+        }
+    }
+
+    /// Creates a new `ImportDocuments` method that imports documents given
+    /// as arrays of values.
+    ///
+    /// The header row contains the attribute names. The values of each
+    /// document must be in the same order as the attribute names in the
+    /// header row. Imports with a header row are always sent one array per
+    /// line regardless of the import type.
+    pub fn with_header_row<N, H, Header, Rows>(
+        collection_name: N,
+        header: Header,
+        rows: Rows,
+    ) -> Self
+    where
+        N: Into<String>,
+        H: Into<String>,
+        Header: IntoIterator<Item = H>,
+        Rows: IntoIterator<Item = T>,
+    {
+        ImportDocuments {
+            collection_name: collection_name.into(),
+            import_type: ImportType::Documents,
+            content: ImportContent::new(
+                Some(header.into_iter().map(Into::into).collect()),
+                Vec::from_iter(rows.into_iter()),
+            ),
+            options: Default::default(),
+        }
+    }
+
+    pub fn with_import_type(mut self, import_type: ImportType) -> Self {
+        self.import_type = import_type;
+        self
+    }
+
+    pub fn with_options(mut self, options: ImportOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn collection_name(&self) -> &str {
+        &self.collection_name
+    }
+
+    pub fn import_type(&self) -> ImportType {
+        self.import_type
+    }
+
+    pub fn header(&self) -> Option<&[String]> {
+        self.content.header()
+    }
+
+    pub fn documents(&self) -> &[T] {
+        self.content.documents()
+    }
+
+    pub fn options(&self) -> &ImportOptions {
+        &self.options
+    }
+}
+
+impl<T> Method for ImportDocuments<T> {
+    type Result = ImportResult;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl<T> Prepare for ImportDocuments<T>
+where
+    T: Serialize + Debug,
+{
+    type Content = ImportContent<T>;
+
+    fn operation(&self) -> Operation {
+        Operation::Create
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_IMPORT)
+    }
+
+    fn parameters(&self) -> Parameters {
+        let mut params = Parameters::new();
+        params.insert(PARAM_COLLECTION, self.collection_name.clone());
+        if self.content.header().is_none() {
+            params.insert(PARAM_TYPE, self.import_type.as_str());
+        }
+        if let Some(on_duplicate) = self.options.on_duplicate() {
+            params.insert(PARAM_ON_DUPLICATE, on_duplicate.as_str());
+        }
+        if let Some(complete) = self.options.complete() {
+            params.insert(PARAM_COMPLETE, complete);
+        }
+        if let Some(overwrite) = self.options.overwrite() {
+            params.insert(PARAM_OVERWRITE, overwrite);
+        }
+        if let Some(from_prefix) = self.options.from_prefix() {
+            params.insert(PARAM_FROM_PREFIX, from_prefix.to_owned());
+        }
+        if let Some(to_prefix) = self.options.to_prefix() {
+            params.insert(PARAM_TO_PREFIX, to_prefix.to_owned());
+        }
+        if let Some(details) = self.options.details() {
+            params.insert(PARAM_DETAILS, details);
+        }
+        if let Some(force_wait_for_sync) = self.options.force_wait_for_sync() {
+            params.insert(PARAM_WAIT_FOR_SYNC, force_wait_for_sync);
+        }
+        params
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        Some(&self.content)
+    }
+
+    fn is_line_delimited(&self) -> bool {
+        self.content.header().is_some() || self.import_type == ImportType::Documents
+    }
+}
@@ -1027,3 +1027,286 @@ impl Default for DocumentModifyOptions {
         }
     }
 }
+
+/// The format in which documents are sent to the server by the
+/// `ImportDocuments` method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImportType {
+    /// Each document is sent as a JSON object on a separate line.
+    Documents,
+    /// All documents are sent as one JSON array.
+    List,
+}
+
+impl ImportType {
+    /// Returns a str representation of this import type.
+    pub fn as_str(&self) -> &str {
+        match *self {
+            ImportType::Documents => "documents",
+            ImportType::List => "list",
+        }
+    }
+}
+
+impl Default for ImportType {
+    fn default() -> Self {
+        ImportType::Documents
+    }
+}
+
+/// Specifies what the server does when an imported document violates a
+/// unique constraint, e.g. when a document with the same key already exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OnDuplicate {
+    /// The document is not imported and counted as an error.
+    Error,
+    /// The existing document is updated with the attributes of the imported
+    /// document.
+    Update,
+    /// The existing document is replaced by the imported document.
+    Replace,
+    /// The document is not imported and counted as ignored.
+    Ignore,
+}
+
+impl OnDuplicate {
+    /// Returns a str representation of this on duplicate action.
+    pub fn as_str(&self) -> &str {
+        match *self {
+            OnDuplicate::Error => "error",
+            OnDuplicate::Update => "update",
+            OnDuplicate::Replace => "replace",
+            OnDuplicate::Ignore => "ignore",
+        }
+    }
+}
+
+/// The content of an `ImportDocuments` method.
+///
+/// If a header row is present it is serialized as the first item followed
+/// by the documents. In this case each document must serialize into an
+/// array of values in the order of the attribute names of the header row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportContent<T> {
+    header: Option<Vec<String>>,
+    documents: Vec<T>,
+}
+
+impl<T> ImportContent<T> {
+    pub fn new(header: Option<Vec<String>>, documents: Vec<T>) -> Self {
+        ImportContent { header, documents }
+    }
+
+    pub fn header(&self) -> Option<&[String]> {
+        self.header.as_ref().map(Vec::as_slice)
+    }
+
+    pub fn documents(&self) -> &[T] {
+        &self.documents
+    }
+}
+
+impl<T> Serialize for ImportContent<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeSeq;
+
+        let header_len = if self.header.is_some() { 1 } else { 0 };
+        let mut seq = serializer.serialize_seq(Some(header_len + self.documents.len()))?;
+        if let Some(ref header) = self.header {
+            seq.serialize_element(header)?;
+        }
+        for document in &self.documents {
+            seq.serialize_element(document)?;
+        }
+        seq.end()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportOptions {
+    on_duplicate: Option<OnDuplicate>,
+    complete: Option<bool>,
+    overwrite: Option<bool>,
+    from_prefix: Option<String>,
+    to_prefix: Option<String>,
+    details: Option<bool>,
+    force_wait_for_sync: Option<bool>,
+}
+
+impl ImportOptions {
+    pub fn new() -> Self {
+        ImportOptions::default()
+    }
+
+    pub fn on_duplicate(&self) -> Option<OnDuplicate> {
+        self.on_duplicate
+    }
+
+    pub fn with_on_duplicate(mut self, on_duplicate: OnDuplicate) -> Self {
+        self.on_duplicate = Some(on_duplicate);
+        self
+    }
+
+    pub fn clear_on_duplicate(mut self) -> Self {
+        self.on_duplicate = None;
+        self
+    }
+
+    pub fn complete(&self) -> Option<bool> {
+        self.complete
+    }
+
+    pub fn with_complete(mut self, complete: bool) -> Self {
+        self.complete = Some(complete);
+        self
+    }
+
+    pub fn clear_complete(mut self) -> Self {
+        self.complete = None;
+        self
+    }
+
+    pub fn overwrite(&self) -> Option<bool> {
+        self.overwrite
+    }
+
+    pub fn with_overwrite(mut self, overwrite: bool) -> Self {
+        self.overwrite = Some(overwrite);
+        self
+    }
+
+    pub fn clear_overwrite(mut self) -> Self {
+        self.overwrite = None;
+        self
+    }
+
+    pub fn from_prefix(&self) -> Option<&String> {
+        self.from_prefix.as_ref()
+    }
+
+    pub fn with_from_prefix<P>(mut self, from_prefix: P) -> Self
+    where
+        P: Into<String>,
+    {
+        self.from_prefix = Some(from_prefix.into());
+        self
+    }
+
+    pub fn clear_from_prefix(mut self) -> Self {
+        self.from_prefix = None;
+        self
+    }
+
+    pub fn to_prefix(&self) -> Option<&String> {
+        self.to_prefix.as_ref()
+    }
+
+    pub fn with_to_prefix<P>(mut self, to_prefix: P) -> Self
+    where
+        P: Into<String>,
+    {
+        self.to_prefix = Some(to_prefix.into());
+        self
+    }
+
+    pub fn clear_to_prefix(mut self) -> Self {
+        self.to_prefix = None;
+        self
+    }
+
+    pub fn details(&self) -> Option<bool> {
+        self.details
+    }
+
+    pub fn with_details(mut self, details: bool) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    pub fn clear_details(mut self) -> Self {
+        self.details = None;
+        self
+    }
+
+    pub fn force_wait_for_sync(&self) -> Option<bool> {
+        self.force_wait_for_sync
+    }
+
+    pub fn with_force_wait_for_sync(mut self, force_wait_for_sync: bool) -> Self {
+        self.force_wait_for_sync = Some(force_wait_for_sync);
+        self
+    }
+
+    pub fn clear_force_wait_for_sync(mut self) -> Self {
+        self.force_wait_for_sync = None;
+        self
+    }
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        ImportOptions {
+            on_duplicate: None,
+            complete: None,
+            overwrite: None,
+            from_prefix: None,
+            to_prefix: None,
+            details: None,
+            force_wait_for_sync: None,
+        }
+    }
+}
+
+/// The result of importing documents.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ImportResult {
+    created: u64,
+    errors: u64,
+    empty: u64,
+    updated: u64,
+    ignored: u64,
+    #[serde(default)]
+    details: Vec<String>,
+}
+
+impl ImportResult {
+    /// Returns the number of documents imported.
+    pub fn created(&self) -> u64 {
+        self.created
+    }
+
+    /// Returns the number of documents that were not imported due to an
+    /// error.
+    pub fn errors(&self) -> u64 {
+        self.errors
+    }
+
+    /// Returns the number of empty lines found in the input.
+    pub fn empty(&self) -> u64 {
+        self.empty
+    }
+
+    /// Returns the number of updated or replaced documents.
+    pub fn updated(&self) -> u64 {
+        self.updated
+    }
+
+    /// Returns the number of documents that were ignored because of a unique
+    /// constraint violation.
+    pub fn ignored(&self) -> u64 {
+        self.ignored
+    }
+
+    /// Returns the messages describing the errors that occurred.
+    ///
+    /// The messages are only returned if the `details` option has been set.
+    pub fn details(&self) -> &[String] {
+        &self.details
+    }
+}
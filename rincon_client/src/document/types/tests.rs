@@ -221,3 +221,63 @@ fn serialize_struct_document_update_with_revision() {
         &json
     );
 }
+
+#[test]
+fn serialize_import_content_without_header() {
+    let documents = vec![
+        MyContent {
+            a: "Hugo".to_owned(),
+            b: 42,
+        },
+        MyContent {
+            a: "Anna".to_owned(),
+            b: 7,
+        },
+    ];
+
+    let content = ImportContent::new(None, documents);
+    let json = serde_json::to_string(&content).unwrap();
+
+    assert_eq!(r#"[{"a":"Hugo","b":42},{"a":"Anna","b":7}]"#, &json);
+}
+
+#[test]
+fn serialize_import_content_with_header() {
+    let header = vec!["a".to_owned(), "b".to_owned()];
+    let rows = vec![("Hugo", 42), ("Anna", 7)];
+
+    let content = ImportContent::new(Some(header), rows);
+    let json = serde_json::to_string(&content).unwrap();
+
+    assert_eq!(r#"[["a","b"],["Hugo",42],["Anna",7]]"#, &json);
+}
+
+#[test]
+fn deserialize_import_result_without_details() {
+    let json_string = r#"{"error":false,"created":2,"errors":1,"empty":0,"updated":0,"ignored":3}"#;
+
+    let result: ImportResult = serde_json::from_str(json_string).unwrap();
+
+    assert_eq!(2, result.created());
+    assert_eq!(1, result.errors());
+    assert_eq!(0, result.empty());
+    assert_eq!(0, result.updated());
+    assert_eq!(3, result.ignored());
+    assert!(result.details().is_empty());
+}
+
+#[test]
+fn deserialize_import_result_with_details() {
+    let json_string = r#"{"error":false,"created":0,"errors":1,"empty":0,"updated":0,"ignored":0,"details":["at position 0: creating document failed with error 'unique constraint violated'"]}"#;
+
+    let result: ImportResult = serde_json::from_str(json_string).unwrap();
+
+    assert_eq!(1, result.errors());
+    assert_eq!(
+        &[
+            "at position 0: creating document failed with error 'unique constraint violated'"
+                .to_owned(),
+        ],
+        result.details()
+    );
+}
@@ -11,11 +11,10 @@ use serde::ser::{Serialize, Serializer};
 use document::types::{DocumentId, DocumentKey, Revision};
 use rincon_core::arango::protocol::{
     FIELD_EDGE_DEFINITIONS, FIELD_ENTITY_FROM, FIELD_ENTITY_ID, FIELD_ENTITY_KEY,
-    FIELD_ENTITY_OLD_REVISION, FIELD_ENTITY_REVISION, FIELD_ENTITY_TO, FIELD_NAME,
-    FIELD_ORPHAN_COLLECTIONS,
+    FIELD_ENTITY_OLD_REVISION, FIELD_ENTITY_REVISION, FIELD_ENTITY_TO, FIELD_IS_SMART, FIELD_NAME,
+    FIELD_NUMBER_OF_SHARDS, FIELD_ORPHAN_COLLECTIONS, FIELD_REPLICATION_FACTOR,
+    FIELD_SMART_GRAPH_ATTRIBUTE,
 };
-use rincon_core::arango::protocol::{FIELD_IS_SMART, FIELD_SMART_GRAPH_ATTRIBUTE};
-use rincon_core::arango::protocol::{FIELD_NUMBER_OF_SHARDS, FIELD_REPLICATION_FACTOR};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Graph {
@@ -25,14 +24,10 @@ pub struct Graph {
     name: String,
     edge_definitions: Vec<EdgeDefinition>,
     orphan_collections: Vec<String>,
-    #[cfg(feature = "enterprise")]
-    smart: bool,
-    #[cfg(feature = "enterprise")]
-    smart_graph_attribute: String,
-    #[cfg(feature = "cluster")]
-    number_of_shards: u16,
-    #[cfg(feature = "cluster")]
-    replication_factor: u64,
+    smart: Option<bool>,
+    smart_graph_attribute: Option<String>,
+    number_of_shards: Option<u16>,
+    replication_factor: Option<u64>,
 }
 
 impl Graph {
@@ -60,23 +55,19 @@ impl Graph {
         &self.orphan_collections
     }
 
-    #[cfg(feature = "enterprise")]
-    pub fn is_smart(&self) -> bool {
+    pub fn is_smart(&self) -> Option<bool> {
         self.smart
     }
 
-    #[cfg(feature = "enterprise")]
-    pub fn smart_graph_attribute(&self) -> &str {
-        &self.smart_graph_attribute
+    pub fn smart_graph_attribute(&self) -> Option<&str> {
+        self.smart_graph_attribute.as_ref().map(String::as_str)
     }
 
-    #[cfg(feature = "cluster")]
-    pub fn number_of_shards(&self) -> u16 {
+    pub fn number_of_shards(&self) -> Option<u16> {
         self.number_of_shards
     }
 
-    #[cfg(feature = "cluster")]
-    pub fn replication_factor(&self) -> u64 {
+    pub fn replication_factor(&self) -> Option<u64> {
         self.replication_factor
     }
 }
@@ -162,13 +153,9 @@ impl<'de> Deserialize<'de> for Graph {
                 let mut name: Option<String> = None;
                 let mut edge_definitions: Option<Vec<EdgeDefinition>> = None;
                 let mut orphan_collections: Option<Vec<String>> = None;
-                #[cfg(feature = "enterprise")]
                 let mut smart: Option<bool> = None;
-                #[cfg(feature = "enterprise")]
                 let mut smart_graph_attribute: Option<String> = None;
-                #[cfg(feature = "cluster")]
                 let mut number_of_shards: Option<u16> = None;
-                #[cfg(feature = "cluster")]
                 let mut replication_factor: Option<u64> = None;
 
                 let mut fields = map;
@@ -192,38 +179,18 @@ impl<'de> Deserialize<'de> for Graph {
                         GraphField::OrphanCollections => {
                             orphan_collections = fields.next_value()?;
                         },
-                        #[cfg(feature = "enterprise")]
                         GraphField::Smart => {
                             smart = fields.next_value()?;
                         },
-                        #[cfg(not(feature = "enterprise"))]
-                        GraphField::Smart => {
-                            let _: Option<bool> = fields.next_value()?;
-                        },
-                        #[cfg(feature = "enterprise")]
                         GraphField::SmartGraphAttribute => {
                             smart_graph_attribute = fields.next_value()?;
                         },
-                        #[cfg(not(feature = "enterprise"))]
-                        GraphField::SmartGraphAttribute => {
-                            let _: Option<String> = fields.next_value()?;
-                        },
-                        #[cfg(feature = "cluster")]
                         GraphField::NumberOfShards => {
                             number_of_shards = fields.next_value()?;
                         },
-                        #[cfg(not(feature = "cluster"))]
-                        GraphField::NumberOfShards => {
-                            let _: Option<u16> = fields.next_value()?;
-                        },
-                        #[cfg(feature = "cluster")]
                         GraphField::ReplicationFactor => {
                             replication_factor = fields.next_value()?;
                         },
-                        #[cfg(not(feature = "cluster"))]
-                        GraphField::ReplicationFactor => {
-                            let _: Option<u64> = fields.next_value()?;
-                        },
                         GraphField::Other(_) => {
                             //ignore
                             let _: Value = fields.next_value()?;
@@ -231,7 +198,6 @@ impl<'de> Deserialize<'de> for Graph {
                     }
                 }
 
-                #[cfg(all(feature = "enterprise", feature = "cluster"))]
                 match (
                     id,
                     key,
@@ -239,10 +205,6 @@ impl<'de> Deserialize<'de> for Graph {
                     name,
                     edge_definitions,
                     orphan_collections,
-                    smart,
-                    smart_graph_attribute,
-                    number_of_shards,
-                    replication_factor,
                 ) {
                     (
                         Some(id),
@@ -251,10 +213,6 @@ impl<'de> Deserialize<'de> for Graph {
                         _name,
                         Some(edge_definitions),
                         Some(orphan_collections),
-                        Some(smart),
-                        Some(smart_graph_attribute),
-                        Some(number_of_shards),
-                        Some(replication_factor),
                     ) => {
                         let (key, name) = match (_key, _name) {
                             (Some(_key), Some(_name)) => (_key, _name),
@@ -278,188 +236,11 @@ impl<'de> Deserialize<'de> for Graph {
                             replication_factor,
                         })
                     },
-                    (None, _, _, _, _, _, _, _, _, _) => {
-                        Err(A::Error::missing_field(FIELD_ENTITY_ID))
-                    },
-                    (_, _, None, _, _, _, _, _, _, _) => {
-                        Err(A::Error::missing_field(FIELD_ENTITY_REVISION))
-                    },
-                    (_, _, _, _, None, _, _, _, _, _) => {
-                        Err(A::Error::missing_field(FIELD_EDGE_DEFINITIONS))
-                    },
-                    (_, _, _, _, _, None, _, _, _, _) => {
-                        Err(A::Error::missing_field(FIELD_ORPHAN_COLLECTIONS))
-                    },
-                    (_, _, _, _, _, _, None, _, _, _) => {
-                        Err(A::Error::missing_field(FIELD_IS_SMART))
-                    },
-                    (_, _, _, _, _, _, _, None, _, _) => {
-                        Err(A::Error::missing_field(FIELD_SMART_GRAPH_ATTRIBUTE))
-                    },
-                    (_, _, _, _, _, _, _, _, None, _) => {
-                        Err(A::Error::missing_field(FIELD_NUMBER_OF_SHARDS))
-                    },
-                    (_, _, _, _, _, _, _, _, _, None) => {
-                        Err(A::Error::missing_field(FIELD_REPLICATION_FACTOR))
-                    },
-                }
-
-                #[cfg(not(any(feature = "enterprise", feature = "cluster")))]
-                match (
-                    id,
-                    key,
-                    revision,
-                    name,
-                    edge_definitions,
-                    orphan_collections,
-                ) {
-                    (
-                        Some(id),
-                        _key,
-                        Some(revision),
-                        _name,
-                        Some(edge_definitions),
-                        Some(orphan_collections),
-                    ) => {
-                        let (key, name) = match (_key, _name) {
-                            (Some(_key), Some(_name)) => (_key, _name),
-                            (Some(_key), None) => (_key.clone(), _key),
-                            (None, Some(_name)) => (_name.clone(), _name),
-                            (None, None) => return Err(A::Error::missing_field("name or _key")),
-                        };
-                        let id = DocumentId::from_str(&id).map_err(A::Error::custom)?;
-                        let key = DocumentKey::from_string(key).map_err(A::Error::custom)?;
-                        let revision = Revision::from_string(revision);
-                        Ok(Graph {
-                            id,
-                            key,
-                            revision,
-                            name,
-                            edge_definitions,
-                            orphan_collections,
-                        })
-                    },
                     (None, _, _, _, _, _) => Err(A::Error::missing_field(FIELD_ENTITY_ID)),
                     (_, _, None, _, _, _) => Err(A::Error::missing_field(FIELD_ENTITY_REVISION)),
                     (_, _, _, _, None, _) => Err(A::Error::missing_field(FIELD_EDGE_DEFINITIONS)),
                     (_, _, _, _, _, None) => Err(A::Error::missing_field(FIELD_ORPHAN_COLLECTIONS)),
                 }
-
-                #[cfg(all(feature = "enterprise", not(feature = "cluster")))]
-                match (
-                    id,
-                    key,
-                    revision,
-                    name,
-                    edge_definitions,
-                    orphan_collections,
-                    smart,
-                    smart_graph_attribute,
-                ) {
-                    (
-                        Some(id),
-                        _key,
-                        Some(revision),
-                        _name,
-                        Some(edge_definitions),
-                        Some(orphan_collections),
-                        Some(smart),
-                        Some(smart_graph_attribute),
-                    ) => {
-                        let (key, name) = match (_key, _name) {
-                            (Some(_key), Some(_name)) => (_key, _name),
-                            (Some(_key), None) => (_key.clone(), _key),
-                            (None, Some(_name)) => (_name.clone(), _name),
-                            (None, None) => return Err(A::Error::missing_field("name or _key")),
-                        };
-                        let id = DocumentId::from_str(&id).map_err(A::Error::custom)?;
-                        let key = DocumentKey::from_string(key).map_err(A::Error::custom)?;
-                        let revision = Revision::from_string(revision);
-                        Ok(Graph {
-                            id,
-                            key,
-                            revision,
-                            name,
-                            edge_definitions,
-                            orphan_collections,
-                            smart,
-                            smart_graph_attribute,
-                        })
-                    },
-                    (None, _, _, _, _, _, _, _) => Err(A::Error::missing_field(FIELD_ENTITY_ID)),
-                    (_, _, None, _, _, _, _, _) => {
-                        Err(A::Error::missing_field(FIELD_ENTITY_REVISION))
-                    },
-                    (_, _, _, _, None, _, _, _) => {
-                        Err(A::Error::missing_field(FIELD_EDGE_DEFINITIONS))
-                    },
-                    (_, _, _, _, _, None, _, _) => {
-                        Err(A::Error::missing_field(FIELD_ORPHAN_COLLECTIONS))
-                    },
-                    (_, _, _, _, _, _, None, _) => Err(A::Error::missing_field(FIELD_IS_SMART)),
-                    (_, _, _, _, _, _, _, None) => {
-                        Err(A::Error::missing_field(FIELD_SMART_GRAPH_ATTRIBUTE))
-                    },
-                }
-
-                #[cfg(all(not(feature = "enterprise"), feature = "cluster"))]
-                match (
-                    id,
-                    key,
-                    revision,
-                    name,
-                    edge_definitions,
-                    orphan_collections,
-                    number_of_shards,
-                    replication_factor,
-                ) {
-                    (
-                        Some(id),
-                        _key,
-                        Some(revision),
-                        _name,
-                        Some(edge_definitions),
-                        Some(orphan_collections),
-                        Some(number_of_shards),
-                        Some(replication_factor),
-                    ) => {
-                        let (key, name) = match (_key, _name) {
-                            (Some(_key), Some(_name)) => (_key, _name),
-                            (Some(_key), None) => (_key.clone(), _key),
-                            (None, Some(_name)) => (_name.clone(), _name),
-                            (None, None) => return Err(A::Error::missing_field("name or _key")),
-                        };
-                        let id = DocumentId::from_str(&id).map_err(A::Error::custom)?;
-                        let key = DocumentKey::from_string(key).map_err(A::Error::custom)?;
-                        let revision = Revision::from_string(revision);
-                        Ok(Graph {
-                            id,
-                            key,
-                            revision,
-                            name,
-                            edge_definitions,
-                            orphan_collections,
-                            number_of_shards,
-                            replication_factor,
-                        })
-                    },
-                    (None, _, _, _, _, _, _, _) => Err(A::Error::missing_field(FIELD_ENTITY_ID)),
-                    (_, _, None, _, _, _, _, _) => {
-                        Err(A::Error::missing_field(FIELD_ENTITY_REVISION))
-                    },
-                    (_, _, _, _, None, _, _, _) => {
-                        Err(A::Error::missing_field(FIELD_EDGE_DEFINITIONS))
-                    },
-                    (_, _, _, _, _, None, _, _) => {
-                        Err(A::Error::missing_field(FIELD_ORPHAN_COLLECTIONS))
-                    },
-                    (_, _, _, _, _, _, None, _) => {
-                        Err(A::Error::missing_field(FIELD_NUMBER_OF_SHARDS))
-                    },
-                    (_, _, _, _, _, _, _, None) => {
-                        Err(A::Error::missing_field(FIELD_REPLICATION_FACTOR))
-                    },
-                }
             }
         }
 
@@ -474,16 +255,14 @@ pub struct NewGraph {
     edge_definitions: Vec<EdgeDefinition>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     orphan_collections: Vec<String>,
-    #[cfg(feature = "enterprise")]
     #[serde(rename = "isSmart")]
-    smart: bool,
-    #[cfg(any(feature = "enterprise", feature = "cluster"))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    smart: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     options: Option<GraphOptions>,
 }
 
 impl NewGraph {
-    #[cfg(not(feature = "enterprise"))]
     pub fn new<Name, Edges>(name: Name, edges: Edges) -> Self
     where
         Name: Into<String>,
@@ -493,22 +272,7 @@ impl NewGraph {
             name: name.into(),
             edge_definitions: Vec::from_iter(edges.into_iter()),
             orphan_collections: Vec::new(),
-            #[cfg(any(feature = "enterprise", feature = "cluster"))]
-            options: None,
-        }
-    }
-
-    #[cfg(feature = "enterprise")]
-    pub fn new<Name, Edges>(name: Name, edges: Edges, smart: bool) -> Self
-    where
-        Name: Into<String>,
-        Edges: IntoIterator<Item = EdgeDefinition>,
-    {
-        NewGraph {
-            name: name.into(),
-            edge_definitions: Vec::from_iter(edges.into_iter()),
-            orphan_collections: Vec::new(),
-            smart,
+            smart: None,
             options: None,
         }
     }
@@ -517,14 +281,7 @@ impl NewGraph {
     where
         Name: Into<String>,
     {
-        #[cfg(not(feature = "enterprise"))]
-        {
-            NewGraph::new(name, Vec::new())
-        }
-        #[cfg(feature = "enterprise")]
-        {
-            NewGraph::new(name, Vec::new(), false)
-        }
+        NewGraph::new(name, Vec::new())
     }
 
     pub fn with_edge_definitions<Edges>(mut self, edges: Edges) -> Self
@@ -551,12 +308,13 @@ impl NewGraph {
         &mut self.orphan_collections
     }
 
-    #[cfg(feature = "enterprise")]
-    pub fn set_smart(&mut self, smart: bool) {
-        self.smart = smart;
+    pub fn set_smart<S>(&mut self, smart: S)
+    where
+        S: Into<Option<bool>>,
+    {
+        self.smart = smart.into();
     }
 
-    #[cfg(any(feature = "enterprise", feature = "cluster"))]
     pub fn options_mut(&mut self) -> &mut GraphOptions {
         self.options.get_or_insert_with(GraphOptions::default)
     }
@@ -573,47 +331,36 @@ impl NewGraph {
         &self.orphan_collections
     }
 
-    #[cfg(feature = "enterprise")]
-    pub fn is_smart(&self) -> bool {
+    pub fn is_smart(&self) -> Option<bool> {
         self.smart
     }
 
-    #[cfg(any(feature = "enterprise", feature = "cluster"))]
     pub fn options(&self) -> Option<&GraphOptions> {
         self.options.as_ref()
     }
 }
 
-#[cfg(any(feature = "enterprise", feature = "cluster"))]
 #[allow(missing_copy_implementations)]
 #[derive(Debug, Clone, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GraphOptions {
-    #[cfg(feature = "enterprise")]
     #[serde(skip_serializing_if = "Option::is_none")]
     smart_graph_attribute: Option<String>,
-    #[cfg(feature = "cluster")]
     #[serde(skip_serializing_if = "Option::is_none")]
     number_of_shards: Option<u16>,
-    #[cfg(feature = "cluster")]
     #[serde(skip_serializing_if = "Option::is_none")]
     replication_factor: Option<u64>,
 }
 
-#[cfg(any(feature = "enterprise", feature = "cluster"))]
 impl GraphOptions {
     fn empty() -> Self {
         GraphOptions {
-            #[cfg(feature = "enterprise")]
             smart_graph_attribute: None,
-            #[cfg(feature = "cluster")]
             number_of_shards: None,
-            #[cfg(feature = "cluster")]
             replication_factor: None,
         }
     }
 
-    #[cfg(feature = "enterprise")]
     pub fn set_smart_graph_attribute<Attr>(&mut self, smart_graph_attribute: Attr)
     where
         Attr: Into<Option<String>>,
@@ -621,7 +368,6 @@ impl GraphOptions {
         self.smart_graph_attribute = smart_graph_attribute.into();
     }
 
-    #[cfg(feature = "cluster")]
     pub fn set_number_of_shards<S>(&mut self, number_of_shards: S)
     where
         S: Into<Option<u16>>,
@@ -629,7 +375,6 @@ impl GraphOptions {
         self.number_of_shards = number_of_shards.into();
     }
 
-    #[cfg(feature = "cluster")]
     pub fn set_replication_factor<R>(&mut self, replication_factor: R)
     where
         R: Into<Option<u64>>,
@@ -637,23 +382,19 @@ impl GraphOptions {
         self.replication_factor = replication_factor.into();
     }
 
-    #[cfg(feature = "enterprise")]
     pub fn smart_graph_attribute(&self) -> Option<&String> {
         self.smart_graph_attribute.as_ref()
     }
 
-    #[cfg(feature = "cluster")]
     pub fn number_of_shards(&self) -> Option<u16> {
         self.number_of_shards
     }
 
-    #[cfg(feature = "cluster")]
     pub fn replication_factor(&self) -> Option<u64> {
         self.replication_factor
     }
 }
 
-#[cfg(any(feature = "enterprise", feature = "cluster"))]
 impl Default for GraphOptions {
     fn default() -> Self {
         GraphOptions::empty()
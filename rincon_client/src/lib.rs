@@ -144,22 +144,18 @@
 //! # }
 //! ```
 //!
-//! # Advanced attributes and optional crate features
+//! # Server specific attributes
 //!
 //! Some attributes of methods or their results are only meaningful in a certain
 //! server configuration, like using RocksDB instead of MMFiles or the server is
-//! setup in a cluster. Those attributes are only available in the API of this
-//! crate if the crate is compiled with the related feature enabled. The
-//! optional crate features are:
+//! setup in a cluster. Those attributes are always available in the API of
+//! this crate. Attributes of methods are optional and validated by the server.
+//! Attributes of results are returned as `Option` and are `None` if the server
+//! does not return them.
 //!
-//! * `mmfiles` : enables MMFiles storage engine related attributes,
-//! * `rocksdb` : enables RocksDB storage engine related attributes,
-//! * `cluster` : enables cluster configuration related attributes,
-//! * `enterprise` : enables attributes supported only by the enterprise
-//! version of ArangoDB,
-//!
-//! It is not necessary to activate any of the optional crate features if an
-//! application does not need to access the feature related attributes.
+//! The configuration of a server can be detected at runtime by the methods
+//! `GetServerVersion`, `GetEngine` and `GetServerRole`, which are combined
+//! into the `ServerCapabilities` type of the `admin` module.
 //!
 //! [ArangoDB]: https://www.arangodb.com
 //! [`rincon_connector`]: https://docs.rs/rincon_connector
@@ -196,6 +192,7 @@ pub mod document;
 pub mod graph;
 #[allow(missing_docs)]
 pub mod index;
+pub mod transaction;
 pub mod user;
 
 pub mod client {
@@ -223,6 +220,8 @@ pub mod client {
     pub use super::graph::types::*;
     pub use super::index::methods::*;
     pub use super::index::types::*;
+    pub use super::transaction::methods::*;
+    pub use super::transaction::types::*;
     pub use super::user::methods::*;
     pub use super::user::types::*;
 }
@@ -0,0 +1,419 @@
+//! Methods for executing transactions.
+
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+
+use super::types::*;
+use rincon_core::api::method::{Method, Operation, Parameters, Prepare, RpcReturnType};
+use rincon_core::arango::protocol::{
+    FIELD_CODE, FIELD_RESULT, HEADER_TRANSACTION_ID, PATH_API_TRANSACTION, PATH_BEGIN,
+};
+
+/// Executes a JavaScript transaction on the server.
+///
+/// The action function of the transaction is executed on the server and its
+/// return value is returned as the result of this method.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecuteTransaction<T> {
+    result_type: PhantomData<T>,
+    transaction: NewTransaction,
+}
+
+impl<T> ExecuteTransaction<T> {
+    /// Constructs a new instance of the `ExecuteTransaction` method for the
+    /// given `NewTransaction`.
+    pub fn new(transaction: NewTransaction) -> Self {
+        ExecuteTransaction {
+            result_type: PhantomData,
+            transaction,
+        }
+    }
+
+    /// Returns the transaction to be executed.
+    pub fn transaction(&self) -> &NewTransaction {
+        &self.transaction
+    }
+}
+
+impl<T> Method for ExecuteTransaction<T>
+where
+    T: DeserializeOwned,
+{
+    type Result = T;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: Some(FIELD_RESULT),
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl<T> Prepare for ExecuteTransaction<T> {
+    type Content = NewTransaction;
+
+    fn operation(&self) -> Operation {
+        Operation::Create
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_TRANSACTION)
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        Some(&self.transaction)
+    }
+}
+
+/// Begins a new stream transaction on the server.
+///
+/// The returned `TransactionInfo` contains the id of the transaction. Method
+/// calls are executed within the transaction by wrapping them into the
+/// `InTransaction` method. A stream transaction must be finished by either
+/// executing the `CommitTransaction` or the `AbortTransaction` method.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BeginTransaction {
+    transaction: NewStreamTransaction,
+}
+
+impl BeginTransaction {
+    /// Constructs a new instance of the `BeginTransaction` method with the
+    /// given `NewStreamTransaction` parameters.
+    pub fn new(transaction: NewStreamTransaction) -> Self {
+        BeginTransaction { transaction }
+    }
+
+    /// Constructs a new instance of the `BeginTransaction` method that will
+    /// start a transaction for the given collections.
+    ///
+    /// All other parameters will be set to their default values.
+    pub fn with_collections(collections: TransactionCollections) -> Self {
+        BeginTransaction {
+            transaction: NewStreamTransaction::new(collections),
+        }
+    }
+
+    /// Returns the parameters of the transaction to be started.
+    pub fn transaction(&self) -> &NewStreamTransaction {
+        &self.transaction
+    }
+}
+
+impl Method for BeginTransaction {
+    type Result = TransactionInfo;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: Some(FIELD_RESULT),
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for BeginTransaction {
+    type Content = NewStreamTransaction;
+
+    fn operation(&self) -> Operation {
+        Operation::Create
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_TRANSACTION) + PATH_BEGIN
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        Some(&self.transaction)
+    }
+}
+
+/// Commits the stream transaction identified by the given id.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommitTransaction {
+    transaction_id: String,
+}
+
+impl CommitTransaction {
+    /// Constructs a new instance of the `CommitTransaction` method for the
+    /// transaction with the given id.
+    pub fn new(transaction_id: String) -> Self {
+        CommitTransaction { transaction_id }
+    }
+
+    /// Constructs a new instance of the `CommitTransaction` method for the
+    /// transaction with the given id.
+    pub fn with_id<I>(transaction_id: I) -> Self
+    where
+        I: Into<String>,
+    {
+        CommitTransaction {
+            transaction_id: transaction_id.into(),
+        }
+    }
+
+    /// Returns the id of the transaction to be committed.
+    pub fn transaction_id(&self) -> &str {
+        &self.transaction_id
+    }
+}
+
+impl Method for CommitTransaction {
+    type Result = TransactionInfo;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: Some(FIELD_RESULT),
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for CommitTransaction {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Replace
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_TRANSACTION) + "/" + &self.transaction_id
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Aborts the stream transaction identified by the given id and rolls back
+/// all changes made within the transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AbortTransaction {
+    transaction_id: String,
+}
+
+impl AbortTransaction {
+    /// Constructs a new instance of the `AbortTransaction` method for the
+    /// transaction with the given id.
+    pub fn new(transaction_id: String) -> Self {
+        AbortTransaction { transaction_id }
+    }
+
+    /// Constructs a new instance of the `AbortTransaction` method for the
+    /// transaction with the given id.
+    pub fn with_id<I>(transaction_id: I) -> Self
+    where
+        I: Into<String>,
+    {
+        AbortTransaction {
+            transaction_id: transaction_id.into(),
+        }
+    }
+
+    /// Returns the id of the transaction to be aborted.
+    pub fn transaction_id(&self) -> &str {
+        &self.transaction_id
+    }
+}
+
+impl Method for AbortTransaction {
+    type Result = TransactionInfo;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: Some(FIELD_RESULT),
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for AbortTransaction {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Delete
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_TRANSACTION) + "/" + &self.transaction_id
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Fetches the status of the stream transaction identified by the given id.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetTransactionStatus {
+    transaction_id: String,
+}
+
+impl GetTransactionStatus {
+    /// Constructs a new instance of the `GetTransactionStatus` method for the
+    /// transaction with the given id.
+    pub fn new(transaction_id: String) -> Self {
+        GetTransactionStatus { transaction_id }
+    }
+
+    /// Constructs a new instance of the `GetTransactionStatus` method for the
+    /// transaction with the given id.
+    pub fn with_id<I>(transaction_id: I) -> Self
+    where
+        I: Into<String>,
+    {
+        GetTransactionStatus {
+            transaction_id: transaction_id.into(),
+        }
+    }
+
+    /// Returns the id of the transaction for which the status shall be
+    /// fetched.
+    pub fn transaction_id(&self) -> &str {
+        &self.transaction_id
+    }
+}
+
+impl Method for GetTransactionStatus {
+    type Result = TransactionInfo;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: Some(FIELD_RESULT),
+        code_field: Some(FIELD_CODE),
+    };
+}
+
+impl Prepare for GetTransactionStatus {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Read
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_TRANSACTION) + "/" + &self.transaction_id
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Executes the wrapped method within the stream transaction identified by
+/// the given id.
+///
+/// This method adds the `x-arango-trx-id` header to the header parameters of
+/// the wrapped method. All other properties are taken from the wrapped method
+/// unchanged. It is meant to be used with document, cursor and index methods
+/// as those are the methods that ArangoDB supports within stream
+/// transactions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InTransaction<M> {
+    transaction_id: String,
+    method: M,
+}
+
+impl<M> InTransaction<M> {
+    /// Constructs a new instance of the `InTransaction` method that executes
+    /// the given method within the transaction with the given id.
+    pub fn new<I>(transaction_id: I, method: M) -> Self
+    where
+        I: Into<String>,
+    {
+        InTransaction {
+            transaction_id: transaction_id.into(),
+            method,
+        }
+    }
+
+    /// Returns the id of the transaction the method is executed in.
+    pub fn transaction_id(&self) -> &str {
+        &self.transaction_id
+    }
+
+    /// Returns the method that is executed within the transaction.
+    pub fn method(&self) -> &M {
+        &self.method
+    }
+
+    /// Unwraps the method that is executed within the transaction.
+    pub fn unwrap(self) -> M {
+        self.method
+    }
+}
+
+impl<M> Method for InTransaction<M>
+where
+    M: Method,
+{
+    type Result = <M as Method>::Result;
+    const RETURN_TYPE: RpcReturnType = <M as Method>::RETURN_TYPE;
+
+    fn return_type(&self) -> RpcReturnType {
+        self.method.return_type()
+    }
+}
+
+impl<M> Prepare for InTransaction<M>
+where
+    M: Prepare,
+{
+    type Content = <M as Prepare>::Content;
+
+    fn operation(&self) -> Operation {
+        self.method.operation()
+    }
+
+    fn path(&self) -> String {
+        self.method.path()
+    }
+
+    fn parameters(&self) -> Parameters {
+        self.method.parameters()
+    }
+
+    fn header(&self) -> Parameters {
+        let mut header = self.method.header();
+        header.insert(HEADER_TRANSACTION_ID, self.transaction_id.to_owned());
+        header
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        self.method.content()
+    }
+
+    fn is_line_delimited(&self) -> bool {
+        self.method.is_line_delimited()
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.method.timeout()
+    }
+}
@@ -0,0 +1,4 @@
+//! Methods and types for executing transactions.
+
+pub mod methods;
+pub mod types;
@@ -0,0 +1,402 @@
+//! Types used in methods for executing transactions.
+
+#[cfg(test)]
+mod tests;
+
+use std::iter::FromIterator;
+
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
+use rincon_core::api::types::JsonValue;
+
+const TRANSACTION_STATUS_RUNNING: &str = "running";
+const TRANSACTION_STATUS_COMMITTED: &str = "committed";
+const TRANSACTION_STATUS_ABORTED: &str = "aborted";
+
+/// This struct holds the names of the collections that are used in a
+/// transaction grouped by the kind of lock that is acquired on them.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TransactionCollections {
+    /// The collections that are read from within the transaction.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    read: Vec<String>,
+
+    /// The collections that are written to within the transaction.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    write: Vec<String>,
+
+    /// The collections that are exclusively locked by the transaction.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    exclusive: Vec<String>,
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(new_without_default_derive))]
+impl TransactionCollections {
+    /// Constructs a new instance of `TransactionCollections` with no
+    /// collections declared.
+    pub fn new() -> Self {
+        TransactionCollections {
+            read: Vec::new(),
+            write: Vec::new(),
+            exclusive: Vec::new(),
+        }
+    }
+
+    /// Sets the collections that are read from within the transaction.
+    pub fn with_read<R, N>(mut self, read: R) -> Self
+    where
+        R: IntoIterator<Item = N>,
+        N: Into<String>,
+    {
+        self.read = Vec::from_iter(read.into_iter().map(Into::into));
+        self
+    }
+
+    /// Sets the collections that are written to within the transaction.
+    pub fn with_write<W, N>(mut self, write: W) -> Self
+    where
+        W: IntoIterator<Item = N>,
+        N: Into<String>,
+    {
+        self.write = Vec::from_iter(write.into_iter().map(Into::into));
+        self
+    }
+
+    /// Sets the collections that are exclusively locked by the transaction.
+    pub fn with_exclusive<E, N>(mut self, exclusive: E) -> Self
+    where
+        E: IntoIterator<Item = N>,
+        N: Into<String>,
+    {
+        self.exclusive = Vec::from_iter(exclusive.into_iter().map(Into::into));
+        self
+    }
+
+    /// Returns the collections that are read from within the transaction.
+    pub fn read(&self) -> &[String] {
+        &self.read
+    }
+
+    /// Returns the collections that are written to within the transaction.
+    pub fn write(&self) -> &[String] {
+        &self.write
+    }
+
+    /// Returns the collections that are exclusively locked by the
+    /// transaction.
+    pub fn exclusive(&self) -> &[String] {
+        &self.exclusive
+    }
+}
+
+/// This struct defines the parameters of a stream transaction that is to be
+/// started.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewStreamTransaction {
+    /// The collections that are used within the transaction.
+    collections: TransactionCollections,
+
+    /// Whether the transaction shall wait until the data is synchronized to
+    /// disk on commit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    wait_for_sync: Option<bool>,
+
+    /// Whether collections that are not declared in the `collections`
+    /// attribute may be read from implicitly.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allow_implicit: Option<bool>,
+
+    /// The timeout in seconds for waiting on collection locks.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lock_timeout: Option<u64>,
+
+    /// The maximum size of the transaction in bytes.
+    ///
+    /// Honored by the RocksDB storage engine only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_transaction_size: Option<u64>,
+}
+
+impl NewStreamTransaction {
+    /// Constructs a new instance of `NewStreamTransaction` for the given
+    /// collections.
+    ///
+    /// All other attributes are set to their default values by the ArangoDB
+    /// server.
+    pub fn new(collections: TransactionCollections) -> Self {
+        NewStreamTransaction {
+            collections,
+            wait_for_sync: None,
+            allow_implicit: None,
+            lock_timeout: None,
+            max_transaction_size: None,
+        }
+    }
+
+    /// Returns the collections that are used within the transaction.
+    pub fn collections(&self) -> &TransactionCollections {
+        &self.collections
+    }
+
+    /// Sets whether the transaction shall wait until the data is synchronized
+    /// to disk on commit.
+    pub fn set_wait_for_sync<W>(&mut self, wait_for_sync: W)
+    where
+        W: Into<Option<bool>>,
+    {
+        self.wait_for_sync = wait_for_sync.into();
+    }
+
+    /// Returns whether the transaction shall wait until the data is
+    /// synchronized to disk on commit.
+    pub fn is_wait_for_sync(&self) -> Option<bool> {
+        self.wait_for_sync
+    }
+
+    /// Sets whether collections that are not declared may be read from
+    /// implicitly.
+    pub fn set_allow_implicit<A>(&mut self, allow_implicit: A)
+    where
+        A: Into<Option<bool>>,
+    {
+        self.allow_implicit = allow_implicit.into();
+    }
+
+    /// Returns whether collections that are not declared may be read from
+    /// implicitly.
+    pub fn is_allow_implicit(&self) -> Option<bool> {
+        self.allow_implicit
+    }
+
+    /// Sets the timeout in seconds for waiting on collection locks.
+    pub fn set_lock_timeout<T>(&mut self, lock_timeout: T)
+    where
+        T: Into<Option<u64>>,
+    {
+        self.lock_timeout = lock_timeout.into();
+    }
+
+    /// Returns the timeout in seconds for waiting on collection locks.
+    pub fn lock_timeout(&self) -> Option<u64> {
+        self.lock_timeout
+    }
+
+    /// Sets the maximum size of the transaction in bytes.
+    ///
+    /// Honored by the RocksDB storage engine only.
+    pub fn set_max_transaction_size<S>(&mut self, max_transaction_size: S)
+    where
+        S: Into<Option<u64>>,
+    {
+        self.max_transaction_size = max_transaction_size.into();
+    }
+
+    /// Returns the maximum size of the transaction in bytes.
+    pub fn max_transaction_size(&self) -> Option<u64> {
+        self.max_transaction_size
+    }
+}
+
+impl From<TransactionCollections> for NewStreamTransaction {
+    fn from(collections: TransactionCollections) -> Self {
+        NewStreamTransaction::new(collections)
+    }
+}
+
+/// This struct defines the parameters of a JavaScript transaction that is to
+/// be executed on the server.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewTransaction {
+    /// The collections that are used within the transaction.
+    collections: TransactionCollections,
+
+    /// The JavaScript source of the function that is executed within the
+    /// transaction.
+    action: String,
+
+    /// The parameters that are passed to the action function.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    params: Option<JsonValue>,
+
+    /// Whether the transaction shall wait until the data is synchronized to
+    /// disk before returning.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    wait_for_sync: Option<bool>,
+
+    /// The timeout in seconds for waiting on collection locks.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lock_timeout: Option<u64>,
+
+    /// The maximum size of the transaction in bytes.
+    ///
+    /// Honored by the RocksDB storage engine only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_transaction_size: Option<u64>,
+}
+
+impl NewTransaction {
+    /// Constructs a new instance of `NewTransaction` for the given
+    /// collections and action.
+    ///
+    /// All other attributes are set to their default values by the ArangoDB
+    /// server.
+    pub fn new<A>(collections: TransactionCollections, action: A) -> Self
+    where
+        A: Into<String>,
+    {
+        NewTransaction {
+            collections,
+            action: action.into(),
+            params: None,
+            wait_for_sync: None,
+            lock_timeout: None,
+            max_transaction_size: None,
+        }
+    }
+
+    /// Returns the collections that are used within the transaction.
+    pub fn collections(&self) -> &TransactionCollections {
+        &self.collections
+    }
+
+    /// Returns the JavaScript source of the function that is executed within
+    /// the transaction.
+    pub fn action(&self) -> &str {
+        &self.action
+    }
+
+    /// Sets the parameters that are passed to the action function.
+    pub fn set_params<P>(&mut self, params: P)
+    where
+        P: Into<Option<JsonValue>>,
+    {
+        self.params = params.into();
+    }
+
+    /// Returns the parameters that are passed to the action function.
+    pub fn params(&self) -> Option<&JsonValue> {
+        self.params.as_ref()
+    }
+
+    /// Sets whether the transaction shall wait until the data is synchronized
+    /// to disk before returning.
+    pub fn set_wait_for_sync<W>(&mut self, wait_for_sync: W)
+    where
+        W: Into<Option<bool>>,
+    {
+        self.wait_for_sync = wait_for_sync.into();
+    }
+
+    /// Returns whether the transaction shall wait until the data is
+    /// synchronized to disk before returning.
+    pub fn is_wait_for_sync(&self) -> Option<bool> {
+        self.wait_for_sync
+    }
+
+    /// Sets the timeout in seconds for waiting on collection locks.
+    pub fn set_lock_timeout<T>(&mut self, lock_timeout: T)
+    where
+        T: Into<Option<u64>>,
+    {
+        self.lock_timeout = lock_timeout.into();
+    }
+
+    /// Returns the timeout in seconds for waiting on collection locks.
+    pub fn lock_timeout(&self) -> Option<u64> {
+        self.lock_timeout
+    }
+
+    /// Sets the maximum size of the transaction in bytes.
+    ///
+    /// Honored by the RocksDB storage engine only.
+    pub fn set_max_transaction_size<S>(&mut self, max_transaction_size: S)
+    where
+        S: Into<Option<u64>>,
+    {
+        self.max_transaction_size = max_transaction_size.into();
+    }
+
+    /// Returns the maximum size of the transaction in bytes.
+    pub fn max_transaction_size(&self) -> Option<u64> {
+        self.max_transaction_size
+    }
+}
+
+/// This struct holds the id and the status of a stream transaction.
+///
+/// It is returned by the methods `BeginTransaction`, `CommitTransaction`,
+/// `AbortTransaction` and `GetTransactionStatus`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TransactionInfo {
+    /// The id of the transaction.
+    id: String,
+
+    /// The status of the transaction.
+    status: TransactionStatus,
+}
+
+impl TransactionInfo {
+    /// Returns the id of the transaction.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the status of the transaction.
+    pub fn status(&self) -> TransactionStatus {
+        self.status
+    }
+
+    /// Unwraps the id of the transaction.
+    pub fn unwrap_id(self) -> String {
+        self.id
+    }
+}
+
+/// This enum defines the possible states of a stream transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransactionStatus {
+    /// The transaction is still running
+    Running,
+    /// The transaction has been committed
+    Committed,
+    /// The transaction has been aborted
+    Aborted,
+}
+
+impl Serialize for TransactionStatus {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use self::TransactionStatus::*;
+        let status_str = match *self {
+            Running => TRANSACTION_STATUS_RUNNING,
+            Committed => TRANSACTION_STATUS_COMMITTED,
+            Aborted => TRANSACTION_STATUS_ABORTED,
+        };
+        serializer.serialize_str(status_str)
+    }
+}
+
+impl<'de> Deserialize<'de> for TransactionStatus {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use self::TransactionStatus::*;
+        use serde::de::Error;
+        let value = String::deserialize(deserializer)?;
+        match &value[..] {
+            TRANSACTION_STATUS_RUNNING => Ok(Running),
+            TRANSACTION_STATUS_COMMITTED => Ok(Committed),
+            TRANSACTION_STATUS_ABORTED => Ok(Aborted),
+            _ => Err(D::Error::custom(format!(
+                "Unknown transaction status: {:?}",
+                value
+            ))),
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use serde_json;
+
+use super::*;
+use rincon_core::api::types::JsonValue;
+
+#[test]
+fn serialize_new_stream_transaction_with_defaults_to_json() {
+    let new_transaction = NewStreamTransaction::new(
+        TransactionCollections::new()
+            .with_read(vec!["customers"])
+            .with_write(vec!["orders", "invoices"]),
+    );
+    let json_str = serde_json::to_string(&new_transaction).unwrap();
+    assert_eq!(
+        r#"{"collections":{"read":["customers"],"write":["orders","invoices"]}}"#,
+        &json_str
+    );
+}
+
+#[test]
+fn serialize_new_stream_transaction_with_all_options_to_json() {
+    let mut new_transaction =
+        NewStreamTransaction::new(TransactionCollections::new().with_exclusive(vec!["orders"]));
+    new_transaction.set_wait_for_sync(true);
+    new_transaction.set_allow_implicit(false);
+    new_transaction.set_lock_timeout(10);
+    new_transaction.set_max_transaction_size(1_048_576);
+    let json_str = serde_json::to_string(&new_transaction).unwrap();
+    assert_eq!(
+        r#"{"collections":{"exclusive":["orders"]},"waitForSync":true,"allowImplicit":false,"lockTimeout":10,"maxTransactionSize":1048576}"#,
+        &json_str
+    );
+}
+
+#[test]
+fn deserialize_transaction_info_from_json() {
+    let json_str = r#"{"id":"1234","status":"running"}"#;
+    let transaction: TransactionInfo = serde_json::from_str(json_str).unwrap();
+    assert_eq!("1234", transaction.id());
+    assert_eq!(TransactionStatus::Running, transaction.status());
+}
+
+#[test]
+fn deserialize_unknown_transaction_status_fails() {
+    let json_str = r#"{"id":"1234","status":"pending"}"#;
+    let result: Result<TransactionInfo, _> = serde_json::from_str(json_str);
+    assert!(result.is_err());
+}
+
+#[test]
+fn serialize_new_transaction_to_json() {
+    let mut new_transaction = NewTransaction::new(
+        TransactionCollections::new().with_write(vec!["orders"]),
+        "function (params) { return params.x; }",
+    );
+    new_transaction.set_params(JsonValue::from(42));
+    new_transaction.set_wait_for_sync(true);
+    new_transaction.set_lock_timeout(5);
+    let json_str = serde_json::to_string(&new_transaction).unwrap();
+    assert_eq!(
+        r#"{"collections":{"write":["orders"]},"action":"function (params) { return params.x; }","params":42,"waitForSync":true,"lockTimeout":5}"#,
+        &json_str
+    );
+}
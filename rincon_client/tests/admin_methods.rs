@@ -6,6 +6,7 @@ extern crate rincon_core;
 extern crate rincon_test_helper;
 
 use rincon_client::admin::methods::*;
+use rincon_client::admin::types::ServerRole;
 use rincon_core::api::connector::Execute;
 
 use rincon_test_helper::*;
@@ -81,3 +82,34 @@ fn get_server_version_sub_part() {
         |_, _| {},
     );
 }
+
+#[test]
+fn get_engine() {
+    arango_system_db_test(
+        |conn, ref mut core| {
+            let method = GetEngine::new();
+            let work = conn.execute(method);
+            let engine = core.run(work).unwrap();
+
+            assert_eq!("mmfiles", engine.name());
+            assert!(engine.is_mmfiles());
+            assert!(!engine.is_rocksdb());
+            assert!(engine.supports().indexes().contains(&"primary".to_owned()));
+        },
+        |_, _| {},
+    );
+}
+
+#[test]
+fn get_server_role() {
+    arango_system_db_test(
+        |conn, ref mut core| {
+            let method = GetServerRole::new();
+            let work = conn.execute(method);
+            let role = core.run(work).unwrap();
+
+            assert_eq!(ServerRole::Single, role);
+        },
+        |_, _| {},
+    );
+}
@@ -3177,3 +3177,234 @@ fn explain_query_with_simple_enumeration() {
         assert_eq!(explanation, explained_query);
     });
 }
+
+#[test]
+fn create_and_list_aql_functions() {
+    arango_test_with_user_db("test_aql_user1", "test_aql_db11", |conn, ref mut core| {
+        let mut new_function = NewAqlFunction::new(
+            "myfunctions::temperature::celsius_to_fahrenheit",
+            "function (celsius) { return celsius * 1.8 + 32; }",
+        );
+        new_function.set_deterministic(true);
+
+        let method = CreateAqlFunction::new(new_function);
+        let created = core.run(conn.execute(method)).unwrap();
+        assert!(created.is_newly_created());
+
+        let method = ListAqlFunctions::with_namespace("myfunctions::temperature");
+        let functions = core.run(conn.execute(method)).unwrap();
+
+        assert_eq!(1, functions.len());
+        let function = &functions[0];
+        assert_eq!(
+            "myfunctions::temperature::celsius_to_fahrenheit",
+            function.name()
+        );
+        assert_eq!("myfunctions::temperature", function.namespace());
+        assert_eq!(
+            "function (celsius) { return celsius * 1.8 + 32; }",
+            function.code()
+        );
+    });
+}
+
+#[test]
+fn replace_existing_aql_function() {
+    arango_test_with_user_db("test_aql_user2", "test_aql_db21", |conn, ref mut core| {
+        let method = CreateAqlFunction::new(NewAqlFunction::new(
+            "myfunctions::double",
+            "function (value) { return value + value; }",
+        ));
+        core.run(conn.execute(method)).unwrap();
+
+        let method = CreateAqlFunction::new(NewAqlFunction::new(
+            "myfunctions::double",
+            "function (value) { return value * 2; }",
+        ));
+        let replaced = core.run(conn.execute(method)).unwrap();
+        assert!(!replaced.is_newly_created());
+
+        let functions = core.run(conn.execute(ListAqlFunctions::new())).unwrap();
+        assert_eq!(1, functions.len());
+        assert_eq!(
+            "function (value) { return value * 2; }",
+            functions[0].code()
+        );
+    });
+}
+
+#[test]
+fn delete_aql_function_by_name() {
+    arango_test_with_user_db("test_aql_user3", "test_aql_db31", |conn, ref mut core| {
+        for name in &["myfunctions::one", "myfunctions::two"] {
+            let method =
+                CreateAqlFunction::new(NewAqlFunction::new(*name, "function () { return 1; }"));
+            core.run(conn.execute(method)).unwrap();
+        }
+
+        let method = DeleteAqlFunction::with_name("myfunctions::one");
+        core.run(conn.execute(method)).unwrap();
+
+        let functions = core.run(conn.execute(ListAqlFunctions::new())).unwrap();
+        assert_eq!(1, functions.len());
+        assert_eq!("myfunctions::two", functions[0].name());
+    });
+}
+
+#[test]
+fn delete_aql_functions_of_namespace() {
+    arango_test_with_user_db("test_aql_user4", "test_aql_db41", |conn, ref mut core| {
+        for name in &["myfunctions::one", "myfunctions::two", "other::three"] {
+            let method =
+                CreateAqlFunction::new(NewAqlFunction::new(*name, "function () { return 1; }"));
+            core.run(conn.execute(method)).unwrap();
+        }
+
+        let method = DeleteAqlFunction::with_namespace("myfunctions");
+        core.run(conn.execute(method)).unwrap();
+
+        let functions = core.run(conn.execute(ListAqlFunctions::new())).unwrap();
+        assert_eq!(1, functions.len());
+        assert_eq!("other::three", functions[0].name());
+    });
+}
+
+#[test]
+fn delete_not_existing_aql_function() {
+    arango_test_with_user_db("test_aql_user5", "test_aql_db51", |conn, ref mut core| {
+        let method = DeleteAqlFunction::with_name("myfunctions::unknown");
+        let result = core.run(conn.execute(method));
+
+        match result {
+            Err(Error::Method(error)) => {
+                assert_eq!(ErrorCode::QueryFunctionNotFound, error.error_code())
+            },
+            _ => panic!("Error expected, but got: {:?}", &result),
+        }
+    });
+}
+
+#[test]
+fn get_query_tracking_properties() {
+    arango_test_with_user_db("test_aql_user6", "test_aql_db61", |conn, ref mut core| {
+        let method = GetQueryTrackingProperties::new();
+        let properties = core.run(conn.execute(method)).unwrap();
+
+        assert!(properties.is_enabled());
+        assert!(properties.is_track_slow_queries());
+        assert!(properties.max_slow_queries() > 0);
+        assert!(properties.slow_query_threshold() > 0.);
+    });
+}
+
+#[test]
+fn change_query_tracking_properties() {
+    arango_test_with_user_db("test_aql_user7", "test_aql_db71", |conn, ref mut core| {
+        let mut updates = QueryTrackingPropertiesUpdate::new();
+        updates.set_max_slow_queries(42);
+        updates.set_max_query_string_length(1024);
+
+        let method = ChangeQueryTrackingProperties::new(updates);
+        let properties = core.run(conn.execute(method)).unwrap();
+
+        assert_eq!(42, properties.max_slow_queries());
+        assert_eq!(1024, properties.max_query_string_length());
+        assert!(properties.is_enabled());
+    });
+}
+
+#[test]
+fn list_current_queries_while_no_query_is_running() {
+    arango_test_with_user_db("test_aql_user8", "test_aql_db81", |conn, ref mut core| {
+        let method = ListCurrentQueries::new();
+        let queries = core.run(conn.execute(method)).unwrap();
+
+        assert!(queries.is_empty());
+    });
+}
+
+#[test]
+fn list_and_clear_slow_queries() {
+    arango_test_with_user_db("test_aql_user9", "test_aql_db91", |conn, ref mut core| {
+        let mut updates = QueryTrackingPropertiesUpdate::new();
+        updates.set_slow_query_threshold(0.1);
+        core.run(conn.execute(ChangeQueryTrackingProperties::new(updates)))
+            .unwrap();
+
+        let query = Query::new("RETURN SLEEP(0.2)");
+        core.run(conn.execute(CreateCursor::<JsonValue>::from_query(query)))
+            .unwrap();
+
+        let slow_queries = core.run(conn.execute(ListSlowQueries::new())).unwrap();
+        assert_eq!(1, slow_queries.len());
+        let slow_query = &slow_queries[0];
+        assert_eq!("RETURN SLEEP(0.2)", slow_query.query());
+        assert_eq!(&QueryState::Finished, slow_query.state());
+        assert!(slow_query.run_time() >= 0.2);
+
+        core.run(conn.execute(ClearSlowQueries::new())).unwrap();
+
+        let slow_queries = core.run(conn.execute(ListSlowQueries::new())).unwrap();
+        assert!(slow_queries.is_empty());
+    });
+}
+
+#[test]
+fn kill_not_existing_query() {
+    arango_test_with_user_db("test_aql_user10", "test_aql_db101", |conn, ref mut core| {
+        let method = KillQuery::new("999999999");
+        let result = core.run(conn.execute(method));
+
+        match result {
+            Err(Error::Method(error)) => assert_eq!(ErrorCode::QueryNotFound, error.error_code()),
+            _ => panic!("Error expected, but got: {:?}", &result),
+        }
+    });
+}
+
+#[test]
+fn get_query_cache_properties() {
+    arango_system_db_test(
+        |conn, ref mut core| {
+            let method = GetQueryCacheProperties::new();
+            let properties = core.run(conn.execute(method)).unwrap();
+
+            assert!(properties.max_results() > 0);
+        },
+        |_, _| {},
+    );
+}
+
+#[test]
+fn change_query_cache_properties() {
+    arango_system_db_test(
+        |conn, ref mut core| {
+            let mut updates = QueryCachePropertiesUpdate::new();
+            updates.set_mode(QueryCacheMode::Demand);
+            updates.set_max_results(64);
+
+            let method = ChangeQueryCacheProperties::new(updates);
+            let properties = core.run(conn.execute(method)).unwrap();
+
+            assert_eq!(QueryCacheMode::Demand, properties.mode());
+            assert_eq!(64, properties.max_results());
+        },
+        |conn, ref mut core| {
+            let mut updates = QueryCachePropertiesUpdate::new();
+            updates.set_mode(QueryCacheMode::Off);
+            updates.set_max_results(128);
+            core.run(conn.execute(ChangeQueryCacheProperties::new(updates)))
+                .unwrap();
+        },
+    );
+}
+
+#[test]
+fn clear_query_cache() {
+    arango_test_with_user_db("test_aql_user11", "test_aql_db111", |conn, ref mut core| {
+        let method = ClearQueryCache::new();
+        let result = core.run(conn.execute(method));
+
+        assert!(result.is_ok());
+    });
+}
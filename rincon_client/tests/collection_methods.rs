@@ -1,3 +1,5 @@
+#[macro_use]
+extern crate serde_json;
 extern crate tokio_core;
 
 extern crate rincon_client;
@@ -5,8 +7,11 @@ extern crate rincon_connector;
 extern crate rincon_core;
 extern crate rincon_test_helper;
 
+use rincon_client::admin::methods::GetEngine;
 use rincon_client::collection::methods::*;
 use rincon_client::collection::types::*;
+use rincon_client::document::methods::InsertDocument;
+use rincon_client::document::types::NewDocument;
 use rincon_core::api::connector::{Error, Execute};
 use rincon_core::api::ErrorCode;
 
@@ -25,8 +30,7 @@ fn create_collection_with_default_properties() {
         assert!(!collection.is_system());
         assert!(!collection.is_wait_for_sync());
 
-        #[cfg(feature = "mmfiles")]
-        assert!(!collection.is_volatile());
+        assert_ne!(Some(true), collection.is_volatile());
     });
 }
 
@@ -46,8 +50,7 @@ fn create_edge_collection_with_wait_for_sync() {
         assert!(!collection.is_system());
         assert!(collection.is_wait_for_sync());
 
-        #[cfg(feature = "mmfiles")]
-        assert!(!collection.is_volatile());
+        assert_ne!(Some(true), collection.is_volatile());
     });
 }
 
@@ -175,6 +178,8 @@ fn get_collection_properties_should_return_collection_properties() {
             .run(conn.execute(CreateCollection::edges_with_name("test_collection2")))
             .unwrap();
 
+        let engine = core.run(conn.execute(GetEngine::new())).unwrap();
+
         let method = GetCollectionProperties::with_name("test_collection1");
         let work = conn.execute(method);
         let collection = core.run(work).unwrap();
@@ -191,12 +196,13 @@ fn get_collection_properties_should_return_collection_properties() {
         assert_eq!(0, collection.key_options().last_value());
         assert!(!collection.is_wait_for_sync());
 
-        #[cfg(feature = "mmfiles")]
-        assert!(!collection.is_volatile());
-        #[cfg(feature = "mmfiles")]
-        assert!(collection.is_do_compact());
-        #[cfg(feature = "mmfiles")]
-        assert_eq!(8, collection.index_buckets());
+        if engine.is_mmfiles() {
+            assert_eq!(Some(false), collection.is_volatile());
+            assert_eq!(Some(true), collection.is_do_compact());
+            assert_eq!(Some(8), collection.index_buckets());
+        } else {
+            assert_eq!(None, collection.index_buckets());
+        }
     });
 }
 
@@ -234,6 +240,7 @@ fn change_collection_properties_wait_for_sync() {
         "test_coll_user10",
         "test_coll_db101",
         |conn, ref mut core| {
+            let engine = core.run(conn.execute(GetEngine::new())).unwrap();
             let _ = core
                 .run(conn.execute(CreateCollection::documents_with_name("test_collection1")))
                 .unwrap();
@@ -243,8 +250,9 @@ fn change_collection_properties_wait_for_sync() {
 
             assert_eq!("test_collection1", original.name());
             assert!(!original.is_wait_for_sync());
-            #[cfg(feature = "mmfiles")]
-            assert_eq!(32 * 1024 * 1024, original.journal_size());
+            if engine.is_mmfiles() {
+                assert_eq!(Some(32 * 1024 * 1024), original.journal_size());
+            }
 
             let mut updates = CollectionPropertiesUpdate::new();
             updates.set_wait_for_sync(Some(true));
@@ -254,19 +262,24 @@ fn change_collection_properties_wait_for_sync() {
 
             assert_eq!("test_collection1", updated.name());
             assert!(updated.is_wait_for_sync());
-            #[cfg(feature = "mmfiles")]
-            assert_eq!(32 * 1024 * 1024, updated.journal_size());
+            if engine.is_mmfiles() {
+                assert_eq!(Some(32 * 1024 * 1024), updated.journal_size());
+            }
         },
     );
 }
 
-#[cfg(feature = "mmfiles")]
 #[test]
 fn change_collection_properties_journal_size() {
     arango_test_with_user_db(
         "test_coll_user11",
         "test_coll_db111",
         |conn, ref mut core| {
+            let engine = core.run(conn.execute(GetEngine::new())).unwrap();
+            if !engine.is_mmfiles() {
+                // the journal size is only supported by the MMFiles engine
+                return;
+            }
             let _ = core
                 .run(conn.execute(CreateCollection::documents_with_name("test_collection1")))
                 .unwrap();
@@ -276,7 +289,7 @@ fn change_collection_properties_journal_size() {
 
             assert_eq!("test_collection1", original.name());
             assert!(!original.is_wait_for_sync());
-            assert_eq!(32 * 1024 * 1024, original.journal_size());
+            assert_eq!(Some(32 * 1024 * 1024), original.journal_size());
 
             let mut updates = CollectionPropertiesUpdate::new();
             updates.set_journal_size(Some(128 * 1024 * 1024));
@@ -286,7 +299,7 @@ fn change_collection_properties_journal_size() {
 
             assert_eq!("test_collection1", updated.name());
             assert!(!updated.is_wait_for_sync());
-            assert_eq!(128 * 1024 * 1024, updated.journal_size());
+            assert_eq!(Some(128 * 1024 * 1024), updated.journal_size());
         },
     );
 }
@@ -362,3 +375,123 @@ fn get_collection_revision_of_new_collection() {
         },
     );
 }
+
+#[test]
+fn get_collection_count_and_truncate_collection() {
+    arango_test_with_user_db(
+        "test_coll_user15",
+        "test_coll_db151",
+        |conn, ref mut core| {
+            let _ = core
+                .run(conn.execute(CreateCollection::documents_with_name("test_collection1")))
+                .unwrap();
+            for name in &["Jane", "John", "Mary"] {
+                let new_document = NewDocument::from_content(json!({ "name": name }));
+                let _ = core
+                    .run(conn.execute(InsertDocument::new("test_collection1", new_document)))
+                    .unwrap();
+            }
+
+            let count = core
+                .run(conn.execute(GetCollectionCount::with_name("test_collection1")))
+                .unwrap();
+            assert_eq!(3, count);
+
+            let truncated = core
+                .run(conn.execute(TruncateCollection::with_name("test_collection1")))
+                .unwrap();
+            assert_eq!("test_collection1", truncated.name());
+
+            let count = core
+                .run(conn.execute(GetCollectionCount::with_name("test_collection1")))
+                .unwrap();
+            assert_eq!(0, count);
+        },
+    );
+}
+
+#[test]
+fn get_collection_figures_of_new_collection() {
+    arango_test_with_user_db(
+        "test_coll_user16",
+        "test_coll_db161",
+        |conn, ref mut core| {
+            let engine = core.run(conn.execute(GetEngine::new())).unwrap();
+            let _ = core
+                .run(conn.execute(CreateCollection::documents_with_name("test_collection1")))
+                .unwrap();
+
+            let method = GetCollectionFigures::with_name("test_collection1");
+            let work = conn.execute(method);
+            let figures = core.run(work).unwrap();
+
+            assert_eq!(1, figures.indexes().count());
+            if engine.is_mmfiles() {
+                assert_eq!(Some(0), figures.alive().map(SizeFigures::count));
+                assert_eq!(Some(0), figures.dead().map(DeadFigures::count));
+            }
+        },
+    );
+}
+
+#[test]
+fn get_collection_checksum_with_revisions_and_data() {
+    arango_test_with_user_db(
+        "test_coll_user17",
+        "test_coll_db171",
+        |conn, ref mut core| {
+            let _ = core
+                .run(conn.execute(CreateCollection::documents_with_name("test_collection1")))
+                .unwrap();
+            let empty = core
+                .run(conn.execute(GetCollectionChecksum::with_name("test_collection1")))
+                .unwrap();
+            assert_eq!("test_collection1", empty.name());
+            assert_eq!("0", empty.checksum());
+
+            let new_document = NewDocument::from_content(json!({ "name": "Jane" }));
+            let _ = core
+                .run(conn.execute(InsertDocument::new("test_collection1", new_document)))
+                .unwrap();
+
+            let mut method = GetCollectionChecksum::with_name("test_collection1");
+            method.set_with_revisions(true);
+            method.set_with_data(true);
+            let checksum = core.run(conn.execute(method)).unwrap();
+
+            assert_eq!("test_collection1", checksum.name());
+            assert_ne!("0", checksum.checksum());
+            assert_ne!("0", checksum.revision());
+        },
+    );
+}
+
+#[test]
+fn unload_and_load_collection() {
+    arango_test_with_user_db(
+        "test_coll_user18",
+        "test_coll_db181",
+        |conn, ref mut core| {
+            let _ = core
+                .run(conn.execute(CreateCollection::documents_with_name("test_collection1")))
+                .unwrap();
+
+            let unloaded = core
+                .run(conn.execute(UnloadCollection::with_name("test_collection1")))
+                .unwrap();
+            assert_eq!("test_collection1", unloaded.name());
+            assert_ne!(CollectionStatus::Loaded, unloaded.status());
+
+            let loaded = core
+                .run(conn.execute(LoadCollection::with_name("test_collection1")))
+                .unwrap();
+            assert_eq!("test_collection1", loaded.name());
+            assert_eq!(CollectionStatus::Loaded, loaded.status());
+
+            let indexes_loaded = core
+                .run(conn.execute(LoadIndexesIntoMemory::with_name("test_collection1")))
+                .unwrap();
+            assert!(indexes_loaded);
+        },
+    );
+}
@@ -2447,3 +2447,89 @@ fn delete_multiple_documents_by_headers_return_old() {
         }
     });
 }
+
+#[test]
+fn import_struct_documents() {
+    arango_test_with_document_collection("customers188", |conn, ref mut core| {
+        let customer1 = Customer {
+            name: "Jane Doe".to_owned(),
+            contact: vec![Contact {
+                address: "1-555-234523".to_owned(),
+                kind: ContactType::Phone,
+                tag: Some(Tag("work".to_owned())),
+            }],
+            gender: Gender::Female,
+            age: 42,
+            active: true,
+            groups: vec![],
+        };
+        let customer2 = Customer {
+            name: "John Doe".to_owned(),
+            contact: vec![Contact {
+                address: "john.doe@mail.com".to_owned(),
+                kind: ContactType::Email,
+                tag: Some(Tag("work".to_owned())),
+            }],
+            gender: Gender::Male,
+            age: 27,
+            active: true,
+            groups: vec![],
+        };
+
+        let method = ImportDocuments::new("customers188", vec![customer1.clone(), customer2])
+            .with_options(ImportOptions::new().with_complete(true));
+        let result = core.run(conn.execute(method)).unwrap();
+
+        assert_eq!(2, result.created());
+        assert_eq!(0, result.errors());
+        assert_eq!(0, result.empty());
+        assert_eq!(0, result.updated());
+        assert_eq!(0, result.ignored());
+
+        let method = ImportDocuments::new("customers188", vec![customer1])
+            .with_import_type(ImportType::List);
+        let result = core.run(conn.execute(method)).unwrap();
+
+        assert_eq!(1, result.created());
+    });
+}
+
+#[test]
+fn import_documents_with_header_row_and_details() {
+    arango_test_with_document_collection("customers189", |conn, ref mut core| {
+        let method = ImportDocuments::with_header_row(
+            "customers189",
+            vec!["_key", "name", "age"],
+            vec![("jane", "Jane Doe", 42), ("john", "John Doe", 27)],
+        );
+        let result = core.run(conn.execute(method)).unwrap();
+
+        assert_eq!(2, result.created());
+        assert_eq!(0, result.errors());
+
+        let method = ImportDocuments::with_header_row(
+            "customers189",
+            vec!["_key", "name", "age"],
+            vec![("jane", "Jane Doe", 43), ("jim", "Jim Doe", 18)],
+        ).with_options(
+            ImportOptions::new()
+                .with_on_duplicate(OnDuplicate::Error)
+                .with_details(true),
+        );
+        let result = core.run(conn.execute(method)).unwrap();
+
+        assert_eq!(1, result.created());
+        assert_eq!(1, result.errors());
+        assert_eq!(1, result.details().len());
+
+        let method = ImportDocuments::with_header_row(
+            "customers189",
+            vec!["_key", "age"],
+            vec![("jane", 44)],
+        ).with_options(ImportOptions::new().with_on_duplicate(OnDuplicate::Update));
+        let result = core.run(conn.execute(method)).unwrap();
+
+        assert_eq!(0, result.created());
+        assert_eq!(1, result.updated());
+    });
+}
@@ -7,6 +7,8 @@ extern crate rincon_connector;
 extern crate rincon_core;
 extern crate rincon_test_helper;
 
+use rincon_client::admin::methods::{GetServerRole, GetServerVersion};
+use rincon_client::admin::types::ServerRole;
 use rincon_client::document::types::NewDocument;
 use rincon_client::graph::methods::*;
 use rincon_client::graph::types::*;
@@ -27,10 +29,7 @@ fn create_graph() {
                 vec!["Groups".to_owned()],
             )];
 
-            #[cfg(not(feature = "enterprise"))]
             let new_graph = NewGraph::new("test_graph1", edge_defs.clone());
-            #[cfg(feature = "enterprise")]
-            let new_graph = NewGraph::new("test_graph1", edge_defs.clone(), false);
 
             let method = CreateGraph::new(new_graph);
             let graph = core.run(conn.execute(method)).unwrap();
@@ -43,28 +42,33 @@ fn create_graph() {
             assert_eq!(edge_defs, graph.edge_definitions());
             assert_eq!(0, graph.orphan_collections().len());
 
-            #[cfg(feature = "enterprise")]
-            assert_eq!(false, graph.is_smart());
-            #[cfg(feature = "enterprise")]
-            assert_eq!("", graph.smart_graph_attribute());
+            assert_ne!(Some(true), graph.is_smart());
+            assert!(graph.smart_graph_attribute().map_or(true, str::is_empty));
         },
     );
 }
 
-#[cfg(feature = "enterprise")]
 #[test]
 fn create_smart_graph() {
     arango_test_with_user_db(
         "test_graph_user11",
         "test_graph_db11",
         |conn, ref mut core| {
+            let version = core.run(conn.execute(GetServerVersion::new())).unwrap();
+            let role = core.run(conn.execute(GetServerRole::new())).unwrap();
+            if version.license() != "enterprise" || role != ServerRole::Coordinator {
+                // smart graphs are only supported by an enterprise cluster
+                return;
+            }
+
             let edge_defs = vec![EdgeDefinition::new(
                 "UsersInGroups",
                 vec!["Users".to_owned()],
                 vec!["Groups".to_owned()],
             )];
 
-            let mut new_graph = NewGraph::new("test_graph1", edge_defs.clone(), true);
+            let mut new_graph = NewGraph::new("test_graph1", edge_defs.clone());
+            new_graph.set_smart(true);
             new_graph
                 .options_mut()
                 .set_smart_graph_attribute("knows".to_owned());
@@ -81,8 +85,8 @@ fn create_smart_graph() {
             assert_eq!(edge_defs, graph.edge_definitions());
             assert_eq!(0, graph.orphan_collections().len());
 
-            assert_eq!(true, graph.is_smart());
-            assert_eq!("knows", graph.smart_graph_attribute());
+            assert_eq!(Some(true), graph.is_smart());
+            assert_eq!(Some("knows"), graph.smart_graph_attribute());
         },
     );
 }
@@ -106,10 +110,7 @@ fn create_graph_with_2_edge_definitions() {
                 ),
             ];
 
-            #[cfg(not(feature = "enterprise"))]
             let new_graph = NewGraph::new("test_graph1", edge_defs.clone());
-            #[cfg(feature = "enterprise")]
-            let new_graph = NewGraph::new("test_graph1", edge_defs.clone(), false);
 
             let method = CreateGraph::new(new_graph);
             let graph = core.run(conn.execute(method)).unwrap();
@@ -122,10 +123,8 @@ fn create_graph_with_2_edge_definitions() {
             assert_eq!(edge_defs, graph.edge_definitions());
             assert_eq!(0, graph.orphan_collections().len());
 
-            #[cfg(feature = "enterprise")]
-            assert_eq!(false, graph.is_smart());
-            #[cfg(feature = "enterprise")]
-            assert_eq!("", graph.smart_graph_attribute());
+            assert_ne!(Some(true), graph.is_smart());
+            assert!(graph.smart_graph_attribute().map_or(true, str::is_empty));
         },
     );
 }
@@ -141,10 +140,7 @@ fn drop_graph() {
                 vec!["Users".to_owned()],
                 vec!["Groups".to_owned()],
             )];
-            #[cfg(not(feature = "enterprise"))]
             let new_graph = NewGraph::new("test_graph1", edge_defs);
-            #[cfg(feature = "enterprise")]
-            let new_graph = NewGraph::new("test_graph1", edge_defs, false);
             let graph = core.run(conn.execute(CreateGraph::new(new_graph))).unwrap();
             assert_eq!("_graphs/test_graph1".to_owned(), graph.id().to_string());
 
@@ -167,10 +163,7 @@ fn get_graph() {
                 vec!["Users".to_owned()],
                 vec!["Groups".to_owned()],
             )];
-            #[cfg(not(feature = "enterprise"))]
             let new_graph = NewGraph::new("test_graph1", edge_defs);
-            #[cfg(feature = "enterprise")]
-            let new_graph = NewGraph::new("test_graph1", edge_defs, false);
             let created = core.run(conn.execute(CreateGraph::new(new_graph))).unwrap();
             assert_eq!("_graphs/test_graph1".to_owned(), created.id().to_string());
 
@@ -193,10 +186,7 @@ fn list_graphs() {
                 vec!["Users".to_owned()],
                 vec!["Groups".to_owned()],
             )];
-            #[cfg(not(feature = "enterprise"))]
             let new_graph1 = NewGraph::new("test_graph1", edge_defs1);
-            #[cfg(feature = "enterprise")]
-            let new_graph1 = NewGraph::new("test_graph1", edge_defs1, false);
             let created1 = core
                 .run(conn.execute(CreateGraph::new(new_graph1)))
                 .unwrap();
@@ -207,10 +197,7 @@ fn list_graphs() {
                 vec!["Users".to_owned()],
                 vec!["Users".to_owned()],
             )];
-            #[cfg(not(feature = "enterprise"))]
             let new_graph2 = NewGraph::new("test_graph2", edge_defs2);
-            #[cfg(feature = "enterprise")]
-            let new_graph2 = NewGraph::new("test_graph2", edge_defs2, false);
             let created2 = core
                 .run(conn.execute(CreateGraph::new(new_graph2)))
                 .unwrap();
@@ -238,10 +225,7 @@ fn add_vertex_collection() {
                 vec!["Users".to_owned()],
                 vec!["Groups".to_owned()],
             )];
-            #[cfg(not(feature = "enterprise"))]
             let new_graph = NewGraph::new("test_graph1", edge_defs);
-            #[cfg(feature = "enterprise")]
-            let new_graph = NewGraph::new("test_graph1", edge_defs, false);
             let created = core.run(conn.execute(CreateGraph::new(new_graph))).unwrap();
             assert_eq!("_graphs/test_graph1".to_owned(), created.id().to_string());
             assert_eq!(0, created.orphan_collections().len());
@@ -273,12 +257,8 @@ fn remove_vertex_collection() {
                 vec!["Users".to_owned()],
                 vec!["Groups".to_owned()],
             )];
-            #[cfg(not(feature = "enterprise"))]
             let new_graph = NewGraph::new("test_graph1", edge_defs)
                 .with_orphan_collections(vec!["add_ons".to_owned(), "spare".to_owned()]);
-            #[cfg(feature = "enterprise")]
-            let new_graph = NewGraph::new("test_graph1", edge_defs, false)
-                .with_orphan_collections(vec!["add_ons".to_owned(), "spare".to_owned()]);
             let created = core.run(conn.execute(CreateGraph::new(new_graph))).unwrap();
             assert_eq!("_graphs/test_graph1".to_owned(), created.id().to_string());
             assert_eq!(2, created.orphan_collections().len());
@@ -305,12 +285,8 @@ fn list_vertex_collections() {
                 vec!["Users".to_owned()],
                 vec!["Groups".to_owned()],
             )];
-            #[cfg(not(feature = "enterprise"))]
             let new_graph = NewGraph::new("test_graph1", edge_defs)
                 .with_orphan_collections(vec!["add_ons".to_owned(), "spare".to_owned()]);
-            #[cfg(feature = "enterprise")]
-            let new_graph = NewGraph::new("test_graph1", edge_defs, false)
-                .with_orphan_collections(vec!["add_ons".to_owned(), "spare".to_owned()]);
             let created = core.run(conn.execute(CreateGraph::new(new_graph))).unwrap();
             assert_eq!("_graphs/test_graph1".to_owned(), created.id().to_string());
 
@@ -337,10 +313,7 @@ fn insert_vertex() {
                 vec!["female".to_owned(), "male".to_owned()],
                 vec!["city".to_owned()],
             )];
-            #[cfg(not(feature = "enterprise"))]
             let new_graph = NewGraph::new("social", edge_defs);
-            #[cfg(feature = "enterprise")]
-            let new_graph = NewGraph::new("social", edge_defs, false);
             let created = core.run(conn.execute(CreateGraph::new(new_graph))).unwrap();
             assert_eq!("_graphs/social".to_owned(), created.id().to_string());
 
@@ -373,10 +346,7 @@ fn add_edge_definition() {
                 vec!["female".to_owned(), "male".to_owned()],
                 vec!["male".to_owned(), "female".to_owned()],
             )];
-            #[cfg(not(feature = "enterprise"))]
             let new_graph = NewGraph::new("social", edge_defs);
-            #[cfg(feature = "enterprise")]
-            let new_graph = NewGraph::new("social", edge_defs, false);
             let created = core.run(conn.execute(CreateGraph::new(new_graph))).unwrap();
             assert_eq!("_graphs/social".to_owned(), created.id().to_string());
 
@@ -427,10 +397,7 @@ fn remove_edge_definition() {
                     vec!["city".to_owned()],
                 ),
             ];
-            #[cfg(not(feature = "enterprise"))]
             let new_graph = NewGraph::new("social", edge_defs);
-            #[cfg(feature = "enterprise")]
-            let new_graph = NewGraph::new("social", edge_defs, false);
             let created = core.run(conn.execute(CreateGraph::new(new_graph))).unwrap();
             assert_eq!("_graphs/social".to_owned(), created.id().to_string());
 
@@ -462,10 +429,7 @@ fn replace_edge_definition() {
                 vec!["female".to_owned(), "male".to_owned()],
                 vec!["male".to_owned(), "female".to_owned()],
             )];
-            #[cfg(not(feature = "enterprise"))]
             let new_graph = NewGraph::new("social", edge_defs);
-            #[cfg(feature = "enterprise")]
-            let new_graph = NewGraph::new("social", edge_defs, false);
             let created = core.run(conn.execute(CreateGraph::new(new_graph))).unwrap();
             assert_eq!("_graphs/social".to_owned(), created.id().to_string());
 
@@ -509,10 +473,7 @@ fn list_edge_collections() {
                     vec!["city".to_owned()],
                 ),
             ];
-            #[cfg(not(feature = "enterprise"))]
             let new_graph = NewGraph::new("social", edge_defs);
-            #[cfg(feature = "enterprise")]
-            let new_graph = NewGraph::new("social", edge_defs, false);
             let created = core.run(conn.execute(CreateGraph::new(new_graph))).unwrap();
             assert_eq!("_graphs/social".to_owned(), created.id().to_string());
 
@@ -537,10 +498,7 @@ fn insert_edge() {
                 vec!["female".to_owned(), "male".to_owned()],
                 vec!["city".to_owned()],
             )];
-            #[cfg(not(feature = "enterprise"))]
             let new_graph = NewGraph::new("social", edge_defs);
-            #[cfg(feature = "enterprise")]
-            let new_graph = NewGraph::new("social", edge_defs, false);
             let created = core.run(conn.execute(CreateGraph::new(new_graph))).unwrap();
             assert_eq!("_graphs/social".to_owned(), created.id().to_string());
 
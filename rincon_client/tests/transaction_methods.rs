@@ -0,0 +1,137 @@
+#![cfg_attr(feature = "cargo-clippy", allow(cyclomatic_complexity))]
+
+extern crate tokio_core;
+
+extern crate rincon_client;
+extern crate rincon_connector;
+extern crate rincon_core;
+extern crate rincon_test_helper;
+
+use std::time::Duration;
+
+use rincon_client::document::methods::*;
+use rincon_client::document::types::*;
+use rincon_client::transaction::methods::*;
+use rincon_client::transaction::types::*;
+use rincon_core::api::connector::{Error, Execute};
+use rincon_core::api::method::{Prepare, WithTimeout};
+use rincon_core::api::types::{JsonString, JsonValue};
+use rincon_core::api::ErrorCode;
+
+use rincon_test_helper::*;
+
+#[test]
+fn in_transaction_forwards_line_delimited_content_of_method() {
+    let import = ImportDocuments::new("customers", Vec::<JsonValue>::new());
+
+    let method = InTransaction::new("4711", import);
+
+    assert!(method.is_line_delimited());
+}
+
+#[test]
+fn in_transaction_forwards_timeout_of_method() {
+    let get = GetDocument::<JsonValue>::new("customers", DocumentKey::new("4711"));
+    let get = WithTimeout::new(get, Duration::from_secs(3));
+
+    let method = InTransaction::new("4711", get);
+
+    assert_eq!(Some(Duration::from_secs(3)), method.timeout());
+}
+
+#[test]
+fn begin_and_commit_stream_transaction() {
+    arango_test_with_document_collection("transaction_customers01", |conn, ref mut core| {
+        let collections = TransactionCollections::new().with_write(vec!["transaction_customers01"]);
+        let method = BeginTransaction::with_collections(collections);
+        let transaction = core.run(conn.execute(method)).unwrap();
+
+        assert_eq!(TransactionStatus::Running, transaction.status());
+
+        let method = GetTransactionStatus::with_id(transaction.id());
+        let status = core.run(conn.execute(method)).unwrap();
+
+        assert_eq!(transaction.id(), status.id());
+        assert_eq!(TransactionStatus::Running, status.status());
+
+        let method = CommitTransaction::with_id(transaction.id());
+        let committed = core.run(conn.execute(method)).unwrap();
+
+        assert_eq!(transaction.id(), committed.id());
+        assert_eq!(TransactionStatus::Committed, committed.status());
+    });
+}
+
+#[test]
+fn insert_document_within_stream_transaction_and_abort_it() {
+    arango_test_with_document_collection("transaction_customers02", |conn, ref mut core| {
+        let collections = TransactionCollections::new().with_write(vec!["transaction_customers02"]);
+        let method = BeginTransaction::with_collections(collections);
+        let transaction = core.run(conn.execute(method)).unwrap();
+
+        let new_document = NewDocument::from_content(JsonString::from_str_unchecked(
+            r#"{"name":"Jane Doe","age":42}"#,
+        ));
+        let method = InTransaction::new(
+            transaction.id(),
+            InsertDocument::new("transaction_customers02", new_document),
+        );
+        let header = core.run(conn.execute(method)).unwrap();
+        let (document_id, _, _) = header.deconstruct();
+
+        let method = AbortTransaction::with_id(transaction.id());
+        let aborted = core.run(conn.execute(method)).unwrap();
+
+        assert_eq!(TransactionStatus::Aborted, aborted.status());
+
+        let method = GetDocument::<JsonString>::with_id(document_id);
+        let result = core.run(conn.execute(method));
+
+        match result {
+            Err(Error::Method(error)) => {
+                assert_eq!(404, error.status_code());
+                assert_eq!(ErrorCode::ArangoDocumentNotFound, error.error_code());
+            },
+            _ => panic!("Error expected, but got: {:?}", &result),
+        }
+    });
+}
+
+#[test]
+fn execute_transaction_returning_the_sum_of_params() {
+    arango_test_with_document_collection("transaction_customers03", |conn, ref mut core| {
+        let mut new_transaction = NewTransaction::new(
+            TransactionCollections::new().with_read(vec!["transaction_customers03"]),
+            "function (params) { return params[0] + params[1]; }",
+        );
+        new_transaction.set_params(JsonValue::from(vec![17, 25]));
+        let method = ExecuteTransaction::<u64>::new(new_transaction);
+        let result = core.run(conn.execute(method)).unwrap();
+
+        assert_eq!(42, result);
+    });
+}
+
+#[test]
+fn execute_transaction_inserting_a_document() {
+    arango_test_with_document_collection("transaction_customers04", |conn, ref mut core| {
+        let new_transaction = NewTransaction::new(
+            TransactionCollections::new().with_write(vec!["transaction_customers04"]),
+            "function () { \
+             var db = require('@arangodb').db; \
+             db.transaction_customers04.save({ _key: 'jane', name: 'Jane Doe' }); \
+             return db.transaction_customers04.count(); \
+             }",
+        );
+        let method = ExecuteTransaction::<u64>::new(new_transaction);
+        let count = core.run(conn.execute(method)).unwrap();
+
+        assert_eq!(1, count);
+
+        let method =
+            GetDocument::<JsonValue>::with_id(DocumentId::new("transaction_customers04", "jane"));
+        let document = core.run(conn.execute(method)).unwrap();
+
+        assert_eq!("Jane Doe", document.content()["name"]);
+    });
+}
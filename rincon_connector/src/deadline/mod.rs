@@ -0,0 +1,41 @@
+//! Deadlines for method calls.
+
+#[cfg(test)]
+mod tests;
+
+use std::time::Duration;
+
+use futures::future::{self, Either};
+use futures::Future;
+use tokio_core::reactor::{self, Timeout};
+
+use rincon_core::api::connector::Error;
+
+/// Returns a future that resolves to the result of the given future or fails
+/// with an `Error::Timeout` if the given future does not complete within the
+/// given duration.
+///
+/// When the deadline is reached the given future is dropped, which cancels
+/// any request still in flight.
+pub(crate) fn with_deadline<F>(
+    future: F,
+    timeout: Duration,
+    reactor: &reactor::Handle,
+) -> Box<Future<Item = F::Item, Error = Error>>
+where
+    F: Future<Error = Error> + 'static,
+{
+    let deadline = match Timeout::new(timeout, reactor) {
+        Ok(deadline) => deadline,
+        Err(error) => return Box::new(future::err(Error::from(error))),
+    };
+    Box::new(future.select2(deadline).then(move |result| match result {
+        Ok(Either::A((item, _))) => Ok(item),
+        Ok(Either::B(_)) => Err(Error::Timeout(format!(
+            "no response received within {:?}",
+            timeout
+        ))),
+        Err(Either::A((error, _))) => Err(error),
+        Err(Either::B((error, _))) => Err(Error::from(error)),
+    }))
+}
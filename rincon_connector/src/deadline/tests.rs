@@ -0,0 +1,42 @@
+use tokio_core::reactor::Core;
+
+use super::*;
+
+#[test]
+fn future_completed_before_deadline_returns_its_item() {
+    let mut core = Core::new().unwrap();
+    let handle = core.handle();
+    let future = future::ok::<_, Error>(42);
+
+    let result = core.run(with_deadline(future, Duration::from_secs(5), &handle));
+
+    assert_eq!(Ok(42), result);
+}
+
+#[test]
+fn future_failed_before_deadline_returns_its_error() {
+    let mut core = Core::new().unwrap();
+    let handle = core.handle();
+    let future = future::err::<u32, _>(Error::Communication("connection refused".into()));
+
+    let result = core.run(with_deadline(future, Duration::from_secs(5), &handle));
+
+    assert_eq!(
+        Err(Error::Communication("connection refused".into())),
+        result
+    );
+}
+
+#[test]
+fn future_not_completed_before_deadline_fails_with_timeout() {
+    let mut core = Core::new().unwrap();
+    let handle = core.handle();
+    let future = future::empty::<u32, Error>();
+
+    let result = core.run(with_deadline(future, Duration::from_millis(10), &handle));
+
+    match result {
+        Err(Error::Timeout(_)) => {},
+        _ => panic!("Timeout error expected"),
+    }
+}
@@ -0,0 +1,165 @@
+//! Interceptors for requests sent and responses received by the
+//! `JsonHttpConnector`.
+//!
+//! An `Interceptor` is registered on a `JsonHttpConnector` by calling its
+//! `with_interceptor()` function. Interceptors see every request right before
+//! it is sent and every response right after its body has been received.
+//! They can be used for logging, collecting metrics, injecting header fields
+//! like tracing ids or `x-arango-*` headers and for rewriting requests.
+
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use hyper::header::Headers;
+use hyper::{self, Request, StatusCode, Uri};
+
+use rincon_core::api::connector::Error;
+
+/// An extension point for requests and responses of the
+/// `JsonHttpConnector`.
+///
+/// The interceptors registered on a connector form a chain. Requests are
+/// passed to the interceptors in the order they have been registered.
+/// Responses are passed to the interceptors in reverse order.
+///
+/// Both functions of this trait have a default implementation that does
+/// nothing, so implementations only need to override the function they are
+/// interested in.
+pub trait Interceptor: Debug {
+    /// Is called with the prepared request right before it is sent.
+    ///
+    /// The interceptor may modify the request, e.g. set additional header
+    /// fields or rewrite the URI. If an error is returned the request is not
+    /// sent and the method call fails with the returned error.
+    fn intercept_request(&self, request: &mut Request) -> Result<(), Error> {
+        let _ = request;
+        Ok(())
+    }
+
+    /// Is called after a response has been received completely and before
+    /// its body is deserialized.
+    fn intercept_response(&self, response: &InterceptedResponse) {
+        let _ = response;
+    }
+}
+
+/// A response received by the `JsonHttpConnector` as passed to an
+/// `Interceptor`.
+#[derive(Debug)]
+pub struct InterceptedResponse<'a> {
+    method: &'a hyper::Method,
+    uri: &'a Uri,
+    status_code: StatusCode,
+    headers: &'a Headers,
+    body: &'a [u8],
+}
+
+impl<'a> InterceptedResponse<'a> {
+    /// Constructs a new `InterceptedResponse`.
+    pub fn new(
+        method: &'a hyper::Method,
+        uri: &'a Uri,
+        status_code: StatusCode,
+        headers: &'a Headers,
+        body: &'a [u8],
+    ) -> Self {
+        InterceptedResponse {
+            method,
+            uri,
+            status_code,
+            headers,
+            body,
+        }
+    }
+
+    /// Returns the HTTP method of the request this response belongs to.
+    pub fn method(&self) -> &hyper::Method {
+        self.method
+    }
+
+    /// Returns the URI of the request this response belongs to.
+    pub fn uri(&self) -> &Uri {
+        self.uri
+    }
+
+    /// Returns the status code of this response.
+    pub fn status_code(&self) -> StatusCode {
+        self.status_code
+    }
+
+    /// Returns the header fields of this response.
+    pub fn headers(&self) -> &Headers {
+        self.headers
+    }
+
+    /// Returns the body of this response.
+    pub fn body(&self) -> &[u8] {
+        self.body
+    }
+}
+
+/// An `Interceptor` that sets a header field on every request.
+///
+/// If the request already contains a header field with the same name its
+/// value is replaced.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SetHeader {
+    name: String,
+    value: String,
+}
+
+impl SetHeader {
+    /// Constructs a new `SetHeader` interceptor for the given header field.
+    pub fn new<N, V>(name: N, value: V) -> Self
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        SetHeader {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+
+    /// Returns the name of the header field.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the value of the header field.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+impl Interceptor for SetHeader {
+    fn intercept_request(&self, request: &mut Request) -> Result<(), Error> {
+        request
+            .headers_mut()
+            .set_raw(self.name.clone(), self.value.clone());
+        Ok(())
+    }
+}
+
+/// Passes the given request to all given interceptors in order.
+///
+/// Stops at the first interceptor that returns an error.
+pub(crate) fn intercept_request(
+    interceptors: &[Arc<Interceptor>],
+    request: &mut Request,
+) -> Result<(), Error> {
+    for interceptor in interceptors {
+        interceptor.intercept_request(request)?;
+    }
+    Ok(())
+}
+
+/// Passes the given response to all given interceptors in reverse order.
+pub(crate) fn intercept_response(
+    interceptors: &[Arc<Interceptor>],
+    response: &InterceptedResponse,
+) {
+    for interceptor in interceptors.iter().rev() {
+        interceptor.intercept_response(response);
+    }
+}
@@ -5,56 +5,122 @@
 //!
 //! The currently provided `Connector`s are:
 //!
-//! * `JsonHttpConnector` : uses JSON over HTTP/HTTPS
+//! * `JsonHttpConnector` : uses JSON or VelocyPack over HTTP/HTTPS
+//!
+//! The requests and responses of a `JsonHttpConnector` can be observed and
+//! modified by registering `Interceptor`s.
 //!
 //! For an example on how to use a connector see the crate level documentation.
 
+mod interceptor;
 #[cfg(test)]
 mod tests;
 
+pub use self::interceptor::*;
+
+use std::cell::Cell;
+use std::error;
+use std::fmt::{self, Debug};
+use std::io;
+use std::rc::Rc;
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
 
-use futures::{future, Future, Stream};
-use hyper::client::HttpConnector;
-use hyper::header::{self, Authorization, Basic, Bearer, ContentLength, ContentType};
+use futures::future::{self, Shared};
+use futures::{Future, Stream};
+use hyper::client::Service;
+use hyper::header::{self, Authorization, Basic, Bearer, ContentLength, ContentType, Headers};
 use hyper::{self, Client, HttpVersion, Request, StatusCode, Uri};
 use hyper_timeout::TimeoutConnector;
-use hyper_tls::HttpsConnector;
-use serde::ser::Serialize;
-use serde_json::{self, Value};
+use serde_json::Value;
 use tokio_core::reactor;
 use url;
 use url::percent_encoding::DEFAULT_ENCODE_SET;
 
-use rincon_core::api;
-use rincon_core::api::auth::{Authentication, Jwt};
+use rincon_core::api::auth::{Authentication, Credentials, Jwt, JwtSecret};
 use rincon_core::api::connector::{Connector, Error, Execute, FutureResult};
-use rincon_core::api::datasource::DataSource;
-use rincon_core::api::method::{Method, Operation, Prepare, RpcReturnType};
+use rincon_core::api::datasource::{ContentFormat, DataSource, Endpoint};
+use rincon_core::api::method::{Method, Operation, Parameters, Prepare, RpcReturnType};
+use rincon_core::api::metrics::{method_name, MethodMetrics, MethodSpan, MetricsSink};
+use rincon_core::api::payload::{self, payload_to_string, serialize_payload};
 use rincon_core::api::user_agent::{RinconUserAgent, UserAgent};
-use rincon_core::arango::protocol::{PATH_DB, SYSTEM_DATABASE};
+use rincon_core::arango::protocol::{
+    HEADER_QUEUE_TIME_SECONDS, PATH_DB, PATH_OPEN_AUTH, SYSTEM_DATABASE,
+};
+use rincon_core::jwt::{is_token_expired, superuser_token};
+use rincon_core::velocypack::CONTENT_TYPE_VELOCYPACK;
+
+use deadline::with_deadline;
+use tls::RustlsConnector;
 
-type HttpClient = Client<TimeoutConnector<HttpsConnector<HttpConnector>>>;
+type HttpClient = Client<MarkConnectFailures<TimeoutConnector<RustlsConnector>>>;
 
 /// A connector that uses JSON over HTTP/HTTPS.
 ///
 /// This `Connector` implementation uses JSON for serializing the payload and
-/// HTTP or HTTPS as the transport protocol.
+/// HTTP or HTTPS as the transport protocol. If the `DataSource` specifies
+/// `ContentFormat::VelocyPack` as content format, the payload is serialized
+/// as VelocyPack instead and VelocyPack responses are accepted.
 ///
 /// This connector supports both authentication methods of the [ArangoDB] REST
 /// API: Json Web Token (JWT) and basic authentication. The authentication
 /// method to be used is specified in the `DataSource`. If `Authentication:Jwt`
-/// is specified but not JWT is set in this connection method calls will return
-/// an `Error::NotAuthenticated` error.
+/// is specified the connector logs in with the credentials of the
+/// `DataSource` before the first method call and caches the token. The token
+/// is shared by all connections of a connector. Method calls executed while
+/// a login is in progress wait for this login instead of logging in again.
+/// The token is refreshed when it is about to expire. If the server rejects
+/// a request with the status code 401 (Unauthorized) the connector logs in
+/// again and sends the request once more. With `Authentication::JwtSecret`
+/// the connector does not log in at all. Instead it generates superuser
+/// tokens signed with the JWT secret.
+///
+/// If the `DataSource` defines failover endpoints, requests are sent to the
+/// currently active endpoint. When no connection to the endpoint can be
+/// established, the request is sent to the next endpoint which then becomes
+/// the active endpoint for all subsequent requests. Requests of methods that
+/// only read data (`Operation::Read` and `Operation::ReadHeader`) are also
+/// sent to the next endpoint if they fail with an `Error::Communication` error
+/// or with the HTTP status code 503 (Service Unavailable) after they have
+/// been written. Requests of all other methods are never replayed once they
+/// have been written, as they might have been executed already. Connections
+/// to the endpoints are pooled and kept alive by the underlying HTTP client.
+///
+/// HTTPS connections are secured by [rustls]. Custom root certificates,
+/// client certificates for mutual TLS, the server name used for SNI and
+/// certificate verification are configured by the `TlsOptions` of the
+/// `DataSource`. Creating the connector fails if the certificates or keys
+/// specified in the `TlsOptions` can not be read.
+///
+/// Each method call must be completed within the timeout of the `DataSource`
+/// or the timeout returned by `Prepare::timeout()` of the method. The timeout
+/// covers the whole method call including a login and all failover attempts.
+/// Otherwise the request is cancelled and the method call fails with an
+/// `Error::Timeout`.
+/// The timeout is also sent to the server as the maximum time a request may
+/// wait in the server's queue.
+///
+/// Additional behavior like logging, metrics or injecting header fields is
+/// added by registering `Interceptor`s with the `with_interceptor()`
+/// function. Timings, status codes and payload sizes of all requests are
+/// reported to a `MetricsSink` set with the `with_metrics_sink()` function.
 ///
 /// For an example on how to use it see the crate level documentation.
+///
+/// [rustls]: https://github.com/ctz/rustls
 #[derive(Debug)]
 pub struct JsonHttpConnector {
     user_agent: &'static UserAgent,
     datasource: Arc<DataSource>,
-    token: Arc<Option<Jwt>>,
+    active_endpoint: Arc<AtomicUsize>,
+    token: Arc<RwLock<Option<Jwt>>>,
+    pending_login: PendingLogin,
+    interceptors: Arc<Vec<Arc<Interceptor>>>,
+    metrics_sink: Option<Arc<MetricsSink>>,
     client: Arc<HttpClient>,
+    reactor: reactor::Handle,
 }
 
 impl JsonHttpConnector {
@@ -72,19 +138,23 @@ impl JsonHttpConnector {
     /// * `reactor` : a handle of a `reactor::Core` instance of the `tokio-core`
     ///   crate.
     pub fn new(datasource: DataSource, reactor: &reactor::Handle) -> Result<Self, Error> {
-        let https_connector = HttpsConnector::new(4, reactor)
-            .map_err(|cause| Error::Communication(cause.to_string()))?;
+        let https_connector = RustlsConnector::new(datasource.tls_options(), reactor)?;
         let mut timeout_connector = TimeoutConnector::new(https_connector, reactor);
         timeout_connector.set_connect_timeout(Some(*datasource.timeout()));
         let client = Client::configure()
-            .connector(timeout_connector)
+            .connector(MarkConnectFailures(timeout_connector))
             .build(reactor);
         debug!("Creating new JSON/HTTP connector for {:?}", &datasource);
         Ok(JsonHttpConnector {
             user_agent: &RinconUserAgent,
             datasource: Arc::new(datasource),
-            token: Arc::new(None),
+            active_endpoint: Arc::new(AtomicUsize::new(0)),
+            token: Arc::new(RwLock::new(None)),
+            pending_login: PendingLogin::default(),
+            interceptors: Arc::new(Vec::new()),
+            metrics_sink: None,
             client: Arc::new(client),
+            reactor: reactor.clone(),
         })
     }
 
@@ -107,21 +177,54 @@ impl JsonHttpConnector {
         datasource: DataSource,
         reactor: &reactor::Handle,
     ) -> Result<Self, Error> {
-        let https_connector = HttpsConnector::new(4, reactor)
-            .map_err(|cause| Error::Communication(cause.to_string()))?;
+        let https_connector = RustlsConnector::new(datasource.tls_options(), reactor)?;
         let mut timeout_connector = TimeoutConnector::new(https_connector, reactor);
         timeout_connector.set_connect_timeout(Some(*datasource.timeout()));
         let client = Client::configure()
-            .connector(timeout_connector)
+            .connector(MarkConnectFailures(timeout_connector))
             .build(reactor);
         debug!("Creating new JSON/HTTP connector for {:?}", &datasource);
         Ok(JsonHttpConnector {
             user_agent,
             datasource: Arc::new(datasource),
-            token: Arc::new(None),
+            active_endpoint: Arc::new(AtomicUsize::new(0)),
+            token: Arc::new(RwLock::new(None)),
+            pending_login: PendingLogin::default(),
+            interceptors: Arc::new(Vec::new()),
+            metrics_sink: None,
             client: Arc::new(client),
+            reactor: reactor.clone(),
         })
     }
+
+    /// Returns this `JsonHttpConnector` with the given interceptor added to
+    /// its chain of interceptors.
+    ///
+    /// The interceptor is applied to all method calls executed by
+    /// connections that are created after this function has been called.
+    pub fn with_interceptor<I>(mut self, interceptor: I) -> Self
+    where
+        I: Interceptor + 'static,
+    {
+        let mut interceptors = Vec::with_capacity(self.interceptors.len() + 1);
+        interceptors.extend(self.interceptors.iter().cloned());
+        interceptors.push(Arc::new(interceptor) as Arc<Interceptor>);
+        self.interceptors = Arc::new(interceptors);
+        self
+    }
+
+    /// Returns this `JsonHttpConnector` with the given metrics sink set.
+    ///
+    /// The sink receives a span for each request sent by connections that
+    /// are created after this function has been called. If a request fails
+    /// over to another endpoint each attempt is reported as a separate span.
+    pub fn with_metrics_sink<S>(mut self, metrics_sink: S) -> Self
+    where
+        S: MetricsSink + 'static,
+    {
+        self.metrics_sink = Some(Arc::new(metrics_sink));
+        self
+    }
 }
 
 impl Connector for JsonHttpConnector {
@@ -131,9 +234,14 @@ impl Connector for JsonHttpConnector {
         JsonHttpConnection {
             user_agent: self.user_agent,
             datasource: self.datasource.clone(),
+            active_endpoint: self.active_endpoint.clone(),
             database: Some(database_name.to_owned()),
             token: self.token.clone(),
+            pending_login: self.pending_login.clone(),
+            interceptors: self.interceptors.clone(),
+            metrics_sink: self.metrics_sink.clone(),
             client: self.client.clone(),
+            reactor: self.reactor.clone(),
         }
     }
 
@@ -142,23 +250,28 @@ impl Connector for JsonHttpConnector {
     }
 
     fn use_auth_token(&mut self, token: Jwt) {
-        self.token = Arc::new(Some(token));
+        store_token(&self.token, Some(token));
     }
 
     fn invalidate_auth_token(&mut self) {
-        self.token = Arc::new(None)
+        store_token(&self.token, None);
     }
 }
 
 /// A connection to a server that actually executes method calls using JSON
 /// over HTTP/HTTPS.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct JsonHttpConnection {
     user_agent: &'static UserAgent,
     datasource: Arc<DataSource>,
+    active_endpoint: Arc<AtomicUsize>,
     database: Option<String>,
-    token: Arc<Option<Jwt>>,
+    token: Arc<RwLock<Option<Jwt>>>,
+    pending_login: PendingLogin,
+    interceptors: Arc<Vec<Arc<Interceptor>>>,
+    metrics_sink: Option<Arc<MetricsSink>>,
     client: Arc<HttpClient>,
+    reactor: reactor::Handle,
 }
 
 impl JsonHttpConnection {
@@ -172,6 +285,12 @@ impl JsonHttpConnection {
         &self.datasource
     }
 
+    /// Returns the endpoint requests are currently sent to.
+    pub fn active_endpoint(&self) -> &Endpoint {
+        let endpoints = self.datasource.endpoints();
+        &endpoints[self.active_endpoint.load(Ordering::SeqCst) % endpoints.len()]
+    }
+
     /// Returns the name of the default database addressed by method calls
     /// if none is specified by the actual method.
     ///
@@ -185,18 +304,49 @@ impl JsonHttpConnection {
     }
 
     /// Returns the authentication token used by this connection.
-    pub fn token(&self) -> Option<&Jwt> {
-        self.token.as_ref().as_ref()
+    pub fn token(&self) -> Option<Jwt> {
+        match self.token.read() {
+            Ok(token) => token.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// Returns the timeout for executing the given method.
+    ///
+    /// This is the timeout specified by the method itself or the timeout of
+    /// the `DataSource` if the method does not specify one.
+    pub fn timeout_for<P>(&self, method: &P) -> Duration
+    where
+        P: Prepare,
+    {
+        method
+            .timeout()
+            .unwrap_or_else(|| *self.datasource.timeout())
     }
 
     /// Builds a HTTP-request for a concrete method call and returns it.
+    ///
+    /// The request is addressed to the currently active endpoint.
     pub fn prepare_request<'p, P>(&self, method: &'p P) -> Result<Request, Error>
+    where
+        P: 'p + Prepare,
+    {
+        self.prepare_request_for_endpoint(self.active_endpoint(), method)
+    }
+
+    /// Builds a HTTP-request for a concrete method call addressed to the
+    /// given endpoint.
+    fn prepare_request_for_endpoint<'p, P>(
+        &self,
+        endpoint: &Endpoint,
+        method: &'p P,
+    ) -> Result<Request, Error>
     where
         P: 'p + Prepare,
     {
         let operation = method.operation();
         let http_method = http_method_for_operation(&operation);
-        let uri = build_request_uri(&self.datasource, self.database(), method);
+        let uri = build_request_uri(&self.datasource, endpoint, self.database(), method);
         let mut request = Request::new(http_method, uri);
         request.set_version(HttpVersion::Http11);
         {
@@ -207,10 +357,9 @@ impl JsonHttpConnection {
                     username: credentials.username().to_owned(),
                     password: Some(credentials.password().to_owned()),
                 })),
-                Authentication::Jwt(_) => match *self.token.as_ref() {
-                    Some(ref token) => headers.set(Authorization(Bearer {
-                        token: token.to_owned(),
-                    })),
+                Authentication::Jwt(_) | Authentication::JwtSecret(_) => match self.token() {
+                    Some(token) => headers.set(Authorization(Bearer { token })),
+                    None if method.path() == PATH_OPEN_AUTH => {},
                     None => {
                         return Err(Error::NotAuthenticated(
                             "the client must be authenticated first, \
@@ -221,16 +370,36 @@ impl JsonHttpConnection {
                 },
                 Authentication::None => {},
             }
+            headers.set_raw(
+                HEADER_QUEUE_TIME_SECONDS,
+                format_seconds(&self.timeout_for(method)),
+            );
             for &(ref name, ref value) in method.header().iter() {
                 headers.set_raw(name.to_string(), value.to_string());
             }
         }
+        let content_format = self.datasource.content_format();
+        if content_format == ContentFormat::VelocyPack {
+            request
+                .headers_mut()
+                .set_raw("Accept", CONTENT_TYPE_VELOCYPACK);
+        }
         if let Some(content) = method.content() {
-            let json = serialize_payload(content)?;
-            trace!("| request body: {:?}", String::from_utf8(json.clone()));
-            request.headers_mut().set(ContentType::json());
-            request.headers_mut().set(ContentLength(json.len() as u64));
-            request.set_body(json);
+            let payload = serialize_payload(content_format, method.is_line_delimited(), content)?;
+            trace!(
+                "| request body: {}",
+                payload_to_string(content_format, &payload)
+            );
+            match content_format {
+                ContentFormat::Json => request.headers_mut().set(ContentType::json()),
+                ContentFormat::VelocyPack => request
+                    .headers_mut()
+                    .set_raw("Content-Type", CONTENT_TYPE_VELOCYPACK),
+            }
+            request
+                .headers_mut()
+                .set(ContentLength(payload.len() as u64));
+            request.set_body(payload);
         }
         Ok(request)
     }
@@ -241,80 +410,556 @@ impl Execute for JsonHttpConnection {
     where
         M: Method + Prepare + 'static,
     {
-        match self.prepare_request(&method) {
-            Ok(request) => {
-                debug!("Sending {:?}", &request);
-                Box::new(
-                    self.client
-                        .request(request)
-                        .map_err(|cause| Error::Communication(cause.to_string()))
-                        .and_then(move |response| {
-                            let status_code = response.status();
-                            response
-                                .body()
-                                .concat2()
-                                .map_err(|cause| Error::Communication(cause.to_string()))
-                                .and_then(move |buffer| {
-                                    parse_return_type::<M>(
-                                        &method.return_type(),
-                                        status_code,
-                                        &buffer,
-                                    )
-                                })
-                        }),
-                )
+        let timeout = self.timeout_for(&method);
+        let method = Rc::new(method);
+        let call = match *self.datasource.authentication() {
+            Authentication::Jwt(ref credentials) => {
+                send_authenticated(self.clone(), credentials.clone(), method, timeout)
             },
-            Err(error) => Box::new(future::err(error)),
+            Authentication::JwtSecret(ref secret) => {
+                self.renew_superuser_token(secret);
+                self.send(method)
+            },
+            _ => self.send(method),
+        };
+        with_deadline(call, timeout, &self.reactor)
+    }
+}
+
+impl JsonHttpConnection {
+    /// Sends the given method to the currently active endpoint.
+    fn send<M>(&self, method: Rc<M>) -> FutureResult<M>
+    where
+        M: Method + Prepare + 'static,
+    {
+        let endpoint_index = self.active_endpoint.load(Ordering::SeqCst);
+        let attempts = self.datasource.endpoints().len();
+        send_request(self.clone(), method, endpoint_index, attempts)
+    }
+
+    /// Generates a new superuser token if there is no token yet or if the
+    /// token is about to expire.
+    fn renew_superuser_token(&self, secret: &JwtSecret) {
+        let now = SystemTime::now();
+        let has_valid_token = self
+            .token()
+            .map_or(false, |token| !is_token_expired(&token, now));
+        if !has_valid_token {
+            debug!("Generating new superuser token");
+            store_token(&self.token, Some(superuser_token(secret, now)));
+        }
+    }
+}
+
+/// Sends the given method using JWT authentication.
+///
+/// If there is no valid token the connection logs in first. If the server
+/// rejects the token, e.g. because it has expired, the connection logs in
+/// again and sends the method once more. A login must be completed within
+/// the given timeout.
+fn send_authenticated<M>(
+    connection: JsonHttpConnection,
+    credentials: Credentials,
+    method: Rc<M>,
+    timeout: Duration,
+) -> FutureResult<M>
+where
+    M: Method + Prepare + 'static,
+{
+    let has_valid_token = connection
+        .token()
+        .map_or(false, |token| !is_token_expired(&token, SystemTime::now()));
+    if has_valid_token {
+        let retry_connection = connection.clone();
+        Box::new(connection.send(method.clone()).or_else(move |error| {
+            if is_unauthorized(&error) {
+                debug!("Token has been rejected by the server, logging in again");
+                store_token(&retry_connection.token, None);
+                login_and_send(retry_connection, &credentials, method, timeout)
+            } else {
+                Box::new(future::err(error))
+            }
+        }))
+    } else {
+        login_and_send(connection, &credentials, method, timeout)
+    }
+}
+
+/// Logs in with the given credentials, stores the received token and then
+/// sends the given method.
+///
+/// If another method call is already logging in, the method is sent as soon
+/// as that login has completed instead of logging in once more. Waiting for
+/// the login fails with an `Error::Timeout` if the login does not complete
+/// within the given timeout.
+fn login_and_send<M>(
+    connection: JsonHttpConnection,
+    credentials: &Credentials,
+    method: Rc<M>,
+    timeout: Duration,
+) -> FutureResult<M>
+where
+    M: Method + Prepare + 'static,
+{
+    let login = connection
+        .pending_login
+        .join_or_start(&connection, credentials, timeout);
+    let login = with_deadline(
+        login.map_err(|error| (*error).clone()),
+        timeout,
+        &connection.reactor,
+    );
+    Box::new(login.then(move |result| match result {
+        Ok(_) => connection.send(method),
+        Err(error) => Box::new(future::err(error)),
+    }))
+}
+
+type LoginFuture = Shared<Box<Future<Item = Jwt, Error = Error>>>;
+
+/// The login that is currently in progress.
+///
+/// It is shared by all connections of a connector, so that method calls
+/// executed while there is no valid token wait for a single login.
+#[derive(Clone, Default)]
+struct PendingLogin(Arc<RwLock<Option<LoginFuture>>>);
+
+impl PendingLogin {
+    /// Returns the login in progress or starts a new login with the given
+    /// credentials.
+    ///
+    /// The login stores the received token when it has completed. A new login
+    /// is cancelled if it does not complete within the given timeout, so that
+    /// a login that never completes does not block subsequent method calls.
+    fn join_or_start(
+        &self,
+        connection: &JsonHttpConnection,
+        credentials: &Credentials,
+        timeout: Duration,
+    ) -> LoginFuture {
+        if let Some(login) = self.current() {
+            return login;
         }
+        debug!("Logging in as user {:?}", credentials.username());
+        let token = connection.token.clone();
+        let pending_login = self.clone();
+        let login = Login {
+            credentials: json!({
+                "username": credentials.username(),
+                "password": credentials.password(),
+            }),
+        };
+        let login = with_deadline(
+            connection.send(Rc::new(login)),
+            timeout,
+            &connection.reactor,
+        );
+        let login: Box<Future<Item = Jwt, Error = Error>> = Box::new(
+            login
+                .then(move |result| {
+                    pending_login.replace(None);
+                    result
+                })
+                .map(move |response| {
+                    store_token(&token, Some(response.jwt.clone()));
+                    response.jwt
+                }),
+        );
+        let login = login.shared();
+        self.replace(Some(login.clone()));
+        login
+    }
+
+    /// Returns the login that is currently in progress if there is one.
+    fn current(&self) -> Option<LoginFuture> {
+        match self.0.read() {
+            Ok(login) => login.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// Replaces the login that is currently in progress.
+    fn replace(&self, login: Option<LoginFuture>) {
+        match self.0.write() {
+            Ok(mut current) => *current = login,
+            Err(poisoned) => *poisoned.into_inner() = login,
+        }
+    }
+}
+
+impl Debug for PendingLogin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("PendingLogin")
+            .field(&self.current().is_some())
+            .finish()
     }
 }
 
+/// Stores the given token, replacing any token stored before.
+fn store_token(token: &RwLock<Option<Jwt>>, new_token: Option<Jwt>) {
+    match token.write() {
+        Ok(mut token) => *token = new_token,
+        Err(poisoned) => *poisoned.into_inner() = new_token,
+    }
+}
+
+/// Returns whether the given error signals that the request has been
+/// rejected because of missing or invalid authentication.
+fn is_unauthorized(error: &Error) -> bool {
+    match *error {
+        Error::Method(ref error) => error.status_code() == StatusCode::Unauthorized.as_u16(),
+        _ => false,
+    }
+}
+
+/// Method to obtain a token for JWT authentication.
+#[derive(Debug)]
+struct Login {
+    credentials: Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct LoginResponse {
+    jwt: Jwt,
+}
+
+impl Method for Login {
+    type Result = LoginResponse;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: None,
+    };
+}
+
+impl Prepare for Login {
+    type Content = Value;
+
+    fn operation(&self) -> Operation {
+        Operation::Create
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_OPEN_AUTH)
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Value> {
+        Some(&self.credentials)
+    }
+}
+
+/// Sends the request for the given method to the endpoint with the given
+/// index and fails over to the next endpoint if the request fails and there
+/// are attempts left.
+fn send_request<M>(
+    connection: JsonHttpConnection,
+    method: Rc<M>,
+    endpoint_index: usize,
+    attempts_left: usize,
+) -> FutureResult<M>
+where
+    M: Method + Prepare + 'static,
+{
+    let endpoint_count = connection.datasource.endpoints().len();
+    let instrumentation = connection.metrics_sink.as_ref().map(|sink| {
+        let span = MethodSpan::start(method_name::<M>(), method.operation(), method.path());
+        sink.span_started(&span);
+        (sink.clone(), span)
+    });
+    let request = {
+        let endpoint = &connection.datasource.endpoints()[endpoint_index % endpoint_count];
+        connection
+            .prepare_request_for_endpoint(endpoint, &*method)
+            .and_then(|mut request| {
+                intercept_request(&connection.interceptors, &mut request).map(|_| request)
+            })
+    };
+    let request = match request {
+        Ok(request) => request,
+        Err(error) => {
+            finish_span(instrumentation, None, 0, None, Some(&error));
+            return Box::new(future::err(error));
+        },
+    };
+    debug!("Sending {:?}", &request);
+    let http_method = request.method().clone();
+    let uri = request.uri().clone();
+    let request_size = request
+        .headers()
+        .get::<ContentLength>()
+        .map_or(0, |content_length| content_length.0);
+    let response = connection.client.request(request);
+    let response_method = method.clone();
+    let interceptors = connection.interceptors.clone();
+    let connect_failed = Rc::new(Cell::new(false));
+    let connect_failed_flag = connect_failed.clone();
+    let exchange = response
+        .map_err(move |cause| {
+            connect_failed_flag.set(is_connect_failure(&cause));
+            Error::Communication(cause.to_string())
+        })
+        .and_then(move |response| {
+            let status_code = response.status();
+            let content_format = content_format_of(response.headers());
+            let headers = response.headers().clone();
+            response
+                .body()
+                .concat2()
+                .map_err(|cause| Error::Communication(cause.to_string()))
+                .map(move |buffer| (status_code, content_format, headers, buffer))
+        });
+    let in_flight = InFlight {
+        instrumentation,
+        request_size,
+    };
+    Box::new(
+        exchange
+            .then(move |exchange| {
+                let (result, status_code, response_size) = match exchange {
+                    Ok((status_code, content_format, headers, buffer)) => {
+                        intercept_response(
+                            &interceptors,
+                            &InterceptedResponse::new(
+                                &http_method,
+                                &uri,
+                                status_code,
+                                &headers,
+                                &buffer,
+                            ),
+                        );
+                        let result = parse_return_type::<M>(
+                            &response_method.return_type(),
+                            status_code,
+                            content_format,
+                            &buffer,
+                        );
+                        let response_size = buffer.len() as u64;
+                        (result, Some(status_code.as_u16()), Some(response_size))
+                    },
+                    Err(error) => (Err(error), None, None),
+                };
+                in_flight.finish(status_code, response_size, result.as_ref().err());
+                result
+            })
+            .or_else(move |error| {
+                if attempts_left > 1
+                    && may_fail_over(method.operation(), &error, connect_failed.get())
+                {
+                    let next_index = (endpoint_index + 1) % endpoint_count;
+                    warn!(
+                        "Request to endpoint {:?} failed: {}; failing over to endpoint {:?}",
+                        &connection.datasource.endpoints()[endpoint_index % endpoint_count],
+                        &error,
+                        &connection.datasource.endpoints()[next_index]
+                    );
+                    connection
+                        .active_endpoint
+                        .store(next_index, Ordering::SeqCst);
+                    send_request(connection, method, next_index, attempts_left - 1)
+                } else {
+                    Box::new(future::err(error))
+                }
+            }),
+    )
+}
+
+/// Formats the given duration as number of seconds with fractional part.
+fn format_seconds(duration: &Duration) -> String {
+    let millis = duration.as_secs() * 1_000 + u64::from(duration.subsec_nanos() / 1_000_000);
+    format!("{}.{:03}", millis / 1_000, millis % 1_000)
+}
+
+/// Reports the metrics of a finished method call to the metrics sink if the
+/// method call is instrumented.
+fn finish_span(
+    instrumentation: Option<(Arc<MetricsSink>, MethodSpan)>,
+    status_code: Option<u16>,
+    request_size: u64,
+    response_size: Option<u64>,
+    error: Option<&Error>,
+) {
+    if let Some((sink, span)) = instrumentation {
+        let metrics = MethodMetrics::new(
+            span.elapsed(),
+            status_code,
+            request_size,
+            response_size,
+            error,
+        );
+        sink.span_finished(&span, &metrics);
+    }
+}
+
+/// The instrumentation of a request that has been sent.
+///
+/// If the method call is cancelled before a response has been received,
+/// e.g. because the deadline of the method call has been reached, the span
+/// is finished with an `Error::Timeout` when this value is dropped.
+struct InFlight {
+    instrumentation: Option<(Arc<MetricsSink>, MethodSpan)>,
+    request_size: u64,
+}
+
+impl InFlight {
+    /// Reports the metrics of the received response or the error.
+    fn finish(
+        mut self,
+        status_code: Option<u16>,
+        response_size: Option<u64>,
+        error: Option<&Error>,
+    ) {
+        finish_span(
+            self.instrumentation.take(),
+            status_code,
+            self.request_size,
+            response_size,
+            error,
+        );
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        if self.instrumentation.is_some() {
+            let error =
+                Error::Timeout("method call cancelled before a response was received".to_owned());
+            finish_span(
+                self.instrumentation.take(),
+                None,
+                self.request_size,
+                None,
+                Some(&error),
+            );
+        }
+    }
+}
+
+/// Returns whether the given error signals that the endpoint a request has
+/// been sent to is not available.
+fn is_endpoint_failure(error: &Error) -> bool {
+    match *error {
+        Error::Communication(_) => true,
+        Error::Method(ref error) => error.status_code() == StatusCode::ServiceUnavailable.as_u16(),
+        _ => false,
+    }
+}
+
+/// Returns whether a request that failed with the given error may be sent
+/// to the next endpoint.
+///
+/// A request that could not be sent because no connection could be
+/// established is always sent to the next endpoint. Requests that have been
+/// written already are only replayed if the method does not modify any data.
+fn may_fail_over(operation: Operation, error: &Error, connect_failed: bool) -> bool {
+    if connect_failed {
+        return true;
+    }
+    match operation {
+        Operation::Read | Operation::ReadHeader => is_endpoint_failure(error),
+        _ => false,
+    }
+}
+
+/// Returns whether the given error has been raised while establishing a
+/// connection, that is before any part of the request has been written.
+fn is_connect_failure(error: &hyper::Error) -> bool {
+    match *error {
+        hyper::Error::Io(ref error) => error
+            .get_ref()
+            .map_or(false, |cause| cause.is::<ConnectFailure>()),
+        _ => false,
+    }
+}
+
+/// A connector for `hyper` that marks the errors of the wrapped connector as
+/// `ConnectFailure`s, so that they can be told apart from errors raised
+/// after a request has been written.
+#[derive(Debug, Clone)]
+struct MarkConnectFailures<C>(C);
+
+impl<C> Service for MarkConnectFailures<C>
+where
+    C: Service<Request = Uri, Error = io::Error>,
+    C::Future: 'static,
+{
+    type Request = Uri;
+    type Response = C::Response;
+    type Error = io::Error;
+    type Future = Box<Future<Item = C::Response, Error = io::Error>>;
+
+    fn call(&self, uri: Uri) -> Self::Future {
+        Box::new(
+            self.0
+                .call(uri)
+                .map_err(|cause| io::Error::new(cause.kind(), ConnectFailure(cause))),
+        )
+    }
+}
+
+/// An error raised while establishing a connection to an endpoint.
+#[derive(Debug)]
+struct ConnectFailure(io::Error);
+
+impl fmt::Display for ConnectFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl error::Error for ConnectFailure {
+    fn description(&self) -> &str {
+        "failed to connect to endpoint"
+    }
+}
+
+/// Determines the format of the content of a response by its content type
+/// header.
+fn content_format_of(headers: &Headers) -> ContentFormat {
+    let is_velocypack = headers
+        .get_raw("Content-Type")
+        .and_then(|raw| raw.one())
+        .map_or(false, |value| {
+            value.starts_with(CONTENT_TYPE_VELOCYPACK.as_bytes())
+        });
+    if is_velocypack {
+        ContentFormat::VelocyPack
+    } else {
+        ContentFormat::Json
+    }
+}
+
+/// Deserializes the payload of a response and logs the payload.
 fn parse_return_type<M>(
     return_type: &RpcReturnType,
     status_code: StatusCode,
+    content_format: ContentFormat,
     payload: &[u8],
 ) -> Result<<M as Method>::Result, Error>
 where
     M: Method,
 {
     debug!("Received response with code {:?}", status_code);
-    if status_code.is_success() {
-        let parse_result = match return_type.result_field {
-            Some(result_field) => match serde_json::from_slice(payload) {
-                Ok(Value::Object(ref mut obj)) => match obj.remove(result_field) {
-                    Some(result_value) => serde_json::from_value(result_value),
-                    None => serde_json::from_slice(payload),
-                },
-                _ => serde_json::from_slice(payload),
-            },
-            None => serde_json::from_slice(payload),
-        };
-        if parse_result.is_err() {
-            debug!("| response body: {}", String::from_utf8_lossy(payload));
-        } else {
-            trace!("| response body: {}", String::from_utf8_lossy(payload));
-        }
-        parse_result.map_err(|cause| Error::Deserialization(cause.to_string()))
+    let result =
+        payload::parse_return_type::<M>(return_type, status_code.as_u16(), content_format, payload);
+    if result.is_err() {
+        debug!(
+            "| response body: {}",
+            payload_to_string(content_format, payload)
+        );
     } else {
-        debug!("| response body: {}", String::from_utf8_lossy(payload));
-        let api_error = serde_json::from_slice(payload).unwrap_or_else(|_| {
-            let error_code = api::ErrorCode::from_u16(status_code.as_u16());
-            let message = if payload.is_empty() {
-                error_code.description().to_owned()
-            } else {
-                String::from_utf8_lossy(payload).to_string()
-            };
-            api::Error::new(status_code.as_u16(), error_code, message)
-        });
-        Err(Error::Method(api_error))
+        trace!(
+            "| response body: {}",
+            payload_to_string(content_format, payload)
+        );
     }
-}
-
-fn serialize_payload<T>(content: &T) -> Result<Vec<u8>, Error>
-where
-    T: Serialize,
-{
-    serde_json::to_vec(content).map_err(|cause| Error::Serialization(cause.to_string()))
+    result
 }
 
 fn header_user_agent_for(agent: &UserAgent) -> header::UserAgent {
@@ -340,16 +985,21 @@ fn http_method_for_operation(operation: &Operation) -> hyper::Method {
     }
 }
 
-fn build_request_uri<P>(datasource: &DataSource, database_name: Option<&String>, prepare: &P) -> Uri
+fn build_request_uri<P>(
+    datasource: &DataSource,
+    endpoint: &Endpoint,
+    database_name: Option<&String>,
+    prepare: &P,
+) -> Uri
 where
     P: Prepare,
 {
     let mut request_uri = String::new();
     request_uri.push_str(datasource.protocol());
     request_uri.push_str("://");
-    request_uri.push_str(datasource.host());
+    request_uri.push_str(endpoint.host());
     request_uri.push(':');
-    request_uri.push_str(&datasource.port().to_string());
+    request_uri.push_str(&endpoint.port().to_string());
     if let Some(database_name) = database_name {
         request_uri.push_str(PATH_DB);
         request_uri.push_str(&percent_encode(database_name));
@@ -1,8 +1,11 @@
+use std::cell::RefCell;
 use std::iter::FromIterator;
 
 use super::*;
+use rincon_core::api;
 use rincon_core::api::auth::{Authentication, Credentials};
-use rincon_core::api::method::{Parameters, Prepare};
+use rincon_core::api::method::{Parameters, Prepare, WithTimeout};
+use rincon_core::api::metrics::ErrorKind;
 use rincon_core::api::user_agent::Version;
 
 struct Prepared<'a> {
@@ -46,7 +49,7 @@ fn build_request_uri_for_http() {
         content: None,
     };
 
-    let uri = build_request_uri(&datasource, None, &prepared);
+    let uri = build_request_uri(&datasource, datasource.primary_endpoint(), None, &prepared);
 
     assert_eq!("http://localhost:8529/_api/user", uri.to_string());
 }
@@ -66,7 +69,7 @@ fn build_request_uri_for_https_with_authentication() {
         content: None,
     };
 
-    let uri = build_request_uri(&datasource, None, &prepared);
+    let uri = build_request_uri(&datasource, datasource.primary_endpoint(), None, &prepared);
 
     assert_eq!("https://localhost:8529/_api/user", uri.to_string());
 }
@@ -83,7 +86,12 @@ fn build_request_uri_for_given_database() {
         content: None,
     };
 
-    let uri = build_request_uri(&datasource, Some(&"given_db_name".to_owned()), &prepared);
+    let uri = build_request_uri(
+        &datasource,
+        datasource.primary_endpoint(),
+        Some(&"given_db_name".to_owned()),
+        &prepared,
+    );
 
     assert_eq!(
         "https://localhost:8529/_db/given_db_name/_api/collection",
@@ -103,7 +111,12 @@ fn build_request_uri_for_specific_database() {
         content: None,
     };
 
-    let uri = build_request_uri(&datasource, datasource.database_name(), &prepared);
+    let uri = build_request_uri(
+        &datasource,
+        datasource.primary_endpoint(),
+        datasource.database_name(),
+        &prepared,
+    );
 
     assert_eq!(
         "https://localhost:8529/_db/url_test/_api/collection",
@@ -123,7 +136,12 @@ fn build_request_uri_for_specific_database_with_one_param() {
         content: None,
     };
 
-    let uri = build_request_uri(&datasource, datasource.database_name(), &prepared);
+    let uri = build_request_uri(
+        &datasource,
+        datasource.primary_endpoint(),
+        datasource.database_name(),
+        &prepared,
+    );
 
     assert_eq!(
         "https://localhost:8529/_db/the%20big%20data/_api/document\
@@ -144,7 +162,12 @@ fn build_request_uri_for_specific_database_with_two_params() {
         content: None,
     };
 
-    let uri = build_request_uri(&datasource, datasource.database_name(), &prepared);
+    let uri = build_request_uri(
+        &datasource,
+        datasource.primary_endpoint(),
+        datasource.database_name(),
+        &prepared,
+    );
 
     assert_eq!(
         "https://localhost:8529/_db/the%20b%C3%BCg%20data/_api/document\
@@ -165,7 +188,12 @@ fn build_request_uri_for_given_database_with_three_params() {
         content: None,
     };
 
-    let uri = build_request_uri(&datasource, Some(&"the big data".to_owned()), &prepared);
+    let uri = build_request_uri(
+        &datasource,
+        datasource.primary_endpoint(),
+        Some(&"the big data".to_owned()),
+        &prepared,
+    );
 
     assert_eq!(
         "https://localhost:8529/_db/the%20big%20data/_api/document\
@@ -174,6 +202,245 @@ fn build_request_uri_for_given_database_with_three_params() {
     );
 }
 
+#[test]
+fn build_request_uri_for_failover_endpoint() {
+    let datasource = DataSource::from_str("http://coordinator1:8529")
+        .unwrap()
+        .with_failover_endpoints(vec![
+            Endpoint::new("coordinator2", 8529),
+            Endpoint::new("coordinator3", 8530),
+        ]);
+    let prepared = Prepared {
+        operation: Operation::Read,
+        path: "/_api/user",
+        params: vec![],
+        content: None,
+    };
+
+    let uri = build_request_uri(&datasource, &datasource.endpoints()[2], None, &prepared);
+
+    assert_eq!("http://coordinator3:8530/_api/user", uri.to_string());
+}
+
+#[test]
+fn communication_error_is_endpoint_failure() {
+    let error = Error::Communication("connection refused".to_owned());
+
+    assert!(is_endpoint_failure(&error));
+}
+
+#[test]
+fn service_unavailable_error_is_endpoint_failure() {
+    let error = Error::Method(api::Error::new(
+        503,
+        api::ErrorCode::HttpServiceUnavailable,
+        "service unavailable",
+    ));
+
+    assert!(is_endpoint_failure(&error));
+}
+
+#[test]
+fn not_found_error_is_not_endpoint_failure() {
+    let error = Error::Method(api::Error::new(
+        404,
+        api::ErrorCode::ArangoDocumentNotFound,
+        "document not found",
+    ));
+
+    assert!(!is_endpoint_failure(&error));
+}
+
+#[test]
+fn read_may_fail_over_on_communication_error() {
+    let error = Error::Communication("connection reset by peer".to_owned());
+
+    assert!(may_fail_over(Operation::Read, &error, false));
+    assert!(may_fail_over(Operation::ReadHeader, &error, false));
+}
+
+#[test]
+fn write_may_not_fail_over_on_communication_error_after_request_has_been_written() {
+    let error = Error::Communication("connection reset by peer".to_owned());
+
+    assert!(!may_fail_over(Operation::Create, &error, false));
+    assert!(!may_fail_over(Operation::Modify, &error, false));
+    assert!(!may_fail_over(Operation::Replace, &error, false));
+    assert!(!may_fail_over(Operation::Delete, &error, false));
+}
+
+#[test]
+fn write_may_not_fail_over_on_service_unavailable() {
+    let error = Error::Method(api::Error::new(
+        503,
+        api::ErrorCode::HttpServiceUnavailable,
+        "service unavailable",
+    ));
+
+    assert!(!may_fail_over(Operation::Create, &error, false));
+}
+
+#[test]
+fn write_may_fail_over_if_no_connection_could_be_established() {
+    let error = Error::Communication("connection refused".to_owned());
+
+    assert!(may_fail_over(Operation::Create, &error, true));
+    assert!(may_fail_over(Operation::Delete, &error, true));
+}
+
+#[test]
+fn read_may_not_fail_over_on_not_found() {
+    let error = Error::Method(api::Error::new(
+        404,
+        api::ErrorCode::ArangoDocumentNotFound,
+        "document not found",
+    ));
+
+    assert!(!may_fail_over(Operation::Read, &error, false));
+}
+
+#[test]
+fn io_error_marked_as_connect_failure_is_detected() {
+    let cause = io::Error::new(io::ErrorKind::ConnectionRefused, "connection refused");
+    let error = hyper::Error::Io(io::Error::new(
+        io::ErrorKind::ConnectionRefused,
+        ConnectFailure(cause),
+    ));
+
+    assert!(is_connect_failure(&error));
+}
+
+#[test]
+fn io_error_not_marked_as_connect_failure_is_not_detected() {
+    let error = hyper::Error::Io(io::Error::new(
+        io::ErrorKind::ConnectionReset,
+        "connection reset by peer",
+    ));
+
+    assert!(!is_connect_failure(&error));
+}
+
+#[test]
+fn unauthorized_error_is_detected() {
+    let error = Error::Method(api::Error::new(
+        401,
+        api::ErrorCode::HttpUnauthorized,
+        "not authorized to execute this request",
+    ));
+
+    assert!(is_unauthorized(&error));
+}
+
+#[test]
+fn not_found_error_is_not_unauthorized() {
+    let error = Error::Method(api::Error::new(
+        404,
+        api::ErrorCode::ArangoDocumentNotFound,
+        "document not found",
+    ));
+
+    assert!(!is_unauthorized(&error));
+}
+
+#[test]
+fn communication_error_is_not_unauthorized() {
+    let error = Error::Communication("connection refused".to_owned());
+
+    assert!(!is_unauthorized(&error));
+}
+
+fn jwt_connector(core: &reactor::Core) -> JsonHttpConnector {
+    let datasource = DataSource::from_str("http://localhost:8529")
+        .unwrap()
+        .with_authentication(Authentication::Jwt(Credentials::new(
+            "root".to_owned(),
+            "s3cur3".to_owned(),
+        )));
+    JsonHttpConnector::new(datasource, &core.handle()).unwrap()
+}
+
+#[test]
+fn prepare_request_without_token_fails_for_jwt_authentication() {
+    let core = reactor::Core::new().unwrap();
+    let connector = jwt_connector(&core);
+    let connection = connector.system_connection();
+    let prepared = Prepared {
+        operation: Operation::Read,
+        path: "/_api/collection",
+        params: vec![],
+        content: None,
+    };
+
+    let result = connection.prepare_request(&prepared);
+
+    match result {
+        Err(Error::NotAuthenticated(_)) => {},
+        _ => panic!("NotAuthenticated error expected"),
+    }
+}
+
+#[test]
+fn prepare_login_request_without_token_for_jwt_authentication() {
+    let core = reactor::Core::new().unwrap();
+    let connector = jwt_connector(&core);
+    let connection = connector.system_connection();
+    let prepared = Prepared {
+        operation: Operation::Create,
+        path: PATH_OPEN_AUTH,
+        params: vec![],
+        content: Some(json!({ "username": "root", "password": "s3cur3" })),
+    };
+
+    let request = connection.prepare_request(&prepared).unwrap();
+
+    assert_eq!(None, request.headers().get::<Authorization<Bearer>>());
+}
+
+#[test]
+fn token_set_on_connector_is_shared_with_existing_connections() {
+    let core = reactor::Core::new().unwrap();
+    let mut connector = jwt_connector(&core);
+    let connection = connector.connection("customers");
+    assert_eq!(None, connection.token());
+
+    connector.use_auth_token("abc.def.ghi".to_owned());
+
+    assert_eq!(Some("abc.def.ghi".to_owned()), connection.token());
+    let prepared = Prepared {
+        operation: Operation::Read,
+        path: "/_api/collection",
+        params: vec![],
+        content: None,
+    };
+    let request = connection.prepare_request(&prepared).unwrap();
+    assert_eq!(
+        Some(&Authorization(Bearer {
+            token: "abc.def.ghi".to_owned()
+        })),
+        request.headers().get::<Authorization<Bearer>>()
+    );
+
+    connector.invalidate_auth_token();
+
+    assert_eq!(None, connection.token());
+}
+
+#[test]
+fn content_format_of_velocypack_response() {
+    let mut headers = Headers::new();
+    headers.set_raw("Content-Type", CONTENT_TYPE_VELOCYPACK);
+
+    assert_eq!(ContentFormat::VelocyPack, content_format_of(&headers));
+}
+
+#[test]
+fn content_format_of_json_response() {
+    let mut headers = Headers::new();
+    headers.set(ContentType::json());
+
+    assert_eq!(ContentFormat::Json, content_format_of(&headers));
+}
+
 #[test]
 fn header_user_agent_for_default_rincon_user_agent() {
     let agent = header_user_agent_for(&RinconUserAgent);
@@ -234,3 +501,279 @@ fn header_user_agent_for_my_user_agent() {
         )
     );
 }
+
+#[derive(Debug)]
+struct RecordingInterceptor {
+    name: &'static str,
+    calls: Rc<RefCell<Vec<String>>>,
+}
+
+impl Interceptor for RecordingInterceptor {
+    fn intercept_request(&self, _request: &mut Request) -> Result<(), Error> {
+        self.calls
+            .borrow_mut()
+            .push(format!("{} request", self.name));
+        Ok(())
+    }
+
+    fn intercept_response(&self, response: &InterceptedResponse) {
+        self.calls.borrow_mut().push(format!(
+            "{} response {}",
+            self.name,
+            response.status_code().as_u16()
+        ));
+    }
+}
+
+#[derive(Debug)]
+struct RejectingInterceptor;
+
+impl Interceptor for RejectingInterceptor {
+    fn intercept_request(&self, _request: &mut Request) -> Result<(), Error> {
+        Err(Error::Communication("request rejected".to_owned()))
+    }
+}
+
+fn prepared_request() -> Request {
+    let core = reactor::Core::new().unwrap();
+    let datasource = DataSource::from_str("http://localhost:8529").unwrap();
+    let connector = JsonHttpConnector::new(datasource, &core.handle()).unwrap();
+    let prepared = Prepared {
+        operation: Operation::Read,
+        path: "/_api/collection",
+        params: vec![],
+        content: None,
+    };
+    connector
+        .system_connection()
+        .prepare_request(&prepared)
+        .unwrap()
+}
+
+#[test]
+fn set_header_interceptor_sets_header_field() {
+    let interceptor = SetHeader::new("x-request-id", "4711");
+    let mut request = prepared_request();
+
+    interceptor.intercept_request(&mut request).unwrap();
+
+    assert_eq!(
+        Some(&b"4711"[..]),
+        request
+            .headers()
+            .get_raw("x-request-id")
+            .and_then(|raw| raw.one())
+    );
+}
+
+#[test]
+fn request_interceptors_are_applied_in_order() {
+    let interceptors: Vec<Arc<Interceptor>> = vec![
+        Arc::new(SetHeader::new("x-request-id", "1")),
+        Arc::new(SetHeader::new("x-request-id", "2")),
+    ];
+    let mut request = prepared_request();
+
+    intercept_request(&interceptors, &mut request).unwrap();
+
+    assert_eq!(
+        Some(&b"2"[..]),
+        request
+            .headers()
+            .get_raw("x-request-id")
+            .and_then(|raw| raw.one())
+    );
+}
+
+#[test]
+fn request_interceptor_error_stops_chain() {
+    let calls = Rc::new(RefCell::new(Vec::new()));
+    let interceptors: Vec<Arc<Interceptor>> = vec![
+        Arc::new(RejectingInterceptor),
+        Arc::new(RecordingInterceptor {
+            name: "first",
+            calls: calls.clone(),
+        }),
+    ];
+    let mut request = prepared_request();
+
+    let result = intercept_request(&interceptors, &mut request);
+
+    match result {
+        Err(Error::Communication(ref message)) => assert_eq!("request rejected", message),
+        _ => panic!("Communication error expected"),
+    }
+    assert!(calls.borrow().is_empty());
+}
+
+#[test]
+fn response_interceptors_are_applied_in_reverse_order() {
+    let calls = Rc::new(RefCell::new(Vec::new()));
+    let interceptors: Vec<Arc<Interceptor>> = vec![
+        Arc::new(RecordingInterceptor {
+            name: "first",
+            calls: calls.clone(),
+        }),
+        Arc::new(RecordingInterceptor {
+            name: "second",
+            calls: calls.clone(),
+        }),
+    ];
+    let uri = Uri::from_str("http://localhost:8529/_api/version").unwrap();
+    let headers = Headers::new();
+    let response =
+        InterceptedResponse::new(&hyper::Method::Get, &uri, StatusCode::Ok, &headers, b"{}");
+
+    intercept_response(&interceptors, &response);
+
+    assert_eq!(
+        vec!["second response 200", "first response 200"],
+        *calls.borrow()
+    );
+}
+
+#[test]
+fn interceptors_are_passed_to_connections() {
+    let core = reactor::Core::new().unwrap();
+    let datasource = DataSource::from_str("http://localhost:8529").unwrap();
+    let connector = JsonHttpConnector::new(datasource, &core.handle())
+        .unwrap()
+        .with_interceptor(SetHeader::new("x-request-id", "1"))
+        .with_interceptor(SetHeader::new("x-arango-frontend", "true"));
+
+    let connection = connector.connection("customers");
+
+    assert_eq!(2, connection.interceptors.len());
+}
+
+#[derive(Debug, Default)]
+struct RecordingSink {
+    finished: RefCell<Vec<(String, MethodMetrics)>>,
+}
+
+impl MetricsSink for RecordingSink {
+    fn span_finished(&self, span: &MethodSpan, metrics: &MethodMetrics) {
+        self.finished
+            .borrow_mut()
+            .push((span.path().to_owned(), metrics.clone()));
+    }
+}
+
+#[test]
+fn finish_span_reports_metrics_to_sink() {
+    let sink = Arc::new(RecordingSink::default());
+    let span = MethodSpan::start("GetResult", Operation::Read, "/_api/version");
+    let error = Error::Method(api::Error::new(
+        401,
+        api::ErrorCode::HttpUnauthorized,
+        "not authorized to execute this request",
+    ));
+
+    finish_span(
+        Some((sink.clone() as Arc<MetricsSink>, span)),
+        Some(401),
+        0,
+        Some(87),
+        Some(&error),
+    );
+
+    let finished = sink.finished.borrow();
+    assert_eq!(1, finished.len());
+    assert_eq!("/_api/version", finished[0].0);
+    assert_eq!(Some(401), finished[0].1.status_code());
+    assert_eq!(Some(87), finished[0].1.response_size());
+    assert_eq!(
+        Some(&ErrorKind::Method(api::ErrorCode::HttpUnauthorized)),
+        finished[0].1.error_kind()
+    );
+}
+
+#[test]
+fn metrics_sink_is_passed_to_connections() {
+    let core = reactor::Core::new().unwrap();
+    let datasource = DataSource::from_str("http://localhost:8529").unwrap();
+    let connector = JsonHttpConnector::new(datasource, &core.handle())
+        .unwrap()
+        .with_metrics_sink(RecordingSink::default());
+
+    let connection = connector.connection("customers");
+
+    assert!(connection.metrics_sink.is_some());
+}
+
+#[test]
+fn format_seconds_with_milliseconds() {
+    assert_eq!("30.000", format_seconds(&Duration::from_secs(30)));
+    assert_eq!("1.250", format_seconds(&Duration::from_millis(1_250)));
+    assert_eq!("0.005", format_seconds(&Duration::from_millis(5)));
+}
+
+#[test]
+fn timeout_for_method_defaults_to_timeout_of_datasource() {
+    let core = reactor::Core::new().unwrap();
+    let datasource = DataSource::from_str("http://localhost:8529")
+        .unwrap()
+        .with_timeout(Duration::from_secs(12));
+    let connector = JsonHttpConnector::new(datasource, &core.handle()).unwrap();
+    let prepared = Prepared {
+        operation: Operation::Read,
+        path: "/_api/collection",
+        params: vec![],
+        content: None,
+    };
+
+    let timeout = connector.system_connection().timeout_for(&prepared);
+
+    assert_eq!(Duration::from_secs(12), timeout);
+}
+
+#[test]
+fn timeout_for_method_with_timeout_overrides_timeout_of_datasource() {
+    let core = reactor::Core::new().unwrap();
+    let datasource = DataSource::from_str("http://localhost:8529")
+        .unwrap()
+        .with_timeout(Duration::from_secs(12));
+    let connector = JsonHttpConnector::new(datasource, &core.handle()).unwrap();
+    let prepared = WithTimeout::new(
+        Prepared {
+            operation: Operation::Read,
+            path: "/_api/collection",
+            params: vec![],
+            content: None,
+        },
+        Duration::from_millis(500),
+    );
+
+    let timeout = connector.system_connection().timeout_for(&prepared);
+
+    assert_eq!(Duration::from_millis(500), timeout);
+}
+
+#[test]
+fn prepare_request_sets_queue_time_header_to_timeout() {
+    let core = reactor::Core::new().unwrap();
+    let datasource = DataSource::from_str("http://localhost:8529").unwrap();
+    let connector = JsonHttpConnector::new(datasource, &core.handle()).unwrap();
+    let prepared = WithTimeout::new(
+        Prepared {
+            operation: Operation::Read,
+            path: "/_api/collection",
+            params: vec![],
+            content: None,
+        },
+        Duration::from_millis(2_500),
+    );
+
+    let request = connector
+        .system_connection()
+        .prepare_request(&prepared)
+        .unwrap();
+
+    assert_eq!(
+        Some(&b"2.500"[..]),
+        request
+            .headers()
+            .get_raw(HEADER_QUEUE_TIME_SECONDS)
+            .and_then(|raw| raw.one())
+    );
+}
@@ -7,12 +7,21 @@
 //! The `Connector` trait is defined in the [`rincon_core`] API. This crate
 //! provides some default implementations to be used out of the box.
 //!
-//! Currently there is only one `Connector` implementation provided:
+//! Currently there are two `Connector` implementations provided:
 //!
-//! * `JsonHttpConnector` : using [JSON] over HTTP/HTTPS
+//! * `JsonHttpConnector` : using [JSON] or [VelocyPack] over HTTP/HTTPS
+//! * `VstConnector` : using [VelocyPack] over VelocyStream
 //!
 //! but more are planned to be added in the future.
 //!
+//! Additionally the `RetryConnector` can wrap any other `Connector` to retry
+//! method calls that failed with a transient error.
+//!
+//! For testing code that uses the driver without a running server the
+//! `MockConnector` records method calls and answers them with canned
+//! responses or with the `FakeArangoDb`, which keeps collections, documents
+//! and cursors in memory.
+//!
 //! # Example
 //!
 //! Using a `Connector` is straight forward. Here is an example of how to use
@@ -57,7 +66,16 @@
 //! on what API you want to use. For more details see the documentation of
 //! those crates.
 //!
-//! TODO: add example on how to use connectors with JWT authentication.
+//! To use JWT authentication instead of basic authentication the
+//! `DataSource` is configured with `Authentication::Jwt`. The connector then
+//! logs in with the given credentials before the first method call and keeps
+//! the token up to date. There is no need to execute the `Authenticate` method
+//! manually.
+//!
+//! Admin tools and services that know the JWT secret of the server can use
+//! `Authentication::JwtSecret` instead, e.g. by calling
+//! `DataSource::with_jwt_secret`. Then the connector generates and signs
+//! superuser tokens locally and no password needs to be stored at all.
 //!
 //! [ArangoDB]: https://www.arangodb.com
 //! [JSON]: https://json.org
@@ -74,18 +92,33 @@
     unused_qualifications
 )]
 
+extern crate byteorder;
+extern crate bytes;
 extern crate futures;
 extern crate hyper;
 extern crate hyper_timeout;
-extern crate hyper_tls;
 #[macro_use]
 extern crate log;
-extern crate native_tls;
+extern crate rand;
+extern crate rustls;
+extern crate rustls_native_certs;
 extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+#[macro_use]
 extern crate serde_json;
 extern crate tokio_core;
+extern crate tokio_io;
+extern crate tokio_rustls;
 extern crate url;
+extern crate webpki;
+extern crate webpki_roots;
 
 extern crate rincon_core;
 
+mod deadline;
 pub mod http;
+pub mod mock;
+pub mod retry;
+pub mod tls;
+pub mod vst;
@@ -0,0 +1,537 @@
+//! An in-memory fake of an ArangoDB server.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+use serde_json::{Map, Value};
+
+use rincon_core::api::method::Operation;
+use rincon_core::api::types::Value as ParameterValue;
+use rincon_core::api::ErrorCode;
+
+use super::{Request, Responder, Response};
+
+const COLLECTION_TYPE_DOCUMENTS: u64 = 2;
+const COLLECTION_TYPE_EDGES: u64 = 3;
+const COLLECTION_STATUS_LOADED: u64 = 3;
+const DEFAULT_BATCH_SIZE: usize = 1000;
+
+/// A `Responder` that keeps collections, documents and cursors in memory.
+///
+/// The `FakeArangoDb` understands the following method calls:
+///
+/// * listing, creating, getting, counting, truncating and dropping
+///   collections
+/// * inserting, getting, replacing, modifying and deleting single documents
+///   and inserting multiple documents
+/// * creating, reading and deleting cursors
+///
+/// Databases do not need to be created. Each database is created when it
+/// is used for the first time.
+///
+/// As the `FakeArangoDb` can not execute AQL, cursors only support queries
+/// of the form `FOR x IN collection RETURN x`, where the collection may be
+/// given as bind parameter. Results for any other query can be registered
+/// with the `with_query_result()` function.
+///
+/// Method calls that are not understood are answered with the status code
+/// 501 (not implemented).
+#[derive(Debug, Default)]
+pub struct FakeArangoDb {
+    databases: HashMap<String, Database>,
+    query_results: HashMap<String, Vec<Value>>,
+    cursors: HashMap<String, VecDeque<Value>>,
+    cursor_batch_sizes: HashMap<String, usize>,
+    next_id: u64,
+}
+
+#[derive(Debug, Default)]
+struct Database {
+    collections: BTreeMap<String, Collection>,
+}
+
+#[derive(Debug)]
+struct Collection {
+    id: String,
+    name: String,
+    kind: u64,
+    documents: BTreeMap<String, Value>,
+}
+
+impl Collection {
+    fn info(&self) -> Value {
+        json!({
+            "id": self.id,
+            "name": self.name,
+            "type": self.kind,
+            "status": COLLECTION_STATUS_LOADED,
+            "isSystem": self.name.starts_with('_'),
+            "waitForSync": false,
+        })
+    }
+}
+
+impl FakeArangoDb {
+    /// Creates a new `FakeArangoDb` without any collections.
+    pub fn new() -> Self {
+        FakeArangoDb::default()
+    }
+
+    /// Creates a document collection with the given name in the given
+    /// database.
+    pub fn with_collection(mut self, database: &str, collection: &str) -> Self {
+        self.create_collection(database, collection, COLLECTION_TYPE_DOCUMENTS);
+        self
+    }
+
+    /// Registers the results that shall be returned for the given query
+    /// string.
+    pub fn with_query_result<Q, R>(mut self, query: Q, results: R) -> Self
+    where
+        Q: Into<String>,
+        R: IntoIterator<Item = Value>,
+    {
+        self.query_results
+            .insert(query.into(), results.into_iter().collect());
+        self
+    }
+
+    /// Returns the documents of the given collection ordered by their keys.
+    pub fn documents(&self, database: &str, collection: &str) -> Vec<Value> {
+        self.databases
+            .get(database)
+            .and_then(|database| database.collections.get(collection))
+            .map(|collection| collection.documents.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    fn next_id(&mut self) -> String {
+        self.next_id += 1;
+        self.next_id.to_string()
+    }
+
+    fn create_collection(&mut self, database: &str, name: &str, kind: u64) -> Value {
+        let id = self.next_id();
+        let collection = Collection {
+            id,
+            name: name.to_owned(),
+            kind,
+            documents: BTreeMap::new(),
+        };
+        let info = collection.info();
+        self.databases
+            .entry(database.to_owned())
+            .or_insert_with(Database::default)
+            .collections
+            .insert(name.to_owned(), collection);
+        info
+    }
+
+    fn collection_mut(&mut self, database: &str, name: &str) -> Result<&mut Collection, Response> {
+        self.databases
+            .get_mut(database)
+            .and_then(|database| database.collections.get_mut(name))
+            .ok_or_else(|| collection_not_found(name))
+    }
+
+    fn handle_collections(
+        &mut self,
+        request: &Request,
+        path: &[&str],
+    ) -> Result<Response, Response> {
+        let database = request.database();
+        match (request.operation(), path) {
+            (Operation::Read, &[]) => {
+                let collections: Vec<Value> = self
+                    .databases
+                    .get(database)
+                    .map(|database| {
+                        database
+                            .collections
+                            .values()
+                            .map(Collection::info)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                Ok(Response::ok(json!({ "result": collections })))
+            },
+            (Operation::Create, &[]) => {
+                let content = request.content().cloned().unwrap_or(Value::Null);
+                let name = match content.get("name").and_then(Value::as_str) {
+                    Some(name) => name.to_owned(),
+                    None => return Err(bad_parameter("collection name is missing")),
+                };
+                let kind = content
+                    .get("type")
+                    .and_then(Value::as_u64)
+                    .unwrap_or(COLLECTION_TYPE_DOCUMENTS);
+                let exists = self
+                    .databases
+                    .get(database)
+                    .map_or(false, |database| database.collections.contains_key(&name));
+                if exists {
+                    return Err(Response::error(
+                        409,
+                        ErrorCode::ArangoDuplicateName,
+                        "duplicate name",
+                    ));
+                }
+                if kind != COLLECTION_TYPE_DOCUMENTS && kind != COLLECTION_TYPE_EDGES {
+                    return Err(bad_parameter("invalid collection type"));
+                }
+                Ok(Response::ok(self.create_collection(database, &name, kind)))
+            },
+            (Operation::Read, &[name]) => {
+                Ok(Response::ok(self.collection_mut(database, name)?.info()))
+            },
+            (Operation::Read, &[name, "count"]) => {
+                let collection = self.collection_mut(database, name)?;
+                let mut info = collection.info();
+                info["count"] = json!(collection.documents.len());
+                Ok(Response::ok(info))
+            },
+            (Operation::Replace, &[name, "truncate"]) => {
+                let collection = self.collection_mut(database, name)?;
+                collection.documents.clear();
+                Ok(Response::ok(collection.info()))
+            },
+            (Operation::Delete, &[name]) => {
+                let id = self.collection_mut(database, name)?.id.clone();
+                if let Some(database) = self.databases.get_mut(database) {
+                    database.collections.remove(name);
+                }
+                Ok(Response::ok(json!({ "id": id })))
+            },
+            _ => Err(not_implemented(request)),
+        }
+    }
+
+    fn handle_documents(&mut self, request: &Request, path: &[&str]) -> Result<Response, Response> {
+        let database = request.database().to_owned();
+        let return_new = is_parameter_set(request, "returnNew");
+        let return_old = is_parameter_set(request, "returnOld");
+        match (request.operation(), path) {
+            (Operation::Create, &[collection]) => match request.content() {
+                Some(&Value::Array(ref documents)) => {
+                    let mut results = Vec::with_capacity(documents.len());
+                    for document in documents {
+                        let result = self.insert_document(&database, collection, document.clone());
+                        results.push(match result {
+                            Ok(stored) => header_of(&stored, None, None, return_new),
+                            Err(error) => error.body().clone(),
+                        });
+                    }
+                    Ok(Response::new(202, Value::Array(results)))
+                },
+                Some(document) => {
+                    let stored = self.insert_document(&database, collection, document.clone())?;
+                    Ok(Response::new(
+                        202,
+                        header_of(&stored, None, None, return_new),
+                    ))
+                },
+                None => Err(bad_parameter("document is missing")),
+            },
+            (Operation::Read, &[collection, key]) | (Operation::ReadHeader, &[collection, key]) => {
+                let collection = self.collection_mut(&database, collection)?;
+                collection
+                    .documents
+                    .get(key)
+                    .cloned()
+                    .map(Response::ok)
+                    .ok_or_else(document_not_found)
+            },
+            (Operation::Replace, &[collection, key]) | (Operation::Modify, &[collection, key]) => {
+                let update = match request.content() {
+                    Some(&Value::Object(ref fields)) => fields.clone(),
+                    _ => return Err(bad_parameter("document must be an object")),
+                };
+                let merge = request.operation() == Operation::Modify;
+                let keep_null = !is_parameter_unset(request, "keepNull");
+                let revision = self.next_id();
+                let collection = self.collection_mut(&database, collection)?;
+                let old = collection
+                    .documents
+                    .get(key)
+                    .cloned()
+                    .ok_or_else(document_not_found)?;
+                let mut new = if merge {
+                    old.as_object().cloned().unwrap_or_default()
+                } else {
+                    Map::new()
+                };
+                for (name, value) in update {
+                    if merge && !keep_null && value.is_null() {
+                        new.remove(&name);
+                    } else {
+                        new.insert(name, value);
+                    }
+                }
+                for field in &["_id", "_key"] {
+                    new.insert((*field).to_owned(), old[*field].clone());
+                }
+                new.insert("_rev".to_owned(), Value::String(revision));
+                let new = Value::Object(new);
+                collection.documents.insert(key.to_owned(), new.clone());
+                Ok(Response::new(
+                    202,
+                    header_of(&new, Some(&old), Some(return_old), return_new),
+                ))
+            },
+            (Operation::Delete, &[collection, key]) => {
+                let collection = self.collection_mut(&database, collection)?;
+                let old = collection
+                    .documents
+                    .remove(key)
+                    .ok_or_else(document_not_found)?;
+                let mut header = header_of(&old, None, None, false);
+                if return_old {
+                    header["old"] = old;
+                }
+                Ok(Response::new(202, header))
+            },
+            _ => Err(not_implemented(request)),
+        }
+    }
+
+    fn insert_document(
+        &mut self,
+        database: &str,
+        collection_name: &str,
+        document: Value,
+    ) -> Result<Value, Response> {
+        let mut fields = match document {
+            Value::Object(fields) => fields,
+            _ => return Err(bad_parameter("document must be an object")),
+        };
+        let key = match fields.get("_key") {
+            Some(&Value::String(ref key)) => key.clone(),
+            Some(_) => return Err(bad_parameter("document key must be a string")),
+            None => self.next_id(),
+        };
+        let revision = self.next_id();
+        let collection = self.collection_mut(database, collection_name)?;
+        if collection.documents.contains_key(&key) {
+            return Err(Response::error(
+                409,
+                ErrorCode::ArangoUniqueConstraintViolated,
+                "unique constraint violated",
+            ));
+        }
+        fields.insert(
+            "_id".to_owned(),
+            Value::String(format!("{}/{}", collection_name, key)),
+        );
+        fields.insert("_key".to_owned(), Value::String(key.clone()));
+        fields.insert("_rev".to_owned(), Value::String(revision));
+        let document = Value::Object(fields);
+        collection.documents.insert(key, document.clone());
+        Ok(document)
+    }
+
+    fn handle_cursors(&mut self, request: &Request, path: &[&str]) -> Result<Response, Response> {
+        match (request.operation(), path) {
+            (Operation::Create, &[]) => {
+                let content = request.content().cloned().unwrap_or(Value::Null);
+                let query = content
+                    .get("query")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                let results =
+                    self.execute_query(request.database(), query, &content["bindVars"])?;
+                let count = results.len();
+                let batch_size = content
+                    .get("batchSize")
+                    .and_then(Value::as_u64)
+                    .map_or(DEFAULT_BATCH_SIZE, |batch_size| batch_size as usize);
+                let id = self.next_id();
+                self.cursors
+                    .insert(id.clone(), results.into_iter().collect());
+                self.cursor_batch_sizes.insert(id.clone(), batch_size);
+                let mut cursor = self.next_batch(&id, 201)?;
+                if content.get("count").and_then(Value::as_bool) == Some(true) {
+                    cursor.body["count"] = json!(count);
+                }
+                Ok(cursor)
+            },
+            (Operation::Replace, &[id]) => self.next_batch(id, 200),
+            (Operation::Delete, &[id]) => {
+                self.cursor_batch_sizes.remove(id);
+                match self.cursors.remove(id) {
+                    Some(_) => Ok(Response::new(202, json!({ "id": id }))),
+                    None => Err(cursor_not_found()),
+                }
+            },
+            _ => Err(not_implemented(request)),
+        }
+    }
+
+    fn execute_query(
+        &mut self,
+        database: &str,
+        query: &str,
+        bind_vars: &Value,
+    ) -> Result<Vec<Value>, Response> {
+        if let Some(results) = self.query_results.get(query) {
+            return Ok(results.clone());
+        }
+        let words: Vec<&str> = query.split_whitespace().collect();
+        match words.as_slice() {
+            &[for_kw, variable, in_kw, collection, return_kw, returned]
+                if for_kw.eq_ignore_ascii_case("FOR")
+                    && in_kw.eq_ignore_ascii_case("IN")
+                    && return_kw.eq_ignore_ascii_case("RETURN")
+                    && variable == returned =>
+            {
+                let collection = if collection.starts_with("@@") {
+                    bind_vars
+                        .get(&collection[1..])
+                        .and_then(Value::as_str)
+                        .ok_or_else(|| {
+                            Response::error(
+                                400,
+                                ErrorCode::QueryBindParameterMissing,
+                                format!("bind parameter '{}' was not declared", &collection[2..]),
+                            )
+                        })?
+                } else {
+                    collection
+                };
+                Ok(self
+                    .collection_mut(database, collection)?
+                    .documents
+                    .values()
+                    .cloned()
+                    .collect())
+            },
+            _ => Err(Response::error(
+                400,
+                ErrorCode::QueryParse,
+                format!("query not supported by FakeArangoDb: {}", query),
+            )),
+        }
+    }
+
+    fn next_batch(&mut self, id: &str, status_code: u16) -> Result<Response, Response> {
+        let batch_size = self
+            .cursor_batch_sizes
+            .get(id)
+            .cloned()
+            .unwrap_or(DEFAULT_BATCH_SIZE);
+        let (batch, has_more) = match self.cursors.get_mut(id) {
+            Some(results) => {
+                let batch_len = batch_size.min(results.len());
+                let batch: Vec<Value> = results.drain(..batch_len).collect();
+                (batch, !results.is_empty())
+            },
+            None => return Err(cursor_not_found()),
+        };
+        let mut cursor = json!({
+            "result": batch,
+            "hasMore": has_more,
+            "cached": false,
+            "error": false,
+            "code": status_code,
+        });
+        if has_more {
+            cursor["id"] = Value::String(id.to_owned());
+        } else {
+            self.cursors.remove(id);
+            self.cursor_batch_sizes.remove(id);
+        }
+        Ok(Response::new(status_code, cursor))
+    }
+}
+
+impl Responder for FakeArangoDb {
+    fn respond(&mut self, request: &Request) -> Response {
+        let segments: Vec<&str> = request
+            .path()
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect();
+        let result = match segments.split_first() {
+            Some((&"_api", rest)) => match rest.split_first() {
+                Some((&"collection", path)) => self.handle_collections(request, path),
+                Some((&"document", path)) => self.handle_documents(request, path),
+                Some((&"cursor", path)) => self.handle_cursors(request, path),
+                _ => Err(not_implemented(request)),
+            },
+            _ => Err(not_implemented(request)),
+        };
+        result.unwrap_or_else(|error| error)
+    }
+}
+
+fn header_of(
+    document: &Value,
+    old: Option<&Value>,
+    return_old: Option<bool>,
+    return_new: bool,
+) -> Value {
+    let mut header = json!({
+        "_id": document["_id"],
+        "_key": document["_key"],
+        "_rev": document["_rev"],
+    });
+    if let Some(old) = old {
+        header["_oldRev"] = old["_rev"].clone();
+        if return_old == Some(true) {
+            header["old"] = old.clone();
+        }
+    }
+    if return_new {
+        header["new"] = document.clone();
+    }
+    header
+}
+
+fn is_parameter_set(request: &Request, name: &str) -> bool {
+    parameter_of(request, name) == Some(true)
+}
+
+fn is_parameter_unset(request: &Request, name: &str) -> bool {
+    parameter_of(request, name) == Some(false)
+}
+
+fn parameter_of(request: &Request, name: &str) -> Option<bool> {
+    request
+        .parameters()
+        .iter()
+        .find(|&&(ref param_name, _)| param_name == name)
+        .and_then(|&(_, ref value)| match *value {
+            ParameterValue::Bool(value) => Some(value),
+            _ => None,
+        })
+}
+
+fn not_implemented(request: &Request) -> Response {
+    Response::error(
+        501,
+        ErrorCode::NotImplemented,
+        format!(
+            "{:?} {} is not supported by FakeArangoDb",
+            request.operation(),
+            request.path()
+        ),
+    )
+}
+
+fn bad_parameter(message: &str) -> Response {
+    Response::error(400, ErrorCode::BadParameter, message)
+}
+
+fn collection_not_found(name: &str) -> Response {
+    Response::error(
+        404,
+        ErrorCode::ArangoCollectionNotFound,
+        format!("collection or view not found: {}", name),
+    )
+}
+
+fn document_not_found() -> Response {
+    Response::error(404, ErrorCode::ArangoDocumentNotFound, "document not found")
+}
+
+fn cursor_not_found() -> Response {
+    Response::error(404, ErrorCode::CursorNotFound, "cursor not found")
+}
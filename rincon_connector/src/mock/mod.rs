@@ -0,0 +1,429 @@
+//! A connector for testing code that uses the driver without a server.
+//!
+//! The `MockConnector` does not communicate with an [ArangoDB] server.
+//! Instead it records every method call it executes as a `Request` and
+//! answers it with a `Response` that has been prepared by the test.
+//!
+//! Responses are taken from a queue of canned responses first. If the queue
+//! is empty the `Responder` the `MockConnector` has been created with is
+//! asked for a response. The `FakeArangoDb` is a `Responder` that keeps
+//! collections, documents and cursors in memory and understands the most
+//! common method calls on them. Any closure taking a `&Request` and
+//! returning a `Response` can be used as `Responder` as well.
+//!
+//! The futures returned by the `MockConnector` are resolved immediately.
+//!
+//! # Example
+//!
+//! ```rust
+//! # extern crate rincon_core;
+//! # extern crate rincon_connector;
+//! # #[macro_use] extern crate serde_json;
+//! # extern crate futures;
+//! # use futures::Future;
+//! # use rincon_core::api::connector::{Connector, Execute};
+//! # use rincon_core::api::method::{Method, Operation, Parameters, Prepare, RpcReturnType};
+//! use rincon_connector::mock::{MockConnector, Response};
+//!
+//! # struct GetVersion;
+//! # impl Method for GetVersion {
+//! #     type Result = serde_json::Value;
+//! #     const RETURN_TYPE: RpcReturnType = RpcReturnType {
+//! #         result_field: None,
+//! #         code_field: None,
+//! #     };
+//! # }
+//! # impl Prepare for GetVersion {
+//! #     type Content = ();
+//! #     fn operation(&self) -> Operation { Operation::Read }
+//! #     fn path(&self) -> String { "/_api/version".to_owned() }
+//! #     fn parameters(&self) -> Parameters { Parameters::empty() }
+//! #     fn header(&self) -> Parameters { Parameters::empty() }
+//! #     fn content(&self) -> Option<&()> { None }
+//! # }
+//! # fn main() {
+//! let connector = MockConnector::new();
+//! connector.push_response(Response::ok(json!({ "server": "arango", "version": "3.3.9" })));
+//!
+//! let version = connector.system_connection().execute(GetVersion).wait().unwrap();
+//!
+//! assert_eq!(json!({ "server": "arango", "version": "3.3.9" }), version);
+//! assert_eq!("/_api/version", connector.requests()[0].path());
+//! # }
+//! ```
+//!
+//! [ArangoDB]: https://www.arangodb.com
+
+#[cfg(test)]
+mod tests;
+
+mod fake;
+
+pub use self::fake::FakeArangoDb;
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fmt::{self, Debug};
+use std::rc::Rc;
+use std::time::Duration;
+
+use futures::future;
+use serde_json::{self, Value};
+
+use rincon_core::api;
+use rincon_core::api::auth::Jwt;
+use rincon_core::api::connector::{Connector, Error, Execute, FutureResult};
+use rincon_core::api::method::{Method, Operation, Parameters, Prepare, RpcReturnType};
+use rincon_core::api::ErrorCode;
+use rincon_core::arango::protocol::SYSTEM_DATABASE;
+
+/// A method call as executed by the `MockConnector`.
+///
+/// It holds everything the `Prepare` implementation of the method returned.
+/// The content is converted into a JSON value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    database: String,
+    operation: Operation,
+    path: String,
+    parameters: Parameters,
+    header: Parameters,
+    content: Option<Value>,
+    line_delimited: bool,
+    timeout: Option<Duration>,
+}
+
+impl Request {
+    /// Creates a new `Request` for the given method executed on the
+    /// database with the given name.
+    pub fn from_method<M>(database: &str, method: &M) -> Result<Self, Error>
+    where
+        M: Prepare,
+    {
+        let content = match method.content() {
+            Some(content) => Some(
+                serde_json::to_value(content)
+                    .map_err(|cause| Error::Serialization(cause.to_string()))?,
+            ),
+            None => None,
+        };
+        Ok(Request {
+            database: database.to_owned(),
+            operation: method.operation(),
+            path: method.path(),
+            parameters: method.parameters(),
+            header: method.header(),
+            content,
+            line_delimited: method.is_line_delimited(),
+            timeout: method.timeout(),
+        })
+    }
+
+    /// Returns the name of the database the method has been executed on.
+    pub fn database(&self) -> &str {
+        &self.database
+    }
+
+    /// Returns the operation of the method.
+    pub fn operation(&self) -> Operation {
+        self.operation
+    }
+
+    /// Returns the resource path of the method.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the query parameters of the method.
+    pub fn parameters(&self) -> &Parameters {
+        &self.parameters
+    }
+
+    /// Returns the header parameters of the method.
+    pub fn header(&self) -> &Parameters {
+        &self.header
+    }
+
+    /// Returns the content of the method as JSON value if any.
+    pub fn content(&self) -> Option<&Value> {
+        self.content.as_ref()
+    }
+
+    /// Returns whether the content of the method is sent as line delimited
+    /// sequence of documents.
+    pub fn is_line_delimited(&self) -> bool {
+        self.line_delimited
+    }
+
+    /// Returns the timeout of the method if it overrides the timeout of the
+    /// `DataSource`.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+}
+
+/// A response to a method call executed by the `MockConnector`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    status_code: u16,
+    body: Value,
+}
+
+impl Response {
+    /// Creates a new `Response` with the given HTTP status code and body.
+    pub fn new(status_code: u16, body: Value) -> Self {
+        Response { status_code, body }
+    }
+
+    /// Creates a new `Response` with status code 200 and the given body.
+    pub fn ok(body: Value) -> Self {
+        Response::new(200, body)
+    }
+
+    /// Creates a new `Response` that signals an error in the same way as
+    /// ArangoDB does.
+    pub fn error<M>(status_code: u16, error_code: ErrorCode, message: M) -> Self
+    where
+        M: Into<String>,
+    {
+        Response::new(
+            status_code,
+            json!({
+                "error": true,
+                "code": status_code,
+                "errorNum": error_code.as_u16(),
+                "errorMessage": message.into(),
+            }),
+        )
+    }
+
+    /// Returns the HTTP status code of this response.
+    pub fn status_code(&self) -> u16 {
+        self.status_code
+    }
+
+    /// Returns the body of this response.
+    pub fn body(&self) -> &Value {
+        &self.body
+    }
+
+    /// Returns whether this response signals a successful method call.
+    pub fn is_success(&self) -> bool {
+        self.status_code >= 200 && self.status_code < 300
+    }
+}
+
+/// A `Responder` answers the requests executed by the `MockConnector`.
+pub trait Responder {
+    /// Returns the response to the given request.
+    fn respond(&mut self, request: &Request) -> Response;
+}
+
+impl<F> Responder for F
+where
+    F: FnMut(&Request) -> Response,
+{
+    fn respond(&mut self, request: &Request) -> Response {
+        self(request)
+    }
+}
+
+/// A `Connector` that records method calls and answers them with prepared
+/// responses.
+///
+/// Clones of a `MockConnector` share the recorded requests and the
+/// prepared responses.
+#[derive(Debug, Clone)]
+pub struct MockConnector {
+    state: Rc<RefCell<MockState>>,
+}
+
+impl MockConnector {
+    /// Creates a new `MockConnector` that answers requests with canned
+    /// responses only.
+    ///
+    /// Requests executed while there is no canned response queued fail with
+    /// an `Error::Communication`.
+    pub fn new() -> Self {
+        MockConnector {
+            state: Rc::new(RefCell::new(MockState {
+                requests: Vec::new(),
+                responses: VecDeque::new(),
+                responder: None,
+                token: None,
+            })),
+        }
+    }
+
+    /// Creates a new `MockConnector` that asks the given `Responder` for a
+    /// response whenever there is no canned response queued.
+    ///
+    /// The responder is called after the request has been recorded. It may
+    /// use clones of the `MockConnector`, e.g. to inspect the requests
+    /// recorded so far.
+    pub fn with_responder<R>(responder: R) -> Self
+    where
+        R: Responder + 'static,
+    {
+        let connector = MockConnector::new();
+        connector.state.borrow_mut().responder = Some(Box::new(responder));
+        connector
+    }
+
+    /// Queues the given response as answer to a future request.
+    ///
+    /// Canned responses are used in the order they have been queued.
+    pub fn push_response(&self, response: Response) {
+        self.state.borrow_mut().responses.push_back(response);
+    }
+
+    /// Returns the requests executed so far in the order of their execution.
+    pub fn requests(&self) -> Vec<Request> {
+        self.state.borrow().requests.clone()
+    }
+
+    /// Removes and returns the requests executed so far.
+    pub fn take_requests(&self) -> Vec<Request> {
+        let mut state = self.state.borrow_mut();
+        state.requests.drain(..).collect()
+    }
+
+    /// Returns the token this connector has been told to use for
+    /// authentication.
+    pub fn auth_token(&self) -> Option<Jwt> {
+        self.state.borrow().token.clone()
+    }
+}
+
+impl Default for MockConnector {
+    fn default() -> Self {
+        MockConnector::new()
+    }
+}
+
+impl Connector for MockConnector {
+    type Connection = MockConnection;
+
+    fn connection(&self, database_name: &str) -> MockConnection {
+        MockConnection {
+            database: database_name.to_owned(),
+            state: Rc::clone(&self.state),
+        }
+    }
+
+    fn system_connection(&self) -> MockConnection {
+        self.connection(SYSTEM_DATABASE)
+    }
+
+    fn use_auth_token(&mut self, token: Jwt) {
+        self.state.borrow_mut().token = Some(token);
+    }
+
+    fn invalidate_auth_token(&mut self) {
+        self.state.borrow_mut().token = None;
+    }
+}
+
+/// A connection provided by the `MockConnector`.
+#[derive(Debug, Clone)]
+pub struct MockConnection {
+    database: String,
+    state: Rc<RefCell<MockState>>,
+}
+
+impl MockConnection {
+    /// Returns the name of the database this connection is bound to.
+    pub fn database(&self) -> &str {
+        &self.database
+    }
+}
+
+impl Execute for MockConnection {
+    fn execute<M>(&self, method: M) -> FutureResult<M>
+    where
+        M: 'static + Method + Prepare,
+    {
+        let result = Request::from_method(&self.database, &method)
+            .and_then(|request| respond(&self.state, request))
+            .and_then(|response| parse_response::<M>(&method.return_type(), &response));
+        Box::new(future::result(result))
+    }
+}
+
+struct MockState {
+    requests: Vec<Request>,
+    responses: VecDeque<Response>,
+    responder: Option<Box<Responder>>,
+    token: Option<Jwt>,
+}
+
+/// Records the given request and answers it with the next canned response
+/// or asks the responder for a response.
+///
+/// The state is not borrowed while the responder is called, so the responder
+/// is free to use the connector.
+fn respond(state: &RefCell<MockState>, request: Request) -> Result<Response, Error> {
+    let canned = {
+        let mut state = state.borrow_mut();
+        state.requests.push(request.clone());
+        state.responses.pop_front()
+    };
+    let response = match canned {
+        Some(response) => Some(response),
+        None => {
+            let responder = state.borrow_mut().responder.take();
+            responder.map(|mut responder| {
+                let response = responder.respond(&request);
+                state.borrow_mut().responder = Some(responder);
+                response
+            })
+        },
+    };
+    response.ok_or_else(|| {
+        Error::Communication(format!(
+            "no response prepared for {:?} {}",
+            request.operation(),
+            request.path()
+        ))
+    })
+}
+
+impl Debug for MockState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MockState")
+            .field("requests", &self.requests)
+            .field("responses", &self.responses)
+            .field("responder", &self.responder.as_ref().map(|_| "Responder"))
+            .field("token", &self.token)
+            .finish()
+    }
+}
+
+fn parse_response<M>(
+    return_type: &RpcReturnType,
+    response: &Response,
+) -> Result<<M as Method>::Result, Error>
+where
+    M: Method,
+{
+    let body = response.body();
+    if response.is_success() {
+        let result = match (return_type.result_field, body) {
+            (Some(result_field), &Value::Object(ref fields)) => {
+                fields.get(result_field).unwrap_or(body).clone()
+            },
+            _ => body.clone(),
+        };
+        serde_json::from_value(result).map_err(|cause| Error::Deserialization(cause.to_string()))
+    } else {
+        let status_code = response.status_code();
+        let api_error = serde_json::from_value(body.clone()).unwrap_or_else(|_| {
+            api::Error::new(
+                status_code,
+                ErrorCode::from_u16(status_code),
+                body.to_string(),
+            )
+        });
+        Err(Error::Method(api_error))
+    }
+}
@@ -0,0 +1,547 @@
+use std::time::Duration;
+
+use futures::Future;
+
+use super::*;
+use rincon_core::api::method::ResultList;
+use rincon_core::arango::protocol::FIELD_RESULT;
+
+#[derive(Debug)]
+struct Call<R> {
+    operation: Operation,
+    path: String,
+    content: Option<Value>,
+    parameters: Parameters,
+    line_delimited: bool,
+    timeout: Option<Duration>,
+    return_type: RpcReturnType,
+    result: ::std::marker::PhantomData<R>,
+}
+
+fn call<R>(operation: Operation, path: &str, content: Option<Value>) -> Call<R> {
+    Call {
+        operation,
+        path: path.to_owned(),
+        content,
+        parameters: Parameters::empty(),
+        line_delimited: false,
+        timeout: None,
+        return_type: RpcReturnType {
+            result_field: None,
+            code_field: None,
+        },
+        result: ::std::marker::PhantomData,
+    }
+}
+
+impl<R> Call<R> {
+    fn with_parameter(mut self, name: &str, value: bool) -> Self {
+        self.parameters.insert(name, value);
+        self
+    }
+
+    fn with_line_delimited_content(mut self) -> Self {
+        self.line_delimited = true;
+        self
+    }
+
+    fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    fn with_result_field(mut self) -> Self {
+        self.return_type = RpcReturnType {
+            result_field: Some(FIELD_RESULT),
+            code_field: None,
+        };
+        self
+    }
+}
+
+impl<R> Method for Call<R>
+where
+    R: ::serde::de::DeserializeOwned,
+{
+    type Result = R;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: None,
+    };
+
+    fn return_type(&self) -> RpcReturnType {
+        self.return_type.clone()
+    }
+}
+
+impl<R> Prepare for Call<R> {
+    type Content = Value;
+
+    fn operation(&self) -> Operation {
+        self.operation
+    }
+
+    fn path(&self) -> String {
+        self.path.clone()
+    }
+
+    fn parameters(&self) -> Parameters {
+        self.parameters.clone()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Value> {
+        self.content.as_ref()
+    }
+
+    fn is_line_delimited(&self) -> bool {
+        self.line_delimited
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+}
+
+fn execute<R>(connector: &MockConnector, database: &str, call: Call<R>) -> Result<R, Error>
+where
+    R: ::serde::de::DeserializeOwned + 'static,
+{
+    connector.connection(database).execute(call).wait()
+}
+
+fn method_error_code<R>(result: Result<R, Error>) -> ErrorCode
+where
+    R: Debug,
+{
+    match result {
+        Err(Error::Method(ref error)) => error.error_code(),
+        _ => panic!("Expected method error, but got: {:?}", result),
+    }
+}
+
+#[test]
+fn mock_connector_records_requests() {
+    let connector = MockConnector::new();
+    connector.push_response(Response::ok(json!({ "a": 1 })));
+
+    let method = call::<Value>(
+        Operation::Create,
+        "/_api/something",
+        Some(json!({ "b": 2 })),
+    )
+    .with_parameter("waitForSync", true);
+    execute(&connector, "test_db", method).unwrap();
+
+    let requests = connector.take_requests();
+    assert_eq!(1, requests.len());
+    assert_eq!("test_db", requests[0].database());
+    assert_eq!(Operation::Create, requests[0].operation());
+    assert_eq!("/_api/something", requests[0].path());
+    assert_eq!(Some(&json!({ "b": 2 })), requests[0].content());
+    let mut expected_parameters = Parameters::new();
+    expected_parameters.insert("waitForSync", true);
+    assert_eq!(&expected_parameters, requests[0].parameters());
+    assert!(!requests[0].is_line_delimited());
+    assert_eq!(None, requests[0].timeout());
+    assert!(connector.requests().is_empty());
+}
+
+#[test]
+fn mock_connector_records_line_delimited_content_and_timeout() {
+    let connector = MockConnector::new();
+    connector.push_response(Response::ok(json!({ "created": 2 })));
+
+    let method = call::<Value>(
+        Operation::Create,
+        "/_api/import",
+        Some(json!([{ "a": 1 }, { "a": 2 }])),
+    )
+    .with_line_delimited_content()
+    .with_timeout(Duration::from_secs(90));
+    execute(&connector, "test_db", method).unwrap();
+
+    let requests = connector.requests();
+    assert!(requests[0].is_line_delimited());
+    assert_eq!(Some(Duration::from_secs(90)), requests[0].timeout());
+}
+
+#[test]
+fn mock_connector_replays_canned_responses_in_order() {
+    let connector = MockConnector::new();
+    connector.push_response(Response::ok(json!(1)));
+    connector.push_response(Response::ok(json!({ "result": 2 })));
+
+    let first = execute(
+        &connector,
+        "_system",
+        call::<u32>(Operation::Read, "/a", None),
+    );
+    let second = execute(
+        &connector,
+        "_system",
+        call::<u32>(Operation::Read, "/b", None).with_result_field(),
+    );
+
+    assert_eq!(Ok(1), first);
+    assert_eq!(Ok(2), second);
+}
+
+#[test]
+fn mock_connector_returns_method_error_for_error_response() {
+    let connector = MockConnector::new();
+    connector.push_response(Response::error(
+        404,
+        ErrorCode::ArangoDocumentNotFound,
+        "document not found",
+    ));
+
+    let result = execute(
+        &connector,
+        "_system",
+        call::<Value>(Operation::Read, "/a", None),
+    );
+
+    assert_eq!(
+        Err(Error::Method(api::Error::new(
+            404,
+            ErrorCode::ArangoDocumentNotFound,
+            "document not found",
+        ))),
+        result
+    );
+}
+
+#[test]
+fn mock_connector_without_response_fails_with_communication_error() {
+    let connector = MockConnector::new();
+
+    let result = execute(
+        &connector,
+        "_system",
+        call::<Value>(Operation::Read, "/a", None),
+    );
+
+    match result {
+        Err(Error::Communication(_)) => {},
+        _ => panic!("Expected communication error, but got: {:?}", result),
+    }
+    assert_eq!(1, connector.requests().len());
+}
+
+#[test]
+fn mock_connector_asks_responder_when_no_canned_response_is_queued() {
+    let connector = MockConnector::with_responder(|request: &Request| {
+        Response::ok(Value::String(request.path().to_owned()))
+    });
+    connector.push_response(Response::ok(json!("canned")));
+
+    let first = execute(
+        &connector,
+        "_system",
+        call::<String>(Operation::Read, "/a", None),
+    );
+    let second = execute(
+        &connector,
+        "_system",
+        call::<String>(Operation::Read, "/b", None),
+    );
+
+    assert_eq!(Ok("canned".to_owned()), first);
+    assert_eq!(Ok("/b".to_owned()), second);
+}
+
+#[test]
+fn mock_connector_responder_may_use_the_connector() {
+    let shared: Rc<RefCell<Option<MockConnector>>> = Rc::new(RefCell::new(None));
+    let responder_connector = shared.clone();
+    let connector = MockConnector::with_responder(move |_: &Request| {
+        let recorded = responder_connector
+            .borrow()
+            .as_ref()
+            .map_or(0, |connector| connector.requests().len());
+        Response::ok(json!(recorded))
+    });
+    *shared.borrow_mut() = Some(connector.clone());
+
+    let first = execute(
+        &connector,
+        "_system",
+        call::<usize>(Operation::Read, "/a", None),
+    );
+    let second = execute(
+        &connector,
+        "_system",
+        call::<usize>(Operation::Read, "/b", None),
+    );
+
+    assert_eq!(Ok(1), first);
+    assert_eq!(Ok(2), second);
+    *shared.borrow_mut() = None;
+}
+
+#[test]
+fn mock_connector_remembers_auth_token() {
+    let mut connector = MockConnector::new();
+
+    connector.use_auth_token("secret".to_owned());
+    assert_eq!(Some("secret".to_owned()), connector.auth_token());
+
+    connector.invalidate_auth_token();
+    assert_eq!(None, connector.auth_token());
+}
+
+#[test]
+fn fake_creates_lists_and_drops_collections() {
+    let connector = MockConnector::with_responder(FakeArangoDb::new());
+
+    let created = execute(
+        &connector,
+        "test_db",
+        call::<Value>(
+            Operation::Create,
+            "/_api/collection",
+            Some(json!({ "name": "customers" })),
+        ),
+    )
+    .unwrap();
+    assert_eq!("customers", created["name"]);
+    assert_eq!(2, created["type"]);
+
+    let duplicate = execute(
+        &connector,
+        "test_db",
+        call::<Value>(
+            Operation::Create,
+            "/_api/collection",
+            Some(json!({ "name": "customers" })),
+        ),
+    );
+    assert_eq!(ErrorCode::ArangoDuplicateName, method_error_code(duplicate));
+
+    let collections = execute(
+        &connector,
+        "test_db",
+        call::<Vec<Value>>(Operation::Read, "/_api/collection", None).with_result_field(),
+    )
+    .unwrap();
+    assert_eq!(1, collections.len());
+
+    let dropped = execute(
+        &connector,
+        "test_db",
+        call::<Value>(Operation::Delete, "/_api/collection/customers", None),
+    )
+    .unwrap();
+    assert_eq!(created["id"], dropped["id"]);
+
+    let not_found = execute(
+        &connector,
+        "test_db",
+        call::<Value>(Operation::Read, "/_api/collection/customers", None),
+    );
+    assert_eq!(
+        ErrorCode::ArangoCollectionNotFound,
+        method_error_code(not_found)
+    );
+}
+
+#[test]
+fn fake_inserts_gets_modifies_and_deletes_documents() {
+    let connector =
+        MockConnector::with_responder(FakeArangoDb::new().with_collection("test_db", "people"));
+
+    let header = execute(
+        &connector,
+        "test_db",
+        call::<Value>(
+            Operation::Create,
+            "/_api/document/people",
+            Some(json!({ "_key": "jane", "name": "Jane", "age": 42 })),
+        ),
+    )
+    .unwrap();
+    assert_eq!("people/jane", header["_id"]);
+
+    let modified = execute(
+        &connector,
+        "test_db",
+        call::<Value>(
+            Operation::Modify,
+            "/_api/document/people/jane",
+            Some(json!({ "age": 43 })),
+        )
+        .with_parameter("returnNew", true),
+    )
+    .unwrap();
+    assert_eq!(header["_rev"], modified["_oldRev"]);
+    assert_eq!(json!("Jane"), modified["new"]["name"]);
+    assert_eq!(json!(43), modified["new"]["age"]);
+
+    let document = execute(
+        &connector,
+        "test_db",
+        call::<Value>(Operation::Read, "/_api/document/people/jane", None),
+    )
+    .unwrap();
+    assert_eq!(modified["new"], document);
+
+    execute(
+        &connector,
+        "test_db",
+        call::<Value>(Operation::Delete, "/_api/document/people/jane", None),
+    )
+    .unwrap();
+    let not_found = execute(
+        &connector,
+        "test_db",
+        call::<Value>(Operation::Read, "/_api/document/people/jane", None),
+    );
+    assert_eq!(
+        ErrorCode::ArangoDocumentNotFound,
+        method_error_code(not_found)
+    );
+}
+
+#[test]
+fn fake_inserts_multiple_documents() {
+    let connector =
+        MockConnector::with_responder(FakeArangoDb::new().with_collection("test_db", "people"));
+
+    let results = execute(
+        &connector,
+        "test_db",
+        call::<ResultList<Value>>(
+            Operation::Create,
+            "/_api/document/people",
+            Some(json!([{ "_key": "a" }, { "_key": "a" }, { "name": "B" }])),
+        ),
+    )
+    .unwrap();
+
+    assert!(results.get(0).unwrap().is_ok());
+    assert!(results.get(1).unwrap().is_err());
+    assert!(results.get(2).unwrap().is_ok());
+}
+
+#[test]
+fn fake_counts_and_truncates_collections() {
+    let connector =
+        MockConnector::with_responder(FakeArangoDb::new().with_collection("test_db", "people"));
+    execute(
+        &connector,
+        "test_db",
+        call::<Value>(
+            Operation::Create,
+            "/_api/document/people",
+            Some(json!([{ "name": "Jane" }, { "name": "John" }])),
+        ),
+    )
+    .unwrap();
+
+    let counted = execute(
+        &connector,
+        "test_db",
+        call::<Value>(Operation::Read, "/_api/collection/people/count", None),
+    )
+    .unwrap();
+    assert_eq!(json!(2), counted["count"]);
+
+    let truncated = execute(
+        &connector,
+        "test_db",
+        call::<Value>(Operation::Replace, "/_api/collection/people/truncate", None),
+    )
+    .unwrap();
+    assert_eq!("people", truncated["name"]);
+
+    let counted = execute(
+        &connector,
+        "test_db",
+        call::<Value>(Operation::Read, "/_api/collection/people/count", None),
+    )
+    .unwrap();
+    assert_eq!(json!(0), counted["count"]);
+}
+
+#[test]
+fn fake_reads_query_results_in_batches() {
+    let fake = FakeArangoDb::new()
+        .with_collection("test_db", "people")
+        .with_query_result("RETURN 1", vec![json!(1)]);
+    let connector = MockConnector::with_responder(fake);
+    for key in &["a", "b", "c"] {
+        execute(
+            &connector,
+            "test_db",
+            call::<Value>(
+                Operation::Create,
+                "/_api/document/people",
+                Some(json!({ "_key": key })),
+            ),
+        )
+        .unwrap();
+    }
+
+    let cursor = execute(
+        &connector,
+        "test_db",
+        call::<Value>(
+            Operation::Create,
+            "/_api/cursor",
+            Some(json!({
+                "query": "FOR p IN @@coll RETURN p",
+                "bindVars": { "@coll": "people" },
+                "batchSize": 2,
+                "count": true,
+            })),
+        ),
+    )
+    .unwrap();
+    assert_eq!(json!(3), cursor["count"]);
+    assert_eq!(json!(true), cursor["hasMore"]);
+    assert_eq!(2, cursor["result"].as_array().unwrap().len());
+
+    let path = format!("/_api/cursor/{}", cursor["id"].as_str().unwrap());
+    let next = execute(
+        &connector,
+        "test_db",
+        call::<Value>(Operation::Replace, &path, None),
+    )
+    .unwrap();
+    assert_eq!(json!(false), next["hasMore"]);
+    assert_eq!(json!("c"), next["result"][0]["_key"]);
+
+    let exhausted = execute(
+        &connector,
+        "test_db",
+        call::<Value>(Operation::Replace, &path, None),
+    );
+    assert_eq!(ErrorCode::CursorNotFound, method_error_code(exhausted));
+
+    let registered = execute(
+        &connector,
+        "test_db",
+        call::<Value>(
+            Operation::Create,
+            "/_api/cursor",
+            Some(json!({ "query": "RETURN 1" })),
+        ),
+    )
+    .unwrap();
+    assert_eq!(json!([1]), registered["result"]);
+
+    let unsupported = execute(
+        &connector,
+        "test_db",
+        call::<Value>(
+            Operation::Create,
+            "/_api/cursor",
+            Some(json!({ "query": "RETURN 2" })),
+        ),
+    );
+    assert_eq!(ErrorCode::QueryParse, method_error_code(unsupported));
+}
@@ -0,0 +1,471 @@
+//! A connector that retries failed method calls.
+//!
+//! This module provides the `RetryConnector` which wraps any other
+//! `Connector` implementation and retries method calls that failed with a
+//! transient error according to a `RetryPolicy`. Between two attempts the
+//! `RetryConnector` waits for an exponentially growing backoff duration with
+//! some random jitter added.
+//!
+//! By default only methods with the operations `Operation::Read` and
+//! `Operation::ReadHeader` are retried as those are idempotent. Retrying
+//! methods that modify data must be enabled explicitly in the `RetryPolicy`.
+//!
+//! The timeout of a method call applies to all attempts together. Each
+//! attempt is only given the time that is left until the deadline of the
+//! method call, and no further attempt is made if the backoff would exceed
+//! the deadline.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! # extern crate rincon_core;
+//! # extern crate rincon_connector;
+//! # extern crate tokio_core;
+//! # use rincon_core::api::datasource::DataSource;
+//! # use rincon_connector::http::JsonHttpConnector;
+//! # use rincon_connector::retry::{RetryConnector, RetryPolicy};
+//! # use tokio_core::reactor::Core;
+//! # use std::str::FromStr;
+//! # fn main() {
+//! let core = Core::new().unwrap();
+//! let datasource = DataSource::from_str("http://localhost:8529").unwrap();
+//! let http_connector = JsonHttpConnector::new(datasource, &core.handle()).unwrap();
+//!
+//! let policy = RetryPolicy::default().with_max_retries(5);
+//! let connector = RetryConnector::new(http_connector, policy, &core.handle());
+//! # }
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures::{future, Future};
+use rand::{self, Rng};
+use tokio_core::reactor::{self, Timeout};
+
+use rincon_core::api::auth::Jwt;
+use rincon_core::api::connector::{Connector, Error, Execute, FutureResult};
+use rincon_core::api::datasource::DEFAULT_TIMEOUT;
+use rincon_core::api::method::{Method, Operation, Parameters, Prepare, RpcReturnType};
+use rincon_core::api::ErrorCode;
+
+use deadline::with_deadline;
+
+/// The default number of retries after the first attempt of a method call.
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+/// The default backoff in milliseconds before the first retry.
+pub const DEFAULT_INITIAL_BACKOFF: u64 = 100;
+/// The default maximum backoff in milliseconds between two attempts.
+pub const DEFAULT_MAX_BACKOFF: u64 = 5_000;
+
+/// Defines which method calls are retried, how often and how long to wait
+/// between two attempts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    retry_writes: bool,
+    error_codes: Vec<ErrorCode>,
+    timeout: Duration,
+}
+
+impl RetryPolicy {
+    /// Returns this `RetryPolicy` with the maximum number of retries after
+    /// the first attempt of a method call set to the given value.
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Returns this `RetryPolicy` with the backoff before the first retry
+    /// set to the given duration.
+    ///
+    /// The backoff is doubled for each further retry.
+    pub fn with_initial_backoff<D>(mut self, initial_backoff: D) -> Self
+    where
+        D: Into<Duration>,
+    {
+        self.initial_backoff = initial_backoff.into();
+        self
+    }
+
+    /// Returns this `RetryPolicy` with the maximum backoff between two
+    /// attempts set to the given duration.
+    pub fn with_max_backoff<D>(mut self, max_backoff: D) -> Self
+    where
+        D: Into<Duration>,
+    {
+        self.max_backoff = max_backoff.into();
+        self
+    }
+
+    /// Returns this `RetryPolicy` with retrying of methods that create,
+    /// modify or delete data enabled or disabled.
+    ///
+    /// Only enable retrying of writes if the methods executed are safe to be
+    /// repeated, e.g. because they are conditional on a revision.
+    pub fn with_retry_writes(mut self, retry_writes: bool) -> Self {
+        self.retry_writes = retry_writes;
+        self
+    }
+
+    /// Returns this `RetryPolicy` with the error codes for which a failed
+    /// method call is retried set to the given list.
+    pub fn with_error_codes<E>(mut self, error_codes: E) -> Self
+    where
+        E: IntoIterator<Item = ErrorCode>,
+    {
+        self.error_codes = error_codes.into_iter().collect();
+        self
+    }
+
+    /// Returns this `RetryPolicy` with the timeout for all attempts of a
+    /// method call together set to the given duration.
+    ///
+    /// This timeout is used for method calls that do not specify their own
+    /// timeout. It should be the same as the timeout of the `DataSource` of
+    /// the wrapped connector.
+    pub fn with_timeout<D>(mut self, timeout: D) -> Self
+    where
+        D: Into<Duration>,
+    {
+        self.timeout = timeout.into();
+        self
+    }
+
+    /// Returns the maximum number of retries after the first attempt of a
+    /// method call.
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Returns the backoff before the first retry.
+    pub fn initial_backoff(&self) -> &Duration {
+        &self.initial_backoff
+    }
+
+    /// Returns the maximum backoff between two attempts.
+    pub fn max_backoff(&self) -> &Duration {
+        &self.max_backoff
+    }
+
+    /// Returns whether methods that create, modify or delete data are
+    /// retried.
+    pub fn is_retry_writes(&self) -> bool {
+        self.retry_writes
+    }
+
+    /// Returns the error codes for which a failed method call is retried.
+    pub fn error_codes(&self) -> &[ErrorCode] {
+        &self.error_codes
+    }
+
+    /// Returns the timeout for all attempts of a method call that does not
+    /// specify its own timeout.
+    pub fn timeout(&self) -> &Duration {
+        &self.timeout
+    }
+
+    /// Returns whether methods with the given operation are retried.
+    pub fn is_retryable_operation(&self, operation: &Operation) -> bool {
+        match *operation {
+            Operation::Read | Operation::ReadHeader => true,
+            Operation::Create | Operation::Modify | Operation::Replace | Operation::Delete => {
+                self.retry_writes
+            },
+        }
+    }
+
+    /// Returns whether a method call that failed with the given error is
+    /// retried.
+    pub fn is_retryable_error(&self, error: &Error) -> bool {
+        match *error {
+            Error::Communication(_) | Error::Timeout(_) => true,
+            Error::Method(ref error) => self.error_codes.contains(&error.error_code()),
+            _ => false,
+        }
+    }
+
+    /// Returns the maximum backoff before the given retry without jitter.
+    ///
+    /// The first retry is number 0.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 1u32.checked_shl(retry).unwrap_or(u32::max_value());
+        self.initial_backoff
+            .checked_mul(factor)
+            .map_or(self.max_backoff, |backoff| backoff.min(self.max_backoff))
+    }
+
+    /// Returns the backoff before the given retry with random jitter
+    /// applied.
+    ///
+    /// The returned duration lies between half of the backoff and the full
+    /// backoff as returned by the `backoff()` function.
+    pub fn backoff_with_jitter(&self, retry: u32) -> Duration {
+        let backoff = duration_to_millis(&self.backoff(retry));
+        let half = backoff / 2;
+        let jitter = if half > 0 {
+            rand::thread_rng().gen_range(0, half + 1)
+        } else {
+            0
+        };
+        Duration::from_millis(backoff - half + jitter)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: DEFAULT_MAX_RETRIES,
+            initial_backoff: Duration::from_millis(DEFAULT_INITIAL_BACKOFF),
+            max_backoff: Duration::from_millis(DEFAULT_MAX_BACKOFF),
+            retry_writes: false,
+            error_codes: vec![ErrorCode::ArangoConflict, ErrorCode::ClusterTimeout],
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT),
+        }
+    }
+}
+
+/// A connector that retries failed method calls of the wrapped connector
+/// according to a `RetryPolicy`.
+#[derive(Debug)]
+pub struct RetryConnector<C> {
+    connector: C,
+    policy: Arc<RetryPolicy>,
+    reactor: reactor::Handle,
+}
+
+impl<C> RetryConnector<C>
+where
+    C: Connector,
+{
+    /// Creates a new `RetryConnector` that wraps the given connector.
+    ///
+    /// # Arguments
+    ///
+    /// * `connector` : the connector that actually executes the method calls
+    /// * `policy` : the `RetryPolicy` that defines which method calls are
+    ///   retried
+    /// * `reactor` : a handle of a `reactor::Core` instance of the
+    ///   `tokio-core` crate used to wait between two attempts.
+    pub fn new(connector: C, policy: RetryPolicy, reactor: &reactor::Handle) -> Self {
+        RetryConnector {
+            connector,
+            policy: Arc::new(policy),
+            reactor: reactor.clone(),
+        }
+    }
+
+    /// Returns the `RetryPolicy` used by this connector.
+    pub fn policy(&self) -> &RetryPolicy {
+        &self.policy
+    }
+
+    /// Returns the wrapped connector.
+    pub fn connector(&self) -> &C {
+        &self.connector
+    }
+
+    /// Unwraps the wrapped connector.
+    pub fn unwrap(self) -> C {
+        self.connector
+    }
+}
+
+impl<C> Connector for RetryConnector<C>
+where
+    C: Connector,
+{
+    type Connection = RetryConnection<C::Connection>;
+
+    fn connection(&self, database_name: &str) -> Self::Connection {
+        RetryConnection {
+            connection: Rc::new(self.connector.connection(database_name)),
+            policy: self.policy.clone(),
+            reactor: self.reactor.clone(),
+        }
+    }
+
+    fn system_connection(&self) -> Self::Connection {
+        RetryConnection {
+            connection: Rc::new(self.connector.system_connection()),
+            policy: self.policy.clone(),
+            reactor: self.reactor.clone(),
+        }
+    }
+
+    fn use_auth_token(&mut self, token: Jwt) {
+        self.connector.use_auth_token(token)
+    }
+
+    fn invalidate_auth_token(&mut self) {
+        self.connector.invalidate_auth_token()
+    }
+}
+
+/// A connection that retries failed method calls of the wrapped connection.
+#[derive(Debug)]
+pub struct RetryConnection<E> {
+    connection: Rc<E>,
+    policy: Arc<RetryPolicy>,
+    reactor: reactor::Handle,
+}
+
+impl<E> RetryConnection<E> {
+    /// Returns the `RetryPolicy` used by this connection.
+    pub fn policy(&self) -> &RetryPolicy {
+        &self.policy
+    }
+
+    /// Returns the wrapped connection.
+    pub fn connection(&self) -> &E {
+        &self.connection
+    }
+}
+
+impl<E> Execute for RetryConnection<E>
+where
+    E: 'static + Execute,
+{
+    fn execute<M>(&self, method: M) -> FutureResult<M>
+    where
+        M: 'static + Method + Prepare,
+    {
+        if self.policy.is_retryable_operation(&method.operation()) {
+            let timeout = method.timeout().unwrap_or(*self.policy.timeout());
+            let deadline = Instant::now() + timeout;
+            with_deadline(
+                execute_with_retry(
+                    self.connection.clone(),
+                    self.policy.clone(),
+                    self.reactor.clone(),
+                    Rc::new(method),
+                    deadline,
+                    0,
+                ),
+                timeout,
+                &self.reactor,
+            )
+        } else {
+            self.connection.execute(method)
+        }
+    }
+}
+
+fn execute_with_retry<E, M>(
+    connection: Rc<E>,
+    policy: Arc<RetryPolicy>,
+    reactor: reactor::Handle,
+    method: Rc<M>,
+    deadline: Instant,
+    retry: u32,
+) -> FutureResult<M>
+where
+    E: 'static + Execute,
+    M: 'static + Method + Prepare,
+{
+    Box::new(
+        connection
+            .execute(Attempt {
+                method: method.clone(),
+                deadline,
+            })
+            .or_else(move |error| -> FutureResult<M> {
+                if retry >= policy.max_retries() || !policy.is_retryable_error(&error) {
+                    return Box::new(future::err(error));
+                }
+                let backoff = policy.backoff_with_jitter(retry);
+                if Instant::now() + backoff >= deadline {
+                    debug!(
+                        "Method call failed: {}; not retrying as the deadline would be exceeded",
+                        &error
+                    );
+                    return Box::new(future::err(error));
+                }
+                debug!(
+                    "Method call failed: {}; retrying in {:?} ({} of {})",
+                    &error,
+                    &backoff,
+                    retry + 1,
+                    policy.max_retries()
+                );
+                match Timeout::new(backoff, &reactor) {
+                    Ok(timeout) => Box::new(timeout.map_err(Error::from).and_then(move |_| {
+                        execute_with_retry(connection, policy, reactor, method, deadline, retry + 1)
+                    })),
+                    Err(_) => Box::new(future::err(error)),
+                }
+            }),
+    )
+}
+
+/// One attempt of executing a method that is shared between all attempts.
+///
+/// The timeout of an attempt is the time left until the deadline of the
+/// method call.
+#[derive(Debug)]
+struct Attempt<M> {
+    method: Rc<M>,
+    deadline: Instant,
+}
+
+impl<M> Method for Attempt<M>
+where
+    M: Method,
+{
+    type Result = <M as Method>::Result;
+    const RETURN_TYPE: RpcReturnType = <M as Method>::RETURN_TYPE;
+
+    fn return_type(&self) -> RpcReturnType {
+        self.method.return_type()
+    }
+}
+
+impl<M> Prepare for Attempt<M>
+where
+    M: Prepare,
+{
+    type Content = <M as Prepare>::Content;
+
+    fn operation(&self) -> Operation {
+        self.method.operation()
+    }
+
+    fn path(&self) -> String {
+        self.method.path()
+    }
+
+    fn parameters(&self) -> Parameters {
+        self.method.parameters()
+    }
+
+    fn header(&self) -> Parameters {
+        self.method.header()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        self.method.content()
+    }
+
+    fn is_line_delimited(&self) -> bool {
+        self.method.is_line_delimited()
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        let now = Instant::now();
+        if now < self.deadline {
+            Some(self.deadline - now)
+        } else {
+            Some(Duration::from_millis(0))
+        }
+    }
+}
+
+fn duration_to_millis(duration: &Duration) -> u64 {
+    duration.as_secs() * 1_000 + u64::from(duration.subsec_nanos() / 1_000_000)
+}
@@ -0,0 +1,229 @@
+use serde_json::Value;
+use tokio_core::reactor::Core;
+
+use super::*;
+use mock::{MockConnection, MockConnector, Response};
+use rincon_core::api;
+use rincon_core::api::method::WithTimeout;
+
+struct Call {
+    operation: Operation,
+}
+
+impl Call {
+    fn read() -> Self {
+        Call {
+            operation: Operation::Read,
+        }
+    }
+
+    fn create() -> Self {
+        Call {
+            operation: Operation::Create,
+        }
+    }
+}
+
+impl Method for Call {
+    type Result = Value;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: None,
+    };
+}
+
+impl Prepare for Call {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        self.operation
+    }
+
+    fn path(&self) -> String {
+        "/_api/version".to_owned()
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+fn conflict() -> Response {
+    Response::error(409, ErrorCode::ArangoConflict, "write-write conflict")
+}
+
+fn retry_connection(
+    connector: &MockConnector,
+    policy: RetryPolicy,
+    core: &Core,
+) -> RetryConnection<MockConnection> {
+    RetryConnector::new(connector.clone(), policy, &core.handle()).system_connection()
+}
+
+fn fast_policy() -> RetryPolicy {
+    RetryPolicy::default()
+        .with_initial_backoff(Duration::from_millis(1))
+        .with_max_backoff(Duration::from_millis(1))
+}
+
+#[test]
+fn default_policy_retries_reads_only() {
+    let policy = RetryPolicy::default();
+
+    assert!(policy.is_retryable_operation(&Operation::Read));
+    assert!(policy.is_retryable_operation(&Operation::ReadHeader));
+    assert!(!policy.is_retryable_operation(&Operation::Create));
+    assert!(!policy.is_retryable_operation(&Operation::Modify));
+    assert!(!policy.is_retryable_operation(&Operation::Replace));
+    assert!(!policy.is_retryable_operation(&Operation::Delete));
+}
+
+#[test]
+fn policy_with_retry_writes_retries_all_operations() {
+    let policy = RetryPolicy::default().with_retry_writes(true);
+
+    assert!(policy.is_retryable_operation(&Operation::Read));
+    assert!(policy.is_retryable_operation(&Operation::Create));
+    assert!(policy.is_retryable_operation(&Operation::Delete));
+}
+
+#[test]
+fn default_policy_retries_transient_errors() {
+    let policy = RetryPolicy::default();
+
+    assert!(policy.is_retryable_error(&Error::Communication("connection reset".to_owned())));
+    assert!(policy.is_retryable_error(&Error::Timeout("30s".to_owned())));
+    assert!(policy.is_retryable_error(&Error::Method(api::Error::new(
+        409,
+        ErrorCode::ArangoConflict,
+        "write-write conflict",
+    ))));
+    assert!(!policy.is_retryable_error(&Error::Method(api::Error::new(
+        404,
+        ErrorCode::ArangoDocumentNotFound,
+        "document not found",
+    ))));
+    assert!(!policy.is_retryable_error(&Error::Deserialization("invalid".to_owned())));
+}
+
+#[test]
+fn backoff_grows_exponentially_up_to_max_backoff() {
+    let policy = RetryPolicy::default()
+        .with_initial_backoff(Duration::from_millis(100))
+        .with_max_backoff(Duration::from_millis(1_000));
+
+    assert_eq!(Duration::from_millis(100), policy.backoff(0));
+    assert_eq!(Duration::from_millis(200), policy.backoff(1));
+    assert_eq!(Duration::from_millis(400), policy.backoff(2));
+    assert_eq!(Duration::from_millis(800), policy.backoff(3));
+    assert_eq!(Duration::from_millis(1_000), policy.backoff(4));
+    assert_eq!(Duration::from_millis(1_000), policy.backoff(40));
+}
+
+#[test]
+fn backoff_with_jitter_lies_between_half_and_full_backoff() {
+    let policy = RetryPolicy::default().with_initial_backoff(Duration::from_millis(100));
+
+    for _ in 0..100 {
+        let backoff = policy.backoff_with_jitter(1);
+        assert!(backoff >= Duration::from_millis(100));
+        assert!(backoff <= Duration::from_millis(200));
+    }
+}
+
+#[test]
+fn retry_connection_retries_retryable_errors() {
+    let mut core = Core::new().unwrap();
+    let connector = MockConnector::new();
+    connector.push_response(conflict());
+    connector.push_response(Response::ok(json!({ "version": "3.3.9" })));
+    let connection = retry_connection(&connector, fast_policy(), &core);
+
+    let result = core.run(connection.execute(Call::read()));
+
+    assert_eq!(Ok(json!({ "version": "3.3.9" })), result);
+    assert_eq!(2, connector.requests().len());
+}
+
+#[test]
+fn retry_connection_does_not_retry_non_idempotent_operations() {
+    let mut core = Core::new().unwrap();
+    let connector = MockConnector::new();
+    connector.push_response(conflict());
+    connector.push_response(Response::ok(json!({ "version": "3.3.9" })));
+    let connection = retry_connection(&connector, fast_policy(), &core);
+
+    let result = core.run(connection.execute(Call::create()));
+
+    match result {
+        Err(Error::Method(ref error)) => assert_eq!(ErrorCode::ArangoConflict, error.error_code()),
+        _ => panic!("Expected conflict, but got: {:?}", result),
+    }
+    assert_eq!(1, connector.requests().len());
+}
+
+#[test]
+fn retry_connection_gives_up_after_max_retries() {
+    let mut core = Core::new().unwrap();
+    let connector = MockConnector::new();
+    for _ in 0..4 {
+        connector.push_response(conflict());
+    }
+    connector.push_response(Response::ok(json!({ "version": "3.3.9" })));
+    let connection = retry_connection(&connector, fast_policy().with_max_retries(2), &core);
+
+    let result = core.run(connection.execute(Call::read()));
+
+    match result {
+        Err(Error::Method(ref error)) => assert_eq!(ErrorCode::ArangoConflict, error.error_code()),
+        _ => panic!("Expected conflict, but got: {:?}", result),
+    }
+    assert_eq!(3, connector.requests().len());
+}
+
+#[test]
+fn retry_connection_gives_attempts_only_the_time_left_until_the_deadline() {
+    let mut core = Core::new().unwrap();
+    let connector = MockConnector::new();
+    connector.push_response(conflict());
+    connector.push_response(Response::ok(json!({ "version": "3.3.9" })));
+    let connection = retry_connection(&connector, fast_policy(), &core);
+
+    let result =
+        core.run(connection.execute(WithTimeout::new(Call::read(), Duration::from_secs(10))));
+
+    assert!(result.is_ok());
+    let requests = connector.requests();
+    let first_timeout = requests[0].timeout().unwrap();
+    let second_timeout = requests[1].timeout().unwrap();
+    assert!(first_timeout <= Duration::from_secs(10));
+    assert!(second_timeout < first_timeout);
+}
+
+#[test]
+fn retry_connection_does_not_retry_when_backoff_exceeds_the_deadline() {
+    let mut core = Core::new().unwrap();
+    let connector = MockConnector::new();
+    connector.push_response(conflict());
+    connector.push_response(Response::ok(json!({ "version": "3.3.9" })));
+    let policy = RetryPolicy::default()
+        .with_initial_backoff(Duration::from_secs(5))
+        .with_timeout(Duration::from_millis(100));
+    let connection = retry_connection(&connector, policy, &core);
+
+    let result = core.run(connection.execute(Call::read()));
+
+    match result {
+        Err(Error::Method(ref error)) => assert_eq!(ErrorCode::ArangoConflict, error.error_code()),
+        _ => panic!("Expected conflict, but got: {:?}", result),
+    }
+    assert_eq!(1, connector.requests().len());
+}
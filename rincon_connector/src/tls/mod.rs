@@ -0,0 +1,279 @@
+//! TLS support for connectors based on [rustls].
+//!
+//! This module provides the `RustlsConnector` which is used by the
+//! `JsonHttpConnector` to establish HTTPS connections. It is configured by
+//! the `TlsOptions` of a `DataSource`.
+//!
+//! [rustls]: https://github.com/ctz/rustls
+
+#[cfg(test)]
+mod tests;
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, Read, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use futures::{future, Future, Poll};
+use hyper::client::{HttpConnector, Service};
+use hyper::Uri;
+use rustls::internal::pemfile;
+use rustls::{
+    Certificate, ClientConfig, ClientSession, PrivateKey, RootCertStore, ServerCertVerified,
+    ServerCertVerifier, TLSError,
+};
+use tokio_core::net::TcpStream;
+use tokio_core::reactor;
+use tokio_io::{AsyncRead, AsyncWrite};
+use tokio_rustls::{ClientConfigExt, TlsStream};
+use webpki::DNSNameRef;
+use webpki_roots::TLS_SERVER_ROOTS;
+
+use rincon_core::api::connector::Error;
+use rincon_core::api::datasource::TlsOptions;
+
+/// The number of threads used for resolving host names.
+const DNS_THREADS: usize = 4;
+
+/// Builds the rustls client configuration for the given `TlsOptions`.
+///
+/// The configuration trusts the root certificates of the native certificate
+/// store of the platform, the well known root certificates provided by the
+/// `webpki-roots` crate and the root certificates specified in the options.
+pub fn client_config(tls_options: &TlsOptions) -> Result<ClientConfig, Error> {
+    let mut config = ClientConfig::new();
+    add_native_root_certificates(&mut config.root_store);
+    config
+        .root_store
+        .add_server_trust_anchors(&TLS_SERVER_ROOTS);
+    if let Some(path) = tls_options.root_certificates() {
+        add_root_certificates(&mut config.root_store, path)?;
+    }
+    if let Some((certificate, private_key)) = tls_options.client_certificate() {
+        let certificate_chain = read_certificates(certificate)?;
+        let private_key = read_private_key(private_key)?;
+        config.set_single_client_cert(certificate_chain, private_key);
+    }
+    if tls_options.is_accept_invalid_certificates() {
+        warn!("Verification of server certificates is turned off");
+        config
+            .dangerous()
+            .set_certificate_verifier(Arc::new(AcceptAnyCertificate));
+    }
+    Ok(config)
+}
+
+/// Adds the root certificates of the native certificate store of the
+/// platform.
+///
+/// Certificates that can not be parsed are skipped. If the native certificate
+/// store can not be loaded no certificates are added.
+fn add_native_root_certificates(root_store: &mut RootCertStore) {
+    match rustls_native_certs::load_native_certs() {
+        Ok(certificates) => {
+            let mut skipped = 0;
+            for certificate in certificates {
+                if root_store.add(&Certificate(certificate.0)).is_err() {
+                    skipped += 1;
+                }
+            }
+            if skipped > 0 {
+                debug!("Skipped {} invalid native root certificates", skipped);
+            }
+        },
+        Err(error) => warn!("Can not load native root certificates: {}", error),
+    }
+}
+
+fn open_pem_file(path: &Path) -> Result<BufReader<File>, Error> {
+    File::open(path).map(BufReader::new).map_err(|cause| {
+        invalid_configuration(&format!("can not open {}: {}", path.display(), cause))
+    })
+}
+
+fn add_root_certificates(root_store: &mut RootCertStore, path: &Path) -> Result<(), Error> {
+    let mut reader = open_pem_file(path)?;
+    match root_store.add_pem_file(&mut reader) {
+        Ok((valid, _)) if valid > 0 => Ok(()),
+        _ => Err(invalid_configuration(&format!(
+            "no valid root certificate found in {}",
+            path.display()
+        ))),
+    }
+}
+
+fn read_certificates(path: &Path) -> Result<Vec<Certificate>, Error> {
+    let mut reader = open_pem_file(path)?;
+    match pemfile::certs(&mut reader) {
+        Ok(ref certificates) if certificates.is_empty() => Err(invalid_configuration(&format!(
+            "no certificate found in {}",
+            path.display()
+        ))),
+        Ok(certificates) => Ok(certificates),
+        Err(()) => Err(invalid_configuration(&format!(
+            "invalid certificate in {}",
+            path.display()
+        ))),
+    }
+}
+
+/// Reads the first private key from the given file.
+///
+/// Keys in PKCS#8 format and RSA keys in PKCS#1 format are supported.
+fn read_private_key(path: &Path) -> Result<PrivateKey, Error> {
+    let mut reader = open_pem_file(path)?;
+    let pkcs8_keys = pemfile::pkcs8_private_keys(&mut reader).unwrap_or_default();
+    if let Some(key) = pkcs8_keys.into_iter().next() {
+        return Ok(key);
+    }
+    let mut reader = open_pem_file(path)?;
+    let rsa_keys = pemfile::rsa_private_keys(&mut reader).unwrap_or_default();
+    rsa_keys.into_iter().next().ok_or_else(|| {
+        invalid_configuration(&format!("no private key found in {}", path.display()))
+    })
+}
+
+fn invalid_configuration(message: &str) -> Error {
+    Error::Communication(format!("Invalid TLS configuration: {}", message))
+}
+
+/// A `ServerCertVerifier` that accepts any certificate presented by a
+/// server.
+#[derive(Debug)]
+struct AcceptAnyCertificate;
+
+impl ServerCertVerifier for AcceptAnyCertificate {
+    fn verify_server_cert(
+        &self,
+        _roots: &RootCertStore,
+        _presented_certs: &[Certificate],
+        _dns_name: DNSNameRef,
+        _ocsp_response: &[u8],
+    ) -> Result<ServerCertVerified, TLSError> {
+        Ok(ServerCertVerified::assertion())
+    }
+}
+
+/// A connector for `hyper` that establishes HTTP connections and HTTPS
+/// connections secured by rustls.
+///
+/// Whether a connection is secured by TLS is determined by the scheme of the
+/// URI of each request.
+#[derive(Clone)]
+pub struct RustlsConnector {
+    http: HttpConnector,
+    tls_config: Arc<ClientConfig>,
+    server_name: Option<String>,
+}
+
+impl RustlsConnector {
+    /// Creates a new `RustlsConnector` configured by the given `TlsOptions`.
+    ///
+    /// Returns an error if the certificates or keys specified in the options
+    /// can not be read.
+    pub fn new(tls_options: &TlsOptions, reactor: &reactor::Handle) -> Result<Self, Error> {
+        let mut http = HttpConnector::new(DNS_THREADS, reactor);
+        http.enforce_http(false);
+        Ok(RustlsConnector {
+            http,
+            tls_config: Arc::new(client_config(tls_options)?),
+            server_name: tls_options.server_name().map(ToOwned::to_owned),
+        })
+    }
+}
+
+impl fmt::Debug for RustlsConnector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RustlsConnector")
+            .field("server_name", &self.server_name)
+            .finish()
+    }
+}
+
+impl Service for RustlsConnector {
+    type Request = Uri;
+    type Response = MaybeTlsStream;
+    type Error = io::Error;
+    type Future = Box<Future<Item = MaybeTlsStream, Error = io::Error>>;
+
+    fn call(&self, uri: Uri) -> Self::Future {
+        let is_https = uri.scheme() == Some("https");
+        let server_name = self
+            .server_name
+            .clone()
+            .or_else(|| uri.host().map(ToOwned::to_owned));
+        let connecting = self.http.call(uri);
+        if !is_https {
+            return Box::new(connecting.map(MaybeTlsStream::Plain));
+        }
+        let tls_config = self.tls_config.clone();
+        Box::new(connecting.and_then(move |tcp_stream| {
+            let server_name = server_name.unwrap_or_default();
+            match DNSNameRef::try_from_ascii_str(&server_name) {
+                Ok(dns_name) => future::Either::A(
+                    tls_config
+                        .connect_async(dns_name, tcp_stream)
+                        .map(MaybeTlsStream::Tls),
+                ),
+                Err(()) => future::Either::B(future::err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("invalid server name for TLS: {:?}", server_name),
+                ))),
+            }
+        }))
+    }
+}
+
+/// A stream that is either a plain TCP stream or secured by TLS.
+pub enum MaybeTlsStream {
+    /// A plain TCP stream.
+    Plain(TcpStream),
+    /// A TCP stream secured by TLS.
+    Tls(TlsStream<TcpStream, ClientSession>),
+}
+
+impl fmt::Debug for MaybeTlsStream {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MaybeTlsStream::Plain(_) => f.write_str("MaybeTlsStream::Plain"),
+            MaybeTlsStream::Tls(_) => f.write_str("MaybeTlsStream::Tls"),
+        }
+    }
+}
+
+impl Read for MaybeTlsStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match *self {
+            MaybeTlsStream::Plain(ref mut stream) => stream.read(buf),
+            MaybeTlsStream::Tls(ref mut stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for MaybeTlsStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match *self {
+            MaybeTlsStream::Plain(ref mut stream) => stream.write(buf),
+            MaybeTlsStream::Tls(ref mut stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match *self {
+            MaybeTlsStream::Plain(ref mut stream) => stream.flush(),
+            MaybeTlsStream::Tls(ref mut stream) => stream.flush(),
+        }
+    }
+}
+
+impl AsyncRead for MaybeTlsStream {}
+
+impl AsyncWrite for MaybeTlsStream {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        match *self {
+            MaybeTlsStream::Plain(ref mut stream) => AsyncWrite::shutdown(stream),
+            MaybeTlsStream::Tls(ref mut stream) => AsyncWrite::shutdown(stream),
+        }
+    }
+}
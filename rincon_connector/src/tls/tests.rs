@@ -0,0 +1,78 @@
+use std::path::PathBuf;
+
+use super::*;
+
+fn cert_file(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("certs")
+        .join(name)
+}
+
+#[test]
+fn client_config_with_default_options() {
+    let mut native_roots = RootCertStore::empty();
+    add_native_root_certificates(&mut native_roots);
+
+    let config = client_config(&TlsOptions::new()).unwrap();
+
+    assert_eq!(
+        native_roots.len() + TLS_SERVER_ROOTS.0.len(),
+        config.root_store.len()
+    );
+}
+
+#[test]
+fn client_config_with_custom_root_certificate() {
+    let default_config = client_config(&TlsOptions::new()).unwrap();
+    let tls_options = TlsOptions::new().with_root_certificates(cert_file("ca.pem"));
+
+    let config = client_config(&tls_options).unwrap();
+
+    assert_eq!(default_config.root_store.len() + 1, config.root_store.len());
+}
+
+#[test]
+fn client_config_with_missing_root_certificates_file() {
+    let tls_options = TlsOptions::new().with_root_certificates(cert_file("missing.pem"));
+
+    match client_config(&tls_options) {
+        Err(Error::Communication(message)) => {
+            assert!(message.starts_with("Invalid TLS configuration: can not open"))
+        },
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn client_config_with_root_certificates_file_without_certificates() {
+    let tls_options = TlsOptions::new().with_root_certificates(cert_file("client.key"));
+
+    match client_config(&tls_options) {
+        Err(Error::Communication(message)) => assert!(
+            message.starts_with("Invalid TLS configuration: no valid root certificate found")
+        ),
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn client_config_with_client_certificate() {
+    let tls_options =
+        TlsOptions::new().with_client_certificate(cert_file("client.pem"), cert_file("client.key"));
+
+    assert!(client_config(&tls_options).is_ok());
+}
+
+#[test]
+fn client_config_with_client_certificate_but_key_file_without_key() {
+    let tls_options =
+        TlsOptions::new().with_client_certificate(cert_file("client.pem"), cert_file("client.pem"));
+
+    match client_config(&tls_options) {
+        Err(Error::Communication(message)) => {
+            assert!(message.starts_with("Invalid TLS configuration: no private key found"))
+        },
+        other => panic!("unexpected result: {:?}", other.map(|_| ())),
+    }
+}
@@ -0,0 +1,736 @@
+//! Connectors that use the VelocyStream protocol.
+//!
+//! This module provides a `Connector` implementation that uses [VelocyStream]
+//! as transport protocol and [VelocyPack] for serializing the payload.
+//!
+//! The `VstConnector` opens one TCP connection to the server and multiplexes
+//! all method calls over this connection. Each method call is sent as a
+//! message with a unique id. The responses are matched to the method calls
+//! by their message id, so the server may answer method calls in any order.
+//!
+//! The `VstConnector` implements version 1.1 of the VelocyStream protocol.
+//! Encrypted connections are not supported.
+//!
+//! [VelocyStream]: https://github.com/arangodb/velocystream
+//! [VelocyPack]: https://github.com/arangodb/velocypack
+
+#[cfg(test)]
+mod tests;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+
+use byteorder::{ByteOrder, LittleEndian};
+use bytes::BytesMut;
+use futures::sync::{mpsc, oneshot};
+use futures::{future, Future, Sink, Stream};
+use serde_json::{self, Map, Value};
+use tokio_core::net::TcpStream;
+use tokio_core::reactor;
+use tokio_io::codec::{Decoder, Encoder};
+use tokio_io::io::write_all;
+use tokio_io::AsyncRead;
+
+use rincon_core::api;
+use rincon_core::api::auth::{Authentication, Jwt};
+use rincon_core::api::connector::{Connector, Error, Execute, FutureResult};
+use rincon_core::api::datasource::{ContentFormat, DataSource};
+use rincon_core::api::method::{Method, Operation, Prepare, RpcReturnType};
+use rincon_core::api::payload::serialize_payload;
+use rincon_core::arango::protocol::SYSTEM_DATABASE;
+use rincon_core::jwt::superuser_token;
+use rincon_core::velocypack;
+
+use deadline::with_deadline;
+
+/// The handshake sent by a client to select version 1.1 of the VelocyStream
+/// protocol.
+pub const VST_1_1_HANDSHAKE: &[u8] = b"VST/1.1\r\n\r\n";
+
+/// The default maximum size of a chunk in bytes including the chunk header.
+pub const DEFAULT_MAX_CHUNK_SIZE: usize = 30_000;
+
+/// The maximum size of a message received from the server in bytes.
+pub const MAX_MESSAGE_SIZE: usize = 256 * 1024 * 1024;
+
+/// The size of a chunk header in bytes.
+const CHUNK_HEADER_SIZE: usize = 24;
+
+/// The maximum number of messages that may be received partially at the
+/// same time.
+const MAX_INCOMPLETE_MESSAGES: usize = 1024;
+
+const MESSAGE_VERSION: u64 = 1;
+const MESSAGE_TYPE_REQUEST: u64 = 1;
+const MESSAGE_TYPE_AUTHENTICATION: u64 = 1000;
+const AUTHENTICATION_PLAIN: &str = "plain";
+const AUTHENTICATION_JWT: &str = "jwt";
+
+/// A connector that uses VelocyPack over VelocyStream.
+///
+/// The connection to the server is established in the background when the
+/// connector is created. Method calls that are not answered within the
+/// timeout of the `DataSource` or the timeout of the method fail with an
+/// `Error::Timeout`. If the `DataSource` specifies basic authentication
+/// the connection is authenticated with the given credentials. When using
+/// JWT authentication the connection is authenticated as soon as a token is
+/// set by calling `use_auth_token()`. If the `DataSource` specifies a JWT
+/// secret the connection is authenticated with a superuser token signed with
+/// this secret.
+///
+/// The connector does not reconnect if the connection to the server is lost.
+/// All method calls executed afterwards fail with an `Error::Communication`
+/// and a new connector has to be created. The connector always connects to
+/// the primary endpoint of the `DataSource`. Failover endpoints are ignored.
+///
+/// Messages received from the server that are larger than `MAX_MESSAGE_SIZE`
+/// are rejected and close the connection.
+#[derive(Debug)]
+pub struct VstConnector {
+    datasource: Arc<DataSource>,
+    token: Arc<RwLock<Option<Jwt>>>,
+    sender: mpsc::UnboundedSender<Command>,
+    message_ids: Arc<AtomicUsize>,
+    reactor: reactor::Handle,
+}
+
+impl VstConnector {
+    /// Creates a new instance of the `VstConnector`.
+    ///
+    /// # Arguments
+    ///
+    /// * `datasource` : a `DataSource` that holds the connection parameters
+    ///   used to connect to the database server.
+    /// * `reactor` : a handle of a `reactor::Core` instance of the `tokio-core`
+    ///   crate.
+    pub fn new(datasource: DataSource, reactor: &reactor::Handle) -> Result<Self, Error> {
+        VstConnector::with_max_chunk_size(datasource, DEFAULT_MAX_CHUNK_SIZE, reactor)
+    }
+
+    /// Creates a new instance of the `VstConnector` that splits messages into
+    /// chunks of the given maximum size.
+    ///
+    /// # Arguments
+    ///
+    /// * `datasource` : a `DataSource` that holds the connection parameters
+    ///   used to connect to the database server.
+    /// * `max_chunk_size` : the maximum size of a chunk in bytes including the
+    ///   chunk header.
+    /// * `reactor` : a handle of a `reactor::Core` instance of the `tokio-core`
+    ///   crate.
+    pub fn with_max_chunk_size(
+        datasource: DataSource,
+        max_chunk_size: usize,
+        reactor: &reactor::Handle,
+    ) -> Result<Self, Error> {
+        if max_chunk_size <= CHUNK_HEADER_SIZE {
+            return Err(Error::Communication(format!(
+                "maximum chunk size must be greater than {}",
+                CHUNK_HEADER_SIZE
+            )));
+        }
+        let address = resolve_address(&datasource)?;
+        let (sender, receiver) = mpsc::unbounded();
+        debug!("Creating new VelocyStream connector for {:?}", &datasource);
+        reactor.spawn(run_connection(address, receiver, max_chunk_size, reactor));
+        let mut connector = VstConnector {
+            datasource: Arc::new(datasource),
+            token: Arc::new(RwLock::new(None)),
+            sender,
+            message_ids: Arc::new(AtomicUsize::new(1)),
+            reactor: reactor.clone(),
+        };
+        let datasource = connector.datasource.clone();
+        match *datasource.authentication() {
+            Authentication::Basic(ref credentials) => {
+                connector.authenticate(vec![
+                    AUTHENTICATION_PLAIN.into(),
+                    credentials.username().into(),
+                    credentials.password().into(),
+                ]);
+            },
+            Authentication::JwtSecret(ref secret) => {
+                connector.use_auth_token(superuser_token(secret, SystemTime::now()));
+            },
+            Authentication::Jwt(_) | Authentication::None => {},
+        }
+        Ok(connector)
+    }
+
+    /// Sends an authentication message with the given credentials to the
+    /// server.
+    ///
+    /// A failed authentication is logged. Method calls executed afterwards
+    /// will fail with an error returned by the server.
+    fn authenticate(&self, credentials: Vec<Value>) {
+        let mut header = vec![MESSAGE_VERSION.into(), MESSAGE_TYPE_AUTHENTICATION.into()];
+        header.extend(credentials);
+        let mut message = Vec::new();
+        velocypack::encode_value(&Value::Array(header), &mut message);
+        let message_id = self.message_ids.fetch_add(1, Ordering::SeqCst) as u64;
+        let (reply, response) = oneshot::channel();
+        if self.sender
+            .unbounded_send(Command::Send(Envelope {
+                message_id,
+                message,
+                reply,
+            }))
+            .is_ok()
+        {
+            self.reactor.spawn(response.then(|result| -> Result<(), ()> {
+                match result {
+                    Ok(message) => match parse_response_header(&message) {
+                        Ok((status_code, _)) if status_code == 200 => {
+                            debug!("Authenticated VelocyStream connection");
+                        },
+                        Ok((status_code, _)) => {
+                            error!("VelocyStream authentication failed with code {}", status_code);
+                        },
+                        Err(error) => error!("VelocyStream authentication failed: {}", error),
+                    },
+                    Err(_) => error!("VelocyStream connection closed during authentication"),
+                }
+                Ok(())
+            }));
+        }
+    }
+}
+
+impl Connector for VstConnector {
+    type Connection = VstConnection;
+
+    fn connection(&self, database_name: &str) -> VstConnection {
+        VstConnection {
+            datasource: self.datasource.clone(),
+            database: database_name.to_owned(),
+            token: self.token.clone(),
+            sender: self.sender.clone(),
+            message_ids: self.message_ids.clone(),
+            reactor: self.reactor.clone(),
+        }
+    }
+
+    fn system_connection(&self) -> VstConnection {
+        self.connection(SYSTEM_DATABASE)
+    }
+
+    fn use_auth_token(&mut self, token: Jwt) {
+        self.authenticate(vec![AUTHENTICATION_JWT.into(), token.clone().into()]);
+        store_token(&self.token, Some(token));
+    }
+
+    fn invalidate_auth_token(&mut self) {
+        store_token(&self.token, None);
+    }
+}
+
+/// A connection that executes method calls by sending VelocyStream messages
+/// over the connection of a `VstConnector`.
+#[derive(Debug)]
+pub struct VstConnection {
+    datasource: Arc<DataSource>,
+    database: String,
+    token: Arc<RwLock<Option<Jwt>>>,
+    sender: mpsc::UnboundedSender<Command>,
+    message_ids: Arc<AtomicUsize>,
+    reactor: reactor::Handle,
+}
+
+impl VstConnection {
+    /// Returns the `DataSource` used by the connection.
+    pub fn datasource(&self) -> &DataSource {
+        &self.datasource
+    }
+
+    /// Returns the name of the database addressed by method calls.
+    pub fn database(&self) -> &str {
+        &self.database
+    }
+
+    /// Returns the authentication token used by this connection.
+    pub fn token(&self) -> Option<Jwt> {
+        match self.token.read() {
+            Ok(token) => token.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+}
+
+impl Execute for VstConnection {
+    fn execute<M>(&self, method: M) -> FutureResult<M>
+    where
+        M: Method + Prepare + 'static,
+    {
+        if let Authentication::Jwt(_) = *self.datasource.authentication() {
+            if self.token().is_none() {
+                return Box::new(future::err(Error::NotAuthenticated(
+                    "the client must be authenticated first, \
+                     when using JWT authentication"
+                        .into(),
+                )));
+            }
+        }
+        let message = match build_request_message(&self.database, &method) {
+            Ok(message) => message,
+            Err(error) => return Box::new(future::err(error)),
+        };
+        let message_id = self.message_ids.fetch_add(1, Ordering::SeqCst) as u64;
+        let (reply, response) = oneshot::channel();
+        debug!("Sending message {} to {}", message_id, &method.path());
+        let envelope = Envelope {
+            message_id,
+            message,
+            reply,
+        };
+        if self.sender.unbounded_send(Command::Send(envelope)).is_err() {
+            return Box::new(future::err(Error::Communication(
+                "VelocyStream connection is closed".into(),
+            )));
+        }
+        let timeout = method
+            .timeout()
+            .unwrap_or_else(|| *self.datasource.timeout());
+        let awaiting_reply = AwaitingReply::new(message_id, self.sender.clone());
+        let response = response.then(move |result| {
+            awaiting_reply.received();
+            result.map_err(|_| {
+                Error::Communication(
+                    "VelocyStream connection closed before a response was received".into(),
+                )
+            })
+        });
+        Box::new(
+            with_deadline(response, timeout, &self.reactor)
+                .and_then(move |message| parse_response::<M>(&method.return_type(), &message)),
+        )
+    }
+}
+
+/// A command for the task that runs the connection to the server.
+#[derive(Debug)]
+enum Command {
+    /// Sends the message of the envelope to the server.
+    Send(Envelope),
+    /// Drops the sender for the response to the message with the given id,
+    /// as the response is not awaited anymore.
+    Cancel(u64),
+}
+
+/// A message to be sent to the server together with the sender for its
+/// response.
+#[derive(Debug)]
+struct Envelope {
+    message_id: u64,
+    message: Vec<u8>,
+    reply: oneshot::Sender<Vec<u8>>,
+}
+
+/// Cancels the message with the given id when it is dropped before the
+/// response has been received, e.g. because the method call timed out.
+#[derive(Debug)]
+struct AwaitingReply {
+    message_id: u64,
+    sender: Option<mpsc::UnboundedSender<Command>>,
+}
+
+impl AwaitingReply {
+    fn new(message_id: u64, sender: mpsc::UnboundedSender<Command>) -> Self {
+        AwaitingReply {
+            message_id,
+            sender: Some(sender),
+        }
+    }
+
+    /// Marks the response as received, so that the message is not
+    /// cancelled.
+    fn received(mut self) {
+        self.sender = None;
+    }
+}
+
+impl Drop for AwaitingReply {
+    fn drop(&mut self) {
+        if let Some(sender) = self.sender.take() {
+            let _ = sender.unbounded_send(Command::Cancel(self.message_id));
+        }
+    }
+}
+
+/// Stores the given token, replacing any token stored before.
+fn store_token(token: &RwLock<Option<Jwt>>, new_token: Option<Jwt>) {
+    match token.write() {
+        Ok(mut token) => *token = new_token,
+        Err(poisoned) => *poisoned.into_inner() = new_token,
+    }
+}
+
+fn resolve_address(datasource: &DataSource) -> Result<SocketAddr, Error> {
+    (datasource.host(), datasource.port())
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| {
+            Error::Communication(format!(
+                "Could not resolve address of {}:{}",
+                datasource.host(),
+                datasource.port()
+            ))
+        })
+}
+
+/// Connects to the server and sends the messages received from the given
+/// channel until the channel or the connection is closed.
+fn run_connection(
+    address: SocketAddr,
+    commands: mpsc::UnboundedReceiver<Command>,
+    max_chunk_size: usize,
+    reactor: &reactor::Handle,
+) -> Box<Future<Item = (), Error = ()>> {
+    let pending: Rc<RefCell<HashMap<u64, oneshot::Sender<Vec<u8>>>>> =
+        Rc::new(RefCell::new(HashMap::new()));
+    Box::new(
+        TcpStream::connect(&address, reactor)
+            .and_then(|stream| write_all(stream, VST_1_1_HANDSHAKE))
+            .map_err(Error::from)
+            .and_then(move |(stream, _)| {
+                let (sink, stream) = stream.framed(VstCodec::new(max_chunk_size)).split();
+                let pending_replies = pending.clone();
+                let writer = commands
+                    .filter_map(move |command| match command {
+                        Command::Send(envelope) => {
+                            pending_replies
+                                .borrow_mut()
+                                .insert(envelope.message_id, envelope.reply);
+                            Some((envelope.message_id, envelope.message))
+                        },
+                        Command::Cancel(message_id) => {
+                            pending_replies.borrow_mut().remove(&message_id);
+                            None
+                        },
+                    })
+                    .map_err(|()| Error::Communication("message channel failed".into()))
+                    .forward(sink)
+                    .map(|_| ());
+                let reader = stream.for_each(move |(message_id, message)| {
+                    match pending.borrow_mut().remove(&message_id) {
+                        Some(reply) => {
+                            let _ = reply.send(message);
+                        },
+                        None => warn!("Received response for unknown message {}", message_id),
+                    }
+                    Ok(())
+                });
+                writer.select(reader).map(|_| ()).map_err(|(error, _)| error)
+            })
+            .map_err(move |error| error!("VelocyStream connection to {} failed: {}", address, error)),
+    )
+}
+
+fn build_request_message<P>(database: &str, method: &P) -> Result<Vec<u8>, Error>
+where
+    P: Prepare,
+{
+    let mut parameters = Map::new();
+    for &(ref key, ref value) in method.parameters().iter() {
+        parameters.insert(key.to_string(), Value::String(value.to_string()));
+    }
+    let mut meta = Map::new();
+    for &(ref name, ref value) in method.header().iter() {
+        meta.insert(name.to_string(), Value::String(value.to_string()));
+    }
+    let header = Value::Array(vec![
+        MESSAGE_VERSION.into(),
+        MESSAGE_TYPE_REQUEST.into(),
+        database.into(),
+        request_type_for_operation(&method.operation()).into(),
+        method.path().into(),
+        Value::Object(parameters),
+        Value::Object(meta),
+    ]);
+    let mut message = Vec::new();
+    velocypack::encode_value(&header, &mut message);
+    if let Some(content) = method.content() {
+        let body = serialize_payload(
+            ContentFormat::VelocyPack,
+            method.is_line_delimited(),
+            content,
+        )?;
+        message.extend_from_slice(&body);
+    }
+    Ok(message)
+}
+
+fn request_type_for_operation(operation: &Operation) -> u64 {
+    match *operation {
+        Operation::Delete => 0,
+        Operation::Read => 1,
+        Operation::Create => 2,
+        Operation::Replace => 3,
+        Operation::ReadHeader => 4,
+        Operation::Modify => 5,
+    }
+}
+
+/// Decodes the header of a response message and returns the response code
+/// and the size of the header in bytes.
+fn parse_response_header(message: &[u8]) -> Result<(u16, usize), Error> {
+    let (header, header_size) = velocypack::decode_value(message)
+        .map_err(|cause| Error::Deserialization(cause.to_string()))?;
+    header
+        .get(2)
+        .and_then(Value::as_u64)
+        .map(|status_code| (status_code as u16, header_size))
+        .ok_or_else(|| Error::Deserialization(format!("Invalid response header: {}", header)))
+}
+
+fn parse_response<M>(
+    return_type: &RpcReturnType,
+    message: &[u8],
+) -> Result<<M as Method>::Result, Error>
+where
+    M: Method,
+{
+    let (status_code, header_size) = parse_response_header(message)?;
+    debug!("Received response with code {:?}", status_code);
+    let body = if header_size < message.len() {
+        velocypack::decode_value(&message[header_size..])
+            .map(|(body, _)| body)
+            .map_err(|cause| Error::Deserialization(cause.to_string()))?
+    } else {
+        Value::Null
+    };
+    trace!("| response body: {}", &body);
+    parse_return_type::<M>(return_type, status_code, body)
+}
+
+fn parse_return_type<M>(
+    return_type: &RpcReturnType,
+    status_code: u16,
+    body: Value,
+) -> Result<<M as Method>::Result, Error>
+where
+    M: Method,
+{
+    if status_code >= 200 && status_code < 300 {
+        let result = match (return_type.result_field, body) {
+            (Some(result_field), Value::Object(mut obj)) => match obj.remove(result_field) {
+                Some(result_value) => result_value,
+                None => Value::Object(obj),
+            },
+            (_, body) => body,
+        };
+        serde_json::from_value(result).map_err(|cause| Error::Deserialization(cause.to_string()))
+    } else {
+        let api_error = serde_json::from_value(body).unwrap_or_else(|_| {
+            let error_code = api::ErrorCode::from_u16(status_code);
+            let message = error_code.description().to_owned();
+            api::Error::new(status_code, error_code, message)
+        });
+        Err(Error::Method(api_error))
+    }
+}
+
+/// A message that has been received partially.
+#[derive(Debug)]
+struct IncompleteMessage {
+    chunks: Vec<Option<Vec<u8>>>,
+    remaining: usize,
+    message_length: usize,
+    received_length: usize,
+}
+
+impl IncompleteMessage {
+    /// Stores the data of the chunk with the given index.
+    ///
+    /// Fails if the message would become longer than its declared length.
+    fn add_chunk(&mut self, message_id: u64, index: usize, data: Vec<u8>) -> Result<(), Error> {
+        let previous_length = match self.chunks[index] {
+            Some(ref previous) => previous.len(),
+            None => {
+                self.remaining -= 1;
+                0
+            },
+        };
+        self.received_length = self.received_length - previous_length + data.len();
+        if self.received_length > self.message_length {
+            return Err(message_length_mismatch(
+                message_id,
+                self.received_length,
+                self.message_length,
+            ));
+        }
+        self.chunks[index] = Some(data);
+        Ok(())
+    }
+}
+
+/// Splits messages into chunks and assembles chunks into messages.
+///
+/// The items of this codec are tuples of a message id and the message.
+#[derive(Debug)]
+struct VstCodec {
+    max_chunk_size: usize,
+    max_num_chunks: usize,
+    incomplete: HashMap<u64, IncompleteMessage>,
+}
+
+impl VstCodec {
+    fn new(max_chunk_size: usize) -> Self {
+        //the server may use smaller chunks than the client, but not smaller
+        //than the default chunk size
+        let min_data_size = max_chunk_size.min(DEFAULT_MAX_CHUNK_SIZE) - CHUNK_HEADER_SIZE;
+        VstCodec {
+            max_chunk_size,
+            max_num_chunks: MAX_MESSAGE_SIZE / min_data_size + 1,
+            incomplete: HashMap::new(),
+        }
+    }
+}
+
+impl Decoder for VstCodec {
+    type Item = (u64, Vec<u8>);
+    type Error = Error;
+
+    fn decode(&mut self, buffer: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        loop {
+            if buffer.len() < CHUNK_HEADER_SIZE {
+                return Ok(None);
+            }
+            let chunk_length = LittleEndian::read_u32(&buffer[0..4]) as usize;
+            if chunk_length < CHUNK_HEADER_SIZE
+                || chunk_length > CHUNK_HEADER_SIZE + MAX_MESSAGE_SIZE
+            {
+                return Err(Error::Deserialization(format!(
+                    "invalid chunk length: {}",
+                    chunk_length
+                )));
+            }
+            if buffer.len() < chunk_length {
+                return Ok(None);
+            }
+            let chunk = buffer.split_to(chunk_length);
+            let chunk_x = LittleEndian::read_u32(&chunk[4..8]) as usize;
+            let message_id = LittleEndian::read_u64(&chunk[8..16]);
+            let data = chunk[CHUNK_HEADER_SIZE..].to_vec();
+            if chunk_x & 1 == 1 {
+                let num_chunks = chunk_x >> 1;
+                let message_length = LittleEndian::read_u64(&chunk[16..24]);
+                if message_length > MAX_MESSAGE_SIZE as u64 {
+                    return Err(Error::Deserialization(format!(
+                        "message {} of {} bytes exceeds the maximum message size of {} bytes",
+                        message_id, message_length, MAX_MESSAGE_SIZE
+                    )));
+                }
+                let message_length = message_length as usize;
+                if num_chunks > self.max_num_chunks {
+                    return Err(Error::Deserialization(format!(
+                        "message {} split into {} chunks exceeds the maximum of {} chunks",
+                        message_id, num_chunks, self.max_num_chunks
+                    )));
+                }
+                if num_chunks <= 1 {
+                    if data.len() != message_length {
+                        return Err(message_length_mismatch(
+                            message_id,
+                            data.len(),
+                            message_length,
+                        ));
+                    }
+                    return Ok(Some((message_id, data)));
+                }
+                if self.incomplete.len() >= MAX_INCOMPLETE_MESSAGES
+                    && !self.incomplete.contains_key(&message_id)
+                {
+                    return Err(Error::Deserialization(format!(
+                        "more than {} messages are received at the same time",
+                        MAX_INCOMPLETE_MESSAGES
+                    )));
+                }
+                let mut message = IncompleteMessage {
+                    chunks: vec![None; num_chunks],
+                    remaining: num_chunks,
+                    message_length,
+                    received_length: 0,
+                };
+                message.add_chunk(message_id, 0, data)?;
+                self.incomplete.insert(message_id, message);
+            } else {
+                let index = chunk_x >> 1;
+                let complete = match self.incomplete.get_mut(&message_id) {
+                    Some(ref mut message) if index < message.chunks.len() => {
+                        message.add_chunk(message_id, index, data)?;
+                        message.remaining == 0
+                    },
+                    _ => {
+                        return Err(Error::Deserialization(format!(
+                            "unexpected chunk {} for message {}",
+                            index, message_id
+                        )))
+                    },
+                };
+                if complete {
+                    if let Some(message) = self.incomplete.remove(&message_id) {
+                        if message.received_length != message.message_length {
+                            return Err(message_length_mismatch(
+                                message_id,
+                                message.received_length,
+                                message.message_length,
+                            ));
+                        }
+                        let message = message
+                            .chunks
+                            .into_iter()
+                            .flat_map(|chunk| chunk.unwrap_or_default())
+                            .collect();
+                        return Ok(Some((message_id, message)));
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn message_length_mismatch(
+    message_id: u64,
+    received_length: usize,
+    message_length: usize,
+) -> Error {
+    Error::Deserialization(format!(
+        "received {} bytes for message {} of {} bytes",
+        received_length, message_id, message_length
+    ))
+}
+
+impl Encoder for VstCodec {
+    type Item = (u64, Vec<u8>);
+    type Error = Error;
+
+    fn encode(&mut self, item: Self::Item, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let (message_id, message) = item;
+        let max_data_size = self.max_chunk_size - CHUNK_HEADER_SIZE;
+        let num_chunks = message.chunks(max_data_size).count().max(1);
+        for index in 0..num_chunks {
+            let start = index * max_data_size;
+            let end = (start + max_data_size).min(message.len());
+            let data = &message[start..end];
+            let chunk_x = if index == 0 {
+                (num_chunks << 1) | 1
+            } else {
+                index << 1
+            };
+            let mut header = [0; CHUNK_HEADER_SIZE];
+            LittleEndian::write_u32(&mut header[0..4], (CHUNK_HEADER_SIZE + data.len()) as u32);
+            LittleEndian::write_u32(&mut header[4..8], chunk_x as u32);
+            LittleEndian::write_u64(&mut header[8..16], message_id);
+            LittleEndian::write_u64(&mut header[16..24], message.len() as u64);
+            buffer.reserve(CHUNK_HEADER_SIZE + data.len());
+            buffer.extend_from_slice(&header);
+            buffer.extend_from_slice(data);
+        }
+        Ok(())
+    }
+}
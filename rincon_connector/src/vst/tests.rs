@@ -0,0 +1,372 @@
+use std::io::{Read, Write};
+use std::net;
+use std::str::FromStr;
+use std::thread;
+
+use tokio_core::reactor::Core;
+
+use super::*;
+use rincon_core::api::method::Parameters;
+use rincon_core::api::ErrorCode;
+
+struct GetVersion;
+
+impl Method for GetVersion {
+    type Result = Value;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: None,
+    };
+}
+
+impl Prepare for GetVersion {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Read
+    }
+
+    fn path(&self) -> String {
+        String::from("/_api/version")
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+fn read_message(stream: &mut net::TcpStream) -> (u64, Vec<u8>) {
+    let mut header = [0; CHUNK_HEADER_SIZE];
+    stream.read_exact(&mut header).unwrap();
+    let chunk_length = LittleEndian::read_u32(&header[0..4]) as usize;
+    let message_id = LittleEndian::read_u64(&header[8..16]);
+    let mut message = vec![0; chunk_length - CHUNK_HEADER_SIZE];
+    stream.read_exact(&mut message).unwrap();
+    (message_id, message)
+}
+
+fn write_message(stream: &mut net::TcpStream, message_id: u64, header: &Value, body: &Value) {
+    let mut message = Vec::new();
+    velocypack::encode_value(header, &mut message);
+    velocypack::encode_value(body, &mut message);
+    let mut buffer = BytesMut::new();
+    VstCodec::new(DEFAULT_MAX_CHUNK_SIZE)
+        .encode((message_id, message), &mut buffer)
+        .unwrap();
+    stream.write_all(&buffer).unwrap();
+}
+
+fn fake_server<H>(handler: H) -> u16
+where
+    H: FnOnce(&mut net::TcpStream) + Send + 'static,
+{
+    let listener = net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut handshake = [0; 11];
+        stream.read_exact(&mut handshake).unwrap();
+        assert_eq!(VST_1_1_HANDSHAKE, &handshake[..]);
+        handler(&mut stream);
+    });
+    port
+}
+
+#[test]
+fn encode_and_decode_message_split_into_chunks() {
+    let message: Vec<u8> = (0..100).collect();
+    let mut codec = VstCodec::new(CHUNK_HEADER_SIZE + 30);
+    let mut buffer = BytesMut::new();
+
+    codec.encode((42, message.clone()), &mut buffer).unwrap();
+
+    assert_eq!(4 * CHUNK_HEADER_SIZE + 100, buffer.len());
+    assert_eq!(Some((42, message)), codec.decode(&mut buffer).unwrap());
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn decode_incomplete_chunk_returns_none() {
+    let mut codec = VstCodec::new(DEFAULT_MAX_CHUNK_SIZE);
+    let mut buffer = BytesMut::new();
+    codec.encode((1, vec![1, 2, 3]), &mut buffer).unwrap();
+    let mut incomplete = buffer.split_to(CHUNK_HEADER_SIZE + 2);
+
+    assert_eq!(None, codec.decode(&mut incomplete).unwrap());
+}
+
+fn first_chunk_header(num_chunks: usize, message_length: u64) -> BytesMut {
+    let mut header = [0; CHUNK_HEADER_SIZE];
+    LittleEndian::write_u32(&mut header[0..4], CHUNK_HEADER_SIZE as u32);
+    LittleEndian::write_u32(&mut header[4..8], ((num_chunks << 1) | 1) as u32);
+    LittleEndian::write_u64(&mut header[8..16], 7);
+    LittleEndian::write_u64(&mut header[16..24], message_length);
+    BytesMut::from(&header[..])
+}
+
+#[test]
+fn decode_message_with_too_many_chunks_fails() {
+    let mut codec = VstCodec::new(DEFAULT_MAX_CHUNK_SIZE);
+    let mut buffer = first_chunk_header(0x7fff_ffff, 1_000);
+
+    match codec.decode(&mut buffer) {
+        Err(Error::Deserialization(_)) => {},
+        result => panic!("Expected deserialization error, but got: {:?}", result),
+    }
+    assert!(codec.incomplete.is_empty());
+}
+
+#[test]
+fn decode_message_exceeding_max_message_size_fails() {
+    let mut codec = VstCodec::new(DEFAULT_MAX_CHUNK_SIZE);
+    let mut buffer = first_chunk_header(2, MAX_MESSAGE_SIZE as u64 + 1);
+
+    match codec.decode(&mut buffer) {
+        Err(Error::Deserialization(_)) => {},
+        result => panic!("Expected deserialization error, but got: {:?}", result),
+    }
+}
+
+#[test]
+fn decode_chunk_longer_than_max_message_size_fails() {
+    let mut codec = VstCodec::new(DEFAULT_MAX_CHUNK_SIZE);
+    let mut buffer = first_chunk_header(1, 0);
+    LittleEndian::write_u32(
+        &mut buffer[0..4],
+        (CHUNK_HEADER_SIZE + MAX_MESSAGE_SIZE + 1) as u32,
+    );
+
+    match codec.decode(&mut buffer) {
+        Err(Error::Deserialization(_)) => {},
+        result => panic!("Expected deserialization error, but got: {:?}", result),
+    }
+}
+
+#[test]
+fn decode_chunks_exceeding_message_length_fails() {
+    let mut codec = VstCodec::new(CHUNK_HEADER_SIZE + 30);
+    let mut buffer = BytesMut::new();
+    codec.encode((7, vec![0; 40]), &mut buffer).unwrap();
+    let mut first_chunk = buffer.split_to(CHUNK_HEADER_SIZE + 30);
+    LittleEndian::write_u64(&mut first_chunk[16..24], 35);
+    LittleEndian::write_u64(&mut buffer[16..24], 35);
+
+    assert_eq!(None, codec.decode(&mut first_chunk).unwrap());
+    match codec.decode(&mut buffer) {
+        Err(Error::Deserialization(_)) => {},
+        result => panic!("Expected deserialization error, but got: {:?}", result),
+    }
+}
+
+#[test]
+fn decode_too_many_incomplete_messages_fails() {
+    let mut codec = VstCodec::new(DEFAULT_MAX_CHUNK_SIZE);
+    for message_id in 0..MAX_INCOMPLETE_MESSAGES as u64 {
+        let mut buffer = first_chunk_header(2, 10);
+        LittleEndian::write_u64(&mut buffer[8..16], message_id);
+        assert_eq!(None, codec.decode(&mut buffer).unwrap());
+    }
+    let mut buffer = first_chunk_header(2, 10);
+    LittleEndian::write_u64(&mut buffer[8..16], MAX_INCOMPLETE_MESSAGES as u64);
+
+    match codec.decode(&mut buffer) {
+        Err(Error::Deserialization(_)) => {},
+        result => panic!("Expected deserialization error, but got: {:?}", result),
+    }
+}
+
+#[test]
+fn dropped_method_call_cancels_its_message() {
+    let (sender, receiver) = mpsc::unbounded();
+
+    drop(AwaitingReply::new(7, sender.clone()));
+    AwaitingReply::new(8, sender.clone()).received();
+    drop(sender);
+
+    let commands = receiver.collect().wait().unwrap();
+    match commands[..] {
+        [Command::Cancel(7)] => {},
+        _ => panic!("Expected cancel of message 7, but got: {:?}", commands),
+    }
+}
+
+#[test]
+fn auth_token_is_shared_with_existing_connections() {
+    let core = Core::new().unwrap();
+    let datasource = DataSource::from_str("vst://127.0.0.1:8529")
+        .unwrap()
+        .without_authentication();
+    let mut connector = VstConnector::new(datasource, &core.handle()).unwrap();
+    let connection = connector.system_connection();
+
+    connector.use_auth_token("abc.def.ghi".to_owned());
+    assert_eq!(Some("abc.def.ghi".to_owned()), connection.token());
+
+    connector.invalidate_auth_token();
+    assert_eq!(None, connection.token());
+}
+
+#[test]
+fn build_request_message_for_method_without_content() {
+    let message = build_request_message("customers", &GetVersion).unwrap();
+
+    let (header, size) = velocypack::decode_value(&message).unwrap();
+
+    assert_eq!(json!([1, 1, "customers", 1, "/_api/version", {}, {}]), header);
+    assert_eq!(message.len(), size);
+}
+
+#[test]
+fn build_request_message_with_content_as_velocypack() {
+    struct InsertDocument(Value);
+
+    impl Prepare for InsertDocument {
+        type Content = Value;
+
+        fn operation(&self) -> Operation {
+            Operation::Create
+        }
+
+        fn path(&self) -> String {
+            String::from("/_api/document/customers")
+        }
+
+        fn parameters(&self) -> Parameters {
+            Parameters::empty()
+        }
+
+        fn header(&self) -> Parameters {
+            Parameters::empty()
+        }
+
+        fn content(&self) -> Option<&Self::Content> {
+            Some(&self.0)
+        }
+    }
+    let document = json!({ "name": "Jane", "age": 42 });
+
+    let message = build_request_message("shop", &InsertDocument(document.clone())).unwrap();
+
+    let (_, header_size) = velocypack::decode_value(&message).unwrap();
+    let (body, body_size) = velocypack::decode_value(&message[header_size..]).unwrap();
+    assert_eq!(document, body);
+    assert_eq!(message.len(), header_size + body_size);
+}
+
+#[test]
+fn build_request_message_for_line_delimited_content_fails() {
+    struct ImportLines(Value);
+
+    impl Prepare for ImportLines {
+        type Content = Value;
+
+        fn operation(&self) -> Operation {
+            Operation::Create
+        }
+
+        fn path(&self) -> String {
+            String::from("/_api/import")
+        }
+
+        fn parameters(&self) -> Parameters {
+            Parameters::empty()
+        }
+
+        fn header(&self) -> Parameters {
+            Parameters::empty()
+        }
+
+        fn content(&self) -> Option<&Self::Content> {
+            Some(&self.0)
+        }
+
+        fn is_line_delimited(&self) -> bool {
+            true
+        }
+    }
+
+    let result = build_request_message("customers", &ImportLines(json!([{ "a": 1 }])));
+
+    match result {
+        Err(Error::Serialization(_)) => {},
+        _ => panic!("Expected serialization error, but got: {:?}", result),
+    }
+}
+
+#[test]
+fn parse_error_response() {
+    let body = json!({
+        "error": true,
+        "code": 404,
+        "errorNum": 1202,
+        "errorMessage": "document not found",
+    });
+
+    let result = parse_return_type::<GetVersion>(&GetVersion::RETURN_TYPE, 404, body);
+
+    match result {
+        Err(Error::Method(error)) => {
+            assert_eq!(404, error.status_code());
+            assert_eq!(ErrorCode::ArangoDocumentNotFound, error.error_code());
+        },
+        _ => panic!("Error expected, but got: {:?}", &result),
+    }
+}
+
+#[test]
+fn execute_method_against_fake_server() {
+    let port = fake_server(|stream| {
+        let (message_id, message) = read_message(stream);
+        let (header, _) = velocypack::decode_value(&message).unwrap();
+        assert_eq!(json!([1, 1, "_system", 1, "/_api/version", {}, {}]), header);
+        write_message(
+            stream,
+            message_id,
+            &json!([1, 2, 200, {}]),
+            &json!({ "server": "arango", "version": "3.3.10" }),
+        );
+    });
+    let mut core = Core::new().unwrap();
+    let datasource = DataSource::from_str(&format!("vst://127.0.0.1:{}", port))
+        .unwrap()
+        .without_authentication();
+    let connector = VstConnector::new(datasource, &core.handle()).unwrap();
+
+    let result = core
+        .run(connector.system_connection().execute(GetVersion))
+        .unwrap();
+
+    assert_eq!(json!({ "server": "arango", "version": "3.3.10" }), result);
+}
+
+#[test]
+fn execute_multiplexed_methods_answered_in_reverse_order() {
+    let port = fake_server(|stream| {
+        let (first_id, _) = read_message(stream);
+        let (second_id, _) = read_message(stream);
+        let header = json!([1, 2, 200, {}]);
+        write_message(stream, second_id, &header, &json!({ "version": "second" }));
+        write_message(stream, first_id, &header, &json!({ "version": "first" }));
+    });
+    let mut core = Core::new().unwrap();
+    let datasource = DataSource::from_str(&format!("vst://127.0.0.1:{}", port))
+        .unwrap()
+        .without_authentication();
+    let connector = VstConnector::new(datasource, &core.handle()).unwrap();
+    let connection = connector.system_connection();
+
+    let (first, second) = core
+        .run(connection.execute(GetVersion).join(connection.execute(GetVersion)))
+        .unwrap();
+
+    assert_eq!(json!({ "version": "first" }), first);
+    assert_eq!(json!({ "version": "second" }), second);
+}
@@ -0,0 +1,158 @@
+extern crate futures;
+extern crate tokio_core;
+
+extern crate rincon_connector;
+extern crate rincon_core;
+
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use futures::Future;
+use tokio_core::reactor::Core;
+
+use rincon_connector::http::JsonHttpConnector;
+use rincon_core::api::auth::{Authentication, Credentials};
+use rincon_core::api::connector::{Connector, Execute};
+use rincon_core::api::datasource::DataSource;
+use rincon_core::api::method::{Method, Operation, Parameters, Prepare, RpcReturnType};
+use rincon_core::api::types::JsonValue;
+use rincon_core::arango::protocol::PATH_API_VERSION;
+
+const VERSION_RESPONSE: &str = r#"{"server":"arango","version":"3.3.9","license":"community"}"#;
+
+const LOGIN_RESPONSE: &str = r#"{"jwt":"abc.def.ghi"}"#;
+
+/// A method that reads the server version.
+#[derive(Debug, Clone, PartialEq)]
+struct GetVersion;
+
+impl Method for GetVersion {
+    type Result = JsonValue;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: None,
+    };
+}
+
+impl Prepare for GetVersion {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Read
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_VERSION)
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Starts a listener on a local port that answers login requests with a
+/// token and every other request with the server version.
+///
+/// Returns the port number, the number of logins and the number of all
+/// requests received so far.
+fn start_stub() -> (u16, Arc<AtomicUsize>, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let logins = Arc::new(AtomicUsize::new(0));
+    let requests = Arc::new(AtomicUsize::new(0));
+    let login_counter = logins.clone();
+    let request_counter = requests.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            if let Ok(mut stream) = stream {
+                if let Some(request_line) = read_request(&mut stream) {
+                    request_counter.fetch_add(1, Ordering::SeqCst);
+                    let body = if request_line.contains("/_open/auth") {
+                        login_counter.fetch_add(1, Ordering::SeqCst);
+                        LOGIN_RESPONSE
+                    } else {
+                        VERSION_RESPONSE
+                    };
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+                         Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    );
+                    let _ = stream.write_all(response.as_bytes());
+                }
+            }
+        }
+    });
+    (port, logins, requests)
+}
+
+/// Reads a request including its content and returns the request line.
+fn read_request(stream: &mut TcpStream) -> Option<String> {
+    let mut request = Vec::new();
+    let mut buffer = [0; 1024];
+    let header_length = loop {
+        if let Some(position) = request.windows(4).position(|window| window == b"\r\n\r\n") {
+            break position + 4;
+        }
+        match stream.read(&mut buffer) {
+            Ok(count) if count > 0 => request.extend_from_slice(&buffer[..count]),
+            _ => return None,
+        }
+    };
+    let header = String::from_utf8_lossy(&request[..header_length]).into_owned();
+    let content_length = header
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(2, ':');
+            match (parts.next(), parts.next()) {
+                (Some(name), Some(value)) if name.eq_ignore_ascii_case("content-length") => {
+                    value.trim().parse::<usize>().ok()
+                },
+                _ => None,
+            }
+        })
+        .next()
+        .unwrap_or(0);
+    while request.len() < header_length + content_length {
+        match stream.read(&mut buffer) {
+            Ok(count) if count > 0 => request.extend_from_slice(&buffer[..count]),
+            _ => return None,
+        }
+    }
+    header.lines().next().map(String::from)
+}
+
+#[test]
+fn concurrent_method_calls_share_a_single_login() {
+    let (port, logins, requests) = start_stub();
+    let mut core = Core::new().unwrap();
+    let datasource = DataSource::from_str(&format!("http://127.0.0.1:{}", port))
+        .unwrap()
+        .with_authentication(Authentication::Jwt(Credentials::new("root", "s3cur3")));
+    let connector = JsonHttpConnector::new(datasource, &core.handle()).unwrap();
+    let connection = connector.system_connection();
+
+    let work = connection
+        .execute(GetVersion)
+        .join(connection.execute(GetVersion));
+    let (version1, version2) = core.run(work).unwrap();
+
+    let expected = JsonValue::from_str(VERSION_RESPONSE).unwrap();
+    assert_eq!(expected, version1);
+    assert_eq!(expected, version2);
+    assert_eq!(1, logins.load(Ordering::SeqCst));
+    assert_eq!(3, requests.load(Ordering::SeqCst));
+}
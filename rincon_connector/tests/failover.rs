@@ -0,0 +1,169 @@
+extern crate tokio_core;
+
+extern crate rincon_connector;
+extern crate rincon_core;
+
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use tokio_core::reactor::Core;
+
+use rincon_connector::http::JsonHttpConnector;
+use rincon_core::api::connector::{Connector, Error, Execute};
+use rincon_core::api::datasource::{DataSource, Endpoint};
+use rincon_core::api::method::{Method, Operation, Parameters, Prepare, RpcReturnType};
+use rincon_core::api::types::JsonValue;
+use rincon_core::arango::protocol::PATH_API_VERSION;
+
+const VERSION_RESPONSE: &str = r#"{"server":"arango","version":"3.3.9","license":"community"}"#;
+
+const UNAVAILABLE_RESPONSE: &str =
+    r#"{"error":true,"code":503,"errorNum":503,"errorMessage":"service unavailable"}"#;
+
+/// A method without content that is sent with the given operation.
+#[derive(Debug, Clone, PartialEq)]
+struct CallServer(Operation);
+
+impl Method for CallServer {
+    type Result = JsonValue;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: None,
+    };
+}
+
+impl Prepare for CallServer {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        self.0
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_VERSION)
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+/// Returns a local port on which no connection is accepted.
+fn refused_port() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.local_addr().unwrap().port()
+}
+
+/// Starts a listener on a local port that answers every request with the
+/// given status line and body.
+///
+/// Returns the port number and the number of requests received so far.
+fn start_stub(status_line: &'static str, body: &'static str) -> (u16, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let received = Arc::new(AtomicUsize::new(0));
+    let counter = received.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            if let Ok(mut stream) = stream {
+                if read_request(&mut stream) {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    let response = format!(
+                        "HTTP/1.1 {}\r\nContent-Type: application/json\r\n\
+                         Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status_line,
+                        body.len(),
+                        body
+                    );
+                    let _ = stream.write_all(response.as_bytes());
+                }
+            }
+        }
+    });
+    (port, received)
+}
+
+/// Reads the header of a request without content.
+fn read_request(stream: &mut TcpStream) -> bool {
+    let mut request = Vec::new();
+    let mut buffer = [0; 1024];
+    while !request.ends_with(b"\r\n\r\n") {
+        match stream.read(&mut buffer) {
+            Ok(count) if count > 0 => request.extend_from_slice(&buffer[..count]),
+            _ => return false,
+        }
+    }
+    true
+}
+
+fn call_server(
+    operation: Operation,
+    primary_port: u16,
+    failover_port: u16,
+) -> Result<JsonValue, Error> {
+    let mut core = Core::new().unwrap();
+    let datasource = DataSource::from_str(&format!("http://127.0.0.1:{}", primary_port))
+        .unwrap()
+        .without_authentication()
+        .with_failover_endpoints(vec![Endpoint::new("127.0.0.1", failover_port)]);
+    let connector = JsonHttpConnector::new(datasource, &core.handle())?;
+    let work = connector.system_connection().execute(CallServer(operation));
+    core.run(work)
+}
+
+#[test]
+fn read_fails_over_from_refused_endpoint_to_live_endpoint() {
+    let (live_port, received) = start_stub("200 OK", VERSION_RESPONSE);
+
+    let version = call_server(Operation::Read, refused_port(), live_port).unwrap();
+
+    assert_eq!(JsonValue::from_str(VERSION_RESPONSE).unwrap(), version);
+    assert_eq!(1, received.load(Ordering::SeqCst));
+}
+
+#[test]
+fn create_fails_over_from_refused_endpoint_to_live_endpoint() {
+    let (live_port, received) = start_stub("200 OK", VERSION_RESPONSE);
+
+    let version = call_server(Operation::Create, refused_port(), live_port).unwrap();
+
+    assert_eq!(JsonValue::from_str(VERSION_RESPONSE).unwrap(), version);
+    assert_eq!(1, received.load(Ordering::SeqCst));
+}
+
+#[test]
+fn read_fails_over_from_unavailable_endpoint_to_live_endpoint() {
+    let (unavailable_port, _) = start_stub("503 Service Unavailable", UNAVAILABLE_RESPONSE);
+    let (live_port, received) = start_stub("200 OK", VERSION_RESPONSE);
+
+    let version = call_server(Operation::Read, unavailable_port, live_port).unwrap();
+
+    assert_eq!(JsonValue::from_str(VERSION_RESPONSE).unwrap(), version);
+    assert_eq!(1, received.load(Ordering::SeqCst));
+}
+
+#[test]
+fn create_is_not_replayed_after_unavailable_endpoint_received_it() {
+    let (unavailable_port, _) = start_stub("503 Service Unavailable", UNAVAILABLE_RESPONSE);
+    let (live_port, received) = start_stub("200 OK", VERSION_RESPONSE);
+
+    let result = call_server(Operation::Create, unavailable_port, live_port);
+
+    match result {
+        Err(Error::Method(ref error)) => assert_eq!(503, error.status_code()),
+        _ => panic!("Expected service unavailable error, but got: {:?}", result),
+    }
+    assert_eq!(0, received.load(Ordering::SeqCst));
+}
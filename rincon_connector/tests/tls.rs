@@ -0,0 +1,227 @@
+extern crate rustls;
+extern crate tokio_core;
+
+extern crate rincon_connector;
+extern crate rincon_core;
+
+use std::fs::File;
+use std::io::{self, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::thread;
+
+use rustls::internal::pemfile;
+use rustls::{
+    AllowAnyAuthenticatedClient, ClientCertVerifier, NoClientAuth, RootCertStore, ServerConfig,
+    ServerSession, Session,
+};
+use tokio_core::reactor::Core;
+
+use rincon_connector::http::JsonHttpConnector;
+use rincon_core::api::connector::{Connector, Error, Execute};
+use rincon_core::api::datasource::{DataSource, TlsOptions};
+use rincon_core::api::method::{Method, Operation, Parameters, Prepare, RpcReturnType};
+use rincon_core::api::types::JsonValue;
+use rincon_core::arango::protocol::PATH_API_VERSION;
+
+const VERSION_RESPONSE: &str = r#"{"server":"arango","version":"3.3.9","license":"community"}"#;
+
+#[derive(Debug, Clone, PartialEq)]
+struct GetServerVersion;
+
+impl Method for GetServerVersion {
+    type Result = JsonValue;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: None,
+        code_field: None,
+    };
+}
+
+impl Prepare for GetServerVersion {
+    type Content = ();
+
+    fn operation(&self) -> Operation {
+        Operation::Read
+    }
+
+    fn path(&self) -> String {
+        String::from(PATH_API_VERSION)
+    }
+
+    fn parameters(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn header(&self) -> Parameters {
+        Parameters::empty()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        None
+    }
+}
+
+fn cert_file(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("certs")
+        .join(name)
+}
+
+fn open_cert_file(name: &str) -> BufReader<File> {
+    BufReader::new(File::open(cert_file(name)).unwrap())
+}
+
+/// Starts a TLS listener on a local port that answers every request with
+/// the server version and returns the port number.
+fn start_tls_listener(require_client_certificate: bool) -> u16 {
+    let client_verifier: Arc<ClientCertVerifier> = if require_client_certificate {
+        let mut client_roots = RootCertStore::empty();
+        client_roots
+            .add_pem_file(&mut open_cert_file("ca.pem"))
+            .unwrap();
+        AllowAnyAuthenticatedClient::new(client_roots)
+    } else {
+        NoClientAuth::new()
+    };
+    let mut config = ServerConfig::new(client_verifier);
+    let certificates = pemfile::certs(&mut open_cert_file("server.pem")).unwrap();
+    let private_key = pemfile::pkcs8_private_keys(&mut open_cert_file("server.key"))
+        .unwrap()
+        .remove(0);
+    let _ = config.set_single_cert(certificates, private_key);
+    let config = Arc::new(config);
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            if let Ok(mut tcp_stream) = stream {
+                let mut session = ServerSession::new(&config);
+                let _ = serve_connection(&mut session, &mut tcp_stream);
+            }
+        }
+    });
+    port
+}
+
+fn serve_connection(session: &mut ServerSession, tcp_stream: &mut TcpStream) -> io::Result<()> {
+    let mut request = Vec::new();
+    let mut buffer = [0; 1024];
+    while !request.ends_with(b"\r\n\r\n") {
+        while session.wants_write() {
+            session.write_tls(tcp_stream)?;
+        }
+        let count = session.read(&mut buffer)?;
+        if count > 0 {
+            request.extend_from_slice(&buffer[..count]);
+            continue;
+        }
+        if session.read_tls(tcp_stream)? == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        session
+            .process_new_packets()
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+    }
+    let response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+        VERSION_RESPONSE.len(),
+        VERSION_RESPONSE
+    );
+    session.write_all(response.as_bytes())?;
+    session.send_close_notify();
+    while session.wants_write() {
+        session.write_tls(tcp_stream)?;
+    }
+    Ok(())
+}
+
+fn get_server_version(url: &str, tls_options: TlsOptions) -> Result<JsonValue, Error> {
+    let mut core = Core::new().unwrap();
+    let datasource = DataSource::from_str(url)
+        .unwrap()
+        .without_authentication()
+        .with_tls_options(tls_options);
+    let connector = JsonHttpConnector::new(datasource, &core.handle())?;
+    let work = connector.system_connection().execute(GetServerVersion);
+    core.run(work)
+}
+
+#[test]
+fn https_connection_with_custom_root_certificates() {
+    let port = start_tls_listener(false);
+    let url = format!("https://localhost:{}", port);
+    let tls_options = TlsOptions::new().with_root_certificates(cert_file("ca.pem"));
+
+    let version = get_server_version(&url, tls_options).unwrap();
+
+    assert_eq!(JsonValue::from_str(VERSION_RESPONSE).unwrap(), version);
+}
+
+#[test]
+fn https_connection_fails_if_certificate_is_issued_by_unknown_authority() {
+    let port = start_tls_listener(false);
+    let url = format!("https://localhost:{}", port);
+
+    let result = get_server_version(&url, TlsOptions::new());
+
+    match result {
+        Err(Error::Communication(_)) => {},
+        _ => panic!("Expected communication error, but got: {:?}", result),
+    }
+}
+
+#[test]
+fn https_connection_accepting_invalid_certificates() {
+    let port = start_tls_listener(false);
+    let url = format!("https://localhost:{}", port);
+    let tls_options = TlsOptions::new().with_accept_invalid_certificates(true);
+
+    let version = get_server_version(&url, tls_options).unwrap();
+
+    assert_eq!(JsonValue::from_str(VERSION_RESPONSE).unwrap(), version);
+}
+
+#[test]
+fn https_connection_with_server_name_override() {
+    let port = start_tls_listener(false);
+    let url = format!("https://127.0.0.1:{}", port);
+    let tls_options = TlsOptions::new()
+        .with_root_certificates(cert_file("ca.pem"))
+        .with_server_name("localhost");
+
+    let version = get_server_version(&url, tls_options).unwrap();
+
+    assert_eq!(JsonValue::from_str(VERSION_RESPONSE).unwrap(), version);
+}
+
+#[test]
+fn https_connection_with_client_certificate() {
+    let port = start_tls_listener(true);
+    let url = format!("https://localhost:{}", port);
+    let tls_options = TlsOptions::new()
+        .with_root_certificates(cert_file("ca.pem"))
+        .with_client_certificate(cert_file("client.pem"), cert_file("client.key"));
+
+    let version = get_server_version(&url, tls_options).unwrap();
+
+    assert_eq!(JsonValue::from_str(VERSION_RESPONSE).unwrap(), version);
+}
+
+#[test]
+fn https_connection_fails_without_client_certificate_if_server_requires_one() {
+    let port = start_tls_listener(true);
+    let url = format!("https://localhost:{}", port);
+    let tls_options = TlsOptions::new().with_root_certificates(cert_file("ca.pem"));
+
+    let result = get_server_version(&url, tls_options);
+
+    match result {
+        Err(Error::Communication(_)) => {},
+        _ => panic!("Expected communication error, but got: {:?}", result),
+    }
+}
@@ -1,6 +1,8 @@
 //! Type definitions for various authentication methods used by the rincon
 //! driver.
 
+use std::fmt;
+
 /// This enum defines the supported authentication methods.
 #[derive(Debug, Clone)]
 pub enum Authentication {
@@ -8,6 +10,9 @@ pub enum Authentication {
     Basic(Credentials),
     /// Authentication via JSON Web Token (JWT).
     Jwt(Credentials),
+    /// Authentication via a superuser JSON Web Token (JWT) that is generated
+    /// and signed locally with the JWT secret of the server.
+    JwtSecret(JwtSecret),
     /// No authentication.
     None,
 }
@@ -44,5 +49,43 @@ impl Credentials {
     }
 }
 
+/// This struct holds the JWT secret that is shared by the servers of an
+/// [ArangoDB] deployment.
+///
+/// The secret is used to sign superuser tokens with HMAC-SHA256 (HS256). It
+/// is configured on the server with the `--server.jwt-secret` option. Anyone
+/// knowing the secret has full access to the server, so it must be kept as
+/// safe as the password of the root user.
+///
+/// [ArangoDB]: https://www.arangodb.com
+#[derive(Clone, PartialEq, Hash)]
+pub struct JwtSecret {
+    /// The secret used to sign tokens.
+    secret: String,
+}
+
+impl JwtSecret {
+    /// Constructs a new `JwtSecret` with the given secret.
+    pub fn new<S>(secret: S) -> Self
+    where
+        S: Into<String>,
+    {
+        JwtSecret {
+            secret: secret.into(),
+        }
+    }
+
+    /// Returns the secret as a byte slice.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.secret.as_bytes()
+    }
+}
+
+impl fmt::Debug for JwtSecret {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("JwtSecret(***)")
+    }
+}
+
 /// Type definition for a JSON Web Token (JWT).
 pub type Jwt = String;
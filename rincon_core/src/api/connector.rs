@@ -17,7 +17,14 @@
 //!
 //! Implementations of a connection know how to serialize and send method calls
 //! to the server and deserialize the responses into the defined result type.
+//!
+//! The `Connector` and `Execute` traits are based on version 0.1 of the
+//! `futures` crate. The `AsyncConnector` and `AsyncExecute` traits are their
+//! counterparts based on `std::future::Future`, which can be awaited in
+//! `async` functions.
+use std::future;
 use std::io;
+use std::pin::Pin;
 
 use futures::Future;
 
@@ -63,6 +70,47 @@ pub trait Execute {
 /// The result of any asynchronous method call
 pub type FutureResult<M> = Box<Future<Item = <M as Method>::Result, Error = Error>>;
 
+/// A connector that provides connections which execute method calls as
+/// `std::future::Future`s.
+///
+/// Connectors and connections of this kind can be shared between threads.
+pub trait AsyncConnector: Send + Sync {
+    /// The type of connections this connector provides.
+    type Connection: 'static + AsyncExecute + Send + Sync;
+
+    /// Establishes a connection to the database with the given name and returns
+    /// the connection.
+    fn connection(&self, database_name: &str) -> Self::Connection;
+
+    /// Establishes a connection to the system database as defined by ArangoDB
+    /// and returns the connection.
+    fn system_connection(&self) -> Self::Connection;
+
+    /// Tells this connector to use the given token for authentication.
+    fn use_auth_token(&mut self, token: Jwt);
+
+    /// Tells this connector to no longer use the configured token for
+    /// authentication.
+    fn invalidate_auth_token(&mut self);
+}
+
+/// A type that can execute method calls as `std::future::Future`s.
+///
+/// Any type that implements this `AsyncExecute` trait can be returned by the
+/// `AsyncConnector` as `AsyncConnector::Connection`.
+pub trait AsyncExecute {
+    /// Executes the given method asynchronously and returns a future result
+    /// that can be awaited.
+    fn execute<M>(&self, method: M) -> AsyncFutureResult<M>
+    where
+        M: 'static + Method + Prepare + Send,
+        <M as Method>::Result: Send;
+}
+
+/// The result of any method call executed by an `AsyncExecute` type.
+pub type AsyncFutureResult<M> =
+    Pin<Box<future::Future<Output = Result<<M as Method>::Result, Error>> + Send>>;
+
 /// The type of error that can occur during communication with the server.
 #[derive(Debug, Clone, PartialEq, Eq, Fail)]
 pub enum Error {
@@ -19,6 +19,7 @@ use std;
 use std::fmt::{self, Debug, Display};
 use std::iter::{ExactSizeIterator, FromIterator, Iterator};
 use std::slice::Iter;
+use std::time::Duration;
 use std::vec::IntoIter;
 
 use serde::de::DeserializeOwned;
@@ -88,13 +89,118 @@ pub trait Prepare {
     ///
     /// The content of a method is usually sent within the body of a REST call.
     fn content(&self) -> Option<&Self::Content>;
+
+    /// Returns whether the items of the content are sent one per line.
+    ///
+    /// Some operations like the import of documents expect the content as
+    /// JSON values separated by newlines instead of a single JSON array. The
+    /// content of such methods must serialize into a sequence. Connectors
+    /// that send the content as VelocyPack reject such methods with an
+    /// `Error::Serialization`, as there is no line delimited form of
+    /// VelocyPack.
+    ///
+    /// The default implementation returns `false`.
+    fn is_line_delimited(&self) -> bool {
+        false
+    }
+
+    /// Returns the timeout for executing this method call.
+    ///
+    /// If the method call is not completed within this duration it is
+    /// cancelled and fails with an `Error::Timeout`. If `None` is returned the
+    /// connector uses the timeout defined by its `DataSource`.
+    ///
+    /// The default implementation returns `None`.
+    fn timeout(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// Wraps a method call and overrides the timeout for its execution.
+///
+/// The timeout of the wrapped method is replaced by the given timeout. All
+/// other functions are forwarded to the wrapped method.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WithTimeout<M> {
+    method: M,
+    timeout: Duration,
+}
+
+impl<M> WithTimeout<M> {
+    /// Constructs a new `WithTimeout` for the given method and timeout.
+    pub fn new<D>(method: M, timeout: D) -> Self
+    where
+        D: Into<Duration>,
+    {
+        WithTimeout {
+            method,
+            timeout: timeout.into(),
+        }
+    }
+
+    /// Returns the wrapped method.
+    pub fn method(&self) -> &M {
+        &self.method
+    }
+
+    /// Unwraps the wrapped method.
+    pub fn into_method(self) -> M {
+        self.method
+    }
+}
+
+impl<M> Method for WithTimeout<M>
+where
+    M: Method,
+{
+    type Result = <M as Method>::Result;
+    const RETURN_TYPE: RpcReturnType = <M as Method>::RETURN_TYPE;
+
+    fn return_type(&self) -> RpcReturnType {
+        self.method.return_type()
+    }
+}
+
+impl<M> Prepare for WithTimeout<M>
+where
+    M: Prepare,
+{
+    type Content = <M as Prepare>::Content;
+
+    fn operation(&self) -> Operation {
+        self.method.operation()
+    }
+
+    fn path(&self) -> String {
+        self.method.path()
+    }
+
+    fn parameters(&self) -> Parameters {
+        self.method.parameters()
+    }
+
+    fn header(&self) -> Parameters {
+        self.method.header()
+    }
+
+    fn content(&self) -> Option<&Self::Content> {
+        self.method.content()
+    }
+
+    fn is_line_delimited(&self) -> bool {
+        self.method.is_line_delimited()
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        Some(self.timeout)
+    }
 }
 
 /// Enumeration of the used operation of a REST API.
 ///
 /// The operations are defined in a logical sense thus being abstract over the
 /// HTTP operations like POST, GET, PUT, PATCH, etc.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Operation {
     /// Create a new entity
     Create,
@@ -0,0 +1,242 @@
+//! Instrumentation of method calls.
+//!
+//! This module defines the `MetricsSink` trait. A sink receives timings,
+//! status codes, payload sizes and error categories of the method calls
+//! executed by a connector. It can forward them to any metrics or tracing
+//! system.
+//!
+//! Each method call is represented by a `MethodSpan`. A span is started
+//! right before the request is sent and finished when the response has been
+//! processed. The span is keyed by the name of the `Method` as returned by
+//! the `method_name()` function and the path as returned by
+//! `Prepare::path()`.
+
+#[cfg(test)]
+mod tests;
+
+use std::any::type_name;
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicUsize, Ordering, ATOMIC_USIZE_INIT};
+use std::time::{Duration, Instant};
+
+use api::connector::Error;
+use api::method::Operation;
+use api::ErrorCode;
+
+static NEXT_SPAN_ID: AtomicUsize = ATOMIC_USIZE_INIT;
+
+/// A receiver of the metrics collected during method execution.
+///
+/// All functions of this trait have a default implementation that does
+/// nothing, so implementations only need to override the functions they are
+/// interested in.
+pub trait MetricsSink: Debug {
+    /// Is called right before the request of a method call is sent.
+    fn span_started(&self, span: &MethodSpan) {
+        let _ = span;
+    }
+
+    /// Is called when the response of a method call has been processed or
+    /// the method call has failed.
+    fn span_finished(&self, span: &MethodSpan, metrics: &MethodMetrics) {
+        let _ = (span, metrics);
+    }
+
+    /// Records a single named value, e.g. a value of the statistics returned
+    /// for an AQL query.
+    fn record_value(&self, name: &str, value: f64) {
+        let _ = (name, value);
+    }
+}
+
+/// Returns the name of the given method type.
+///
+/// The name is the name of the type without the module path and without
+/// generic parameters, e.g. `GetDocument` for the type
+/// `rincon_client::document::methods::GetDocument<Customer>`. Thus the name
+/// is stable across crate versions and does not depend on the type of the
+/// documents a method is used with.
+pub fn method_name<M>() -> &'static str {
+    let name = type_name::<M>();
+    let name = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// Identifies one execution of a method call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MethodSpan {
+    id: u64,
+    method_name: &'static str,
+    operation: Operation,
+    path: String,
+    started_at: Instant,
+}
+
+impl MethodSpan {
+    /// Starts a new span for the method with the given name, operation and
+    /// path.
+    ///
+    /// Each span gets a unique id assigned.
+    pub fn start<P>(method_name: &'static str, operation: Operation, path: P) -> Self
+    where
+        P: Into<String>,
+    {
+        MethodSpan {
+            id: NEXT_SPAN_ID.fetch_add(1, Ordering::SeqCst) as u64,
+            method_name,
+            operation,
+            path: path.into(),
+            started_at: Instant::now(),
+        }
+    }
+
+    /// Returns the unique id of this span.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Returns the name of the method.
+    pub fn method_name(&self) -> &'static str {
+        self.method_name
+    }
+
+    /// Returns the operation of the method.
+    pub fn operation(&self) -> Operation {
+        self.operation
+    }
+
+    /// Returns the path of the method.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the point in time when this span has been started.
+    pub fn started_at(&self) -> Instant {
+        self.started_at
+    }
+
+    /// Returns the time elapsed since this span has been started.
+    pub fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+}
+
+/// The metrics collected for one execution of a method call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MethodMetrics {
+    duration: Duration,
+    status_code: Option<u16>,
+    request_size: u64,
+    response_size: Option<u64>,
+    error_kind: Option<ErrorKind>,
+}
+
+impl MethodMetrics {
+    /// Constructs new `MethodMetrics`.
+    ///
+    /// The `status_code` and the `response_size` are `None` if no response
+    /// has been received. The `error` is `None` if the method call has been
+    /// successful.
+    pub fn new(
+        duration: Duration,
+        status_code: Option<u16>,
+        request_size: u64,
+        response_size: Option<u64>,
+        error: Option<&Error>,
+    ) -> Self {
+        MethodMetrics {
+            duration,
+            status_code,
+            request_size,
+            response_size,
+            error_kind: error.map(ErrorKind::of),
+        }
+    }
+
+    /// Returns the time the method call took.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Returns the HTTP status code of the response if a response has been
+    /// received.
+    pub fn status_code(&self) -> Option<u16> {
+        self.status_code
+    }
+
+    /// Returns the size of the request payload in bytes.
+    pub fn request_size(&self) -> u64 {
+        self.request_size
+    }
+
+    /// Returns the size of the response payload in bytes if a response has
+    /// been received.
+    pub fn response_size(&self) -> Option<u64> {
+        self.response_size
+    }
+
+    /// Returns the category of the error if the method call failed.
+    pub fn error_kind(&self) -> Option<&ErrorKind> {
+        self.error_kind.as_ref()
+    }
+
+    /// Returns whether the method call has been successful.
+    pub fn is_success(&self) -> bool {
+        self.error_kind.is_none()
+    }
+}
+
+/// The category of an error that occurred during a method call.
+///
+/// There is one variant for each variant of the `connector::Error`. Errors
+/// returned by the server also carry the `ErrorCode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    /// A communication error.
+    Communication,
+    /// An error during deserialization of a response.
+    Deserialization,
+    /// An error returned by the server.
+    Method(ErrorCode),
+    /// The caller is not authenticated.
+    NotAuthenticated,
+    /// An error during serialization of a request.
+    Serialization,
+    /// A timeout.
+    Timeout,
+}
+
+impl ErrorKind {
+    /// Returns the category of the given error.
+    pub fn of(error: &Error) -> Self {
+        match *error {
+            Error::Communication(_) => ErrorKind::Communication,
+            Error::Deserialization(_) => ErrorKind::Deserialization,
+            Error::Method(ref error) => ErrorKind::Method(error.error_code()),
+            Error::NotAuthenticated(_) => ErrorKind::NotAuthenticated,
+            Error::Serialization(_) => ErrorKind::Serialization,
+            Error::Timeout(_) => ErrorKind::Timeout,
+        }
+    }
+
+    /// Returns the name of this category.
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            ErrorKind::Communication => "communication",
+            ErrorKind::Deserialization => "deserialization",
+            ErrorKind::Method(_) => "method",
+            ErrorKind::NotAuthenticated => "not_authenticated",
+            ErrorKind::Serialization => "serialization",
+            ErrorKind::Timeout => "timeout",
+        }
+    }
+
+    /// Returns the error code if this category represents an error returned
+    /// by the server.
+    pub fn error_code(&self) -> Option<ErrorCode> {
+        match *self {
+            ErrorKind::Method(error_code) => Some(error_code),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use super::*;
+use api;
+
+#[test]
+fn error_kind_of_communication_error() {
+    let error = Error::Communication("connection refused".to_owned());
+
+    let kind = ErrorKind::of(&error);
+
+    assert_eq!(ErrorKind::Communication, kind);
+    assert_eq!("communication", kind.as_str());
+    assert_eq!(None, kind.error_code());
+}
+
+#[test]
+fn error_kind_of_method_error() {
+    let error = Error::Method(api::Error::new(
+        404,
+        ErrorCode::ArangoDocumentNotFound,
+        "document not found",
+    ));
+
+    let kind = ErrorKind::of(&error);
+
+    assert_eq!(ErrorKind::Method(ErrorCode::ArangoDocumentNotFound), kind);
+    assert_eq!("method", kind.as_str());
+    assert_eq!(Some(ErrorCode::ArangoDocumentNotFound), kind.error_code());
+}
+
+#[test]
+fn error_kind_of_timeout_error() {
+    let error = Error::Timeout("no response within 30s".to_owned());
+
+    let kind = ErrorKind::of(&error);
+
+    assert_eq!(ErrorKind::Timeout, kind);
+    assert_eq!("timeout", kind.as_str());
+}
+
+#[test]
+fn method_metrics_of_successful_method_call() {
+    let metrics = MethodMetrics::new(Duration::from_millis(12), Some(200), 42, Some(1024), None);
+
+    assert!(metrics.is_success());
+    assert_eq!(None, metrics.error_kind());
+    assert_eq!(Duration::from_millis(12), metrics.duration());
+    assert_eq!(Some(200), metrics.status_code());
+    assert_eq!(42, metrics.request_size());
+    assert_eq!(Some(1024), metrics.response_size());
+}
+
+#[test]
+fn method_metrics_of_failed_method_call() {
+    let error = Error::Communication("connection refused".to_owned());
+
+    let metrics = MethodMetrics::new(Duration::from_millis(3), None, 42, None, Some(&error));
+
+    assert!(!metrics.is_success());
+    assert_eq!(Some(&ErrorKind::Communication), metrics.error_kind());
+    assert_eq!(None, metrics.status_code());
+    assert_eq!(None, metrics.response_size());
+}
+
+#[test]
+fn method_spans_have_unique_ids() {
+    let path = "/_api/collection/customers";
+
+    let span1 = MethodSpan::start("GetCollection", Operation::Read, path);
+    let span2 = MethodSpan::start("GetCollection", Operation::Read, path);
+
+    assert_ne!(span1.id(), span2.id());
+    assert_eq!("GetCollection", span1.method_name());
+    assert_eq!(Operation::Read, span1.operation());
+    assert_eq!(path, span1.path());
+}
+
+mod some_module {
+    pub struct SomeMethod<T>(pub T);
+}
+
+#[test]
+fn method_name_of_method_type() {
+    assert_eq!("MethodSpan", method_name::<MethodSpan>());
+}
+
+#[test]
+fn method_name_strips_module_path_and_generic_parameters() {
+    assert_eq!(
+        "SomeMethod",
+        method_name::<some_module::SomeMethod<some_module::SomeMethod<String>>>()
+    );
+}
@@ -4,6 +4,8 @@ pub mod auth;
 pub mod connector;
 pub mod datasource;
 pub mod method;
+pub mod metrics;
+pub mod payload;
 pub mod query;
 //pub mod statement;
 pub mod types;
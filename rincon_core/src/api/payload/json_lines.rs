@@ -0,0 +1,223 @@
+//! Serialization of sequences as JSON values separated by newlines.
+
+use serde::ser::{self, Error as SerError, Impossible, Serialize};
+use serde_json::{self, Error};
+
+/// A serializer that writes each element of a sequence as JSON value on its
+/// own line.
+///
+/// The elements are serialized directly into the output buffer. Values that
+/// are not sequences are rejected.
+#[derive(Debug)]
+pub struct JsonLinesSerializer<'a> {
+    output: &'a mut Vec<u8>,
+}
+
+impl<'a> JsonLinesSerializer<'a> {
+    /// Creates a new `JsonLinesSerializer` that appends to the given buffer.
+    pub fn new(output: &'a mut Vec<u8>) -> Self {
+        JsonLinesSerializer { output }
+    }
+}
+
+fn not_a_sequence() -> Error {
+    Error::custom("line delimited content must be a sequence")
+}
+
+impl<'a> ser::Serializer for JsonLinesSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = Impossible<(), Error>;
+    type SerializeStruct = Impossible<(), Error>;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    fn serialize_bool(self, _value: bool) -> Result<(), Error> {
+        Err(not_a_sequence())
+    }
+
+    fn serialize_i8(self, _value: i8) -> Result<(), Error> {
+        Err(not_a_sequence())
+    }
+
+    fn serialize_i16(self, _value: i16) -> Result<(), Error> {
+        Err(not_a_sequence())
+    }
+
+    fn serialize_i32(self, _value: i32) -> Result<(), Error> {
+        Err(not_a_sequence())
+    }
+
+    fn serialize_i64(self, _value: i64) -> Result<(), Error> {
+        Err(not_a_sequence())
+    }
+
+    fn serialize_u8(self, _value: u8) -> Result<(), Error> {
+        Err(not_a_sequence())
+    }
+
+    fn serialize_u16(self, _value: u16) -> Result<(), Error> {
+        Err(not_a_sequence())
+    }
+
+    fn serialize_u32(self, _value: u32) -> Result<(), Error> {
+        Err(not_a_sequence())
+    }
+
+    fn serialize_u64(self, _value: u64) -> Result<(), Error> {
+        Err(not_a_sequence())
+    }
+
+    fn serialize_f32(self, _value: f32) -> Result<(), Error> {
+        Err(not_a_sequence())
+    }
+
+    fn serialize_f64(self, _value: f64) -> Result<(), Error> {
+        Err(not_a_sequence())
+    }
+
+    fn serialize_char(self, _value: char) -> Result<(), Error> {
+        Err(not_a_sequence())
+    }
+
+    fn serialize_str(self, _value: &str) -> Result<(), Error> {
+        Err(not_a_sequence())
+    }
+
+    fn serialize_bytes(self, _value: &[u8]) -> Result<(), Error> {
+        Err(not_a_sequence())
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        Err(not_a_sequence())
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        Err(not_a_sequence())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        Err(not_a_sequence())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<(), Error> {
+        Err(not_a_sequence())
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        Err(not_a_sequence())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self, Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self, Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        Err(not_a_sequence())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(not_a_sequence())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Err(not_a_sequence())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        Err(not_a_sequence())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Err(not_a_sequence())
+    }
+}
+
+impl<'a> ser::SerializeSeq for JsonLinesSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        serde_json::to_writer(&mut *self.output, value)?;
+        self.output.push(b'\n');
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl<'a> ser::SerializeTuple for JsonLinesSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
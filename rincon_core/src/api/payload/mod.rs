@@ -0,0 +1,151 @@
+//! Serialization of request payloads and deserialization of response
+//! payloads.
+//!
+//! The functions of this module are shared by the connector implementations,
+//! so that the content of method calls is converted the same way regardless
+//! of the transport protocol.
+
+#[cfg(test)]
+mod tests;
+
+mod json_lines;
+
+use serde::de::DeserializeOwned;
+use serde::ser::Serialize;
+use serde_json::{self, Value};
+
+use api;
+use api::connector::Error;
+use api::datasource::ContentFormat;
+use api::method::{Method, RpcReturnType};
+use velocypack;
+
+use self::json_lines::JsonLinesSerializer;
+
+/// Serializes the content of a method call into the payload of a request.
+///
+/// Line delimited content must be a sequence, of which each element is
+/// written as JSON value on its own line. Line delimited content can not be
+/// serialized as VelocyPack.
+pub fn serialize_payload<T>(
+    content_format: ContentFormat,
+    line_delimited: bool,
+    content: &T,
+) -> Result<Vec<u8>, Error>
+where
+    T: Serialize,
+{
+    match content_format {
+        ContentFormat::Json if line_delimited => serialize_json_lines(content),
+        ContentFormat::Json => {
+            serde_json::to_vec(content).map_err(|cause| Error::Serialization(cause.to_string()))
+        },
+        ContentFormat::VelocyPack if line_delimited => Err(Error::Serialization(
+            "line delimited content can not be sent as VelocyPack".into(),
+        )),
+        ContentFormat::VelocyPack => {
+            velocypack::to_vec(content).map_err(|cause| Error::Serialization(cause.to_string()))
+        },
+    }
+}
+
+/// Serializes the given content as JSON values separated by newlines.
+///
+/// Each element of the sequence is written directly into the payload without
+/// building an intermediate tree of JSON values.
+fn serialize_json_lines<T>(content: &T) -> Result<Vec<u8>, Error>
+where
+    T: Serialize,
+{
+    let mut payload = Vec::new();
+    content
+        .serialize(JsonLinesSerializer::new(&mut payload))
+        .map_err(|cause| Error::Serialization(cause.to_string()))?;
+    Ok(payload)
+}
+
+/// Deserializes the payload of a response into the result of the method
+/// call.
+///
+/// If the status code signals an error the payload is deserialized into an
+/// `api::Error`. If the payload does not contain an error, the error is
+/// derived from the status code.
+pub fn parse_return_type<M>(
+    return_type: &RpcReturnType,
+    status_code: u16,
+    content_format: ContentFormat,
+    payload: &[u8],
+) -> Result<<M as Method>::Result, Error>
+where
+    M: Method,
+{
+    if (200..300).contains(&status_code) {
+        match content_format {
+            ContentFormat::Json => deserialize_json_result(return_type, payload)
+                .map_err(|cause| Error::Deserialization(cause.to_string())),
+            ContentFormat::VelocyPack => deserialize_velocypack_result(return_type, payload)
+                .map_err(|cause| Error::Deserialization(cause.to_string())),
+        }
+    } else {
+        let api_error = match content_format {
+            ContentFormat::Json => serde_json::from_slice(payload).ok(),
+            ContentFormat::VelocyPack => velocypack::from_slice(payload).ok(),
+        };
+        let api_error = api_error.unwrap_or_else(|| {
+            let error_code = api::ErrorCode::from_u16(status_code);
+            let message = if payload.is_empty() {
+                error_code.description().to_owned()
+            } else {
+                payload_to_string(content_format, payload)
+            };
+            api::Error::new(status_code, error_code, message)
+        });
+        Err(Error::Method(api_error))
+    }
+}
+
+fn deserialize_json_result<T>(
+    return_type: &RpcReturnType,
+    payload: &[u8],
+) -> Result<T, serde_json::Error>
+where
+    T: DeserializeOwned,
+{
+    match return_type.result_field {
+        Some(result_field) => match serde_json::from_slice(payload) {
+            Ok(Value::Object(ref mut obj)) => match obj.remove(result_field) {
+                Some(result_value) => serde_json::from_value(result_value),
+                None => serde_json::from_slice(payload),
+            },
+            _ => serde_json::from_slice(payload),
+        },
+        None => serde_json::from_slice(payload),
+    }
+}
+
+fn deserialize_velocypack_result<T>(
+    return_type: &RpcReturnType,
+    payload: &[u8],
+) -> Result<T, velocypack::Error>
+where
+    T: DeserializeOwned,
+{
+    match return_type.result_field {
+        Some(result_field) => match velocypack::find_attribute(payload, result_field)? {
+            Some(result_value) => velocypack::from_slice(result_value),
+            None => velocypack::from_slice(payload),
+        },
+        None => velocypack::from_slice(payload),
+    }
+}
+
+/// Returns a human readable representation of the given payload for logging.
+pub fn payload_to_string(content_format: ContentFormat, payload: &[u8]) -> String {
+    match content_format {
+        ContentFormat::Json => String::from_utf8_lossy(payload).to_string(),
+        ContentFormat::VelocyPack => match velocypack::decode_value(payload) {
+            Ok((value, _)) => value.to_string(),
+            Err(_) => format!("{:?}", payload),
+        },
+    }
+}
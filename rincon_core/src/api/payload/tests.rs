@@ -0,0 +1,173 @@
+use super::*;
+use api::ErrorCode;
+use arango::protocol::FIELD_RESULT;
+
+struct GetResult;
+
+impl Method for GetResult {
+    type Result = Value;
+    const RETURN_TYPE: RpcReturnType = RpcReturnType {
+        result_field: Some(FIELD_RESULT),
+        code_field: None,
+    };
+}
+
+#[test]
+fn serialize_payload_as_json() {
+    let payload = serialize_payload(ContentFormat::Json, false, &json!({"a": 1})).unwrap();
+
+    assert_eq!("{\"a\":1}", String::from_utf8(payload).unwrap());
+}
+
+#[test]
+fn serialize_payload_as_velocypack() {
+    let payload = serialize_payload(ContentFormat::VelocyPack, false, &json!({"a": 1})).unwrap();
+
+    assert_eq!(vec![0x14, 0x06, 0x41, b'a', 0x31, 0x01], payload);
+}
+
+#[test]
+fn serialize_line_delimited_payload_as_velocypack_fails() {
+    let result = serialize_payload(ContentFormat::VelocyPack, true, &json!([{"a": 1}]));
+
+    match result {
+        Err(Error::Serialization(_)) => {},
+        _ => panic!("Expected serialization error, but got: {:?}", result),
+    }
+}
+
+#[test]
+fn serialize_payload_as_json_lines() {
+    let payload =
+        serialize_payload(ContentFormat::Json, true, &json!([{"a": 1}, ["b", "c"]])).unwrap();
+
+    assert_eq!(
+        "{\"a\":1}\n[\"b\",\"c\"]\n",
+        String::from_utf8(payload).unwrap()
+    );
+}
+
+#[test]
+fn serialize_vec_of_structs_as_json_lines() {
+    #[derive(Serialize)]
+    struct Person {
+        name: &'static str,
+        age: u16,
+    }
+    let people = vec![
+        Person {
+            name: "Jane",
+            age: 42,
+        },
+        Person {
+            name: "John",
+            age: 27,
+        },
+    ];
+
+    let payload = serialize_payload(ContentFormat::Json, true, &people).unwrap();
+
+    assert_eq!(
+        "{\"name\":\"Jane\",\"age\":42}\n{\"name\":\"John\",\"age\":27}\n",
+        String::from_utf8(payload).unwrap()
+    );
+}
+
+#[test]
+fn serialize_payload_as_json_lines_fails_for_non_sequence() {
+    let result = serialize_payload(ContentFormat::Json, true, &json!({"a": 1}));
+
+    match result {
+        Err(Error::Serialization(_)) => {},
+        _ => panic!("Expected serialization error, but got: {:?}", result),
+    }
+}
+
+#[test]
+fn parse_json_result_field() {
+    let payload = br#"{"error":false,"code":200,"result":{"name":"a"}}"#;
+
+    let result =
+        parse_return_type::<GetResult>(&GetResult::RETURN_TYPE, 200, ContentFormat::Json, payload);
+
+    assert_eq!(Ok(json!({ "name": "a" })), result);
+}
+
+#[test]
+fn parse_json_error() {
+    let payload =
+        br#"{"error":true,"code":404,"errorNum":1203,"errorMessage":"collection not found"}"#;
+
+    let result =
+        parse_return_type::<GetResult>(&GetResult::RETURN_TYPE, 404, ContentFormat::Json, payload);
+
+    assert_eq!(
+        Err(Error::Method(api::Error::new(
+            404,
+            ErrorCode::ArangoCollectionNotFound,
+            "collection not found"
+        ))),
+        result
+    );
+}
+
+#[test]
+fn parse_error_without_payload() {
+    let result =
+        parse_return_type::<GetResult>(&GetResult::RETURN_TYPE, 401, ContentFormat::Json, b"");
+
+    match result {
+        Err(Error::Method(error)) => {
+            assert_eq!(401, error.status_code());
+            assert_eq!(ErrorCode::HttpUnauthorized, error.error_code());
+        },
+        _ => panic!("Error expected, but got: {:?}", &result),
+    }
+}
+
+#[test]
+fn parse_velocypack_result_field() {
+    let payload = velocypack::to_vec(&json!({
+        "error": false,
+        "code": 200,
+        "result": { "name": "customers" },
+    }))
+    .unwrap();
+
+    let result = parse_return_type::<GetResult>(
+        &GetResult::RETURN_TYPE,
+        200,
+        ContentFormat::VelocyPack,
+        &payload,
+    )
+    .unwrap();
+
+    assert_eq!(json!({ "name": "customers" }), result);
+}
+
+#[test]
+fn parse_velocypack_error() {
+    let payload = velocypack::to_vec(&json!({
+        "error": true,
+        "code": 404,
+        "errorNum": 1202,
+        "errorMessage": "document not found",
+    }))
+    .unwrap();
+
+    let result = parse_return_type::<GetResult>(
+        &GetResult::RETURN_TYPE,
+        404,
+        ContentFormat::VelocyPack,
+        &payload,
+    );
+
+    match result {
+        Err(Error::Method(error)) => {
+            assert_eq!(404, error.status_code());
+            assert_eq!(ErrorCode::ArangoDocumentNotFound, error.error_code());
+            assert_eq!("document not found", error.message());
+        },
+        _ => panic!("Error expected, but got: {:?}", &result),
+    }
+}
@@ -22,6 +22,8 @@ pub const FIELD_CODE: &str = "code";
 #[allow(missing_docs)]
 pub const FIELD_COLLECTIONS: &str = "collections";
 #[allow(missing_docs)]
+pub const FIELD_COUNT: &str = "count";
+#[allow(missing_docs)]
 pub const FIELD_EDGE: &str = "edge";
 #[allow(missing_docs)]
 pub const FIELD_EDGE_DEFINITIONS: &str = "edgeDefinitions";
@@ -48,6 +50,8 @@ pub const FIELD_ERROR_MESSAGE: &str = "errorMessage";
 #[allow(missing_docs)]
 pub const FIELD_ERROR_NUMBER: &str = "errorNum";
 #[allow(missing_docs)]
+pub const FIELD_FIGURES: &str = "figures";
+#[allow(missing_docs)]
 pub const FIELD_GRAPH: &str = "graph";
 #[allow(missing_docs)]
 pub const FIELD_GRAPHS: &str = "graphs";
@@ -68,6 +72,8 @@ pub const FIELD_REPLICATION_FACTOR: &str = "replicationFactor";
 #[allow(missing_docs)]
 pub const FIELD_RESULT: &str = "result";
 #[allow(missing_docs)]
+pub const FIELD_ROLE: &str = "role";
+#[allow(missing_docs)]
 pub const FIELD_SMART_GRAPH_ATTRIBUTE: &str = "smartGraphAttribute";
 #[allow(missing_docs)]
 pub const FIELD_VERTEX: &str = "vertex";
@@ -76,33 +82,59 @@ pub const FIELD_VERTEX: &str = "vertex";
 pub const HEADER_IF_MATCH: &str = "If-Match";
 #[allow(missing_docs)]
 pub const HEADER_IF_NON_MATCH: &str = "If-None-Match";
+#[allow(missing_docs)]
+pub const HEADER_QUEUE_TIME_SECONDS: &str = "x-arango-queue-time-seconds";
+#[allow(missing_docs)]
+pub const HEADER_TRANSACTION_ID: &str = "x-arango-trx-id";
 
 #[allow(missing_docs)]
 pub const PARAM_COLLECTION: &str = "collection";
 #[allow(missing_docs)]
+pub const PARAM_COMPLETE: &str = "complete";
+#[allow(missing_docs)]
 pub const PARAM_DETAILS: &str = "details";
 #[allow(missing_docs)]
 pub const PARAM_EXCLUDE_SYSTEM: &str = "excludeSystem";
 #[allow(missing_docs)]
+pub const PARAM_FROM_PREFIX: &str = "fromPrefix";
+#[allow(missing_docs)]
+pub const PARAM_GROUP: &str = "group";
+#[allow(missing_docs)]
 pub const PARAM_IGNORE_REVISIONS: &str = "ignoreRevs";
 #[allow(missing_docs)]
 pub const PARAM_KEEP_NULL: &str = "keepNull";
 #[allow(missing_docs)]
 pub const PARAM_MERGE_OBJECTS: &str = "mergeObjects";
 #[allow(missing_docs)]
+pub const PARAM_NAMESPACE: &str = "namespace";
+#[allow(missing_docs)]
 pub const PARAM_ONLY_GET: &str = "onlyget";
 #[allow(missing_docs)]
+pub const PARAM_ON_DUPLICATE: &str = "onDuplicate";
+#[allow(missing_docs)]
+pub const PARAM_OVERWRITE: &str = "overwrite";
+#[allow(missing_docs)]
 pub const PARAM_RETURN_NEW: &str = "returnNew";
 #[allow(missing_docs)]
 pub const PARAM_RETURN_OLD: &str = "returnOld";
 #[allow(missing_docs)]
+pub const PARAM_TO_PREFIX: &str = "toPrefix";
+#[allow(missing_docs)]
+pub const PARAM_TYPE: &str = "type";
+#[allow(missing_docs)]
 pub const PARAM_WAIT_FOR_SYNC: &str = "waitForSync";
 #[allow(missing_docs)]
 pub const PARAM_WAIT_FOR_SYNC_REPLICATION: &str = "waitForSyncReplication";
+#[allow(missing_docs)]
+pub const PARAM_WITH_DATA: &str = "withData";
+#[allow(missing_docs)]
+pub const PARAM_WITH_REVISIONS: &str = "withRevisions";
 
 #[allow(missing_docs)]
 pub const PATH_ADMIN: &str = "/_admin";
 #[allow(missing_docs)]
+pub const PATH_API_AQLFUNCTION: &str = "/_api/aqlfunction";
+#[allow(missing_docs)]
 pub const PATH_API_COLLECTION: &str = "/_api/collection";
 #[allow(missing_docs)]
 pub const PATH_API_CURSOR: &str = "/_api/cursor";
@@ -111,20 +143,34 @@ pub const PATH_API_DATABASE: &str = "/_api/database";
 #[allow(missing_docs)]
 pub const PATH_API_DOCUMENT: &str = "/_api/document";
 #[allow(missing_docs)]
+pub const PATH_API_ENGINE: &str = "/_api/engine";
+#[allow(missing_docs)]
 pub const PATH_API_EXPLAIN: &str = "/_api/explain";
 #[allow(missing_docs)]
 pub const PATH_API_GHARIAL: &str = "/_api/gharial";
 #[allow(missing_docs)]
+pub const PATH_API_IMPORT: &str = "/_api/import";
+#[allow(missing_docs)]
 pub const PATH_API_INDEX: &str = "/_api/index";
 #[allow(missing_docs)]
 pub const PATH_API_QUERY: &str = "/_api/query";
 #[allow(missing_docs)]
+pub const PATH_API_QUERY_CACHE: &str = "/_api/query-cache";
+#[allow(missing_docs)]
+pub const PATH_API_TRANSACTION: &str = "/_api/transaction";
+#[allow(missing_docs)]
 pub const PATH_API_USER: &str = "/_api/user";
 #[allow(missing_docs)]
 pub const PATH_API_VERSION: &str = "/_api/version";
 #[allow(missing_docs)]
 pub const PATH_OPEN_AUTH: &str = "/_open/auth";
 
+#[allow(missing_docs)]
+pub const PATH_BEGIN: &str = "/begin";
+#[allow(missing_docs)]
+pub const PATH_CHECKSUM: &str = "/checksum";
+#[allow(missing_docs)]
+pub const PATH_COUNT: &str = "/count";
 #[allow(missing_docs)]
 pub const PATH_CURRENT: &str = "/current";
 #[allow(missing_docs)]
@@ -134,14 +180,34 @@ pub const PATH_DB: &str = "/_db/";
 #[allow(missing_docs)]
 pub const PATH_EDGE: &str = "/edge";
 #[allow(missing_docs)]
+pub const PATH_ENTRIES: &str = "/entries";
+#[allow(missing_docs)]
+pub const PATH_FIGURES: &str = "/figures";
+#[allow(missing_docs)]
+pub const PATH_LOAD: &str = "/load";
+#[allow(missing_docs)]
+pub const PATH_LOAD_INDEXES_INTO_MEMORY: &str = "/loadIndexesIntoMemory";
+#[allow(missing_docs)]
 pub const PATH_PROPERTIES: &str = "/properties";
 #[allow(missing_docs)]
 pub const PATH_RENAME: &str = "/rename";
 #[allow(missing_docs)]
 pub const PATH_REVISION: &str = "/revision";
 #[allow(missing_docs)]
+pub const PATH_ROLE: &str = "/role";
+#[allow(missing_docs)]
+pub const PATH_ROTATE: &str = "/rotate";
+#[allow(missing_docs)]
+pub const PATH_SERVER: &str = "/server";
+#[allow(missing_docs)]
+pub const PATH_SLOW: &str = "/slow";
+#[allow(missing_docs)]
 pub const PATH_TARGET_VERSION: &str = "/target-version";
 #[allow(missing_docs)]
+pub const PATH_TRUNCATE: &str = "/truncate";
+#[allow(missing_docs)]
+pub const PATH_UNLOAD: &str = "/unload";
+#[allow(missing_docs)]
 pub const PATH_USER: &str = "/user";
 #[allow(missing_docs)]
 pub const PATH_VERTEX: &str = "/vertex";
@@ -0,0 +1,94 @@
+//! Generation and inspection of JSON Web Tokens (JWT).
+//!
+//! An [ArangoDB] server accepts tokens that are signed with its JWT secret
+//! using HMAC-SHA256 (HS256). Tokens issued this way authenticate the client
+//! as superuser without the need to log in with a username and password.
+//!
+//! The connectors use the functions provided by this module when
+//! the `DataSource` specifies `Authentication::JwtSecret` and to check whether
+//! a cached token is about to expire.
+//!
+//! [ArangoDB]: https://www.arangodb.com
+
+#[cfg(test)]
+mod tests;
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use base64;
+use hmac::{Hmac, Mac};
+use serde_json::{self, Value};
+use sha2::Sha256;
+
+use api::auth::{Jwt, JwtSecret};
+
+/// The header of the generated tokens.
+const TOKEN_HEADER: &str = r#"{"alg":"HS256","typ":"JWT"}"#;
+
+/// The issuer claim expected by the server.
+const TOKEN_ISSUER: &str = "arangodb";
+
+/// The server id claim of the generated tokens.
+const TOKEN_SERVER_ID: &str = "rincon";
+
+/// The lifetime of the generated tokens in seconds.
+pub const TOKEN_LIFETIME: u64 = 3600;
+
+/// Tokens that expire within this number of seconds are considered expired.
+pub const TOKEN_EXPIRY_MARGIN: u64 = 30;
+
+/// Generates a superuser token signed with the given secret.
+///
+/// The token is issued at the given point in time and expires after
+/// `TOKEN_LIFETIME` seconds.
+pub fn superuser_token(secret: &JwtSecret, issued_at: SystemTime) -> Jwt {
+    let issued_at = issued_at
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+    let claims = json!({
+        "iss": TOKEN_ISSUER,
+        "server_id": TOKEN_SERVER_ID,
+        "iat": issued_at,
+        "exp": issued_at + TOKEN_LIFETIME,
+    });
+    let claims = serde_json::to_vec(&claims).expect("claims are always serializable");
+    let mut token = encode_segment(TOKEN_HEADER.as_bytes());
+    token.push('.');
+    token.push_str(&encode_segment(&claims));
+    let signature = sign(secret, token.as_bytes());
+    token.push('.');
+    token.push_str(&encode_segment(&signature));
+    token
+}
+
+/// Returns whether the given token expires before the given point in time
+/// plus a safety margin.
+///
+/// Tokens that do not contain an expiration time or that can not be decoded
+/// are considered valid. In this case the server decides whether a token is
+/// accepted.
+pub fn is_token_expired(token: &str, now: SystemTime) -> bool {
+    let expires_at = token
+        .split('.')
+        .nth(1)
+        .and_then(|payload| base64::decode_config(payload, base64::URL_SAFE_NO_PAD).ok())
+        .and_then(|payload| serde_json::from_slice::<Value>(&payload).ok())
+        .and_then(|claims| claims.get("exp").and_then(Value::as_u64));
+    match (expires_at, now.duration_since(UNIX_EPOCH)) {
+        (Some(expires_at), Ok(now)) => expires_at <= now.as_secs() + TOKEN_EXPIRY_MARGIN,
+        _ => false,
+    }
+}
+
+/// Signs the given content with HMAC-SHA256 using the given secret.
+fn sign(secret: &JwtSecret, content: &[u8]) -> Vec<u8> {
+    let mut mac =
+        Hmac::<Sha256>::new_varkey(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.input(content);
+    mac.result().code().to_vec()
+}
+
+fn encode_segment(segment: &[u8]) -> String {
+    base64::encode_config(segment, base64::URL_SAFE_NO_PAD)
+}
@@ -0,0 +1,100 @@
+use std::time::Duration;
+
+use super::*;
+
+fn seconds_since_epoch(seconds: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(seconds)
+}
+
+#[test]
+fn superuser_token_is_signed_with_secret() {
+    let secret = JwtSecret::new("s3cr3t");
+
+    let token = superuser_token(&secret, seconds_since_epoch(1_500_000_000));
+
+    assert_eq!(
+        "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9.\
+         eyJleHAiOjE1MDAwMDM2MDAsImlhdCI6MTUwMDAwMDAwMCwiaXNzIjoiYXJhbmdvZGIiLCJzZXJ2ZXJfaWQiOiJyaW5jb24ifQ.\
+         pJP_zDWIEW1vONq-zzWsnqX2DfIufpYb3cmjOcZ-fQM",
+        token
+    );
+}
+
+#[test]
+fn superuser_token_contains_claims() {
+    let secret = JwtSecret::new("s3cr3t");
+
+    let token = superuser_token(&secret, seconds_since_epoch(1_500_000_000));
+
+    let payload = token.split('.').nth(1).unwrap();
+    let claims: serde_json::Value =
+        serde_json::from_slice(&base64::decode_config(payload, base64::URL_SAFE_NO_PAD).unwrap())
+            .unwrap();
+    assert_eq!(
+        json!({
+            "iss": "arangodb",
+            "server_id": "rincon",
+            "iat": 1_500_000_000,
+            "exp": 1_500_003_600,
+        }),
+        claims
+    );
+}
+
+#[test]
+fn superuser_tokens_for_different_secrets_differ() {
+    let issued_at = seconds_since_epoch(1_500_000_000);
+
+    let token1 = superuser_token(&JwtSecret::new("s3cr3t"), issued_at);
+    let token2 = superuser_token(&JwtSecret::new("an0th3r"), issued_at);
+
+    assert_ne!(token1, token2);
+}
+
+fn token_with_claims(claims: &serde_json::Value) -> String {
+    let header = base64::encode_config(br#"{"alg":"HS256","typ":"JWT"}"#, base64::URL_SAFE_NO_PAD);
+    let payload = base64::encode_config(
+        &serde_json::to_vec(claims).unwrap(),
+        base64::URL_SAFE_NO_PAD,
+    );
+    format!("{}.{}.c2lnbmF0dXJl", header, payload)
+}
+
+#[test]
+fn token_with_future_expiration_time_is_not_expired() {
+    let token = token_with_claims(&json!({ "iss": "arangodb", "exp": 1_500_003_600 }));
+    let now = seconds_since_epoch(1_500_000_000);
+
+    assert!(!is_token_expired(&token, now));
+}
+
+#[test]
+fn token_with_past_expiration_time_is_expired() {
+    let token = token_with_claims(&json!({ "iss": "arangodb", "exp": 1_500_000_000 }));
+    let now = seconds_since_epoch(1_500_003_600);
+
+    assert!(is_token_expired(&token, now));
+}
+
+#[test]
+fn token_expiring_within_margin_is_expired() {
+    let token = token_with_claims(&json!({ "iss": "arangodb", "exp": 1_500_000_010 }));
+    let now = seconds_since_epoch(1_500_000_000);
+
+    assert!(is_token_expired(&token, now));
+}
+
+#[test]
+fn token_without_expiration_time_is_not_expired() {
+    let token = token_with_claims(&json!({ "iss": "arangodb" }));
+    let now = seconds_since_epoch(1_500_000_000);
+
+    assert!(!is_token_expired(&token, now));
+}
+
+#[test]
+fn malformed_token_is_not_expired() {
+    let now = seconds_since_epoch(1_500_000_000);
+
+    assert!(!is_token_expired("not-a-jwt", now));
+}
@@ -17,6 +17,10 @@
 //! Url, Value, JsonValue and JsonString * `arango` : defines constants of
 //! values used by the ArangoDB REST API
 //!
+//! Additionally the `jwt` module generates and inspects JSON Web Tokens and
+//! the `velocypack` module implements the VelocyPack serialization format.
+//! Both are used by all `Connector` implementations.
+//!
 //! By defining this core API the driver can be easily extended with new methods
 //! that may be added in newer versions of ArangoDB and use different
 //! implementations of `Connector`s while the methods of the REST API are
@@ -29,18 +33,26 @@
     unused_qualifications
 )]
 
+extern crate base64;
+extern crate byteorder;
 #[macro_use]
 extern crate failure;
 extern crate futures;
+extern crate hmac;
 extern crate regex;
+#[macro_use]
 extern crate serde;
 #[macro_use]
 extern crate serde_derive;
+#[macro_use]
 extern crate serde_json;
+extern crate sha2;
 extern crate url;
 
 pub mod api;
 pub mod arango;
+pub mod jwt;
+pub mod velocypack;
 
 const LIB_NAME: &str = "rincon";
 const LIB_VERSION_MAJOR: &str = env!("CARGO_PKG_VERSION_MAJOR");
@@ -0,0 +1,376 @@
+//! Deserialization of VelocyPack into Rust data structures.
+
+use std::fmt;
+
+use byteorder::{ByteOrder, LittleEndian};
+use serde::de::{self, DeserializeSeed, IntoDeserializer, Visitor};
+
+use super::{
+    add_length, decode_key, descend, is_object, read_container, read_uint, slice, str_from_utf8,
+    value_size, Error, MAX_DEPTH,
+};
+
+impl de::Error for Error {
+    fn custom<T>(message: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Error::Conversion(message.to_string())
+    }
+}
+
+/// A deserializer that decodes VelocyPack into Rust data structures.
+///
+/// Strings are borrowed from the input where possible. Binary values are
+/// presented as sequences of bytes. Arrays and objects nested deeper than 128
+/// levels are rejected.
+#[derive(Debug)]
+pub struct Deserializer<'de> {
+    input: &'de [u8],
+    remaining_depth: usize,
+}
+
+impl<'de> Deserializer<'de> {
+    /// Creates a new `Deserializer` that reads from the given bytes.
+    pub fn from_slice(input: &'de [u8]) -> Self {
+        Deserializer {
+            input,
+            remaining_depth: MAX_DEPTH,
+        }
+    }
+
+    /// Returns the bytes that have not been consumed yet.
+    pub fn remaining(&self) -> &'de [u8] {
+        self.input
+    }
+
+    fn peek(&self) -> Result<u8, Error> {
+        self.input.first().cloned().ok_or(Error::UnexpectedEnd)
+    }
+
+    /// Consumes the next value and returns its bytes.
+    fn next_value(&mut self) -> Result<&'de [u8], Error> {
+        let size = value_size(self.input)?;
+        let value = slice(self.input, 0, size)?;
+        self.input = &self.input[size..];
+        Ok(value)
+    }
+
+    /// Consumes the next value which must be an array or object and returns
+    /// the number of items and a deserializer for its items.
+    fn next_container(&mut self) -> Result<(usize, Deserializer<'de>), Error> {
+        let remaining_depth = descend(self.remaining_depth)?;
+        let value = self.next_value()?;
+        let (byte_length, offset, num_items) = read_container(value)?;
+        let items = slice(value, offset, byte_length)?;
+        Ok((
+            num_items,
+            Deserializer {
+                input: items,
+                remaining_depth,
+            },
+        ))
+    }
+}
+
+impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let head = self.peek()?;
+        match head {
+            0x01..=0x09 | 0x13 => {
+                let (num_items, mut items) = self.next_container()?;
+                visitor.visit_seq(Items {
+                    de: &mut items,
+                    remaining: num_items,
+                })
+            },
+            0x0a..=0x12 | 0x14 => {
+                let (num_items, mut members) = self.next_container()?;
+                visitor.visit_map(Items {
+                    de: &mut members,
+                    remaining: num_items,
+                })
+            },
+            0x18 | 0x1e | 0x1f => {
+                self.next_value()?;
+                visitor.visit_unit()
+            },
+            0x19 | 0x1a => {
+                self.next_value()?;
+                visitor.visit_bool(head == 0x1a)
+            },
+            0x1b => {
+                let value = self.next_value()?;
+                visitor.visit_f64(LittleEndian::read_f64(&value[1..]))
+            },
+            0x1c => {
+                let value = self.next_value()?;
+                visitor.visit_i64(LittleEndian::read_i64(&value[1..]))
+            },
+            0x20..=0x27 => {
+                let value = self.next_value()?;
+                visitor.visit_i64(LittleEndian::read_int(&value[1..], value.len() - 1))
+            },
+            0x28..=0x2f => {
+                let value = self.next_value()?;
+                visitor.visit_u64(LittleEndian::read_uint(&value[1..], value.len() - 1))
+            },
+            0x30..=0x39 => {
+                self.next_value()?;
+                visitor.visit_u64(u64::from(head - 0x30))
+            },
+            0x3a..=0x3f => {
+                self.next_value()?;
+                visitor.visit_i64(i64::from(head) - 0x40)
+            },
+            0x40..=0xbe => {
+                let value = self.next_value()?;
+                visitor.visit_borrowed_str(str_from_utf8(&value[1..])?)
+            },
+            0xbf => {
+                let value = self.next_value()?;
+                visitor.visit_borrowed_str(str_from_utf8(&value[9..])?)
+            },
+            0xc0..=0xc7 => {
+                let value = self.next_value()?;
+                let width = (head - 0xbf) as usize;
+                let size = add_length(1 + width, read_uint(value, 1, width)?)?;
+                let bytes = slice(value, 1 + width, size)?;
+                visitor.visit_seq(de::value::SeqDeserializer::new(bytes.iter().cloned()))
+            },
+            _ => Err(Error::UnsupportedType(head)),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        if self.peek()? == 0x18 {
+            self.next_value()?;
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &str, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let head = self.peek()?;
+        if is_object(head) {
+            let (num_items, mut members) = self.next_container()?;
+            if num_items != 1 {
+                return Err(Error::Conversion(format!(
+                    "expected object with a single key for enum, but got {} keys",
+                    num_items
+                )));
+            }
+            visitor.visit_enum(Variant { de: &mut members })
+        } else {
+            visitor.visit_enum(UnitVariant { de: self })
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string bytes byte_buf
+        unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// Gives access to the items of an array or the members of an object.
+struct Items<'a, 'de: 'a> {
+    de: &'a mut Deserializer<'de>,
+    remaining: usize,
+}
+
+impl<'de, 'a> de::SeqAccess<'de> for Items<'a, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+impl<'de, 'a> de::MapAccess<'de> for Items<'a, 'de> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        deserialize_key(self.de, seed).map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+/// Deserializes an object key which is either a string or the id of a
+/// translated attribute name.
+fn deserialize_key<'de, K>(de: &mut Deserializer<'de>, seed: K) -> Result<K::Value, Error>
+where
+    K: DeserializeSeed<'de>,
+{
+    match de.peek()? {
+        0x40..=0xbf => seed.deserialize(de),
+        _ => {
+            let key = de.next_value()?;
+            let name = decode_key(key)?.into_owned();
+            seed.deserialize(name.into_deserializer())
+        },
+    }
+}
+
+/// Gives access to an externally tagged enum variant with content.
+struct Variant<'a, 'de: 'a> {
+    de: &'a mut Deserializer<'de>,
+}
+
+impl<'de, 'a> de::EnumAccess<'de> for Variant<'a, 'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self), Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant = deserialize_key(self.de, seed)?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de, 'a> de::VariantAccess<'de> for Variant<'a, 'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        de::Deserialize::deserialize(self.de)
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.de)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_seq(self.de, visitor)
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_map(self.de, visitor)
+    }
+}
+
+/// Gives access to an enum variant without content that is encoded as
+/// string.
+struct UnitVariant<'a, 'de: 'a> {
+    de: &'a mut Deserializer<'de>,
+}
+
+impl<'de, 'a> de::EnumAccess<'de> for UnitVariant<'a, 'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self), Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant = seed.deserialize(&mut *self.de)?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de, 'a> de::VariantAccess<'de> for UnitVariant<'a, 'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, _seed: T) -> Result<T::Value, Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        Err(de::Error::invalid_type(
+            de::Unexpected::UnitVariant,
+            &"newtype variant",
+        ))
+    }
+
+    fn tuple_variant<V>(self, _len: usize, _visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(de::Error::invalid_type(
+            de::Unexpected::UnitVariant,
+            &"tuple variant",
+        ))
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(de::Error::invalid_type(
+            de::Unexpected::UnitVariant,
+            &"struct variant",
+        ))
+    }
+}
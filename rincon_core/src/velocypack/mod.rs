@@ -0,0 +1,578 @@
+//! Encoding and decoding of the VelocyPack serialization format.
+//!
+//! [VelocyPack] is the binary serialization format used by [ArangoDB]. This
+//! module provides a serde `Serializer` and `Deserializer` for VelocyPack.
+//! Any type that implements `Serialize` or `Deserialize` can be encoded or
+//! decoded by using the `to_vec()` and `from_slice()` functions. Conversion
+//! between VelocyPack and `serde_json::Value`s is provided by the
+//! `encode_value()` and `decode_value()` functions.
+//!
+//! When encoding, arrays and objects are written in the compact format
+//! without index tables. When decoding, all array and object formats are
+//! supported. Binary values are decoded to sequences of bytes. The types
+//! external, BCD and custom are not supported. Input with arrays and objects
+//! nested deeper than 128 levels is rejected.
+//!
+//! [ArangoDB]: https://www.arangodb.com
+//! [VelocyPack]: https://github.com/arangodb/velocypack
+
+#[cfg(test)]
+mod tests;
+
+mod de;
+mod ser;
+
+pub use self::de::Deserializer;
+pub use self::ser::{Compound, Serializer};
+
+use std::borrow::Cow;
+use std::error;
+use std::fmt;
+use std::str;
+
+use byteorder::{ByteOrder, LittleEndian};
+use serde::de::Deserialize;
+use serde::ser::Serialize;
+use serde_json::{Map, Number, Value};
+
+/// The media type of VelocyPack encoded content.
+pub const CONTENT_TYPE_VELOCYPACK: &str = "application/x-velocypack";
+
+/// The maximum nesting depth of arrays and objects that is decoded.
+///
+/// Deeper nested input is rejected as it would overflow the stack of the
+/// recursive decoders.
+const MAX_DEPTH: usize = 128;
+
+/// An error that may occur during encoding or decoding of VelocyPack.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The value can not be converted to or from the data model.
+    Conversion(String),
+    /// The input ended before a complete value could be decoded.
+    UnexpectedEnd,
+    /// The value type is not supported by this implementation.
+    UnsupportedType(u8),
+    /// The input is not valid VelocyPack.
+    Invalid(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Conversion(ref message) => write!(f, "Conversion failed: {}", message),
+            Error::UnexpectedEnd => f.write_str("Unexpected end of VelocyPack input"),
+            Error::UnsupportedType(head) => {
+                write!(f, "Unsupported VelocyPack type: 0x{:02x}", head)
+            },
+            Error::Invalid(ref message) => write!(f, "Invalid VelocyPack: {}", message),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::Conversion(_) => "conversion failed",
+            Error::UnexpectedEnd => "unexpected end of VelocyPack input",
+            Error::UnsupportedType(_) => "unsupported VelocyPack type",
+            Error::Invalid(_) => "invalid VelocyPack",
+        }
+    }
+}
+
+/// Serializes the given value into a VelocyPack encoded byte vector.
+pub fn to_vec<T>(value: &T) -> Result<Vec<u8>, Error>
+where
+    T: Serialize,
+{
+    let mut serializer = Serializer::new();
+    value.serialize(&mut serializer)?;
+    Ok(serializer.into_inner())
+}
+
+/// Deserializes an instance of type `T` from the first VelocyPack value in
+/// the given bytes.
+pub fn from_slice<'de, T>(bytes: &'de [u8]) -> Result<T, Error>
+where
+    T: Deserialize<'de>,
+{
+    let mut deserializer = Deserializer::from_slice(bytes);
+    T::deserialize(&mut deserializer)
+}
+
+/// Encodes the given `Value` as VelocyPack and appends it to the buffer.
+pub fn encode_value(value: &Value, buffer: &mut Vec<u8>) {
+    match *value {
+        Value::Null => buffer.push(0x18),
+        Value::Bool(false) => buffer.push(0x19),
+        Value::Bool(true) => buffer.push(0x1a),
+        Value::Number(ref number) => encode_number(number, buffer),
+        Value::String(ref string) => encode_string(string, buffer),
+        Value::Array(ref array) => {
+            if array.is_empty() {
+                buffer.push(0x01);
+            } else {
+                let mut content = Vec::new();
+                for item in array {
+                    encode_value(item, &mut content);
+                }
+                encode_compact(0x13, &content, array.len(), buffer);
+            }
+        },
+        Value::Object(ref object) => {
+            if object.is_empty() {
+                buffer.push(0x0a);
+            } else {
+                let mut content = Vec::new();
+                for (key, item) in object {
+                    encode_string(key, &mut content);
+                    encode_value(item, &mut content);
+                }
+                encode_compact(0x14, &content, object.len(), buffer);
+            }
+        },
+    }
+}
+
+fn encode_number(number: &Number, buffer: &mut Vec<u8>) {
+    if let Some(value) = number.as_i64() {
+        encode_int(value, buffer);
+    } else if let Some(value) = number.as_u64() {
+        encode_uint(value, buffer);
+    } else {
+        encode_double(number.as_f64().unwrap_or(0.), buffer);
+    }
+}
+
+fn encode_int(value: i64, buffer: &mut Vec<u8>) {
+    if value >= 0 {
+        encode_uint(value as u64, buffer);
+    } else if value >= -6 {
+        buffer.push((0x40 + value) as u8);
+    } else {
+        let mut bytes = [0; 8];
+        LittleEndian::write_i64(&mut bytes, value);
+        let mut size = 8;
+        while size > 1 && bytes[size - 1] == 0xff && bytes[size - 2] & 0x80 != 0 {
+            size -= 1;
+        }
+        buffer.push(0x1f + size as u8);
+        buffer.extend_from_slice(&bytes[..size]);
+    }
+}
+
+fn encode_uint(value: u64, buffer: &mut Vec<u8>) {
+    if value <= 9 {
+        buffer.push(0x30 + value as u8);
+        return;
+    }
+    let mut bytes = [0; 8];
+    LittleEndian::write_u64(&mut bytes, value);
+    let mut size = 8;
+    while size > 1 && bytes[size - 1] == 0 {
+        size -= 1;
+    }
+    buffer.push(0x27 + size as u8);
+    buffer.extend_from_slice(&bytes[..size]);
+}
+
+fn encode_double(value: f64, buffer: &mut Vec<u8>) {
+    let mut bytes = [0; 8];
+    LittleEndian::write_f64(&mut bytes, value);
+    buffer.push(0x1b);
+    buffer.extend_from_slice(&bytes);
+}
+
+fn encode_string(value: &str, buffer: &mut Vec<u8>) {
+    let bytes = value.as_bytes();
+    if bytes.len() <= 126 {
+        buffer.push(0x40 + bytes.len() as u8);
+    } else {
+        let mut length = [0; 8];
+        LittleEndian::write_u64(&mut length, bytes.len() as u64);
+        buffer.push(0xbf);
+        buffer.extend_from_slice(&length);
+    }
+    buffer.extend_from_slice(bytes);
+}
+
+fn encode_compact(head: u8, content: &[u8], num_items: usize, buffer: &mut Vec<u8>) {
+    let mut num_items_bytes = Vec::new();
+    write_varint(num_items as u64, &mut num_items_bytes);
+    num_items_bytes.reverse();
+    let base_length = 1 + content.len() + num_items_bytes.len();
+    let mut byte_length = base_length + 1;
+    while base_length + varint_length(byte_length as u64) != byte_length {
+        byte_length = base_length + varint_length(byte_length as u64);
+    }
+    buffer.push(head);
+    write_varint(byte_length as u64, buffer);
+    buffer.extend_from_slice(content);
+    buffer.extend_from_slice(&num_items_bytes);
+}
+
+fn write_varint(mut value: u64, buffer: &mut Vec<u8>) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buffer.push(byte);
+            break;
+        } else {
+            buffer.push(byte | 0x80);
+        }
+    }
+}
+
+fn varint_length(mut value: u64) -> usize {
+    let mut length = 1;
+    while value >= 0x80 {
+        value >>= 7;
+        length += 1;
+    }
+    length
+}
+
+/// Decodes the first VelocyPack value in the given bytes.
+///
+/// Returns the decoded `Value` and the number of bytes it occupies.
+pub fn decode_value(bytes: &[u8]) -> Result<(Value, usize), Error> {
+    decode_nested_value(bytes, MAX_DEPTH)
+}
+
+fn decode_nested_value(bytes: &[u8], remaining_depth: usize) -> Result<(Value, usize), Error> {
+    let head = *bytes.first().ok_or(Error::UnexpectedEnd)?;
+    match head {
+        0x01..=0x09 | 0x13 => {
+            let remaining_depth = descend(remaining_depth)?;
+            let (byte_length, mut offset, num_items) = read_container(bytes)?;
+            let items = slice(bytes, 0, byte_length)?;
+            let mut array = Vec::with_capacity(num_items);
+            for _ in 0..num_items {
+                let (item, size) =
+                    decode_nested_value(slice(items, offset, byte_length)?, remaining_depth)?;
+                array.push(item);
+                offset += size;
+            }
+            Ok((Value::Array(array), byte_length))
+        },
+        0x0a..=0x12 | 0x14 => {
+            let remaining_depth = descend(remaining_depth)?;
+            let (byte_length, offset, num_items) = read_container(bytes)?;
+            let items = slice(bytes, 0, byte_length)?;
+            let object = decode_members(items, offset, num_items, remaining_depth)?;
+            Ok((Value::Object(object), byte_length))
+        },
+        0x18 | 0x1e | 0x1f => Ok((Value::Null, 1)),
+        0x19 => Ok((Value::Bool(false), 1)),
+        0x1a => Ok((Value::Bool(true), 1)),
+        0x1b => {
+            let value = LittleEndian::read_f64(slice(bytes, 1, 9)?);
+            let number = Number::from_f64(value).map_or(Value::Null, Value::Number);
+            Ok((number, 9))
+        },
+        0x1c => {
+            let value = LittleEndian::read_i64(slice(bytes, 1, 9)?);
+            Ok((Value::Number(value.into()), 9))
+        },
+        0x20..=0x27 => {
+            let size = (head - 0x1f) as usize;
+            let value = LittleEndian::read_int(slice(bytes, 1, 1 + size)?, size);
+            Ok((Value::Number(value.into()), 1 + size))
+        },
+        0x28..=0x2f => {
+            let size = (head - 0x27) as usize;
+            let value = LittleEndian::read_uint(slice(bytes, 1, 1 + size)?, size);
+            Ok((Value::Number(value.into()), 1 + size))
+        },
+        0x30..=0x39 => Ok((Value::Number(u64::from(head - 0x30).into()), 1)),
+        0x3a..=0x3f => Ok((Value::Number((i64::from(head) - 0x40).into()), 1)),
+        0x40..=0xbe => {
+            let length = (head - 0x40) as usize;
+            let string = decode_str(slice(bytes, 1, 1 + length)?)?;
+            Ok((Value::String(string), 1 + length))
+        },
+        0xbf => {
+            let size = add_length(9, read_uint(bytes, 1, 8)?)?;
+            let string = decode_str(slice(bytes, 9, size)?)?;
+            Ok((Value::String(string), size))
+        },
+        0xc0..=0xc7 => {
+            let width = (head - 0xbf) as usize;
+            let size = add_length(1 + width, read_uint(bytes, 1, width)?)?;
+            let data = slice(bytes, 1 + width, size)?;
+            let array = data
+                .iter()
+                .map(|byte| Value::Number((*byte).into()))
+                .collect();
+            Ok((Value::Array(array), size))
+        },
+        _ => Err(Error::UnsupportedType(head)),
+    }
+}
+
+fn decode_members(
+    items: &[u8],
+    mut offset: usize,
+    num_items: usize,
+    remaining_depth: usize,
+) -> Result<Map<String, Value>, Error> {
+    let mut object = Map::new();
+    for _ in 0..num_items {
+        let key_bytes = slice(items, offset, items.len())?;
+        let key = decode_key(key_bytes)?.into_owned();
+        offset += value_size(key_bytes)?;
+        let (value, size) =
+            decode_nested_value(slice(items, offset, items.len())?, remaining_depth)?;
+        offset += size;
+        object.insert(key, value);
+    }
+    Ok(object)
+}
+
+/// Returns the value of the attribute with the given name if the first
+/// VelocyPack value in the given bytes is an object that contains such an
+/// attribute.
+///
+/// The value is returned as slice of the given bytes without decoding it.
+pub fn find_attribute<'a>(bytes: &'a [u8], name: &str) -> Result<Option<&'a [u8]>, Error> {
+    match bytes.first() {
+        Some(&head) if is_object(head) => {},
+        _ => return Ok(None),
+    }
+    let (byte_length, mut offset, num_items) = read_container(bytes)?;
+    let items = slice(bytes, 0, byte_length)?;
+    for _ in 0..num_items {
+        let key_bytes = slice(items, offset, byte_length)?;
+        offset += value_size(key_bytes)?;
+        let value_bytes = slice(items, offset, byte_length)?;
+        let size = value_size(value_bytes)?;
+        offset += size;
+        if decode_key(key_bytes)? == name {
+            return Ok(Some(slice(value_bytes, 0, size)?));
+        }
+    }
+    Ok(None)
+}
+
+/// Returns the number of bytes occupied by the first VelocyPack value in the
+/// given bytes.
+fn value_size(bytes: &[u8]) -> Result<usize, Error> {
+    let head = *bytes.first().ok_or(Error::UnexpectedEnd)?;
+    match head {
+        0x01 | 0x0a | 0x18..=0x1a | 0x1e | 0x1f | 0x30..=0x3f => Ok(1),
+        0x02..=0x05 => read_uint(bytes, 1, 1 << (head - 0x02)),
+        0x06..=0x09 => read_uint(bytes, 1, 1 << (head - 0x06)),
+        0x0b..=0x12 => read_uint(bytes, 1, 1 << ((head - 0x0b) % 4)),
+        0x13 | 0x14 => read_varint(bytes, 1).map(|(byte_length, _)| byte_length as usize),
+        0x1b | 0x1c => Ok(9),
+        0x20..=0x27 => Ok((head - 0x1e) as usize),
+        0x28..=0x2f => Ok((head - 0x26) as usize),
+        0x40..=0xbe => Ok((head - 0x3f) as usize),
+        0xbf => add_length(9, read_uint(bytes, 1, 8)?),
+        0xc0..=0xc7 => {
+            let width = (head - 0xbf) as usize;
+            add_length(1 + width, read_uint(bytes, 1, width)?)
+        },
+        _ => Err(Error::UnsupportedType(head)),
+    }
+}
+
+fn is_object(head: u8) -> bool {
+    match head {
+        0x0a..=0x12 | 0x14 => true,
+        _ => false,
+    }
+}
+
+/// Reads the byte length, the offset of the first item and the number of
+/// items of an array or object.
+///
+/// Fails if the header claims more items than fit into the byte length of
+/// the container, as each item occupies at least one byte.
+fn read_container(bytes: &[u8]) -> Result<(usize, usize, usize), Error> {
+    let head = *bytes.first().ok_or(Error::UnexpectedEnd)?;
+    let (byte_length, offset, num_items) = match head {
+        0x01 | 0x0a => (1, 1, 0),
+        0x02..=0x05 => {
+            let width = 1 << (head - 0x02);
+            let byte_length = read_uint(bytes, 1, width)?;
+            let items = slice(bytes, 0, byte_length)?;
+            let offset = skip_padding(items, 1 + width);
+            let num_items = if offset < byte_length {
+                (byte_length - offset) / value_size(&items[offset..])?.max(1)
+            } else {
+                0
+            };
+            (byte_length, offset, num_items)
+        },
+        0x06..=0x09 => {
+            let (byte_length, num_items, start) =
+                read_indexed_header(bytes, head - 0x06, head == 0x09)?;
+            let offset = skip_padding(slice(bytes, 0, byte_length)?, start);
+            (byte_length, offset, num_items)
+        },
+        0x0b..=0x12 => {
+            let width_exponent = (head - 0x0b) % 4;
+            let (byte_length, num_items, start) =
+                read_indexed_header(bytes, width_exponent, width_exponent == 3)?;
+            let offset = skip_padding(slice(bytes, 0, byte_length)?, start);
+            (byte_length, offset, num_items)
+        },
+        0x13 | 0x14 => {
+            let (byte_length, offset) = read_varint(bytes, 1)?;
+            let byte_length = byte_length as usize;
+            let num_items = read_reverse_varint(slice(bytes, 0, byte_length)?)? as usize;
+            (byte_length, offset, num_items)
+        },
+        _ => {
+            return Err(Error::Invalid(format!(
+                "expected array or object, but found type 0x{:02x}",
+                head
+            )))
+        },
+    };
+    if num_items > byte_length.saturating_sub(offset) {
+        return Err(Error::Invalid(format!(
+            "{} items do not fit into a container of {} bytes",
+            num_items, byte_length
+        )));
+    }
+    Ok((byte_length, offset, num_items))
+}
+
+/// Decodes an object key which is either a string or the id of a translated
+/// attribute name.
+fn decode_key(bytes: &[u8]) -> Result<Cow<str>, Error> {
+    let head = *bytes.first().ok_or(Error::UnexpectedEnd)?;
+    match head {
+        0x40..=0xbe => {
+            let length = (head - 0x40) as usize;
+            str_from_utf8(slice(bytes, 1, 1 + length)?).map(Cow::Borrowed)
+        },
+        0xbf => {
+            let size = add_length(9, read_uint(bytes, 1, 8)?)?;
+            str_from_utf8(slice(bytes, 9, size)?).map(Cow::Borrowed)
+        },
+        0x28..=0x39 => match decode_value(bytes)? {
+            (Value::Number(ref id), _) => translate_attribute(id.as_u64()).map(Cow::Borrowed),
+            (key, _) => Err(Error::Invalid(format!("invalid object key: {}", key))),
+        },
+        _ => Err(Error::Invalid(format!(
+            "invalid object key of type 0x{:02x}",
+            head
+        ))),
+    }
+}
+
+fn translate_attribute(id: Option<u64>) -> Result<&'static str, Error> {
+    match id {
+        Some(1) => Ok("_key"),
+        Some(2) => Ok("_rev"),
+        Some(3) => Ok("_id"),
+        Some(4) => Ok("_from"),
+        Some(5) => Ok("_to"),
+        _ => Err(Error::Invalid(format!("unknown attribute id: {:?}", id))),
+    }
+}
+
+fn decode_str(bytes: &[u8]) -> Result<String, Error> {
+    str_from_utf8(bytes).map(str::to_owned)
+}
+
+fn str_from_utf8(bytes: &[u8]) -> Result<&str, Error> {
+    str::from_utf8(bytes).map_err(|cause| Error::Invalid(cause.to_string()))
+}
+
+/// Reads the byte length, the number of items and the offset of the first
+/// item of an array or object with index table.
+fn read_indexed_header(
+    bytes: &[u8],
+    width_exponent: u8,
+    num_items_at_end: bool,
+) -> Result<(usize, usize, usize), Error> {
+    let width = 1 << width_exponent;
+    let byte_length = read_uint(bytes, 1, width)?;
+    if num_items_at_end {
+        let num_items = read_uint(bytes, byte_length.saturating_sub(8), 8)?;
+        Ok((byte_length, num_items, 1 + width))
+    } else {
+        let num_items = read_uint(bytes, 1 + width, width)?;
+        Ok((byte_length, num_items, 1 + 2 * width))
+    }
+}
+
+fn skip_padding(bytes: &[u8], mut offset: usize) -> usize {
+    while offset < bytes.len() && bytes[offset] == 0 {
+        offset += 1;
+    }
+    offset
+}
+
+/// Returns the remaining nesting depth for the items of a container.
+fn descend(remaining_depth: usize) -> Result<usize, Error> {
+    remaining_depth.checked_sub(1).ok_or_else(|| {
+        Error::Invalid(format!(
+            "arrays and objects are nested deeper than {} levels",
+            MAX_DEPTH
+        ))
+    })
+}
+
+/// Adds a length read from the input to the size of a header.
+fn add_length(header_size: usize, length: usize) -> Result<usize, Error> {
+    header_size
+        .checked_add(length)
+        .ok_or_else(|| Error::Invalid(format!("length {} exceeds the addressable size", length)))
+}
+
+fn slice(bytes: &[u8], start: usize, end: usize) -> Result<&[u8], Error> {
+    if start <= end && end <= bytes.len() {
+        Ok(&bytes[start..end])
+    } else {
+        Err(Error::UnexpectedEnd)
+    }
+}
+
+fn read_uint(bytes: &[u8], offset: usize, width: usize) -> Result<usize, Error> {
+    let value = LittleEndian::read_uint(slice(bytes, offset, add_length(offset, width)?)?, width);
+    Ok(value as usize)
+}
+
+fn read_varint(bytes: &[u8], mut offset: usize) -> Result<(u64, usize), Error> {
+    let mut value = 0u64;
+    let mut shift = 0;
+    loop {
+        let byte = *bytes.get(offset).ok_or(Error::UnexpectedEnd)?;
+        offset += 1;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok((value, offset));
+        }
+        shift += 7;
+        if shift > 63 {
+            return Err(Error::Invalid(
+                "variable length integer too long".to_owned(),
+            ));
+        }
+    }
+}
+
+fn read_reverse_varint(bytes: &[u8]) -> Result<u64, Error> {
+    let mut value = 0u64;
+    let mut shift = 0;
+    for byte in bytes.iter().rev() {
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+        shift += 7;
+        if shift > 63 {
+            return Err(Error::Invalid(
+                "variable length integer too long".to_owned(),
+            ));
+        }
+    }
+    Err(Error::UnexpectedEnd)
+}
@@ -0,0 +1,415 @@
+//! Serialization of Rust data structures into VelocyPack.
+
+use std::fmt;
+
+use serde::ser::{self, Serialize};
+use serde_json::Value;
+
+use super::{
+    decode_value, encode_compact, encode_double, encode_int, encode_string, encode_uint, Error,
+};
+
+const ARRAY: u8 = 0x13;
+const OBJECT: u8 = 0x14;
+
+impl ser::Error for Error {
+    fn custom<T>(message: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Error::Conversion(message.to_string())
+    }
+}
+
+/// A serializer that encodes Rust data structures as VelocyPack.
+///
+/// Arrays and objects are written in the compact format without index
+/// tables. Enums are encoded externally tagged in the same way as
+/// `serde_json` does.
+#[derive(Debug, Default)]
+pub struct Serializer {
+    output: Vec<u8>,
+}
+
+impl Serializer {
+    /// Creates a new `Serializer` with an empty output buffer.
+    pub fn new() -> Self {
+        Serializer { output: Vec::new() }
+    }
+
+    /// Consumes this `Serializer` and returns the encoded bytes.
+    pub fn into_inner(self) -> Vec<u8> {
+        self.output
+    }
+
+    fn start_compound(&mut self, head: u8, variant: Option<&str>) -> Compound {
+        let variant_start = variant.map(|variant| {
+            let start = self.output.len();
+            encode_string(variant, &mut self.output);
+            start
+        });
+        let start = self.output.len();
+        Compound {
+            serializer: self,
+            head,
+            start,
+            num_items: 0,
+            variant_start,
+        }
+    }
+
+    fn end_compound(&mut self, head: u8, start: usize, num_items: usize) {
+        let content = self.output.split_off(start);
+        if num_items == 0 {
+            self.output.push(if head == ARRAY { 0x01 } else { 0x0a });
+        } else {
+            encode_compact(head, &content, num_items, &mut self.output);
+        }
+    }
+}
+
+impl<'a> ser::Serializer for &'a mut Serializer {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Compound<'a>;
+    type SerializeTupleStruct = Compound<'a>;
+    type SerializeTupleVariant = Compound<'a>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Compound<'a>;
+
+    fn serialize_bool(self, value: bool) -> Result<(), Error> {
+        self.output.push(if value { 0x1a } else { 0x19 });
+        Ok(())
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<(), Error> {
+        self.serialize_i64(i64::from(value))
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<(), Error> {
+        self.serialize_i64(i64::from(value))
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<(), Error> {
+        self.serialize_i64(i64::from(value))
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<(), Error> {
+        encode_int(value, &mut self.output);
+        Ok(())
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<(), Error> {
+        self.serialize_u64(u64::from(value))
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<(), Error> {
+        self.serialize_u64(u64::from(value))
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<(), Error> {
+        self.serialize_u64(u64::from(value))
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<(), Error> {
+        encode_uint(value, &mut self.output);
+        Ok(())
+    }
+
+    fn serialize_f32(self, value: f32) -> Result<(), Error> {
+        self.serialize_f64(f64::from(value))
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<(), Error> {
+        encode_double(value, &mut self.output);
+        Ok(())
+    }
+
+    fn serialize_char(self, value: char) -> Result<(), Error> {
+        let mut buffer = [0; 4];
+        self.serialize_str(value.encode_utf8(&mut buffer))
+    }
+
+    fn serialize_str(self, value: &str) -> Result<(), Error> {
+        encode_string(value, &mut self.output);
+        Ok(())
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<(), Error> {
+        use serde::ser::SerializeSeq;
+        let mut seq = self.serialize_seq(Some(value.len()))?;
+        for byte in value {
+            seq.serialize_element(byte)?;
+        }
+        seq.end()
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        self.output.push(0x18);
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), Error> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let start = self.output.len();
+        encode_string(variant, &mut self.output);
+        value.serialize(&mut *self)?;
+        self.end_compound(OBJECT, start, 1);
+        Ok(())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Compound<'a>, Error> {
+        Ok(self.start_compound(ARRAY, None))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Compound<'a>, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Compound<'a>, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, Error> {
+        Ok(self.start_compound(ARRAY, Some(variant)))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Compound<'a>, Error> {
+        Ok(self.start_compound(OBJECT, None))
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Compound<'a>, Error> {
+        Ok(self.start_compound(OBJECT, None))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a>, Error> {
+        Ok(self.start_compound(OBJECT, Some(variant)))
+    }
+}
+
+/// Serializes the items of an array or the members of an object.
+#[derive(Debug)]
+pub struct Compound<'a> {
+    serializer: &'a mut Serializer,
+    head: u8,
+    start: usize,
+    num_items: usize,
+    variant_start: Option<usize>,
+}
+
+impl<'a> Compound<'a> {
+    fn serialize_item<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut *self.serializer)?;
+        self.num_items += 1;
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.serializer
+            .end_compound(self.head, self.start, self.num_items);
+        if let Some(variant_start) = self.variant_start {
+            self.serializer.end_compound(OBJECT, variant_start, 1);
+        }
+        Ok(())
+    }
+}
+
+impl<'a> ser::SerializeSeq for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.serialize_item(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Compound::end(self)
+    }
+}
+
+impl<'a> ser::SerializeTuple for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.serialize_item(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Compound::end(self)
+    }
+}
+
+impl<'a> ser::SerializeTupleStruct for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.serialize_item(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Compound::end(self)
+    }
+}
+
+impl<'a> ser::SerializeTupleVariant for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.serialize_item(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Compound::end(self)
+    }
+}
+
+impl<'a> ser::SerializeMap for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let start = self.serializer.output.len();
+        key.serialize(&mut *self.serializer)?;
+        match self.serializer.output[start] {
+            0x40..=0xbf => Ok(()),
+            _ => {
+                // like JSON we accept numbers as keys by converting them
+                // into strings
+                let key = self.serializer.output.split_off(start);
+                match decode_value(&key)? {
+                    (Value::Number(number), _) => {
+                        encode_string(&number.to_string(), &mut self.serializer.output);
+                        Ok(())
+                    },
+                    (key, _) => Err(Error::Conversion(format!(
+                        "object key must be a string, but got: {}",
+                        key
+                    ))),
+                }
+            },
+        }
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.serialize_item(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Compound::end(self)
+    }
+}
+
+impl<'a> ser::SerializeStruct for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        encode_string(key, &mut self.serializer.output);
+        self.serialize_item(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Compound::end(self)
+    }
+}
+
+impl<'a> ser::SerializeStructVariant for Compound<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        encode_string(key, &mut self.serializer.output);
+        self.serialize_item(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Compound::end(self)
+    }
+}
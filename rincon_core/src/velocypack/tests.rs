@@ -0,0 +1,353 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde_json;
+
+use super::*;
+
+fn roundtrip(value: &Value) -> Value {
+    let mut buffer = Vec::new();
+    encode_value(value, &mut buffer);
+    let (decoded, size) = decode_value(&buffer).unwrap();
+    assert_eq!(buffer.len(), size);
+    decoded
+}
+
+#[test]
+fn encode_simple_values() {
+    let mut buffer = Vec::new();
+    encode_value(&json!(null), &mut buffer);
+    encode_value(&json!(false), &mut buffer);
+    encode_value(&json!(true), &mut buffer);
+    encode_value(&json!(0), &mut buffer);
+    encode_value(&json!(9), &mut buffer);
+    encode_value(&json!(-1), &mut buffer);
+    encode_value(&json!(-6), &mut buffer);
+
+    assert_eq!(vec![0x18, 0x19, 0x1a, 0x30, 0x39, 0x3f, 0x3a], buffer);
+}
+
+#[test]
+fn encode_integers() {
+    let mut buffer = Vec::new();
+    encode_value(&json!(10), &mut buffer);
+    encode_value(&json!(256), &mut buffer);
+    encode_value(&json!(-7), &mut buffer);
+    encode_value(&json!(-200), &mut buffer);
+
+    assert_eq!(
+        vec![0x28, 0x0a, 0x29, 0x00, 0x01, 0x20, 0xf9, 0x21, 0x38, 0xff],
+        buffer
+    );
+}
+
+#[test]
+fn encode_short_string() {
+    let mut buffer = Vec::new();
+    encode_value(&json!("abc"), &mut buffer);
+
+    assert_eq!(vec![0x43, b'a', b'b', b'c'], buffer);
+}
+
+#[test]
+fn encode_compact_array() {
+    let mut buffer = Vec::new();
+    encode_value(&json!([1, 2, 3]), &mut buffer);
+
+    assert_eq!(vec![0x13, 0x06, 0x31, 0x32, 0x33, 0x03], buffer);
+}
+
+#[test]
+fn encode_compact_object() {
+    let mut buffer = Vec::new();
+    encode_value(&json!({"a": 1}), &mut buffer);
+
+    assert_eq!(vec![0x14, 0x06, 0x41, b'a', 0x31, 0x01], buffer);
+}
+
+#[test]
+fn decode_array_without_index_table() {
+    let bytes = [0x02, 0x05, 0x31, 0x32, 0x33];
+
+    let (value, size) = decode_value(&bytes).unwrap();
+
+    assert_eq!(json!([1, 2, 3]), value);
+    assert_eq!(5, size);
+}
+
+#[test]
+fn decode_array_with_index_table() {
+    let bytes = [
+        0x06, 0x0b, 0x03, 0x31, 0x42, b'a', b'b', 0x18, 0x03, 0x04, 0x07,
+    ];
+
+    let (value, size) = decode_value(&bytes).unwrap();
+
+    assert_eq!(json!([1, "ab", null]), value);
+    assert_eq!(11, size);
+}
+
+#[test]
+fn decode_sorted_object_with_index_table() {
+    let bytes = [
+        0x0b, 0x0d, 0x02, 0x41, b'a', 0x31, 0x41, b'b', 0x42, b'x', b'y', 0x03, 0x06,
+    ];
+
+    let (value, size) = decode_value(&bytes).unwrap();
+
+    assert_eq!(json!({"a": 1, "b": "xy"}), value);
+    assert_eq!(13, size);
+}
+
+#[test]
+fn decode_object_with_translated_attribute_keys() {
+    let bytes = [0x0b, 0x09, 0x01, 0x31, 0x43, b'k', b'e', b'y', 0x03];
+
+    let (value, _) = decode_value(&bytes).unwrap();
+
+    assert_eq!(json!({"_key": "key"}), value);
+}
+
+#[test]
+fn decode_unsupported_type_fails() {
+    let bytes = [0xf0, 0x00];
+
+    assert_eq!(Err(Error::UnsupportedType(0xf0)), decode_value(&bytes));
+}
+
+#[test]
+fn decode_truncated_input_fails() {
+    let bytes = [0x43, b'a', b'b'];
+
+    assert_eq!(Err(Error::UnexpectedEnd), decode_value(&bytes));
+}
+
+#[test]
+fn decode_array_with_more_items_than_bytes_fails() {
+    let mut bytes = vec![0x09];
+    bytes.extend_from_slice(&[18, 0, 0, 0, 0, 0, 0, 0]);
+    bytes.push(0x31);
+    bytes.extend_from_slice(&[0, 0, 0, 0, 0, 1, 0, 0]);
+
+    match decode_value(&bytes) {
+        Err(Error::Invalid(_)) => {},
+        result => panic!("Expected invalid VelocyPack error, but got: {:?}", result),
+    }
+}
+
+#[test]
+fn decode_values_with_overflowing_length_fails() {
+    let long_string = [0xbf, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff];
+    let binary = [0xc7, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff];
+
+    for bytes in &[&long_string[..], &binary[..]] {
+        match decode_value(bytes) {
+            Err(Error::Invalid(_)) => {},
+            result => panic!("Expected invalid VelocyPack error, but got: {:?}", result),
+        }
+        match from_slice::<Value>(bytes) {
+            Err(Error::Invalid(_)) => {},
+            result => panic!("Expected invalid VelocyPack error, but got: {:?}", result),
+        }
+    }
+}
+
+#[test]
+fn decode_too_deeply_nested_arrays_fails() {
+    let mut value = json!([]);
+    for _ in 0..MAX_DEPTH {
+        value = json!([value]);
+    }
+    let mut buffer = Vec::new();
+    encode_value(&value, &mut buffer);
+
+    match decode_value(&buffer) {
+        Err(Error::Invalid(_)) => {},
+        result => panic!("Expected invalid VelocyPack error, but got: {:?}", result),
+    }
+    match from_slice::<Value>(&buffer) {
+        Err(Error::Invalid(_)) => {},
+        result => panic!("Expected invalid VelocyPack error, but got: {:?}", result),
+    }
+}
+
+#[test]
+fn decode_arrays_nested_up_to_the_maximum_depth() {
+    let mut value = json!([]);
+    for _ in 1..MAX_DEPTH {
+        value = json!([value]);
+    }
+
+    assert_eq!(value, roundtrip(&value));
+    assert_eq!(
+        value,
+        from_slice::<Value>(&to_vec(&value).unwrap()).unwrap()
+    );
+}
+
+#[test]
+fn roundtrip_nested_document() {
+    let value = json!({
+        "_key": "4711",
+        "name": "Jane Doe",
+        "age": 42,
+        "balance": -1234.5,
+        "big": 18_446_744_073_709_551_615u64,
+        "small": -9_223_372_036_854_775_808i64,
+        "active": true,
+        "tags": ["a", "b", [], {}],
+        "address": { "city": "Vienna", "zip": null }
+    });
+
+    assert_eq!(value, roundtrip(&value));
+}
+
+#[test]
+fn roundtrip_long_string() {
+    let value = Value::String("x".repeat(1000));
+
+    assert_eq!(value, roundtrip(&value));
+}
+
+#[test]
+fn roundtrip_large_array() {
+    let value = Value::Array((0..1000).map(|i| json!(i)).collect());
+
+    assert_eq!(value, roundtrip(&value));
+}
+
+#[test]
+fn serialize_and_deserialize_struct() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Customer {
+        name: String,
+        age: u16,
+    }
+    let customer = Customer {
+        name: "Jane Doe".to_owned(),
+        age: 42,
+    };
+
+    let bytes = to_vec(&customer).unwrap();
+    let deserialized: Customer = from_slice(&bytes).unwrap();
+
+    assert_eq!(customer, deserialized);
+}
+
+#[test]
+fn serialize_struct_with_optional_fields_and_enums() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Status {
+        Active,
+        Locked(String),
+        Moved { to: u32 },
+    }
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Account {
+        id: i64,
+        email: Option<String>,
+        status: Vec<Status>,
+        balance: f64,
+    }
+    let account = Account {
+        id: -4711,
+        email: None,
+        status: vec![
+            Status::Active,
+            Status::Locked("fraud".to_owned()),
+            Status::Moved { to: 300 },
+        ],
+        balance: 12.5,
+    };
+
+    let bytes = to_vec(&account).unwrap();
+    let deserialized: Account = from_slice(&bytes).unwrap();
+
+    assert_eq!(account, deserialized);
+    assert_eq!(
+        serde_json::to_value(&account).unwrap(),
+        decode_value(&bytes).unwrap().0
+    );
+}
+
+#[test]
+fn serialize_map_with_integer_keys() {
+    let mut map = BTreeMap::new();
+    map.insert(1u32, "one");
+    map.insert(42u32, "forty-two");
+
+    let bytes = to_vec(&map).unwrap();
+
+    assert_eq!(
+        json!({"1": "one", "42": "forty-two"}),
+        decode_value(&bytes).unwrap().0
+    );
+}
+
+#[test]
+fn serialize_empty_array_and_object() {
+    let empty_array: Vec<u8> = Vec::new();
+    let empty_object = HashMap::<String, u8>::new();
+
+    assert_eq!(vec![0x01], to_vec(&empty_array).unwrap());
+    assert_eq!(vec![0x0a], to_vec(&empty_object).unwrap());
+}
+
+#[test]
+fn deserialize_struct_from_object_with_index_table() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Pair<'a> {
+        a: u8,
+        b: &'a str,
+    }
+    let bytes = [
+        0x0b, 0x0d, 0x02, 0x41, b'a', 0x31, 0x41, b'b', 0x42, b'x', b'y', 0x03, 0x06,
+    ];
+
+    let pair: Pair = from_slice(&bytes).unwrap();
+
+    assert_eq!(Pair { a: 1, b: "xy" }, pair);
+}
+
+#[test]
+fn deserialize_struct_with_translated_attribute_keys() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Document {
+        _key: String,
+    }
+    let bytes = [0x0b, 0x09, 0x01, 0x31, 0x43, b'k', b'e', b'y', 0x03];
+
+    let document: Document = from_slice(&bytes).unwrap();
+
+    assert_eq!(
+        Document {
+            _key: "key".to_owned(),
+        },
+        document
+    );
+}
+
+#[test]
+fn deserialize_binary_into_bytes() {
+    let bytes = [0xc0, 0x03, 0x01, 0x02, 0x03];
+
+    let data: Vec<u8> = from_slice(&bytes).unwrap();
+
+    assert_eq!(vec![1, 2, 3], data);
+}
+
+#[test]
+fn find_attribute_in_object() {
+    let bytes = to_vec(&json!({"code": 200, "result": {"name": "test"}})).unwrap();
+
+    let result = find_attribute(&bytes, "result").unwrap().unwrap();
+
+    assert_eq!(json!({"name": "test"}), decode_value(result).unwrap().0);
+    assert_eq!(None, find_attribute(&bytes, "error").unwrap());
+}
+
+#[test]
+fn find_attribute_in_non_object_returns_none() {
+    let bytes = to_vec(&json!(["result"])).unwrap();
+
+    assert_eq!(None, find_attribute(&bytes, "result").unwrap());
+}
@@ -7,7 +7,9 @@ use serde::ser::Serialize;
 use tokio_core::reactor::Core;
 
 use rincon_client::admin::methods::*;
-use rincon_client::admin::types::{ServerVersion, TargetVersion};
+use rincon_client::admin::types::{
+    Engine, ServerCapabilities, ServerRole, ServerVersion, TargetVersion,
+};
 use rincon_client::database::methods::{
     CreateDatabase, DropDatabase, ListAccessibleDatabases, ListDatabases,
 };
@@ -30,6 +32,7 @@ use database_session::DatabaseSession;
 pub struct ArangoSession<C> {
     connector: Rc<C>,
     core: Rc<RefCell<Core>>,
+    capabilities: RefCell<Option<ServerCapabilities>>,
 }
 
 impl<C> ArangoSession<C>
@@ -41,6 +44,7 @@ where
         ArangoSession {
             connector: Rc::new(connector),
             core: Rc::new(RefCell::new(core)),
+            capabilities: RefCell::new(None),
         }
     }
 
@@ -69,6 +73,33 @@ where
         self.execute(GetTargetVersion::new())
     }
 
+    /// Gets the storage engine the server is using.
+    pub fn get_engine(&self) -> Result<Engine> {
+        self.execute(GetEngine::new())
+    }
+
+    /// Gets the role of the server in a cluster.
+    pub fn get_server_role(&self) -> Result<ServerRole> {
+        self.execute(GetServerRole::new())
+    }
+
+    /// Gets the capabilities of the server.
+    ///
+    /// The capabilities are queried from the server on the first call and
+    /// cached for the lifetime of this session.
+    pub fn server_capabilities(&self) -> Result<ServerCapabilities> {
+        if let Some(ref capabilities) = *self.capabilities.borrow() {
+            return Ok(capabilities.clone());
+        }
+        let capabilities = ServerCapabilities::new(
+            self.get_server_version()?,
+            self.get_engine()?,
+            self.get_server_role()?,
+        );
+        *self.capabilities.borrow_mut() = Some(capabilities.clone());
+        Ok(capabilities)
+    }
+
     /// Returns a new `DatabaseSession` for the system database.
     ///
     /// In *ArangoDB* the system database usually has the name `_system`.
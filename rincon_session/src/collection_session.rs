@@ -9,7 +9,8 @@ use tokio_core::reactor::Core;
 
 use rincon_client::collection::methods::*;
 use rincon_client::collection::types::{
-    Collection, CollectionProperties, CollectionPropertiesUpdate, CollectionRevision, RenameTo,
+    Collection, CollectionChecksum, CollectionFigures, CollectionProperties,
+    CollectionPropertiesUpdate, CollectionRevision, RenameTo,
 };
 use rincon_client::document::methods::*;
 use rincon_client::document::types::{
@@ -21,17 +22,19 @@ use rincon_client::index::types::{
     Index, IndexId, IndexIdOption, IndexKey, NewFulltextIndex, NewGeoIndex, NewHashIndex,
     NewPersistentIndex, NewSkipListIndex,
 };
-use rincon_core::api::connector::{Connector, Execute};
+use rincon_core::api::connector::Connector;
 use rincon_core::api::method::{Method, Prepare, ResultList};
 use rincon_core::api::types::Entity;
 
 use super::Result;
+use transaction_session::execute_in_transaction;
 
 /// A session for operating with a specific collection.
 #[derive(Debug)]
 pub struct CollectionSession<C> {
     entity: Entity<Collection>,
     database_name: String,
+    transaction_id: Option<String>,
     connector: Rc<C>,
     core: Rc<RefCell<Core>>,
 }
@@ -50,21 +53,30 @@ where
         CollectionSession {
             entity,
             database_name,
+            transaction_id: None,
             connector,
             core,
         }
     }
 
+    /// Returns this `CollectionSession` with all method calls being executed
+    /// within the stream transaction with the given id.
+    pub(crate) fn with_transaction_id(mut self, transaction_id: String) -> Self {
+        self.transaction_id = Some(transaction_id);
+        self
+    }
+
     /// Executes an API method applied to the database of this session.
+    ///
+    /// If this session is bound to a stream transaction the method is
+    /// executed within that transaction.
     fn execute<M>(&self, method: M) -> Result<<M as Method>::Result>
     where
         M: 'static + Method + Prepare,
     {
-        self.core.borrow_mut().run(
-            self.connector
-                .connection(&self.database_name)
-                .execute(method),
-        )
+        let connection = self.connector.connection(&self.database_name);
+        let result = execute_in_transaction(&connection, self.transaction_id(), method);
+        self.core.borrow_mut().run(result)
     }
 
     /// Returns the name of the database this collection is located in.
@@ -72,6 +84,14 @@ where
         &self.database_name
     }
 
+    /// Returns the id of the stream transaction this session is bound to.
+    ///
+    /// It returns `None` if the method calls of this session are not executed
+    /// within a stream transaction.
+    pub fn transaction_id(&self) -> Option<&str> {
+        self.transaction_id.as_ref().map(String::as_str)
+    }
+
     /// Returns the name of the collection this `CollectionSession` operates
     /// with.
     pub fn name(&self) -> &str {
@@ -86,7 +106,7 @@ where
     /// It returns `Some(&Collection)` if this session holds a loaded collection
     /// entity or `None` otherwise.
     ///
-    /// If the collection entity is not loaded the `load()` function can be
+    /// If the collection entity is not loaded the `fetch()` function can be
     /// used to get a session with a loaded collection entity.
     pub fn entity(&self) -> Option<&Collection> {
         match self.entity {
@@ -120,6 +140,7 @@ where
             .map(|collection| CollectionSession {
                 entity: Entity::Object(collection),
                 database_name: self.database_name,
+                transaction_id: self.transaction_id,
                 connector: self.connector,
                 core: self.core,
             })
@@ -148,6 +169,7 @@ where
         )).map(|collection| CollectionSession {
             entity: Entity::Object(collection),
             database_name: self.database_name,
+            transaction_id: self.transaction_id,
             connector: self.connector,
             core: self.core,
         })
@@ -175,6 +197,65 @@ where
         ))
     }
 
+    /// Counts the documents in the collection represented by this session.
+    pub fn count(&self) -> Result<u64> {
+        self.execute(GetCollectionCount::with_name(self.name()))
+    }
+
+    /// Gets statistical information about the collection represented by
+    /// this session.
+    pub fn get_figures(&self) -> Result<CollectionFigures> {
+        self.execute(GetCollectionFigures::with_name(self.name()))
+    }
+
+    /// Calculates a checksum for the collection represented by this session.
+    ///
+    /// # Arguments
+    ///
+    /// * `with_revisions` : whether to include the revision ids of the documents
+    /// * `with_data`      : whether to include the data of the documents
+    pub fn get_checksum(
+        &self,
+        with_revisions: bool,
+        with_data: bool,
+    ) -> Result<CollectionChecksum> {
+        self.execute(GetCollectionChecksum::new(
+            self.name().into(),
+            with_revisions,
+            with_data,
+        ))
+    }
+
+    /// Removes all documents from the collection represented by this session.
+    ///
+    /// The indexes of the collection are left intact.
+    pub fn truncate(&self) -> Result<Collection> {
+        self.execute(TruncateCollection::with_name(self.name()))
+    }
+
+    /// Loads the collection represented by this session into memory.
+    pub fn load(&self) -> Result<Collection> {
+        self.execute(LoadCollection::with_name(self.name()))
+    }
+
+    /// Removes the collection represented by this session from memory.
+    pub fn unload(&self) -> Result<Collection> {
+        self.execute(UnloadCollection::with_name(self.name()))
+    }
+
+    /// Loads the indexes of the collection represented by this session into
+    /// memory.
+    pub fn load_indexes_into_memory(&self) -> Result<bool> {
+        self.execute(LoadIndexesIntoMemory::with_name(self.name()))
+    }
+
+    /// Rotates the journal of the collection represented by this session.
+    ///
+    /// This function is only supported by the MMFiles storage engine.
+    pub fn rotate_journal(&self) -> Result<bool> {
+        self.execute(RotateCollectionJournal::with_name(self.name()))
+    }
+
     /// Inserts a new document into this collection.
     pub fn insert_document<D, T>(&self, document: D) -> Result<DocumentHeader>
     where
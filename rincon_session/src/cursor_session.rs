@@ -8,17 +8,20 @@ use tokio_core::reactor::Core;
 
 use rincon_client::cursor::methods::*;
 use rincon_client::cursor::types::{Cursor, CursorStatistics, Warning};
-use rincon_core::api::connector::{Connector, Execute};
+use rincon_core::api::connector::{Connector, FutureResult};
 use rincon_core::api::method::{Method, Prepare};
+use rincon_core::api::metrics::MetricsSink;
 use rincon_core::api::types::{Empty, EMPTY};
 
 use super::Result;
+use transaction_session::execute_in_transaction;
 
 /// A session for operating with a specific `Cursor`.
 #[derive(Debug)]
 pub struct CursorSession<T, C> {
     cursor: Cursor<T>,
     database_name: String,
+    transaction_id: Option<String>,
     connector: Rc<C>,
     core: Rc<RefCell<Core>>,
 }
@@ -38,21 +41,30 @@ where
         CursorSession {
             cursor,
             database_name,
+            transaction_id: None,
             connector,
             core,
         }
     }
 
+    /// Returns this `CursorSession` with all method calls being executed
+    /// within the stream transaction with the given id.
+    pub(crate) fn with_transaction_id(mut self, transaction_id: String) -> Self {
+        self.transaction_id = Some(transaction_id);
+        self
+    }
+
     /// Executes an API method applied to the database of this session.
+    ///
+    /// If this session is bound to a stream transaction the method is
+    /// executed within that transaction.
     fn execute<M>(&self, method: M) -> Result<<M as Method>::Result>
     where
         M: 'static + Method + Prepare,
     {
-        self.core.borrow_mut().run(
-            self.connector
-                .connection(&self.database_name)
-                .execute(method),
-        )
+        let connection = self.connector.connection(&self.database_name);
+        let result = execute_in_transaction(&connection, self.transaction_id(), method);
+        self.core.borrow_mut().run(result)
     }
 
     /// Returns the name of the database the query has been executed for.
@@ -60,6 +72,15 @@ where
         &self.database_name
     }
 
+    /// Returns the id of the stream transaction the query has been executed
+    /// in.
+    ///
+    /// It returns `None` if the query has not been executed within a stream
+    /// transaction.
+    pub fn transaction_id(&self) -> Option<&str> {
+        self.transaction_id.as_ref().map(String::as_str)
+    }
+
     /// Returns the `Cursor` entity of this session.
     pub fn entity(&self) -> &Cursor<T> {
         &self.cursor
@@ -118,6 +139,12 @@ where
         self.cursor.warnings()
     }
 
+    /// Records whether the result was served from the query cache and the
+    /// statistics of the query to the given metrics sink.
+    pub fn record_to(&self, sink: &MetricsSink) {
+        self.cursor.record_to(sink)
+    }
+
     /// Checks whether this cursor has more results and if yes fetches a
     /// cursor with the next batch of results and returns it as a new
     /// `CursorSession`.
@@ -131,6 +158,7 @@ where
                 .map(|cursor| CursorSession {
                     cursor,
                     database_name: self.database_name.clone(),
+                    transaction_id: self.transaction_id.clone(),
                     connector: self.connector.clone(),
                     core: self.core.clone(),
                 })
@@ -165,6 +193,7 @@ where
             has_more,
             cursor_id,
             database_name: self.database_name,
+            transaction_id: self.transaction_id,
             connector: self.connector,
             core: self.core,
         }
@@ -172,32 +201,69 @@ where
 }
 
 /// An `Iterator` over all results for a specific cursor.
+///
+/// If the iterator is dropped before all results have been consumed the
+/// cursor is deleted on the server.
 #[derive(Debug)]
-pub struct CursorSessionIntoIter<T, C> {
+pub struct CursorSessionIntoIter<T, C>
+where
+    C: 'static + Connector,
+{
     batch: IntoIter<T>,
     count: Option<u64>,
     has_more: bool,
     cursor_id: Option<String>,
     database_name: String,
+    transaction_id: Option<String>,
     connector: Rc<C>,
     core: Rc<RefCell<Core>>,
 }
 
 impl<T, C> CursorSessionIntoIter<T, C>
 where
-    T: 'static + DeserializeOwned,
     C: 'static + Connector,
 {
+    /// Prepares the execution of an API method applied to the database of
+    /// this session.
+    ///
+    /// If this session is bound to a stream transaction the method is
+    /// executed within that transaction.
+    fn prepare<M>(&self, method: M) -> FutureResult<M>
+    where
+        M: 'static + Method + Prepare,
+    {
+        let connection = self.connector.connection(&self.database_name);
+        execute_in_transaction(
+            &connection,
+            self.transaction_id.as_ref().map(String::as_str),
+            method,
+        )
+    }
+
     /// Executes an API method applied to the database of this session.
     fn execute<M>(&self, method: M) -> Result<<M as Method>::Result>
     where
         M: 'static + Method + Prepare,
     {
-        self.core.borrow_mut().run(
-            self.connector
-                .connection(&self.database_name)
-                .execute(method),
-        )
+        self.core.borrow_mut().run(self.prepare(method))
+    }
+}
+
+impl<T, C> Drop for CursorSessionIntoIter<T, C>
+where
+    C: 'static + Connector,
+{
+    fn drop(&mut self) {
+        if !self.has_more {
+            return;
+        }
+        if let Some(cursor_id) = self.cursor_id.take() {
+            let delete_cursor = self.prepare(DeleteCursor::new(cursor_id));
+            if let Ok(mut core) = self.core.try_borrow_mut() {
+                //the cursor times out on the server if deleting it fails
+                let _ = core.run(delete_cursor);
+            }
+        }
     }
 }
 
@@ -4,8 +4,17 @@ use std::rc::Rc;
 use serde::de::DeserializeOwned;
 use tokio_core::reactor::Core;
 
-use rincon_client::aql::methods::{ExplainQuery, ParseQuery};
-use rincon_client::aql::types::{ExplainOptions, ExplainedQuery, ParsedQuery};
+use rincon_client::aql::methods::{
+    ChangeQueryCacheProperties, ChangeQueryTrackingProperties, ClearQueryCache, ClearSlowQueries,
+    CreateAqlFunction, DeleteAqlFunction, ExplainQuery, GetQueryCacheProperties,
+    GetQueryTrackingProperties, KillQuery, ListAqlFunctions, ListCurrentQueries,
+    ListQueryCacheEntries, ListSlowQueries, ParseQuery,
+};
+use rincon_client::aql::types::{
+    AqlFunction, AqlFunctionCreated, AqlFunctionsDeleted, CachedQuery, ExplainOptions,
+    ExplainedQuery, NewAqlFunction, ParsedQuery, QueryCacheProperties, QueryCachePropertiesUpdate,
+    QueryTrackingProperties, QueryTrackingPropertiesUpdate, RunningQuery,
+};
 use rincon_client::collection::methods::{CreateCollection, DropCollection, ListCollections};
 use rincon_client::collection::types::Collection;
 use rincon_client::cursor::methods::CreateCursor;
@@ -15,15 +24,20 @@ use rincon_client::document::methods::GetDocument;
 use rincon_client::document::types::{Document, DocumentId};
 use rincon_client::graph::methods::{CreateGraph, DropGraph, ListGraphs};
 use rincon_client::graph::types::{Graph, NewGraph};
+use rincon_client::transaction::methods::{BeginTransaction, ExecuteTransaction};
+use rincon_client::transaction::types::{
+    NewStreamTransaction, NewTransaction, TransactionCollections,
+};
 use rincon_core::api::connector::{Connector, Execute};
 use rincon_core::api::method::{Method, Prepare};
 use rincon_core::api::query::Query;
-use rincon_core::api::types::Entity;
+use rincon_core::api::types::{Empty, Entity};
 
 use super::Result;
 use collection_session::CollectionSession;
 use cursor_session::CursorSession;
 use graph_session::GraphSession;
+use transaction_session::TransactionSession;
 
 /// A session for operating with a specific database.
 #[derive(Debug)]
@@ -154,6 +168,139 @@ where
         self.execute(ParseQuery::from_query(query.into()))
     }
 
+    /// Returns the AQL queries currently running in the database of this
+    /// session.
+    pub fn list_current_queries(&self) -> Result<Vec<RunningQuery>> {
+        self.execute(ListCurrentQueries::new())
+    }
+
+    /// Returns the slow AQL queries tracked for the database of this session.
+    pub fn list_slow_queries(&self) -> Result<Vec<RunningQuery>> {
+        self.execute(ListSlowQueries::new())
+    }
+
+    /// Clears the list of slow AQL queries tracked for the database of this
+    /// session.
+    pub fn clear_slow_queries(&self) -> Result<Empty> {
+        self.execute(ClearSlowQueries::new())
+    }
+
+    /// Kills the running AQL query with the given id.
+    pub fn kill_query<I>(&self, query_id: I) -> Result<Empty>
+    where
+        I: Into<String>,
+    {
+        self.execute(KillQuery::new(query_id))
+    }
+
+    /// Returns the current configuration of the AQL query tracking.
+    pub fn get_query_tracking_properties(&self) -> Result<QueryTrackingProperties> {
+        self.execute(GetQueryTrackingProperties::new())
+    }
+
+    /// Changes the configuration of the AQL query tracking.
+    ///
+    /// Returns the configuration after the changes have been applied.
+    pub fn change_query_tracking_properties(
+        &self,
+        updates: QueryTrackingPropertiesUpdate,
+    ) -> Result<QueryTrackingProperties> {
+        self.execute(ChangeQueryTrackingProperties::new(updates))
+    }
+
+    /// Clears all results in the AQL query result cache of the database of
+    /// this session.
+    pub fn clear_query_cache(&self) -> Result<Empty> {
+        self.execute(ClearQueryCache::new())
+    }
+
+    /// Returns the entries of the AQL query result cache of the database of
+    /// this session.
+    pub fn list_query_cache_entries(&self) -> Result<Vec<CachedQuery>> {
+        self.execute(ListQueryCacheEntries::new())
+    }
+
+    /// Returns the global configuration of the AQL query result cache.
+    pub fn get_query_cache_properties(&self) -> Result<QueryCacheProperties> {
+        self.execute(GetQueryCacheProperties::new())
+    }
+
+    /// Changes the global configuration of the AQL query result cache.
+    ///
+    /// Returns the configuration after the changes have been applied.
+    pub fn change_query_cache_properties(
+        &self,
+        updates: QueryCachePropertiesUpdate,
+    ) -> Result<QueryCacheProperties> {
+        self.execute(ChangeQueryCacheProperties::new(updates))
+    }
+
+    /// Returns all user defined AQL functions registered in the database of
+    /// this session.
+    pub fn list_aql_functions(&self) -> Result<Vec<AqlFunction>> {
+        self.execute(ListAqlFunctions::new())
+    }
+
+    /// Returns the user defined AQL functions in the given namespace.
+    pub fn list_aql_functions_in_namespace<N>(&self, namespace: N) -> Result<Vec<AqlFunction>>
+    where
+        N: Into<String>,
+    {
+        self.execute(ListAqlFunctions::with_namespace(namespace))
+    }
+
+    /// Registers a user defined AQL function in the database of this session.
+    ///
+    /// An existing function with the same name is replaced.
+    pub fn create_aql_function(&self, function: NewAqlFunction) -> Result<AqlFunctionCreated> {
+        self.execute(CreateAqlFunction::new(function))
+    }
+
+    /// Makes sure the given user defined AQL function is registered in the
+    /// database of this session.
+    ///
+    /// The function is only (re-)registered if no function with the same name
+    /// exists or if its code or determinism differ from the given definition.
+    /// Servers that do not report the determinism of functions are only
+    /// compared by code.
+    ///
+    /// Returns `true` if the function has been registered by this call and
+    /// `false` if it was already up to date.
+    pub fn ensure_aql_function(&self, function: NewAqlFunction) -> Result<bool> {
+        let up_to_date = self
+            .list_aql_functions_in_namespace(function.namespace())?
+            .iter()
+            .any(|existing| {
+                existing.name() == function.name()
+                    && existing.code() == function.code()
+                    && existing.is_deterministic().map_or(true, |deterministic| {
+                        deterministic == function.is_deterministic().unwrap_or(false)
+                    })
+            });
+        if up_to_date {
+            Ok(false)
+        } else {
+            self.create_aql_function(function).map(|_| true)
+        }
+    }
+
+    /// Removes the user defined AQL function with the given fully qualified
+    /// name.
+    pub fn delete_aql_function<N>(&self, name: N) -> Result<AqlFunctionsDeleted>
+    where
+        N: Into<String>,
+    {
+        self.execute(DeleteAqlFunction::with_name(name))
+    }
+
+    /// Removes all user defined AQL functions in the given namespace.
+    pub fn delete_aql_functions_in_namespace<N>(&self, namespace: N) -> Result<AqlFunctionsDeleted>
+    where
+        N: Into<String>,
+    {
+        self.execute(DeleteAqlFunction::with_namespace(namespace))
+    }
+
     /// Fetch the document with the given id from the database of this session.
     pub fn get_document<T>(&self, id: DocumentId) -> Result<Document<T>>
     where
@@ -162,6 +309,49 @@ where
         self.execute(GetDocument::with_id(id))
     }
 
+    /// Executes a JavaScript transaction and returns the result of its action
+    /// function.
+    pub fn execute_transaction<T>(&self, new_transaction: NewTransaction) -> Result<T>
+    where
+        T: 'static + DeserializeOwned,
+    {
+        self.execute(ExecuteTransaction::new(new_transaction))
+    }
+
+    /// Begins a new stream transaction for the given collections and
+    /// returns a `TransactionSession` for it.
+    ///
+    /// All other transaction options are left to their default settings.
+    ///
+    /// To specify transaction options use the
+    /// `begin_transaction_opt(&self, NewStreamTransaction)` function.
+    pub fn begin_transaction(
+        &self,
+        collections: TransactionCollections,
+    ) -> Result<TransactionSession<C>> {
+        self.begin_transaction_opt(NewStreamTransaction::new(collections))
+    }
+
+    /// Begins a new stream transaction and returns a `TransactionSession`
+    /// for it.
+    ///
+    /// It requires a `NewStreamTransaction` struct as a parameter which allows
+    /// full control over all supported transaction options.
+    pub fn begin_transaction_opt(
+        &self,
+        new_transaction: NewStreamTransaction,
+    ) -> Result<TransactionSession<C>> {
+        self.execute(BeginTransaction::new(new_transaction))
+            .map(|transaction| {
+                TransactionSession::new(
+                    transaction.unwrap_id(),
+                    self.database_name.clone(),
+                    self.connector.clone(),
+                    self.core.clone(),
+                )
+            })
+    }
+
     /// Returns a new `CollectionSession` for the collection with the given
     /// name.
     pub fn use_collection_with_name<N>(&self, collection_name: N) -> CollectionSession<C>
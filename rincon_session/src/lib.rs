@@ -160,6 +160,44 @@
 //! Now we are ready to conveniently interact with the [ArangoDB] server as
 //! shown in the example at the beginning of this chapter.
 //!
+//! # Sharing sessions between threads
+//!
+//! An `ArangoSession` and all sessions obtained from it must be used on the
+//! thread they have been created on. Multi-threaded applications, like web
+//! servers that handle requests on a pool of worker threads, can use a
+//! `SharedArangoSession` instead. It runs the connector and the reactor in a
+//! dedicated thread. A `SharedArangoSession` and the `SharedDatabaseSession`s
+//! obtained from it implement `Send` and `Sync` and can be cloned cheaply.
+//!
+//! As the connector is created on the runtime thread, the
+//! `SharedArangoSession` takes a function that creates the connector for a
+//! given reactor handle:
+//!
+//! ```rust
+//! # extern crate rincon_core;
+//! # extern crate rincon_connector;
+//! # extern crate rincon_session;
+//! use rincon_connector::http::JsonHttpConnector;
+//! use rincon_core::api::datasource::DataSource;
+//! use rincon_session::SharedArangoSession;
+//! use std::str::FromStr;
+//! use std::thread;
+//!
+//! fn main() {
+//!     let datasource = DataSource::from_str("http://localhost:8529")
+//!         .expect("invalid URL for datasource")
+//!         .with_basic_authentication("root", "s3cur3");
+//!
+//!     let session = SharedArangoSession::new(move |reactor| {
+//!         JsonHttpConnector::new(datasource, reactor)
+//!     }).unwrap();
+//!
+//!     let friendsbook = session.use_database_with_name("friendsbook");
+//!
+//!     thread::spawn(move || friendsbook.list_collections());
+//! }
+//! ```
+//!
 //! [ArangoDB]: https://www.arangodb.com
 
 #![doc(html_root_url = "https://docs.rs/rincon_session/0.1.0")]
@@ -182,6 +220,8 @@ mod cursor_session;
 mod database_session;
 mod edge_collection_session;
 mod graph_session;
+mod shared_session;
+mod transaction_session;
 mod vertex_collection_session;
 
 pub use self::arango_session::*;
@@ -190,17 +230,27 @@ pub use self::cursor_session::*;
 pub use self::database_session::*;
 pub use self::edge_collection_session::*;
 pub use self::graph_session::*;
+pub use self::shared_session::*;
+pub use self::transaction_session::*;
 pub use self::vertex_collection_session::*;
 
 pub mod client {
     //! Re-export of types from the `rincon_client` crate that are used in the
     //! public API of this crate.
 
-    pub use rincon_client::admin::types::{ServerVersion, TargetVersion};
-    pub use rincon_client::aql::types::{ExplainOptions, ExplainedQuery, ParsedQuery};
+    pub use rincon_client::admin::types::{
+        Engine, EngineSupports, ServerCapabilities, ServerRole, ServerVersion, TargetVersion,
+    };
+    pub use rincon_client::aql::types::{
+        AqlFunction, AqlFunctionCreated, AqlFunctionsDeleted, CachedQuery, ExplainOptions,
+        ExplainedQuery, NewAqlFunction, ParsedQuery, QueryCacheMode, QueryCacheProperties,
+        QueryCachePropertiesUpdate, QueryState, QueryTrackingProperties,
+        QueryTrackingPropertiesUpdate, RunningQuery,
+    };
     pub use rincon_client::collection::types::{
-        Collection, CollectionProperties, CollectionPropertiesUpdate, CollectionRevision,
-        CollectionStatus, CollectionType, NewCollection, RenameTo,
+        Collection, CollectionChecksum, CollectionFigures, CollectionProperties,
+        CollectionPropertiesUpdate, CollectionRevision, CollectionStatus, CollectionType,
+        DeadFigures, FileFigures, NewCollection, RenameTo, SizeFigures,
     };
     pub use rincon_client::cursor::types::{Cursor, CursorStatistics, NewCursor, Warning};
     pub use rincon_client::database::types::{Database, NewDatabase};
@@ -212,6 +262,10 @@ pub mod client {
     pub use rincon_client::graph::types::{
         EdgeCollection, EdgeDefinition, Graph, NewEdge, NewGraph, VertexCollection,
     };
+    pub use rincon_client::transaction::types::{
+        NewStreamTransaction, NewTransaction, TransactionCollections, TransactionInfo,
+        TransactionStatus,
+    };
     pub use rincon_client::user::types::{NewUser, Permission, User, UserExtra, UserUpdate};
 }
 
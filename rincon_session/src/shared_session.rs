@@ -0,0 +1,499 @@
+use std::fmt::{self, Debug};
+use std::sync::mpsc as std_mpsc;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::{self, ThreadId};
+
+use futures::sync::{mpsc, oneshot};
+use futures::{Future, Stream};
+use serde::de::DeserializeOwned;
+use serde::ser::Serialize;
+use tokio_core::reactor::{Core, Handle};
+
+use rincon_client::admin::methods::{GetEngine, GetServerRole, GetServerVersion, GetTargetVersion};
+use rincon_client::admin::types::{
+    Engine, ServerCapabilities, ServerRole, ServerVersion, TargetVersion,
+};
+use rincon_client::aql::methods::{ExplainQuery, ParseQuery};
+use rincon_client::aql::types::{ExplainedQuery, ParsedQuery};
+use rincon_client::collection::methods::{CreateCollection, DropCollection, ListCollections};
+use rincon_client::collection::types::Collection;
+use rincon_client::cursor::methods::{CreateCursor, DeleteCursor, ReadNextBatchFromCursor};
+use rincon_client::cursor::types::{Cursor, NewCursor};
+use rincon_client::database::methods::{
+    CreateDatabase, DropDatabase, ListAccessibleDatabases, ListDatabases,
+};
+use rincon_client::document::methods::GetDocument;
+use rincon_client::document::types::{Document, DocumentId};
+use rincon_client::graph::methods::ListGraphs;
+use rincon_client::graph::types::Graph;
+use rincon_client::transaction::methods::ExecuteTransaction;
+use rincon_client::transaction::types::NewTransaction;
+use rincon_client::user::types::{NewUser, UserExtra};
+use rincon_core::api::connector::{Connector, Error, Execute};
+use rincon_core::api::method::{Method, Prepare};
+use rincon_core::api::query::Query;
+use rincon_core::api::types::{Empty, EMPTY};
+use rincon_core::arango::protocol::SYSTEM_DATABASE;
+
+use super::Result;
+
+/// A method call that is sent to the runtime thread of a session.
+type Job<C> = Box<FnOnce(&C, &Handle) + Send>;
+
+/// Runs the connector and the reactor of shared sessions in a dedicated
+/// thread.
+///
+/// The thread is terminated when the last session that uses it is dropped.
+struct SessionRuntime<C> {
+    jobs: Mutex<mpsc::UnboundedSender<Job<C>>>,
+    thread_id: ThreadId,
+}
+
+impl<C> SessionRuntime<C>
+where
+    C: 'static + Connector,
+{
+    /// Starts a new runtime thread and creates the connector within that
+    /// thread.
+    fn start<F>(new_connector: F) -> Result<Self>
+    where
+        F: 'static + FnOnce(&Handle) -> Result<C> + Send,
+    {
+        let (job_sender, job_receiver) = mpsc::unbounded::<Job<C>>();
+        let (started_sender, started_receiver) = std_mpsc::channel();
+        let runtime_thread = thread::Builder::new()
+            .name("rincon-session-runtime".to_owned())
+            .spawn(move || {
+                let mut core = match Core::new() {
+                    Ok(core) => core,
+                    Err(error) => {
+                        let _ = started_sender.send(Err(Error::from(error)));
+                        return;
+                    },
+                };
+                let handle = core.handle();
+                let connector = match new_connector(&handle) {
+                    Ok(connector) => connector,
+                    Err(error) => {
+                        let _ = started_sender.send(Err(error));
+                        return;
+                    },
+                };
+                let _ = started_sender.send(Ok(()));
+                //runs until all sessions using this runtime have been dropped
+                let _ = core.run(job_receiver.for_each(|job| {
+                    job(&connector, &handle);
+                    Ok(())
+                }));
+            })?;
+        started_receiver
+            .recv()
+            .map_err(|_| runtime_terminated())??;
+        Ok(SessionRuntime {
+            jobs: Mutex::new(job_sender),
+            thread_id: runtime_thread.thread().id(),
+        })
+    }
+
+    /// Executes the given method on the runtime thread and blocks until its
+    /// result is available.
+    ///
+    /// If no database name is given the method is executed on the system
+    /// database.
+    ///
+    /// Fails if called on the runtime thread itself, as waiting for the
+    /// result would block the reactor that is supposed to produce it.
+    fn execute<M>(&self, database_name: Option<&str>, method: M) -> Result<<M as Method>::Result>
+    where
+        M: 'static + Method + Prepare + Send,
+        <M as Method>::Result: Send,
+    {
+        if thread::current().id() == self.thread_id {
+            return Err(Error::Communication(
+                "methods of a shared session can not be executed on its runtime thread".to_owned(),
+            ));
+        }
+        let (result_sender, result_receiver) = oneshot::channel();
+        let database_name = database_name.map(ToOwned::to_owned);
+        let job: Job<C> = Box::new(move |connector: &C, handle: &Handle| {
+            let connection = match database_name {
+                Some(ref database_name) => connector.connection(database_name),
+                None => connector.system_connection(),
+            };
+            //sending the result fails if the caller has gone away in the meantime
+            handle.spawn(
+                connection
+                    .execute(method)
+                    .then(|result| result_sender.send(result))
+                    .map_err(|_| ()),
+            );
+        });
+        let sent = match self.jobs.lock() {
+            Ok(jobs) => jobs.unbounded_send(job),
+            Err(poisoned) => poisoned.into_inner().unbounded_send(job),
+        };
+        sent.map_err(|_| runtime_terminated())?;
+        result_receiver.wait().map_err(|_| runtime_terminated())?
+    }
+}
+
+impl<C> Debug for SessionRuntime<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("SessionRuntime")
+    }
+}
+
+fn runtime_terminated() -> Error {
+    Error::Communication("the runtime thread of the session has terminated".to_owned())
+}
+
+/// A session for operating with an ArangoDB server that can be shared
+/// between threads.
+///
+/// In contrast to the `ArangoSession` a `SharedArangoSession` implements
+/// `Send` and `Sync` and is cheap to clone. Thus it can be stored in the
+/// state of a multi-threaded server application and used from any thread.
+///
+/// The connector and the reactor used to execute method calls live in a
+/// dedicated runtime thread that is started when the session is created.
+/// Method calls are sent to that thread and the calling thread blocks until
+/// the result is available. The runtime thread terminates when the last
+/// session created from this `SharedArangoSession` has been dropped.
+///
+/// As all functions block the calling thread, they should not be called
+/// directly within an asynchronous task. Use the facility of your framework
+/// for running blocking code instead, e.g. `web::block` in actix-web or
+/// `spawn_blocking` in tokio.
+#[derive(Debug)]
+pub struct SharedArangoSession<C> {
+    runtime: Arc<SessionRuntime<C>>,
+    capabilities: Arc<Mutex<Option<ServerCapabilities>>>,
+}
+
+impl<C> Clone for SharedArangoSession<C> {
+    fn clone(&self) -> Self {
+        SharedArangoSession {
+            runtime: self.runtime.clone(),
+            capabilities: self.capabilities.clone(),
+        }
+    }
+}
+
+impl<C> SharedArangoSession<C>
+where
+    C: 'static + Connector,
+{
+    /// Instantiates a new `SharedArangoSession`.
+    ///
+    /// The given function is called on the runtime thread of the session to
+    /// create the connector. It gets the handle of the reactor that runs on
+    /// the runtime thread.
+    ///
+    /// Returns an error if the runtime thread can not be started or the
+    /// connector can not be created.
+    pub fn new<F>(new_connector: F) -> Result<Self>
+    where
+        F: 'static + FnOnce(&Handle) -> Result<C> + Send,
+    {
+        SessionRuntime::start(new_connector).map(|runtime| SharedArangoSession {
+            runtime: Arc::new(runtime),
+            capabilities: Arc::new(Mutex::new(None)),
+        })
+    }
+
+    /// Executes an API method applied to the system database.
+    pub fn execute<M>(&self, method: M) -> Result<<M as Method>::Result>
+    where
+        M: 'static + Method + Prepare + Send,
+        <M as Method>::Result: Send,
+    {
+        self.runtime.execute(None, method)
+    }
+
+    /// Gets the server name and version number.
+    pub fn get_server_version(&self) -> Result<ServerVersion> {
+        self.execute(GetServerVersion::new())
+    }
+
+    /// Gets the server name and version number with additional details.
+    pub fn get_server_version_details(&self) -> Result<ServerVersion> {
+        self.execute(GetServerVersion::with_details())
+    }
+
+    /// Gets the database version a server requires.
+    pub fn get_target_version(&self) -> Result<TargetVersion> {
+        self.execute(GetTargetVersion::new())
+    }
+
+    /// Gets the storage engine the server is using.
+    pub fn get_engine(&self) -> Result<Engine> {
+        self.execute(GetEngine::new())
+    }
+
+    /// Gets the role of the server in a cluster.
+    pub fn get_server_role(&self) -> Result<ServerRole> {
+        self.execute(GetServerRole::new())
+    }
+
+    /// Gets the capabilities of the server.
+    ///
+    /// The capabilities are queried from the server on the first call and
+    /// cached. The cache is shared by all clones of this session.
+    pub fn server_capabilities(&self) -> Result<ServerCapabilities> {
+        if let Some(ref capabilities) = *self.cached_capabilities() {
+            return Ok(capabilities.clone());
+        }
+        //the lock is not held while querying the server, so concurrent callers
+        //may query the capabilities more than once
+        let capabilities = ServerCapabilities::new(
+            self.get_server_version()?,
+            self.get_engine()?,
+            self.get_server_role()?,
+        );
+        *self.cached_capabilities() = Some(capabilities.clone());
+        Ok(capabilities)
+    }
+
+    fn cached_capabilities(&self) -> MutexGuard<Option<ServerCapabilities>> {
+        self.capabilities
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Returns a new `SharedDatabaseSession` for the system database.
+    pub fn use_system_database(&self) -> SharedDatabaseSession<C> {
+        SharedDatabaseSession::new(SYSTEM_DATABASE.to_owned(), self.runtime.clone())
+    }
+
+    /// Returns a new `SharedDatabaseSession` for the given database name.
+    pub fn use_database_with_name<N>(&self, database_name: N) -> SharedDatabaseSession<C>
+    where
+        N: Into<String>,
+    {
+        SharedDatabaseSession::new(database_name.into(), self.runtime.clone())
+    }
+
+    /// Creates a new database with the given attributes.
+    ///
+    /// If the database could be created successfully a
+    /// `SharedDatabaseSession` using the just created database is returned.
+    pub fn create_database<N, E>(
+        &self,
+        name: N,
+        users: Vec<NewUser<E>>,
+    ) -> Result<SharedDatabaseSession<C>>
+    where
+        N: Into<String>,
+        E: 'static + UserExtra + Serialize + Send,
+    {
+        let database_name = name.into();
+        self.execute(CreateDatabase::with_name_for_users(
+            database_name.clone(),
+            users,
+        ))
+        .map(|_| SharedDatabaseSession::new(database_name, self.runtime.clone()))
+    }
+
+    /// Drops an existing database with the given name.
+    ///
+    /// Returns true if the database has been dropped successfully.
+    pub fn drop_database<N>(&self, name: N) -> Result<bool>
+    where
+        N: Into<String>,
+    {
+        self.execute(DropDatabase::with_name(name))
+    }
+
+    /// Retrieves a list of all existing databases.
+    pub fn list_databases(&self) -> Result<Vec<String>> {
+        self.execute(ListDatabases::new())
+    }
+
+    /// Retrieves a list of all databases the current user has access to.
+    pub fn list_accessible_databases(&self) -> Result<Vec<String>> {
+        self.execute(ListAccessibleDatabases::new())
+    }
+}
+
+/// A session for operating with a specific database that can be shared
+/// between threads.
+///
+/// A `SharedDatabaseSession` is obtained from a `SharedArangoSession`. It
+/// implements `Send` and `Sync` and is cheap to clone. See the documentation
+/// of the `SharedArangoSession` for details about how method calls are
+/// executed.
+///
+/// Operations that are not provided as a function of this session can be
+/// executed by passing the method to the `execute()` function.
+#[derive(Debug)]
+pub struct SharedDatabaseSession<C> {
+    database_name: String,
+    runtime: Arc<SessionRuntime<C>>,
+}
+
+impl<C> Clone for SharedDatabaseSession<C> {
+    fn clone(&self) -> Self {
+        SharedDatabaseSession {
+            database_name: self.database_name.clone(),
+            runtime: self.runtime.clone(),
+        }
+    }
+}
+
+impl<C> SharedDatabaseSession<C>
+where
+    C: 'static + Connector,
+{
+    fn new(database_name: String, runtime: Arc<SessionRuntime<C>>) -> Self {
+        SharedDatabaseSession {
+            database_name,
+            runtime,
+        }
+    }
+
+    /// Executes an API method applied to the database of this session.
+    pub fn execute<M>(&self, method: M) -> Result<<M as Method>::Result>
+    where
+        M: 'static + Method + Prepare + Send,
+        <M as Method>::Result: Send,
+    {
+        self.runtime.execute(Some(&self.database_name), method)
+    }
+
+    /// Returns the name of the database this `SharedDatabaseSession` operates
+    /// with.
+    pub fn name(&self) -> &str {
+        &self.database_name
+    }
+
+    /// Unwraps the database name out of this session.
+    pub fn unwrap(self) -> String {
+        self.database_name
+    }
+
+    /// Drops the database that is used in this session.
+    ///
+    /// Returns true if the database has been dropped successfully.
+    ///
+    /// After calling this function all clones of this `SharedDatabaseSession`
+    /// are no longer valid.
+    pub fn drop(self) -> Result<bool> {
+        self.runtime
+            .execute(None, DropDatabase::new(self.database_name))
+    }
+
+    /// Executes a query and returns a cursor with the first result set.
+    ///
+    /// All cursor options and query execution options are left to their default
+    /// settings.
+    ///
+    /// To specify cursor options and/or query execution options use the
+    /// `query_opt(&self, NewCursor)` function.
+    pub fn query<T>(&self, query: Query) -> Result<Cursor<T>>
+    where
+        T: 'static + DeserializeOwned + Send,
+    {
+        self.execute(CreateCursor::from_query(query))
+    }
+
+    /// Executes a query and returns a cursor with the first result set.
+    ///
+    /// It requires a `NewCursor` struct as a parameter which allows full
+    /// control over all supported cursor options and query execution options.
+    ///
+    /// To execute a query with all options left at their defaults the
+    /// `query(&self, Query)` function might be more suitable.
+    pub fn query_opt<T>(&self, new_cursor: NewCursor) -> Result<Cursor<T>>
+    where
+        T: 'static + DeserializeOwned + Send,
+    {
+        self.execute(CreateCursor::new(new_cursor))
+    }
+
+    /// Checks whether the given cursor has more results and if yes fetches
+    /// a cursor with the next batch of results.
+    ///
+    /// This function returns `None` if there are no more results for the
+    /// cursor. It returns `Some(Error)` if fetching the next batch of results
+    /// fails.
+    pub fn next_cursor<T>(&self, cursor: &Cursor<T>) -> Option<Result<Cursor<T>>>
+    where
+        T: 'static + DeserializeOwned + Send,
+    {
+        cursor
+            .id()
+            .map(|id| self.execute(ReadNextBatchFromCursor::with_id_ref(id)))
+    }
+
+    /// Deletes the given cursor on the server if it is still existing
+    /// otherwise does nothing.
+    pub fn delete_cursor<T>(&self, cursor: &Cursor<T>) -> Result<Empty> {
+        match cursor.id() {
+            Some(id) => self.execute(DeleteCursor::with_id_ref(id)),
+            None => Ok(EMPTY),
+        }
+    }
+
+    /// Generates an execution plan for a query but does not execute it.
+    pub fn explain_query(&self, query: Query) -> Result<ExplainedQuery> {
+        self.execute(ExplainQuery::with_defaults(query))
+    }
+
+    /// Parses a query a validates the syntax but does not execute it.
+    ///
+    /// If the query can be parsed without error the abstract syntax tree (AST)
+    /// of the query is returned.
+    pub fn parse_query<Q>(&self, query: Q) -> Result<ParsedQuery>
+    where
+        Q: Into<String>,
+    {
+        self.execute(ParseQuery::from_query(query.into()))
+    }
+
+    /// Fetch the document with the given id from the database of this session.
+    pub fn get_document<T>(&self, id: DocumentId) -> Result<Document<T>>
+    where
+        T: 'static + DeserializeOwned + Send,
+    {
+        self.execute(GetDocument::with_id(id))
+    }
+
+    /// Executes a JavaScript transaction and returns the result of its action
+    /// function.
+    pub fn execute_transaction<T>(&self, new_transaction: NewTransaction) -> Result<T>
+    where
+        T: 'static + DeserializeOwned + Send,
+    {
+        self.execute(ExecuteTransaction::new(new_transaction))
+    }
+
+    /// Creates a new collection within the database of this session.
+    pub fn create_collection<N>(&self, collection_name: N) -> Result<Collection>
+    where
+        N: Into<String>,
+    {
+        self.execute(CreateCollection::with_name(collection_name))
+            .map(Collection::from)
+    }
+
+    /// Drops the collection with the given name from the database of this
+    /// session and returns the identifier of the dropped collection.
+    pub fn drop_collection<N>(&self, collection_name: N) -> Result<String>
+    where
+        N: Into<String>,
+    {
+        self.execute(DropCollection::with_name(collection_name))
+    }
+
+    /// Fetches a list of all collections in this database.
+    ///
+    /// System collections are not included in the returned list.
+    pub fn list_collections(&self) -> Result<Vec<Collection>> {
+        self.execute(ListCollections::new())
+    }
+
+    /// Fetches a list of all graphs in this database.
+    pub fn list_graphs(&self) -> Result<Vec<Graph>> {
+        self.execute(ListGraphs::new())
+    }
+}
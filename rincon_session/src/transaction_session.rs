@@ -0,0 +1,264 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::thread;
+
+use serde::de::DeserializeOwned;
+use tokio_core::reactor::Core;
+
+use rincon_client::collection::types::Collection;
+use rincon_client::cursor::methods::CreateCursor;
+use rincon_client::cursor::types::NewCursor;
+use rincon_client::document::methods::GetDocument;
+use rincon_client::document::types::{Document, DocumentId};
+use rincon_client::transaction::methods::{
+    AbortTransaction, CommitTransaction, GetTransactionStatus, InTransaction,
+};
+use rincon_client::transaction::types::{TransactionInfo, TransactionStatus};
+use rincon_core::api::connector::{Connector, Error, Execute, FutureResult};
+use rincon_core::api::method::{Method, Prepare};
+use rincon_core::api::query::Query;
+use rincon_core::api::types::Entity;
+
+use super::Result;
+use collection_session::CollectionSession;
+use cursor_session::CursorSession;
+
+/// A session for executing method calls within a stream transaction.
+///
+/// A `TransactionSession` is obtained by calling the `begin_transaction`
+/// function of a `DatabaseSession`. The transaction is finished by calling
+/// either `commit()` or `abort()`.
+///
+/// If a `TransactionSession` is dropped without being finished explicitly the
+/// transaction is aborted. If commit on drop is activated by calling
+/// `set_commit_on_drop(true)` the transaction is committed instead, unless
+/// the current thread is panicking.
+#[derive(Debug)]
+pub struct TransactionSession<C>
+where
+    C: 'static + Connector,
+{
+    transaction_id: String,
+    database_name: String,
+    commit_on_drop: bool,
+    finished: bool,
+    connector: Rc<C>,
+    core: Rc<RefCell<Core>>,
+}
+
+impl<C> TransactionSession<C>
+where
+    C: 'static + Connector,
+{
+    /// Instantiates a new `TransactionSession` for the stream transaction
+    /// with the given id.
+    pub(crate) fn new(
+        transaction_id: String,
+        database_name: String,
+        connector: Rc<C>,
+        core: Rc<RefCell<Core>>,
+    ) -> Self {
+        TransactionSession {
+            transaction_id,
+            database_name,
+            commit_on_drop: false,
+            finished: false,
+            connector,
+            core,
+        }
+    }
+
+    /// Executes an API method within the transaction of this session.
+    ///
+    /// ArangoDB supports document, cursor and index methods to be executed
+    /// within a stream transaction.
+    pub fn execute<M>(&self, method: M) -> Result<<M as Method>::Result>
+    where
+        M: 'static + Method + Prepare,
+    {
+        self.execute_outside(InTransaction::new(self.transaction_id.as_str(), method))
+    }
+
+    /// Executes an API method applied to the database of this session without
+    /// binding it to the transaction.
+    fn execute_outside<M>(&self, method: M) -> Result<<M as Method>::Result>
+    where
+        M: 'static + Method + Prepare,
+    {
+        self.core.borrow_mut().run(
+            self.connector
+                .connection(&self.database_name)
+                .execute(method),
+        )
+    }
+
+    /// Returns the id of the transaction of this session.
+    pub fn id(&self) -> &str {
+        &self.transaction_id
+    }
+
+    /// Returns the name of the database the transaction is running in.
+    pub fn database_name(&self) -> &str {
+        &self.database_name
+    }
+
+    /// Sets whether the transaction shall be committed when this session is
+    /// dropped without being finished explicitly.
+    ///
+    /// By default the transaction is aborted on drop.
+    pub fn set_commit_on_drop(&mut self, commit_on_drop: bool) {
+        self.commit_on_drop = commit_on_drop;
+    }
+
+    /// Returns whether the transaction is committed when this session is
+    /// dropped without being finished explicitly.
+    pub fn is_commit_on_drop(&self) -> bool {
+        self.commit_on_drop
+    }
+
+    /// Fetches the current status of the transaction from the server.
+    pub fn status(&self) -> Result<TransactionStatus> {
+        self.execute_outside(GetTransactionStatus::with_id(self.transaction_id.as_str()))
+            .map(|info| info.status())
+    }
+
+    /// Commits the transaction of this session.
+    ///
+    /// After calling this function the associated `TransactionSession` is no
+    /// longer valid.
+    pub fn commit(mut self) -> Result<TransactionStatus> {
+        self.finish(CommitTransaction::with_id(self.transaction_id.as_str()))
+    }
+
+    /// Aborts the transaction of this session and rolls back all changes
+    /// made within the transaction.
+    ///
+    /// After calling this function the associated `TransactionSession` is no
+    /// longer valid.
+    pub fn abort(mut self) -> Result<TransactionStatus> {
+        self.finish(AbortTransaction::with_id(self.transaction_id.as_str()))
+    }
+
+    /// Finishes the transaction by executing the given method.
+    ///
+    /// The transaction is only marked as finished if the server has answered
+    /// the request for this transaction. If the request failed for any other
+    /// reason, like a communication error, the transaction is still aborted
+    /// when this session is dropped.
+    fn finish<M>(&mut self, method: M) -> Result<TransactionStatus>
+    where
+        M: 'static + Method<Result = TransactionInfo> + Prepare,
+    {
+        let result = self.execute_outside(method).map(|info| info.status());
+        self.finished = match result {
+            Ok(_) => true,
+            Err(Error::Method(ref error)) => error.status_code() < 500,
+            Err(_) => false,
+        };
+        result
+    }
+
+    /// Executes a query within the transaction and returns a cursor with the
+    /// first result set.
+    ///
+    /// All cursor options and query execution options are left to their default
+    /// settings.
+    pub fn query<T>(&self, query: Query) -> Result<CursorSession<T, C>>
+    where
+        T: 'static + DeserializeOwned,
+    {
+        self.query_opt(NewCursor::from(query))
+    }
+
+    /// Executes a query within the transaction and returns a cursor with the
+    /// first result set.
+    ///
+    /// It requires a `NewCursor` struct as a parameter which allows full
+    /// control over all supported cursor options and query execution options.
+    pub fn query_opt<T>(&self, new_cursor: NewCursor) -> Result<CursorSession<T, C>>
+    where
+        T: 'static + DeserializeOwned,
+    {
+        self.execute(CreateCursor::new(new_cursor)).map(|cursor| {
+            CursorSession::new(
+                cursor,
+                self.database_name.clone(),
+                self.connector.clone(),
+                self.core.clone(),
+            ).with_transaction_id(self.transaction_id.clone())
+        })
+    }
+
+    /// Fetches the document with the given id within the transaction.
+    pub fn get_document<T>(&self, id: DocumentId) -> Result<Document<T>>
+    where
+        T: 'static + DeserializeOwned,
+    {
+        self.execute(GetDocument::with_id(id))
+    }
+
+    /// Returns a new `CollectionSession` for the collection with the given
+    /// name whose method calls are executed within the transaction.
+    pub fn use_collection_with_name<N>(&self, collection_name: N) -> CollectionSession<C>
+    where
+        N: Into<String>,
+    {
+        CollectionSession::new(
+            Entity::Name(collection_name.into()),
+            self.database_name.clone(),
+            self.connector.clone(),
+            self.core.clone(),
+        ).with_transaction_id(self.transaction_id.clone())
+    }
+
+    /// Returns a new `CollectionSession` for the given collection whose
+    /// method calls are executed within the transaction.
+    pub fn use_collection(&self, collection: Collection) -> CollectionSession<C> {
+        CollectionSession::new(
+            Entity::Object(collection),
+            self.database_name.clone(),
+            self.connector.clone(),
+            self.core.clone(),
+        ).with_transaction_id(self.transaction_id.clone())
+    }
+}
+
+impl<C> Drop for TransactionSession<C>
+where
+    C: 'static + Connector,
+{
+    fn drop(&mut self) {
+        if self.finished {
+            return;
+        }
+        let transaction_id = self.transaction_id.clone();
+        let connection = self.connector.connection(&self.database_name);
+        let future = if self.commit_on_drop && !thread::panicking() {
+            connection.execute(CommitTransaction::new(transaction_id))
+        } else {
+            connection.execute(AbortTransaction::new(transaction_id))
+        };
+        if let Ok(mut core) = self.core.try_borrow_mut() {
+            let _ = core.run(future);
+        }
+    }
+}
+
+/// Executes the given method on the given connection.
+///
+/// If a transaction id is given the method is executed within that stream
+/// transaction.
+pub(crate) fn execute_in_transaction<E, M>(
+    connection: &E,
+    transaction_id: Option<&str>,
+    method: M,
+) -> FutureResult<M>
+where
+    E: Execute,
+    M: 'static + Method + Prepare,
+{
+    match transaction_id {
+        Some(transaction_id) => connection.execute(InTransaction::new(transaction_id, method)),
+        None => connection.execute(method),
+    }
+}
@@ -36,7 +36,6 @@ fn create_collection() {
     });
 }
 
-// this test also tests whether the propagation of crate features is working
 #[test]
 fn get_collection_properties() {
     arango_session_test_with_user_db("socius11", "the_social_network11", |connector, core| {
@@ -52,12 +51,11 @@ fn get_collection_properties() {
         expect_that!(&properties.status(), eq(CollectionStatus::Loaded));
         expect_that!(&properties.is_wait_for_sync(), eq(false));
 
-        #[cfg(feature = "mmfiles")]
-        {
-            expect_that!(&properties.is_volatile(), eq(false));
-            expect_that!(&properties.is_do_compact(), eq(true));
-            expect_that!(&properties.index_buckets(), gt(0));
-            expect_that!(&properties.journal_size(), gt(0));
+        if arango.server_capabilities().unwrap().is_mmfiles() {
+            expect_that!(&properties.is_volatile(), eq(Some(false)));
+            expect_that!(&properties.is_do_compact(), eq(Some(true)));
+            expect_that!(&properties.index_buckets().unwrap(), gt(0));
+            expect_that!(&properties.journal_size().unwrap(), gt(0));
         }
     });
 }
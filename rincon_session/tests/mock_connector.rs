@@ -0,0 +1,559 @@
+#[macro_use]
+extern crate galvanic_assert;
+#[macro_use]
+extern crate serde_derive;
+
+#[macro_use]
+extern crate serde_json;
+extern crate tokio_core;
+
+extern crate rincon_connector;
+extern crate rincon_core;
+extern crate rincon_session;
+
+use galvanic_assert::matchers::*;
+use tokio_core::reactor::Core;
+
+use rincon_connector::mock::{FakeArangoDb, MockConnector, Request, Response};
+use rincon_core::api::connector::Error;
+use rincon_core::api::method::Operation;
+use rincon_core::api::query::Query;
+use rincon_core::api::ErrorCode;
+use rincon_session::client::*;
+use rincon_session::*;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Person {
+    name: String,
+    age: u16,
+}
+
+#[test]
+fn collection_session_on_fake_arango_db() {
+    let connector = MockConnector::with_responder(FakeArangoDb::new());
+    let arango = ArangoSession::new(connector, Core::new().unwrap());
+    let database = arango.use_database_with_name("the_social_network");
+
+    let people = database.create_collection("people").unwrap();
+    if let Some(collection) = people.entity() {
+        expect_that!(&collection.name(), eq("people"));
+        expect_that!(&collection.kind(), eq(CollectionType::Documents));
+    } else {
+        panic!("DatabaseSession::create_collection did not return Entity::Object");
+    }
+
+    let jane = Person {
+        name: "Jane Doe".to_owned(),
+        age: 42,
+    };
+    let header = people
+        .insert_document(NewDocument::from_content(jane.clone()).with_key(DocumentKey::new("jane")))
+        .unwrap();
+    expect_that!(&header.id().collection_name(), eq("people"));
+    expect_that!(&header.id().document_key(), eq("jane"));
+
+    let document = people
+        .get_document::<Person>(DocumentKey::new("jane"))
+        .unwrap();
+    expect_that!(document.content(), eq(jane.clone()));
+
+    let cursor = database
+        .query::<Person>(Query::new("FOR p IN people RETURN p"))
+        .unwrap();
+    expect_that!(&cursor.result(), eq(&[jane][..]));
+
+    let not_found = people.get_document::<Person>(DocumentKey::new("john"));
+    match not_found {
+        Err(Error::Method(ref error)) => {
+            expect_that!(&error.error_code(), eq(ErrorCode::ArangoDocumentNotFound))
+        },
+        _ => panic!("Expected document not found, but got: {:?}", not_found),
+    }
+}
+
+fn begin_transaction_without_finish_response(
+    connector: &MockConnector,
+) -> TransactionSession<MockConnector> {
+    connector.push_response(Response::ok(json!({
+        "result": { "id": "1234", "status": "running" },
+        "error": false,
+        "code": 201,
+    })));
+    let arango = ArangoSession::new(connector.clone(), Core::new().unwrap());
+    arango
+        .use_database_with_name("the_social_network")
+        .begin_transaction(TransactionCollections::new().with_write(vec!["people"]))
+        .unwrap()
+}
+
+fn begin_transaction_on_mock_connector(
+    connector: &MockConnector,
+) -> TransactionSession<MockConnector> {
+    let transaction = begin_transaction_without_finish_response(connector);
+    connector.push_response(Response::ok(json!({
+        "result": { "id": "1234", "status": "aborted" },
+        "error": false,
+        "code": 200,
+    })));
+    transaction
+}
+
+#[test]
+fn transaction_session_aborts_transaction_when_dropped() {
+    let connector = MockConnector::new();
+    let transaction = begin_transaction_on_mock_connector(&connector);
+
+    drop(transaction);
+
+    let requests = connector.requests();
+    expect_that!(&requests.len(), eq(2));
+    expect_that!(&requests[1].operation(), eq(Operation::Delete));
+    expect_that!(&requests[1].path(), eq("/_api/transaction/1234"));
+}
+
+#[test]
+fn transaction_session_commits_transaction_when_dropped_with_commit_on_drop() {
+    let connector = MockConnector::new();
+    let mut transaction = begin_transaction_on_mock_connector(&connector);
+    transaction.set_commit_on_drop(true);
+
+    drop(transaction);
+
+    let requests = connector.requests();
+    expect_that!(&requests.len(), eq(2));
+    expect_that!(&requests[1].operation(), eq(Operation::Replace));
+    expect_that!(&requests[1].path(), eq("/_api/transaction/1234"));
+}
+
+#[test]
+fn transaction_session_does_not_finish_transaction_twice_when_dropped() {
+    let connector = MockConnector::new();
+    let transaction = begin_transaction_on_mock_connector(&connector);
+
+    transaction.abort().unwrap();
+
+    let requests = connector.requests();
+    expect_that!(&requests.len(), eq(2));
+    expect_that!(&requests[1].operation(), eq(Operation::Delete));
+}
+
+#[test]
+fn transaction_session_aborts_transaction_when_commit_fails_to_communicate() {
+    let connector = MockConnector::new();
+    let transaction = begin_transaction_without_finish_response(&connector);
+
+    let result = transaction.commit();
+
+    match result {
+        Err(Error::Communication(_)) => {},
+        _ => panic!("Expected communication error, but got: {:?}", result),
+    }
+    let requests = connector.requests();
+    expect_that!(&requests.len(), eq(3));
+    expect_that!(&requests[1].operation(), eq(Operation::Replace));
+    expect_that!(&requests[2].operation(), eq(Operation::Delete));
+    expect_that!(&requests[2].path(), eq("/_api/transaction/1234"));
+}
+
+#[test]
+fn transaction_session_does_not_abort_transaction_when_server_rejects_commit() {
+    let connector = MockConnector::new();
+    let transaction = begin_transaction_without_finish_response(&connector);
+    connector.push_response(Response::error(
+        410,
+        ErrorCode::TransactionAborted,
+        "transaction aborted",
+    ));
+
+    let result = transaction.commit();
+
+    match result {
+        Err(Error::Method(ref error)) => {
+            expect_that!(&error.error_code(), eq(ErrorCode::TransactionAborted))
+        },
+        _ => panic!("Expected transaction aborted, but got: {:?}", result),
+    }
+    let requests = connector.requests();
+    expect_that!(&requests.len(), eq(2));
+    expect_that!(&requests[1].operation(), eq(Operation::Replace));
+}
+
+#[test]
+fn collection_session_sends_expected_requests() {
+    let connector = MockConnector::new();
+    connector.push_response(Response::ok(json!({
+        "_id": "people/1",
+        "_key": "1",
+        "_rev": "_WkyoIaj--_",
+    })));
+    let arango = ArangoSession::new(connector.clone(), Core::new().unwrap());
+    let people = arango
+        .use_database_with_name("the_social_network")
+        .use_collection_with_name("people");
+
+    people
+        .insert_document_synced(Person {
+            name: "John Doe".to_owned(),
+            age: 27,
+        })
+        .unwrap();
+
+    let requests = connector.requests();
+    expect_that!(&requests.len(), eq(1));
+    expect_that!(&requests[0].database(), eq("the_social_network"));
+    expect_that!(&requests[0].operation(), eq(Operation::Create));
+    expect_that!(&requests[0].path(), eq("/_api/document/people"));
+    expect_that!(
+        &requests[0].content().map(ToString::to_string),
+        eq(Some(r#"{"age":27,"name":"John Doe"}"#.to_owned()))
+    );
+}
+
+#[test]
+fn arango_session_caches_server_capabilities() {
+    let connector = MockConnector::new();
+    connector.push_response(Response::ok(json!({
+        "server": "arango",
+        "version": "3.3.9",
+        "license": "enterprise",
+    })));
+    connector.push_response(Response::ok(json!({
+        "name": "rocksdb",
+        "supports": {
+            "dfdb": false,
+            "indexes": ["primary", "edge", "hash", "skiplist", "persistent"],
+        },
+    })));
+    connector.push_response(Response::ok(json!({
+        "role": "COORDINATOR",
+        "error": false,
+        "code": 200,
+    })));
+    let arango = ArangoSession::new(connector.clone(), Core::new().unwrap());
+
+    let capabilities = arango.server_capabilities().unwrap();
+    expect_that!(&capabilities.role(), eq(ServerRole::Coordinator));
+    expect_that!(&capabilities.is_cluster(), eq(true));
+    expect_that!(&capabilities.is_enterprise(), eq(true));
+    expect_that!(&capabilities.is_rocksdb(), eq(true));
+    expect_that!(&capabilities.is_mmfiles(), eq(false));
+
+    let cached = arango.server_capabilities().unwrap();
+    expect_that!(&cached, eq(capabilities));
+
+    let requests = connector.requests();
+    expect_that!(&requests.len(), eq(3));
+    expect_that!(&requests[0].path(), eq("/_api/version"));
+    expect_that!(&requests[1].path(), eq("/_api/engine"));
+    expect_that!(&requests[2].path(), eq("/_admin/server/role"));
+}
+
+#[test]
+fn collection_session_counts_and_truncates_collection_on_fake_arango_db() {
+    let connector = MockConnector::with_responder(
+        FakeArangoDb::new().with_collection("the_social_network", "people"),
+    );
+    let arango = ArangoSession::new(connector, Core::new().unwrap());
+    let people = arango
+        .use_database_with_name("the_social_network")
+        .use_collection_with_name("people");
+    for name in &["Jane Doe", "John Doe"] {
+        people
+            .insert_document_synced(Person {
+                name: (*name).to_owned(),
+                age: 42,
+            })
+            .unwrap();
+    }
+
+    expect_that!(&people.count().unwrap(), eq(2));
+
+    let truncated = people.truncate().unwrap();
+    expect_that!(&truncated.name(), eq("people"));
+    expect_that!(&truncated.kind(), eq(CollectionType::Documents));
+    expect_that!(&people.count().unwrap(), eq(0));
+}
+
+#[test]
+fn collection_session_fails_to_count_unknown_collection() {
+    let connector = MockConnector::with_responder(FakeArangoDb::new());
+    let arango = ArangoSession::new(connector, Core::new().unwrap());
+    let unknown = arango
+        .use_database_with_name("the_social_network")
+        .use_collection_with_name("unknown");
+
+    let result = unknown.count();
+
+    match result {
+        Err(Error::Method(ref error)) => {
+            expect_that!(&error.status_code(), eq(404));
+            expect_that!(&error.error_code(), eq(ErrorCode::ArangoCollectionNotFound));
+        },
+        _ => panic!("Expected collection not found, but got: {:?}", result),
+    }
+}
+
+const CELSIUS_TO_FAHRENHEIT: &str = "myfunctions::temperature::celsius_to_fahrenheit";
+
+const CELSIUS_TO_FAHRENHEIT_CODE: &str = "function (celsius) { return celsius * 1.8 + 32; }";
+
+/// Ensures the given function on a mock server that has the given functions
+/// registered.
+///
+/// Returns the result of `ensure_aql_function` and the recorded requests.
+fn ensure_aql_function_on_mock_connector(
+    functions: serde_json::Value,
+    function: NewAqlFunction,
+) -> (bool, Vec<Request>) {
+    let connector = MockConnector::new();
+    connector.push_response(Response::ok(json!({
+        "result": functions,
+        "error": false,
+        "code": 200,
+    })));
+    connector.push_response(Response::new(
+        201,
+        json!({
+            "isNewlyCreated": false,
+            "error": false,
+            "code": 201,
+        }),
+    ));
+    let arango = ArangoSession::new(connector.clone(), Core::new().unwrap());
+    let database = arango.use_database_with_name("the_social_network");
+
+    let registered = database.ensure_aql_function(function).unwrap();
+
+    (registered, connector.requests())
+}
+
+#[test]
+fn database_session_ensure_aql_function_creates_missing_function() {
+    let function = NewAqlFunction::new(CELSIUS_TO_FAHRENHEIT, CELSIUS_TO_FAHRENHEIT_CODE);
+
+    let (registered, requests) = ensure_aql_function_on_mock_connector(json!([]), function);
+
+    expect_that!(&registered, eq(true));
+    expect_that!(&requests.len(), eq(2));
+    expect_that!(&requests[1].operation(), eq(Operation::Create));
+    expect_that!(
+        &requests[1].content().map(|content| content["code"].clone()),
+        eq(Some(json!(CELSIUS_TO_FAHRENHEIT_CODE)))
+    );
+}
+
+#[test]
+fn database_session_ensure_aql_function_keeps_function_with_same_code() {
+    let function = NewAqlFunction::new(CELSIUS_TO_FAHRENHEIT, CELSIUS_TO_FAHRENHEIT_CODE);
+
+    let (registered, requests) = ensure_aql_function_on_mock_connector(
+        json!([{
+            "name": CELSIUS_TO_FAHRENHEIT,
+            "code": CELSIUS_TO_FAHRENHEIT_CODE,
+        }]),
+        function,
+    );
+
+    expect_that!(&registered, eq(false));
+    expect_that!(&requests.len(), eq(1));
+}
+
+#[test]
+fn database_session_ensure_aql_function_keeps_function_with_same_determinism() {
+    let mut function = NewAqlFunction::new(CELSIUS_TO_FAHRENHEIT, CELSIUS_TO_FAHRENHEIT_CODE);
+    function.set_deterministic(true);
+
+    let (registered, requests) = ensure_aql_function_on_mock_connector(
+        json!([{
+            "name": CELSIUS_TO_FAHRENHEIT,
+            "code": CELSIUS_TO_FAHRENHEIT_CODE,
+            "isDeterministic": true,
+        }]),
+        function,
+    );
+
+    expect_that!(&registered, eq(false));
+    expect_that!(&requests.len(), eq(1));
+}
+
+#[test]
+fn database_session_ensure_aql_function_recreates_function_with_changed_code() {
+    let function = NewAqlFunction::new(CELSIUS_TO_FAHRENHEIT, CELSIUS_TO_FAHRENHEIT_CODE);
+
+    let (registered, requests) = ensure_aql_function_on_mock_connector(
+        json!([{
+            "name": CELSIUS_TO_FAHRENHEIT,
+            "code": "function (celsius) { return celsius * 2 + 30; }",
+        }]),
+        function,
+    );
+
+    expect_that!(&registered, eq(true));
+    expect_that!(&requests.len(), eq(2));
+    expect_that!(&requests[1].operation(), eq(Operation::Create));
+    expect_that!(
+        &requests[1].content().map(|content| content["code"].clone()),
+        eq(Some(json!(CELSIUS_TO_FAHRENHEIT_CODE)))
+    );
+}
+
+#[test]
+fn database_session_ensure_aql_function_recreates_function_with_changed_determinism() {
+    let function = NewAqlFunction::new(CELSIUS_TO_FAHRENHEIT, CELSIUS_TO_FAHRENHEIT_CODE);
+
+    let (registered, requests) = ensure_aql_function_on_mock_connector(
+        json!([{
+            "name": CELSIUS_TO_FAHRENHEIT,
+            "code": CELSIUS_TO_FAHRENHEIT_CODE,
+            "isDeterministic": true,
+        }]),
+        function,
+    );
+
+    expect_that!(&registered, eq(true));
+    expect_that!(&requests.len(), eq(2));
+    expect_that!(&requests[1].operation(), eq(Operation::Create));
+}
+
+#[test]
+fn database_session_decodes_running_queries() {
+    let connector = MockConnector::new();
+    connector.push_response(Response::ok(json!([
+        {
+            "id": "2981",
+            "query": "FOR p IN people FILTER p.age > @age RETURN p",
+            "bindVars": { "age": 42 },
+            "started": "2018-06-12T10:01:17Z",
+            "runTime": 125.5,
+            "state": "executing",
+        },
+        {
+            "id": "2982",
+            "query": "FOR p IN people RETURN p",
+            "bindVars": {},
+            "started": "2018-06-12T10:01:19Z",
+            "runTime": 0.25,
+            "state": "waiting for the moon",
+        }
+    ])));
+    let arango = ArangoSession::new(connector, Core::new().unwrap());
+    let database = arango.use_database_with_name("the_social_network");
+
+    let queries = database.list_current_queries().unwrap();
+
+    expect_that!(&queries.len(), eq(2));
+    expect_that!(&queries[0].id(), eq("2981"));
+    expect_that!(
+        &queries[0].query(),
+        eq("FOR p IN people FILTER p.age > @age RETURN p")
+    );
+    expect_that!(&queries[0].bind_vars()["age"], eq(json!(42)));
+    expect_that!(&queries[0].started(), eq("2018-06-12T10:01:17Z"));
+    expect_that!(&queries[0].run_time(), eq(125.5));
+    expect_that!(&queries[0].state(), eq(&QueryState::Executing));
+    expect_that!(&queries[1].bind_vars().is_empty(), eq(true));
+    expect_that!(
+        &queries[1].state(),
+        eq(&QueryState::Custom("waiting for the moon".to_owned()))
+    );
+}
+
+#[test]
+fn database_session_fails_to_kill_unknown_query() {
+    let connector = MockConnector::new();
+    connector.push_response(Response::error(
+        404,
+        ErrorCode::QueryNotFound,
+        "query with id 2981 not found",
+    ));
+    let arango = ArangoSession::new(connector, Core::new().unwrap());
+    let database = arango.use_database_with_name("the_social_network");
+
+    let result = database.kill_query("2981");
+
+    match result {
+        Err(Error::Method(ref error)) => {
+            expect_that!(&error.status_code(), eq(404));
+            expect_that!(&error.error_code(), eq(ErrorCode::QueryNotFound));
+        },
+        _ => panic!("Expected query not found, but got: {:?}", result),
+    }
+}
+
+#[test]
+fn database_session_decodes_query_cache_entries() {
+    let connector = MockConnector::new();
+    connector.push_response(Response::ok(json!([
+        {
+            "hash": "7254409358813924321",
+            "query": "FOR p IN people FILTER p.age > @age RETURN p.name",
+            "bindVars": { "age": 42 },
+            "size": 182,
+            "results": 3,
+            "started": "2018-06-12T10:01:17Z",
+            "hits": 5,
+            "runTime": 0.0021,
+            "dataSources": ["people"],
+        }
+    ])));
+    let arango = ArangoSession::new(connector, Core::new().unwrap());
+    let database = arango.use_database_with_name("the_social_network");
+
+    let entries = database.list_query_cache_entries().unwrap();
+
+    expect_that!(&entries.len(), eq(1));
+    expect_that!(&entries[0].hash(), eq("7254409358813924321"));
+    expect_that!(
+        &entries[0].query(),
+        eq("FOR p IN people FILTER p.age > @age RETURN p.name")
+    );
+    expect_that!(&entries[0].bind_vars()["age"], eq(json!(42)));
+    expect_that!(&entries[0].size(), eq(182));
+    expect_that!(&entries[0].results(), eq(3));
+    expect_that!(&entries[0].started(), eq("2018-06-12T10:01:17Z"));
+    expect_that!(&entries[0].hits(), eq(5));
+    expect_that!(&entries[0].run_time(), eq(0.0021));
+    expect_that!(&entries[0].data_sources(), eq(&["people".to_owned()][..]));
+}
+
+#[test]
+fn database_session_decodes_changed_query_cache_properties() {
+    let connector = MockConnector::new();
+    connector.push_response(Response::ok(json!({
+        "mode": "demand",
+        "maxResults": 256,
+        "error": false,
+        "code": 200,
+    })));
+    let arango = ArangoSession::new(connector, Core::new().unwrap());
+    let database = arango.use_database_with_name("the_social_network");
+    let mut updates = QueryCachePropertiesUpdate::new();
+    updates.set_mode(QueryCacheMode::Demand);
+
+    let properties = database.change_query_cache_properties(updates).unwrap();
+
+    expect_that!(&properties.mode(), eq(QueryCacheMode::Demand));
+    expect_that!(&properties.max_results(), eq(256));
+}
+
+#[test]
+fn database_session_fails_to_decode_unknown_query_cache_mode() {
+    let connector = MockConnector::new();
+    connector.push_response(Response::ok(json!({
+        "mode": "sometimes",
+        "maxResults": 128,
+        "error": false,
+        "code": 200,
+    })));
+    let arango = ArangoSession::new(connector, Core::new().unwrap());
+    let database = arango.use_database_with_name("the_social_network");
+
+    let result = database.get_query_cache_properties();
+
+    match result {
+        Err(Error::Deserialization(ref message)) => {
+            expect_that!(&message.contains("Unknown query cache mode"), eq(true));
+        },
+        _ => panic!("Expected deserialization error, but got: {:?}", result),
+    }
+}
@@ -0,0 +1,145 @@
+#[macro_use]
+extern crate galvanic_assert;
+#[macro_use]
+extern crate serde_json;
+
+extern crate rincon_connector;
+extern crate rincon_core;
+extern crate rincon_session;
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use galvanic_assert::matchers::*;
+
+use rincon_connector::mock::{FakeArangoDb, MockConnector, Request, Response};
+use rincon_core::api::connector::Error;
+use rincon_core::api::query::Query;
+use rincon_core::api::ErrorCode;
+use rincon_session::client::*;
+use rincon_session::*;
+
+fn shared_session_on_fake_arango_db() -> SharedArangoSession<MockConnector> {
+    SharedArangoSession::new(|_| {
+        Ok(MockConnector::with_responder(
+            FakeArangoDb::new()
+                .with_collection("the_social_network", "people")
+                .with_query_result(
+                    "FOR p IN people RETURN p.name",
+                    vec![json!("Jane"), json!("John"), json!("Mary")],
+                ),
+        ))
+    })
+    .unwrap()
+}
+
+#[test]
+fn shared_database_session_can_be_used_from_multiple_threads() {
+    let arango = shared_session_on_fake_arango_db();
+    let database = arango.use_database_with_name("the_social_network");
+
+    let workers: Vec<_> = (0..4)
+        .map(|_| {
+            let database = database.clone();
+            thread::spawn(move || {
+                database
+                    .query::<String>(Query::new("FOR p IN people RETURN p.name"))
+                    .map(|cursor| cursor.result().to_vec())
+            })
+        })
+        .collect();
+
+    for worker in workers {
+        let names = worker.join().unwrap().unwrap();
+        expect_that!(
+            &names,
+            eq(vec![
+                "Jane".to_owned(),
+                "John".to_owned(),
+                "Mary".to_owned()
+            ])
+        );
+    }
+}
+
+#[test]
+fn shared_database_session_fetches_next_cursor() {
+    let arango = shared_session_on_fake_arango_db();
+    let database = arango.use_database_with_name("the_social_network");
+    let mut new_cursor = NewCursor::from(Query::new("FOR p IN people RETURN p.name"));
+    new_cursor.set_batch_size(2);
+
+    let cursor = database.query_opt::<String>(new_cursor).unwrap();
+    expect_that!(
+        &cursor.result(),
+        eq(&["Jane".to_owned(), "John".to_owned()][..])
+    );
+    expect_that!(&cursor.has_more(), eq(true));
+
+    let next = database.next_cursor(&cursor).unwrap().unwrap();
+    expect_that!(&next.result(), eq(&["Mary".to_owned()][..]));
+    expect_that!(&next.has_more(), eq(false));
+    expect_that!(&database.next_cursor(&next).is_none(), eq(true));
+}
+
+#[test]
+fn shared_database_session_returns_errors_of_method_calls() {
+    let arango = shared_session_on_fake_arango_db();
+    let database = arango.use_database_with_name("the_social_network");
+
+    let collection = database.create_collection("friends").unwrap();
+    expect_that!(&collection.name(), eq("friends"));
+
+    let dropped = database.drop_collection("enemies");
+    match dropped {
+        Err(Error::Method(ref error)) => {
+            expect_that!(&error.error_code(), eq(ErrorCode::ArangoCollectionNotFound))
+        },
+        _ => panic!("Expected collection not found, but got: {:?}", dropped),
+    }
+}
+
+#[test]
+fn creating_shared_session_fails_if_connector_can_not_be_created() {
+    let result = SharedArangoSession::<MockConnector>::new(|_| {
+        Err(Error::Communication("no connector for you".to_owned()))
+    });
+
+    match result {
+        Err(Error::Communication(ref message)) => {
+            expect_that!(&message.as_str(), eq("no connector for you"))
+        },
+        _ => panic!("Expected communication error, but got: {:?}", result),
+    }
+}
+
+#[test]
+fn shared_session_fails_to_execute_method_on_its_runtime_thread() {
+    let session_slot = Arc::new(Mutex::new(None::<SharedArangoSession<MockConnector>>));
+    let nested_result = Arc::new(Mutex::new(None));
+    let responder_slot = session_slot.clone();
+    let responder_result = nested_result.clone();
+    let arango = SharedArangoSession::new(move |_| {
+        Ok(MockConnector::with_responder(move |_: &Request| {
+            let session = responder_slot.lock().unwrap().take();
+            if let Some(session) = session {
+                *responder_result.lock().unwrap() = Some(session.get_engine());
+            }
+            Response::ok(json!({
+                "server": "arango",
+                "version": "3.3.9",
+                "license": "community",
+            }))
+        }))
+    })
+    .unwrap();
+    *session_slot.lock().unwrap() = Some(arango.clone());
+
+    let version = arango.get_server_version().unwrap();
+
+    expect_that!(&version.version(), eq("3.3.9"));
+    match nested_result.lock().unwrap().take() {
+        Some(Err(Error::Communication(_))) => {},
+        other => panic!("Expected communication error, but got: {:?}", other),
+    }
+}
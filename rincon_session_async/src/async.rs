@@ -1,6 +1,10 @@
+use std::fmt::{self, Debug};
+use std::mem;
 use std::rc::Rc;
+use std::vec::IntoIter;
 
-use futures::Future;
+use futures::future::{self, Executor};
+use futures::{Async, Future, Poll, Stream};
 use serde::de::DeserializeOwned;
 use serde::ser::Serialize;
 
@@ -12,7 +16,7 @@ pub use rincon_core::api::connector::Error;
 pub use rincon_core::api::query::Query;
 pub use rincon_core::api::types::Empty;
 
-use rincon_client::cursor::methods::CreateCursor;
+use rincon_client::cursor::methods::{CreateCursor, DeleteCursor, ReadNextBatchFromCursor};
 use rincon_client::database::methods::{CreateDatabase, DropDatabase};
 use rincon_client::graph::methods::CreateGraph;
 use rincon_core::api::connector::{Connector, Execute};
@@ -20,34 +24,90 @@ use rincon_core::arango::protocol::SYSTEM_DATABASE;
 
 pub type FutureResult<T> = Box<Future<Item = T, Error = Error>>;
 
+/// The type of tasks a session runs in the background, like deleting cursors
+/// that have not been consumed completely.
+pub type BackgroundTask = Box<Future<Item = (), Error = ()>>;
+
+/// Executor for the background tasks of a session.
+///
+/// If no executor is set background tasks are not run at all.
+#[derive(Clone)]
+struct TaskExecutor(Option<Rc<Executor<BackgroundTask>>>);
+
+impl TaskExecutor {
+    fn spawn(&self, task: BackgroundTask) {
+        if let Some(ref executor) = self.0 {
+            //errors of background tasks can not be reported to anyone
+            let _ = executor.execute(task);
+        }
+    }
+}
+
+impl Debug for TaskExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("TaskExecutor")
+    }
+}
+
 #[derive(Debug)]
 pub struct ArangoSession<C> {
     connector: Rc<C>,
+    executor: TaskExecutor,
 }
 
 impl<C> ArangoSession<C>
 where
     C: 'static + Connector,
 {
+    /// Instantiates a new `ArangoSession` without an executor for background
+    /// tasks.
+    ///
+    /// A session without executor does not delete cursors of `CursorStream`s
+    /// that are dropped before all results have been consumed. Such cursors
+    /// are removed by the server when their time to live expires.
     pub fn new(connector: C) -> Self {
         ArangoSession {
             connector: Rc::new(connector),
+            executor: TaskExecutor(None),
         }
     }
 
-    pub fn close(self) {
-        //TODO see if a close() method has any purpose
+    /// Sets the executor that is used to run background tasks.
+    ///
+    /// Background tasks are used to delete cursors on the server when a
+    /// `CursorStream` is dropped before all results have been consumed. A
+    /// `tokio_core::reactor::Handle` can be used as executor.
+    pub fn with_executor<E>(mut self, executor: E) -> Self
+    where
+        E: 'static + Executor<BackgroundTask>,
+    {
+        self.executor = TaskExecutor(Some(Rc::new(executor)));
+        self
     }
 
+    /// Closes this session.
+    ///
+    /// The connector is dropped as soon as all `DatabaseSession`s and
+    /// `CursorStream`s created from this session are dropped as well.
+    pub fn close(self) {}
+
     pub fn use_system_database(&self) -> DatabaseSession<C> {
-        DatabaseSession::new(SYSTEM_DATABASE.to_owned(), self.connector.clone())
+        DatabaseSession::new(
+            SYSTEM_DATABASE.to_owned(),
+            self.connector.clone(),
+            self.executor.clone(),
+        )
     }
 
     pub fn use_database<DbName>(&self, database_name: DbName) -> DatabaseSession<C>
     where
         DbName: Into<String>,
     {
-        DatabaseSession::new(database_name.into(), self.connector.clone())
+        DatabaseSession::new(
+            database_name.into(),
+            self.connector.clone(),
+            self.executor.clone(),
+        )
     }
 
     pub fn create_database<UserInfo>(
@@ -58,12 +118,13 @@ where
         UserInfo: UserExtra + Serialize + 'static,
     {
         let connector = self.connector.clone();
+        let executor = self.executor.clone();
         let database_name = new_database.name().to_owned();
         Box::new(
             self.connector
                 .system_connection()
                 .execute(CreateDatabase::new(new_database))
-                .map(move |_| DatabaseSession::new(database_name, connector)),
+                .map(move |_| DatabaseSession::new(database_name, connector, executor)),
         )
     }
 }
@@ -72,16 +133,18 @@ where
 pub struct DatabaseSession<C> {
     database_name: String,
     connector: Rc<C>,
+    executor: TaskExecutor,
 }
 
 impl<C> DatabaseSession<C>
 where
     C: 'static + Connector,
 {
-    fn new(database_name: String, connector: Rc<C>) -> Self {
+    fn new(database_name: String, connector: Rc<C>, executor: TaskExecutor) -> Self {
         DatabaseSession {
             database_name,
             connector,
+            executor,
         }
     }
 
@@ -138,6 +201,42 @@ where
         )
     }
 
+    /// Executes a query and returns a `Stream` over all results of the query.
+    ///
+    /// The stream fetches the next batch of results from the server when all
+    /// results of the current batch have been consumed. Thus only one batch
+    /// of results is held in memory at a time.
+    ///
+    /// All cursor options and query execution options are left to their default
+    /// settings.
+    ///
+    /// To specify cursor options and/or query execution options use the
+    /// `query_stream_opt(&self, NewCursor)` function.
+    pub fn query_stream<T>(&self, query: Query) -> CursorStream<T, C>
+    where
+        T: 'static + DeserializeOwned,
+    {
+        self.query_stream_opt(query.into())
+    }
+
+    /// Executes a query and returns a `Stream` over all results of the query.
+    ///
+    /// It requires a `NewCursor` struct as a parameter which allows full
+    /// control over all supported cursor options and query execution options.
+    /// The batch size of the cursor determines how many results are fetched
+    /// from the server at once.
+    pub fn query_stream_opt<T>(&self, new_cursor: NewCursor) -> CursorStream<T, C>
+    where
+        T: 'static + DeserializeOwned,
+    {
+        CursorStream::new(
+            self.query_opt(new_cursor),
+            self.database_name.clone(),
+            self.connector.clone(),
+            self.executor.clone(),
+        )
+    }
+
     /// Creates a new graph in the database represented by this
     /// `DatabaseSession`.
     pub fn create_graph(&self, new_graph: NewGraph) -> FutureResult<GraphSession<C>> {
@@ -152,6 +251,164 @@ where
     }
 }
 
+/// A `Stream` over all results of a query.
+///
+/// If a `CursorStream` is dropped before all results have been consumed the
+/// cursor is deleted on the server by a background task that is run by the
+/// executor of the `ArangoSession`, if the session has one. This includes the case where the stream
+/// is dropped while the first batch of results is still being fetched.
+pub struct CursorStream<T, C>
+where
+    T: 'static,
+    C: 'static + Connector,
+{
+    state: CursorStreamState<T>,
+    cursor_id: Option<String>,
+    database_name: String,
+    connector: Rc<C>,
+    executor: TaskExecutor,
+}
+
+enum CursorStreamState<T> {
+    Fetching(FutureResult<Cursor<T>>),
+    Streaming(IntoIter<T>),
+    Done,
+}
+
+impl<T, C> CursorStream<T, C>
+where
+    T: 'static + DeserializeOwned,
+    C: 'static + Connector,
+{
+    fn new(
+        first_cursor: FutureResult<Cursor<T>>,
+        database_name: String,
+        connector: Rc<C>,
+        executor: TaskExecutor,
+    ) -> Self {
+        CursorStream {
+            state: CursorStreamState::Fetching(first_cursor),
+            cursor_id: None,
+            database_name,
+            connector,
+            executor,
+        }
+    }
+
+    /// Returns the name of the database the query is executed for.
+    pub fn database_name(&self) -> &str {
+        &self.database_name
+    }
+
+    /// Returns the id of the cursor on the server if there are more results
+    /// to be fetched.
+    pub fn cursor_id(&self) -> Option<&String> {
+        self.cursor_id.as_ref()
+    }
+}
+
+impl<T, C> Stream for CursorStream<T, C>
+where
+    T: 'static + DeserializeOwned,
+    C: 'static + Connector,
+{
+    type Item = T;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+        use self::CursorStreamState::*;
+        loop {
+            match mem::replace(&mut self.state, Done) {
+                Fetching(mut future) => match future.poll()? {
+                    Async::Ready(cursor) => {
+                        let has_more = cursor.has_more();
+                        let (cursor_id, _, result) = cursor.unwrap();
+                        self.cursor_id = if has_more { cursor_id } else { None };
+                        self.state = Streaming(result.into_iter());
+                    },
+                    Async::NotReady => {
+                        self.state = Fetching(future);
+                        return Ok(Async::NotReady);
+                    },
+                },
+                Streaming(mut batch) => {
+                    if let Some(next) = batch.next() {
+                        self.state = Streaming(batch);
+                        return Ok(Async::Ready(Some(next)));
+                    }
+                    if let Some(cursor_id) = self.cursor_id.clone() {
+                        self.state = Fetching(Box::new(
+                            self.connector
+                                .connection(&self.database_name)
+                                .execute(ReadNextBatchFromCursor::new(cursor_id)),
+                        ));
+                    }
+                },
+                Done => return Ok(Async::Ready(None)),
+            }
+        }
+    }
+}
+
+impl<T, C> Drop for CursorStream<T, C>
+where
+    T: 'static,
+    C: 'static + Connector,
+{
+    fn drop(&mut self) {
+        let cursor_id = self.cursor_id.take();
+        let connection = self.connector.connection(&self.database_name);
+        match mem::replace(&mut self.state, CursorStreamState::Done) {
+            CursorStreamState::Fetching(future) => {
+                //the cursor id is only known after the pending batch arrived
+                self.executor.spawn(Box::new(future.then(move |result| {
+                    let cursor_id = match result {
+                        Ok(ref cursor) if cursor.has_more() => cursor.id().cloned(),
+                        Ok(_) => None,
+                        Err(_) => cursor_id,
+                    };
+                    delete_cursor(&connection, cursor_id)
+                })));
+            },
+            CursorStreamState::Streaming(_) | CursorStreamState::Done => {
+                if cursor_id.is_some() {
+                    self.executor.spawn(delete_cursor(&connection, cursor_id));
+                }
+            },
+        }
+    }
+}
+
+fn delete_cursor<E>(connection: &E, cursor_id: Option<String>) -> BackgroundTask
+where
+    E: Execute,
+{
+    match cursor_id {
+        Some(cursor_id) => Box::new(
+            connection
+                .execute(DeleteCursor::new(cursor_id))
+                .map(|_| ())
+                .map_err(|_| ()),
+        ),
+        None => Box::new(future::ok(())),
+    }
+}
+
+impl<T, C> Debug for CursorStream<T, C>
+where
+    T: 'static,
+    C: 'static + Connector + Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CursorStream")
+            .field("cursor_id", &self.cursor_id)
+            .field("database_name", &self.database_name)
+            .field("connector", &self.connector)
+            .field("executor", &self.executor)
+            .finish()
+    }
+}
+
 #[derive(Debug)]
 pub struct GraphSession<C> {
     graph: Graph,
@@ -1,6 +1,7 @@
 #[macro_use]
 extern crate galvanic_assert;
 
+extern crate futures;
 extern crate tokio_core;
 
 extern crate rincon_client;
@@ -8,6 +9,7 @@ extern crate rincon_connector;
 extern crate rincon_session_async;
 extern crate rincon_test_helper;
 
+use futures::Stream;
 use galvanic_assert::matchers::*;
 
 use rincon_session_async::*;
@@ -39,3 +41,33 @@ fn create_graph() {
         assert_that!(&graph_session.graph().name(), eq("social"));
     });
 }
+
+#[test]
+fn query_stream_fetches_all_batches() {
+    arango_session_test_with_user_db("socius11", "the_social_network11", |connector, mut core| {
+        let arango = ArangoSession::new(connector);
+        let database = arango.use_database("the_social_network11");
+
+        let mut new_cursor = NewCursor::from(Query::new("FOR i IN 1..25 RETURN i"));
+        new_cursor.set_batch_size(10);
+        let stream = database.query_stream_opt::<u32>(new_cursor);
+        let results = core.run(stream.collect()).unwrap();
+
+        assert_that!(&results, eq((1..26).collect::<Vec<u32>>()));
+    });
+}
+
+#[test]
+fn query_stream_dropped_before_all_results_are_consumed() {
+    arango_session_test_with_user_db("socius12", "the_social_network12", |connector, mut core| {
+        let arango = ArangoSession::new(connector).with_executor(core.handle());
+        let database = arango.use_database("the_social_network12");
+
+        let mut new_cursor = NewCursor::from(Query::new("FOR i IN 1..25 RETURN i"));
+        new_cursor.set_batch_size(10);
+        let stream = database.query_stream_opt::<u32>(new_cursor).take(3);
+        let results = core.run(stream.collect()).unwrap();
+
+        assert_that!(&results, eq(vec![1, 2, 3]));
+    });
+}
@@ -0,0 +1,135 @@
+#[macro_use]
+extern crate galvanic_assert;
+
+extern crate futures;
+#[macro_use]
+extern crate serde_json;
+extern crate tokio_core;
+
+extern crate rincon_connector;
+extern crate rincon_core;
+extern crate rincon_session_async;
+
+use std::time::Duration;
+
+use futures::Stream;
+use galvanic_assert::matchers::*;
+use tokio_core::reactor::Core;
+
+use rincon_connector::mock::{MockConnector, Response};
+use rincon_core::api::method::Operation;
+use rincon_session_async::*;
+
+fn push_first_batch_and_delete_responses(connector: &MockConnector) {
+    connector.push_response(Response::new(
+        201,
+        json!({
+            "result": [1, 2, 3, 4, 5, 6, 7, 8, 9, 10],
+            "hasMore": true,
+            "id": "4711",
+            "cached": false,
+            "error": false,
+            "code": 201,
+        }),
+    ));
+    connector.push_response(Response::new(
+        202,
+        json!({
+            "id": "4711",
+            "error": false,
+            "code": 202,
+        }),
+    ));
+}
+
+fn run_background_tasks(core: &mut Core) {
+    core.turn(Some(Duration::from_millis(0)));
+}
+
+#[test]
+fn query_stream_deletes_cursor_when_dropped_before_all_results_are_consumed() {
+    let mut core = Core::new().unwrap();
+    let connector = MockConnector::new();
+    push_first_batch_and_delete_responses(&connector);
+    let arango = ArangoSession::new(connector.clone()).with_executor(core.handle());
+    let database = arango.use_database("the_social_network");
+
+    let stream = database
+        .query_stream::<u32>(Query::new("FOR i IN 1..25 RETURN i"))
+        .take(3);
+    let results = core.run(stream.collect()).unwrap();
+    run_background_tasks(&mut core);
+
+    expect_that!(&results, eq(vec![1, 2, 3]));
+    let requests = connector.requests();
+    expect_that!(&requests.len(), eq(2));
+    expect_that!(&requests[0].path(), eq("/_api/cursor"));
+    expect_that!(&requests[1].operation(), eq(Operation::Delete));
+    expect_that!(&requests[1].path(), eq("/_api/cursor/4711"));
+}
+
+#[test]
+fn query_stream_deletes_cursor_when_dropped_while_first_batch_is_fetched() {
+    let mut core = Core::new().unwrap();
+    let connector = MockConnector::new();
+    push_first_batch_and_delete_responses(&connector);
+    let arango = ArangoSession::new(connector.clone()).with_executor(core.handle());
+    let database = arango.use_database("the_social_network");
+
+    let stream = database.query_stream::<u32>(Query::new("FOR i IN 1..25 RETURN i"));
+    drop(stream);
+    run_background_tasks(&mut core);
+
+    let requests = connector.requests();
+    expect_that!(&requests.len(), eq(2));
+    expect_that!(&requests[0].path(), eq("/_api/cursor"));
+    expect_that!(&requests[1].operation(), eq(Operation::Delete));
+    expect_that!(&requests[1].path(), eq("/_api/cursor/4711"));
+}
+
+#[test]
+fn query_stream_does_not_delete_cursor_when_all_results_are_consumed() {
+    let mut core = Core::new().unwrap();
+    let connector = MockConnector::new();
+    connector.push_response(Response::new(
+        201,
+        json!({
+            "result": [1, 2, 3],
+            "hasMore": false,
+            "cached": false,
+            "error": false,
+            "code": 201,
+        }),
+    ));
+    let arango = ArangoSession::new(connector.clone()).with_executor(core.handle());
+    let database = arango.use_database("the_social_network");
+
+    let stream = database.query_stream::<u32>(Query::new("FOR i IN 1..3 RETURN i"));
+    let results = core.run(stream.collect()).unwrap();
+    run_background_tasks(&mut core);
+
+    expect_that!(&results, eq(vec![1, 2, 3]));
+    let requests = connector.requests();
+    expect_that!(&requests.len(), eq(1));
+    expect_that!(&requests[0].path(), eq("/_api/cursor"));
+}
+
+#[test]
+fn query_stream_of_session_without_executor_does_not_delete_cursor_when_dropped() {
+    let mut core = Core::new().unwrap();
+    let connector = MockConnector::new();
+    push_first_batch_and_delete_responses(&connector);
+    let arango = ArangoSession::new(connector.clone());
+    let database = arango.use_database("the_social_network");
+
+    let stream = database
+        .query_stream::<u32>(Query::new("FOR i IN 1..25 RETURN i"))
+        .take(3);
+    let results = core.run(stream.collect()).unwrap();
+    run_background_tasks(&mut core);
+
+    expect_that!(&results, eq(vec![1, 2, 3]));
+    let requests = connector.requests();
+    expect_that!(&requests.len(), eq(1));
+    expect_that!(&requests[0].path(), eq("/_api/cursor"));
+}